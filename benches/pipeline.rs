@@ -0,0 +1,146 @@
+//! 수집 파이프라인 핫 패스 벤치마크
+//!
+//! `cargo bench` 로 실행, 릴리스 간 비교는 `--save-baseline` / `--baseline` 사용
+
+use std::hint::black_box;
+use std::sync::Arc;
+
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use prost::Message;
+use rlog_agent::collector::{split_line, to_content};
+use rlog_agent::forwarder::event_to_log;
+use rlog_agent::log_metrics::LogMetrics;
+use rlog_agent::models::LogEvent;
+use rlog_agent::proto::log::LogBatch;
+use rlog_agent::settings::LogMetricRule;
+
+const LINES: usize = 1000;
+
+fn sample_chunk() -> BytesMut {
+    let mut buf = BytesMut::new();
+    for i in 0..LINES {
+        let level = if i % 10 == 0 { "ERROR" } else { "INFO" };
+        buf.put_slice(
+            format!("2026-01-01T00:00:00.000Z {level} [worker-{}] request handled path=/api/v1/items/{i} status=200 elapsed_ms=12\n", i % 8)
+                .as_bytes(),
+        );
+    }
+    buf
+}
+
+fn sample_events(chunk: &BytesMut) -> Vec<LogEvent> {
+    let path: Arc<str> = Arc::from("/var/log/app.log");
+    let mut buf = chunk.clone();
+    let mut scanned = 0;
+    let mut offset = 0;
+    let mut events = Vec::with_capacity(LINES);
+
+    while let Some(line) = split_line(&mut buf, &mut scanned) {
+        let len = line.len() as u64;
+        events.push(LogEvent {
+            label: "app".to_string(),
+            content: to_content(line),
+            timestamp: Utc::now(),
+            path: Arc::clone(&path),
+            offset,
+            file_id: 1,
+            fields: Vec::new(),
+        });
+        offset += len;
+    }
+    events
+}
+
+fn sample_batch(chunk: &BytesMut) -> LogBatch {
+    LogBatch {
+        batch_id: "bench".to_string(),
+        send_at: None,
+        logs: sample_events(chunk).into_iter().map(event_to_log).collect(),
+        trace_id: "bench".to_string(),
+        metrics: Vec::new(),
+        tags: Default::default(),
+    }
+}
+
+fn line_splitting(c: &mut Criterion) {
+    let chunk = sample_chunk();
+    let mut group = c.benchmark_group("collector");
+    group.throughput(Throughput::Bytes(chunk.len() as u64));
+
+    group.bench_function("split_lines", |b| {
+        b.iter_batched(
+            || chunk.clone(),
+            |mut buf| {
+                let mut scanned = 0;
+                while let Some(line) = split_line(&mut buf, &mut scanned) {
+                    black_box(to_content(line));
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn rule_matching(c: &mut Criterion) {
+    let events = sample_events(&sample_chunk());
+    let metrics = LogMetrics::new(vec![
+        LogMetricRule {
+            name: "app_errors".to_string(),
+            source: Some("app".to_string()),
+            contains: "ERROR".to_string(),
+        },
+        LogMetricRule {
+            name: "not_found".to_string(),
+            source: None,
+            contains: "status=404".to_string(),
+        },
+    ]);
+
+    let mut group = c.benchmark_group("log_metrics");
+    group.throughput(Throughput::Elements(events.len() as u64));
+    group.bench_function("observe", |b| {
+        b.iter(|| {
+            for event in &events {
+                metrics.observe(black_box(event));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn batch_assembly(c: &mut Criterion) {
+    let chunk = sample_chunk();
+    let mut group = c.benchmark_group("forwarder");
+    group.throughput(Throughput::Elements(LINES as u64));
+
+    group.bench_function("event_to_log", |b| {
+        b.iter_batched(
+            || sample_events(&chunk),
+            |events| black_box(events.into_iter().map(event_to_log).collect::<Vec<_>>()),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn serialization(c: &mut Criterion) {
+    let batch = sample_batch(&sample_chunk());
+    let mut group = c.benchmark_group("streamer");
+    group.throughput(Throughput::Bytes(batch.encoded_len() as u64));
+
+    group.bench_function("encode_batch", |b| {
+        let mut buf = Vec::with_capacity(batch.encoded_len());
+        b.iter(|| {
+            buf.clear();
+            batch.encode(&mut buf).unwrap();
+            black_box(&buf);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, line_splitting, rule_matching, batch_assembly, serialization);
+criterion_main!(benches);
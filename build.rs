@@ -2,11 +2,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=proto/log.proto");
     println!("cargo:rerun-if-changed=proto/auth.proto");
     println!("cargo:rerun-if-changed=proto/health.proto");
+    println!("cargo:rerun-if-changed=proto/control.proto");
+    println!("cargo:rerun-if-changed=proto/workload.proto");
     println!("cargo:rerun-if-changed=proto");
 
-    tonic_prost_build::compile_protos("proto/log.proto")?;
+    // 수집한 라인을 복사 없이 그대로 인코딩하도록 Bytes 로 생성
+    tonic_prost_build::configure()
+        .bytes(".log.Log.line")
+        .compile_protos(&["proto/log.proto"], &["proto"])?;
     tonic_prost_build::compile_protos("proto/auth.proto")?;
     tonic_prost_build::compile_protos("proto/health.proto")?;
+    tonic_prost_build::compile_protos("proto/control.proto")?;
+    tonic_prost_build::compile_protos("proto/workload.proto")?;
 
     Ok(())
 }
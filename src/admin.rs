@@ -0,0 +1,408 @@
+//! 로컬 관리 API
+//!
+//! `state/admin.sock` Unix 소켓에서 한 줄 명령을 받아 한 줄 JSON 으로 응답 (`status` CLI, 외부 도구용)
+//!
+//! ```text
+//! $ echo status | socat - UNIX-CONNECT:state/admin.sock
+//! {"version":"0.1.0","pid":1234,...}
+//! ```
+//!
+//! `tap [label=<라벨>] [count=<개수>]` 는 파이프라인을 지나는 이벤트를 개수만큼 한 줄씩 스트리밍
+//!
+//! `sources`, `add_source <수집 대상 JSON>`, `remove_source <라벨>`, `reload_sources`(시작 때와 같은 경로로 읽은 설정의 `sources` 에 맞춤) 는
+//! 파이프라인을 다시 시작하지 않고 실행 중인 수집 대상을 바꿈. `pause_source <라벨>`, `resume_source <라벨>` 은
+//! 읽기만 멈추고 위치는 유지 (재개하면 멈춘 동안 쌓인 라인부터)
+//!
+//! `spool list`, `spool resend [<큐|파일>...]`, `spool purge <큐|파일>...` 는 디스크 큐(스풀, dead-letter)에 쌓인
+//! 배치를 조회/재전송/삭제. 큐 이름(`spool`, `dead_letter`)은 큐 전체, 파일 이름은 배치 하나를 가리킴
+//!
+//! `log_level` 은 현재/시작 시 로그 필터를 조회, `log_level toggle` 은 SIGUSR1 과 같이 debug 전환,
+//! `log_level reset` 은 시작 시 필터로 복원, `log_level <필터>` 는 필터 교체 (예: `info,rlog_agent=trace`)
+
+use std::sync::OnceLock;
+
+use serde_json::json;
+
+use crate::collector_manager::{self, Request};
+use crate::log_level;
+use crate::messages as msg;
+use crate::settings::{Settings, SourceSettings};
+use crate::spool::{Spool, DEAD_LETTER_DIR, SPOOL_DIR};
+use crate::status;
+use crate::streamer;
+
+pub static ADMIN_SOCKET_PATH: &str = "state/admin.sock";
+
+const DEFAULT_TAP_COUNT: usize = 20;
+
+type Reload = Box<dyn Fn() -> anyhow::Result<Settings> + Send + Sync>;
+
+/// `reload_sources` 가 설정을 다시 읽는 방법
+static RELOAD: OnceLock<Reload> = OnceLock::new();
+
+/// `reload_sources` 가 시작 때와 같은 경로(설정 파일이나 `RLOG_CONFIG`, CLI 덮어쓰기)로 설정을 읽도록 지정,
+/// 지정하지 않으면 설정 파일만 읽음
+pub fn configure_reload(reload: impl Fn() -> anyhow::Result<Settings> + Send + Sync + 'static) {
+    let _ = RELOAD.set(Box::new(reload));
+}
+
+/// `tap` 명령 인자
+#[derive(Debug, PartialEq, Eq)]
+struct TapRequest {
+    label: Option<String>,
+    count: usize,
+}
+
+fn parse_tap(args: &str) -> Result<TapRequest, String> {
+    let mut request = TapRequest {
+        label: None,
+        count: DEFAULT_TAP_COUNT,
+    };
+
+    for arg in args.split_whitespace() {
+        match arg.split_once('=') {
+            Some(("label", label)) => request.label = Some(label.to_string()),
+            Some(("count", count)) => {
+                request.count = count
+                    .parse()
+                    .map_err(|_| format!("{}: {}", msg::ADMIN_INVALID_COUNT, count))?;
+            }
+            _ => return Err(format!("{}: {}", msg::ADMIN_UNKNOWN_TAP_ARG, arg)),
+        }
+    }
+
+    Ok(request)
+}
+
+/// 수집 대상 변경 명령 처리, 해당 명령이 아니면 None
+async fn handle_sources(name: &str, args: &str) -> Option<serde_json::Value> {
+    let request = match name {
+        "sources" => Request::List,
+        "add_source" => match serde_json::from_str::<SourceSettings>(args) {
+            Ok(source) => Request::Add(Box::new(source)),
+            Err(e) => return Some(json!({ "error": format!("{}: {}", msg::ADMIN_INVALID_SOURCE, e) })),
+        },
+        "remove_source" => Request::Remove(args.trim().to_string()),
+        "pause_source" => Request::SetPaused {
+            label: args.trim().to_string(),
+            paused: true,
+        },
+        "resume_source" => Request::SetPaused {
+            label: args.trim().to_string(),
+            paused: false,
+        },
+        "reload_sources" => match RELOAD.get().map_or_else(Settings::load_settings, |reload| reload()) {
+            Ok(settings) => Request::Sync(settings.sources),
+            Err(e) => return Some(json!({ "error": format!("{:#}", e) })),
+        },
+        _ => return None,
+    };
+
+    Some(match collector_manager::request(request).await {
+        Ok(result) => json!({ "result": result }),
+        Err(e) => json!({ "error": format!("{:#}", e) }),
+    })
+}
+
+/// 디스크 큐 명령 처리, 인자가 없으면 `list`
+fn handle_spool(args: &str) -> anyhow::Result<serde_json::Value> {
+    let (action, targets) = args.split_once(' ').unwrap_or((args, ""));
+    let queues = [Spool::open(SPOOL_DIR)?, Spool::open(DEAD_LETTER_DIR)?];
+
+    match action {
+        "" | "list" => {
+            let mut batches = Vec::new();
+            for queue in &queues {
+                batches.extend(queue.entries()?);
+            }
+            Ok(json!({ "batches": batches }))
+        }
+        // 지정한 dead-letter 배치는 스풀로 옮긴 뒤 스풀 전체를 바로 재전송
+        "resend" => {
+            let [spool, dead_letter] = &queues;
+            let mut requeued = 0;
+
+            for path in select(dead_letter, targets)? {
+                dead_letter.move_to(&path, spool)?;
+                requeued += 1;
+            }
+
+            streamer::request_resend();
+            Ok(json!({ "requeued": requeued, "resend_requested": true }))
+        }
+        "purge" => {
+            if targets.trim().is_empty() {
+                anyhow::bail!(msg::ADMIN_SPOOL_TARGET_REQUIRED);
+            }
+
+            let mut purged = 0;
+            for queue in &queues {
+                for path in select(queue, targets)? {
+                    queue.remove(&path);
+                    purged += 1;
+                }
+            }
+            Ok(json!({ "purged": purged }))
+        }
+        other => anyhow::bail!("{}: {}", msg::ADMIN_UNKNOWN_SPOOL_ACTION, other),
+    }
+}
+
+/// 로그 필터 명령 처리, 인자가 없으면 조회
+fn handle_log_level(args: &str) -> anyhow::Result<serde_json::Value> {
+    let controller = log_level::controller().ok_or_else(|| anyhow::anyhow!(msg::LOG_LEVEL_UNAVAILABLE))?;
+
+    match args {
+        "" => {}
+        "toggle" => {
+            controller.toggle_debug()?;
+        }
+        "reset" => controller.set(controller.base())?,
+        filter => controller.set(filter)?,
+    }
+    Ok(json!({ "filter": controller.current(), "base": controller.base() }))
+}
+
+/// 큐 이름이면 큐 전체, 파일 이름이면 이 큐에 있는 해당 배치
+fn select(queue: &Spool, targets: &str) -> anyhow::Result<Vec<std::path::PathBuf>> {
+    let targets: Vec<&str> = targets.split_whitespace().collect();
+    if targets.contains(&queue.name()) {
+        return queue.list();
+    }
+
+    Ok(targets.iter().filter_map(|file| queue.find(file)).collect())
+}
+
+/// 명령 처리 후 응답 JSON 반환
+fn handle(command: &str) -> serde_json::Value {
+    let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+
+    match name {
+        "status" => serde_json::to_value(status::registry().snapshot())
+            .unwrap_or_else(|e| json!({ "error": e.to_string() })),
+        "spool" => handle_spool(args.trim()).unwrap_or_else(|e| json!({ "error": format!("{:#}", e) })),
+        "log_level" => handle_log_level(args.trim()).unwrap_or_else(|e| json!({ "error": format!("{:#}", e) })),
+        "" => json!({ "error": msg::ADMIN_EMPTY_COMMAND.text() }),
+        _ => json!({ "error": format!("{}: {}", msg::ADMIN_UNKNOWN_COMMAND, command) }),
+    }
+}
+
+#[cfg(unix)]
+pub use unix_impl::{query, spawn, stream};
+
+#[cfg(unix)]
+mod unix_impl {
+    use std::fs;
+    use std::path::Path;
+
+    use anyhow::{anyhow, Context, Result};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio_util::sync::CancellationToken;
+    use tracing::{debug, error, info, warn};
+
+    use super::{handle, handle_sources, parse_tap, TapRequest, ADMIN_SOCKET_PATH};
+    use crate::ephemeral;
+    use crate::messages as msg;
+    use crate::tap;
+    use serde_json::json;
+    use tokio::io::AsyncWrite;
+    use tokio::sync::broadcast::error::RecvError;
+
+    /// 관리 소켓 서버 실행, `shutdown` 시 소켓 파일 삭제
+    pub fn spawn(shutdown: CancellationToken) {
+        // 읽기 전용 루트에서도 돌도록 상태 없는 실행은 소켓 파일을 만들지 않음
+        if ephemeral::is_enabled() {
+            info!("{}", msg::ADMIN_SOCKET_DISABLED_EPHEMERAL);
+            return;
+        }
+
+        let listener = match bind(Path::new(ADMIN_SOCKET_PATH)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!(error = %format!("{:#}", e), "{}", msg::ADMIN_SOCKET_START_FAILED);
+                return;
+            }
+        };
+
+        info!(path = ADMIN_SOCKET_PATH, "{}", msg::ADMIN_SOCKET_STARTED);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            tokio::spawn(serve(stream));
+                        }
+                        Err(e) => error!(error = %e, "{}", msg::ADMIN_ACCEPT_FAILED),
+                    },
+                }
+            }
+
+            let _ = fs::remove_file(ADMIN_SOCKET_PATH);
+        });
+    }
+
+    fn bind(path: &Path) -> Result<UnixListener> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+        }
+
+        // 이전 실행이 비정상 종료되어 남은 소켓 파일 정리
+        if path.exists() {
+            fs::remove_file(path).context(msg::ADMIN_STALE_SOCKET_REMOVE_FAILED)?;
+        }
+
+        let listener = UnixListener::bind(path).context(msg::ADMIN_BIND_FAILED)?;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+            .context(msg::ADMIN_PERMISSIONS_FAILED)?;
+
+        Ok(listener)
+    }
+
+    async fn serve(stream: UnixStream) {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            let command = line.trim();
+            debug!(command = command, "{}", msg::ADMIN_COMMAND_RECEIVED);
+
+            let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+            if name == "tap" {
+                match parse_tap(args) {
+                    Ok(request) => serve_tap(request, &mut writer).await,
+                    Err(e) => {
+                        let _ = write_line(&mut writer, &json!({ "error": e })).await;
+                    }
+                }
+                // 스트리밍 명령은 완료 후 연결 종료
+                break;
+            }
+
+            let response = match handle_sources(name, args).await {
+                Some(response) => response,
+                None => handle(command),
+            };
+            let mut response = response.to_string();
+            response.push('\n');
+
+            if writer.write_all(response.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// 조건에 맞는 이벤트를 `count` 개 전달할 때까지 스트리밍
+    async fn serve_tap<W: AsyncWrite + Unpin>(request: TapRequest, writer: &mut W) {
+        let mut rx = tap::subscribe();
+        let mut sent = 0;
+
+        while sent < request.count {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!(skipped = skipped, "{}", msg::TAP_LAGGED);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+
+            if request.label.as_ref().is_some_and(|label| *label != event.label) {
+                continue;
+            }
+
+            let Ok(value) = serde_json::to_value(&event) else {
+                continue;
+            };
+
+            if write_line(writer, &value).await.is_err() {
+                break;
+            }
+            sent += 1;
+        }
+    }
+
+    async fn write_line<W: AsyncWrite + Unpin>(
+        writer: &mut W,
+        value: &serde_json::Value,
+    ) -> std::io::Result<()> {
+        let mut line = value.to_string();
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await
+    }
+
+    /// 스트리밍 명령을 보내고 응답 한 줄마다 `on_line` 호출, 서버가 연결을 닫으면 종료
+    pub async fn stream<F>(command: &str, mut on_line: F) -> Result<()>
+    where
+        F: FnMut(serde_json::Value) -> Result<()>,
+    {
+        let stream = UnixStream::connect(ADMIN_SOCKET_PATH)
+            .await
+            .with_context(|| format!("{} ({})", msg::ADMIN_CONNECT_FAILED, ADMIN_SOCKET_PATH))?;
+
+        let (reader, mut writer) = stream.into_split();
+        writer.write_all(format!("{}\n", command).as_bytes()).await?;
+
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            let value: serde_json::Value =
+                serde_json::from_str(&line).context(msg::ADMIN_RESPONSE_PARSE_FAILED)?;
+
+            if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+                return Err(anyhow!("{}: {}", msg::ADMIN_COMMAND_FAILED, error));
+            }
+
+            on_line(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// 실행 중인 에이전트에 명령을 보내고 응답 JSON 반환
+    pub async fn query(command: &str) -> Result<serde_json::Value> {
+        let stream = UnixStream::connect(ADMIN_SOCKET_PATH)
+            .await
+            .with_context(|| format!("{} ({})", msg::ADMIN_CONNECT_FAILED, ADMIN_SOCKET_PATH))?;
+
+        let (reader, mut writer) = stream.into_split();
+        writer.write_all(format!("{}\n", command).as_bytes()).await?;
+
+        let response = BufReader::new(reader)
+            .lines()
+            .next_line()
+            .await?
+            .ok_or_else(|| anyhow!(msg::ADMIN_NO_RESPONSE))?;
+
+        let value: serde_json::Value =
+            serde_json::from_str(&response).context(msg::ADMIN_RESPONSE_PARSE_FAILED)?;
+
+        if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+            return Err(anyhow!("{}: {}", msg::ADMIN_COMMAND_FAILED, error));
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(not(unix))]
+pub fn spawn(_shutdown: tokio_util::sync::CancellationToken) {
+    tracing::info!("{}", msg::UNIX_ONLY_ADMIN);
+}
+
+#[cfg(not(unix))]
+pub async fn query(_command: &str) -> anyhow::Result<serde_json::Value> {
+    let _ = (handle, handle_sources, parse_tap);
+    anyhow::bail!(msg::UNIX_ONLY_ADMIN)
+}
+
+#[cfg(not(unix))]
+pub async fn stream<F>(_command: &str, _on_line: F) -> anyhow::Result<()>
+where
+    F: FnMut(serde_json::Value) -> anyhow::Result<()>,
+{
+    anyhow::bail!(msg::UNIX_ONLY_ADMIN)
+}
@@ -0,0 +1,1090 @@
+//! 에이전트 조립 및 실행
+//!
+//! 바이너리뿐 아니라 다른 Rust 서비스에서도 인프로세스로 로그를 수집/전송할 수 있도록
+//! Collector → Forwarder → Sink 파이프라인을 [`Agent`] 로 묶어 제공
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::RwLock;
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tonic::transport::Channel;
+use tracing::{error, info, warn};
+
+use crate::anonymize::Anonymizer;
+use crate::auth::client::AuthClient;
+use crate::auth::interceptor::AuthInterceptor;
+use crate::auth::oauth2::OAuth2Client;
+use crate::auth::token_manager::TokenManager;
+use crate::clock;
+use crate::collector_manager::CollectorManager;
+use crate::control::ControlChannel;
+use crate::crash;
+use crate::disk_guard;
+use crate::durability;
+use crate::dry_run::DryRunSink;
+use crate::endpoint;
+use crate::ephemeral;
+use crate::error_code::ErrorCode;
+use crate::forwarder::Forwarder;
+use crate::health::HealthReporter;
+use crate::http_fallback::HttpFallback;
+use crate::log_metrics::LogMetrics;
+use crate::hot_restart::{self, CollectorOffset, Handover};
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::{LogEvent, MetricEvent};
+use crate::parser::timestamp;
+use crate::governor::Governor;
+use crate::routing::Router;
+use crate::sampling::Sampler;
+use crate::schedule;
+use crate::sink_health;
+use crate::sinks;
+use crate::spiffe;
+use crate::proto::log::LogBatch;
+use crate::retry::{self, Backoff};
+use crate::settings::{AuthMode, AuthSettings, Settings, SourceSettings};
+use crate::spool::{self, Spool, SPOOL_DIR};
+use crate::status::{self, ConnectionState};
+use crate::streamer::Streamer;
+use crate::supervisor::{self, RestartPolicy, SharedReceiver};
+use crate::systemd;
+use crate::updater::Updater;
+
+const COLLECTOR_CHANNEL_CAPACITY: usize = 100;
+const BATCH_CHANNEL_CAPACITY: usize = 1000;
+const METRIC_CHANNEL_CAPACITY: usize = 100;
+
+/// 서버 연결 제한 시간
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 드레인 제한 시간 초과 후 Streamer 가 잔여 배치를 스풀에 저장할 때까지 기다리는 시간
+const SPOOL_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// `stop_collecting` 으로 멈춘 동안 디스크 큐에 자리가 났는지 다시 보는 주기
+const SPOOL_FULL_RECHECK: Duration = Duration::from_secs(5);
+
+/// 프로세스에 살아 있는 Agent 가 있는지, 재시도/시간대/토큰 저장소 등 전역 설정을 나눠 쓰므로 하나만 허용
+static INSTANCE: AtomicBool = AtomicBool::new(false);
+
+/// 살아 있는 Agent 표시, Agent 가 drop 되면 (실행이 끝나면) 해제
+struct Instance;
+
+impl Instance {
+    fn acquire() -> Result<Self> {
+        if INSTANCE.swap(true, Ordering::AcqRel) {
+            bail!(msg::AGENT_ALREADY_RUNNING);
+        }
+        Ok(Self)
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        INSTANCE.store(false, Ordering::Release);
+    }
+}
+
+/// 완성된 LogBatch 의 전달 대상
+pub enum Sink {
+    /// rlog 서버로 gRPC 스트리밍 전송 (인증, 재시도, 스풀 포함)
+    Grpc {
+        server_addr: String,
+        project_key: String,
+    },
+    /// 호출자가 소유한 채널로 전달 (인프로세스 소비)
+    Channel(Sender<LogBatch>),
+    /// 배치 요약을 표준 출력으로 출력 (dry-run)
+    Stdout,
+}
+
+/// [`Agent`] 빌더
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use rlog_agent::{Agent, Sink};
+/// use rlog_agent::settings::SourceSettings;
+/// use tokio_util::sync::CancellationToken;
+///
+/// let agent = Agent::builder()
+///     .source(SourceSettings::new("app", "/var/log/app.log"))
+///     .sink(Sink::Grpc {
+///         server_addr: "http://localhost:50051".into(),
+///         project_key: "your-project-key".into(),
+///     })
+///     .batch_size(500)
+///     .build()?;
+///
+/// agent.run(CancellationToken::new()).await
+/// # }
+/// ```
+#[derive(Default)]
+pub struct AgentBuilder {
+    settings: Settings,
+    sink: Option<Sink>,
+}
+
+impl AgentBuilder {
+    /// 설정 전체 지정, 별도 `sink` 미지정 시 설정의 서버로 gRPC 전송
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// 수집 대상 추가
+    pub fn source(mut self, source: SourceSettings) -> Self {
+        self.settings.sources.push(source);
+        self
+    }
+
+    pub fn sink(mut self, sink: Sink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// 배치당 최대 로그 수
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.settings.batch_size = batch_size;
+        self
+    }
+
+    /// 강제 플러시 주기 (초)
+    pub fn flush_interval(mut self, secs: u64) -> Self {
+        self.settings.flush_interval = secs;
+        self
+    }
+
+    /// 종료 시 잔여 배치 전송 제한 시간 (초)
+    pub fn drain_timeout(mut self, secs: u64) -> Self {
+        self.settings.drain_timeout = secs;
+        self
+    }
+
+    /// Forwarder/Streamer 레인 수
+    pub fn pipeline_workers(mut self, workers: usize) -> Self {
+        self.settings.pipeline_workers = workers;
+        self
+    }
+
+    pub fn build(self) -> Result<Agent> {
+        let mut settings = self.settings;
+
+        let sink = match self.sink {
+            Some(Sink::Grpc {
+                server_addr,
+                project_key,
+            }) => {
+                settings.server_addr = server_addr;
+                settings.project_key = project_key;
+                Sink::Grpc {
+                    server_addr: settings.server_addr.clone(),
+                    project_key: settings.project_key.clone(),
+                }
+            }
+            Some(sink) => sink,
+            None => Sink::Grpc {
+                server_addr: settings.server_addr.clone(),
+                project_key: settings.project_key.clone(),
+            },
+        };
+
+        // SPIFFE, OAuth2 모드는 project_key 불필요
+        if let Sink::Grpc {
+            server_addr,
+            project_key,
+        } = &sink
+            && (server_addr.trim().is_empty()
+                || (project_key.trim().is_empty() && settings.auth.mode.requires_project_key()))
+        {
+            bail!(msg::GRPC_SETTINGS_REQUIRED);
+        }
+
+        Ok(Agent {
+            settings,
+            sink,
+            _instance: Instance::acquire()?,
+        })
+    }
+}
+
+/// 로그 수집 에이전트
+///
+/// [`Agent::run`] 은 전달받은 `shutdown` 이 취소될 때까지 실행되며,
+/// 취소 시 Collector 종료 → Forwarder 플러시 → Sink 드레인 순으로 정리 후 반환.
+/// 재시도, 시간대, 토큰 저장소, 상태 레지스트리 등을 프로세스 전역으로 설정하므로 한 프로세스에 하나만 둘 수 있으며,
+/// 이전 Agent 가 drop 되기 (실행이 끝나기) 전에 `build` 하면 실패
+pub struct Agent {
+    settings: Settings,
+    sink: Sink,
+    _instance: Instance,
+}
+
+impl Agent {
+    pub fn builder() -> AgentBuilder {
+        AgentBuilder::default()
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub async fn run(self, shutdown: CancellationToken) -> Result<()> {
+        // 내부 오류로 인한 종료가 호출자의 토큰까지 취소하지 않도록 분리
+        let shutdown = shutdown.child_token();
+        let budget = MemoryBudget::new(self.settings.max_memory_mb);
+        status::registry().set_budget(budget.clone());
+        let handover = Handover::take();
+        disk_guard::configure(self.settings.min_free_disk_mb);
+        durability::configure(self.settings.durability);
+        // dry-run, 채널 Sink 도 같은 정책/시간대를 쓰도록 Sink 와 무관한 전역 설정은 여기서 적용
+        retry::configure(&self.settings.retry);
+        clock::configure(&self.settings.clock_skew);
+        timestamp::configure(self.settings.timezone);
+        endpoint::configure(&self.settings.tls);
+
+        if let Some(governor) = self.settings.resource_governor.clone() {
+            tokio::spawn(Governor::new(governor).start(shutdown.child_token()));
+        }
+
+        let result = match self.sink {
+            Sink::Grpc { .. } => run_grpc(self.settings, budget, handover, shutdown).await,
+            Sink::Channel(tx) => run_local(self.settings, tx, budget, handover, shutdown).await,
+            Sink::Stdout => {
+                let (tx, rx) = mpsc::channel::<LogBatch>(BATCH_CHANNEL_CAPACITY);
+                let sink_handle = tokio::spawn(DryRunSink::new(rx).start());
+
+                let result = run_local(self.settings, tx, budget, handover, shutdown).await;
+                let _ = sink_handle.await;
+                result
+            }
+        };
+
+        // 종료 중 저장한 디스크 큐, 커서까지 `durability: shutdown` 이면 여기서 fsync
+        durability::flush();
+        result
+    }
+}
+
+async fn run_grpc(
+    settings: Settings,
+    budget: MemoryBudget,
+    handover: Option<Handover>,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let workers = lane_count(&settings);
+    let (event_txs, event_rxs) = lane_channels::<LogEvent>(workers, COLLECTOR_CHANNEL_CAPACITY, "collector");
+    let (batch_txs, batch_rxs) = lane_channels::<LogBatch>(workers, BATCH_CHANNEL_CAPACITY, "batch");
+    let (metric_tx, metric_rx) = mpsc::channel::<MetricEvent>(METRIC_CHANNEL_CAPACITY);
+
+    // 라우팅 조건식, 추가 Sink, 익명화 설정 오류는 수집 시작 전에 반환
+    let router = Arc::new(Router::new(&settings.routes, &settings.sinks)?);
+    let anonymizer = Arc::new(Anonymizer::new(&settings.anonymize.clone().unwrap_or_default())?);
+    let (sink_txs, mut sinks_handle) = sinks::start(&settings, budget.clone())?;
+    if settings.auth.mode == AuthMode::Spiffe {
+        spiffe::spawn_watch(spiffe::socket(&settings.auth));
+    }
+
+    // 수집 대상 시작 시 바로 구간을 판정하도록 먼저 실행
+    schedule::spawn(shutdown.child_token());
+
+    let mut collectors = CollectorManager::new(event_txs, settings.read_backend, budget.clone(), shutdown.child_token());
+    collectors.start_all(settings.sources.clone(), handover.as_ref()).await?;
+    let collectors_handle = collectors.spawn_serve();
+
+    let mut forwarder_handle = start_forwarders(
+        event_rxs,
+        supervisor::shared(metric_rx),
+        batch_txs,
+        &settings,
+        router,
+        sink_txs,
+        anonymizer,
+        budget.clone(),
+        shutdown.child_token(),
+    );
+
+    spool::configure(settings.max_spool_mb, settings.spool_eviction);
+    let spool = Spool::open(SPOOL_DIR)?;
+    if let Some(handover) = &handover {
+        handover.check_spool(&spool);
+    }
+    status::registry().set_spool(spool.clone());
+
+    // 서버 연결 전에도 수집은 진행되므로 바로 준비 완료
+    systemd::notify("READY=1");
+    systemd::spawn_watchdog(shutdown.child_token());
+
+    // shutdown 과 별개로, 드레인 제한 시간 초과 시에만 취소
+    let drain_deadline = CancellationToken::new();
+
+    let mut forwarder_done = false;
+    let mut streamer_done = false;
+    let mut streamer_handle = None;
+    let mut fatal = None;
+
+    // 서버 장애 중에도 시작할 수 있도록 연결/인증은 배치를 디스크 큐에 쌓으며 재시도
+    let connection = tokio::select! {
+        result = &mut forwarder_handle => {
+            forwarder_done = true;
+            fatal = supervisor_failure("Forwarder", result);
+            None
+        }
+        connection = connect_or_spool(&settings, &batch_rxs, &spool, &budget, &shutdown) => connection,
+    };
+
+    if let Some((channel, token_manager)) = connection {
+        let token_manager = Arc::new(RwLock::new(token_manager));
+        let interceptor = {
+            let tm = token_manager.read().await;
+            AuthInterceptor::new(tm.get_shared_token())
+        };
+        status::registry().set_access_token(token_manager.read().await.get_shared_token());
+        let http_fallback = settings
+            .http_fallback
+            .clone()
+            .map(|fallback| HttpFallback::new(fallback, Arc::clone(&token_manager)).map(Arc::new))
+            .transpose()?;
+
+        // 이전 실행에서 남은 크래시 보고서 전송
+        tokio::spawn(crash::report_pending(channel.clone(), interceptor.clone()));
+
+        let mut handle = start_streamers(
+            batch_rxs.clone(),
+            settings.server_addr.clone(),
+            channel.clone(),
+            Arc::clone(&token_manager),
+            interceptor.clone(),
+            spool.clone(),
+            budget.clone(),
+            settings.max_event_age,
+            http_fallback,
+            drain_deadline.clone(),
+            shutdown.child_token(),
+        );
+
+        // 명령 채널이 없어도 수집/전송에는 지장이 없으므로 감독 대상(fatal)에서 제외
+        tokio::spawn(crash::in_subsystem(
+            "ControlChannel",
+            ControlChannel::new(
+                channel.clone(),
+                interceptor.clone(),
+                Arc::clone(&token_manager),
+                settings.clone(),
+                shutdown.clone(),
+            )
+            .start(),
+        ));
+
+        if let Some(update) = settings.update.clone() {
+            tokio::spawn(crash::in_subsystem("Updater", Updater::new(update, shutdown.clone()).start()));
+        }
+
+        let health_handle = start_health_reporter(
+            settings.server_addr.clone(),
+            channel,
+            Arc::clone(&token_manager),
+            interceptor,
+            metric_tx,
+            shutdown.child_token(),
+        );
+
+        // 감독 태스크가 종료되었다면 재시작 한도를 넘긴 복구 불가 상태.
+        // 종료 신호로 함께 끝난 태스크를 장애로 보지 않도록 종료 신호를 먼저 확인
+        fatal = tokio::select! {
+            biased;
+            _ = shutdown.cancelled() => {
+                info!("{}", msg::SHUTDOWN_SIGNAL_RECEIVED);
+                None
+            }
+            result = &mut forwarder_handle => {
+                forwarder_done = true;
+                supervisor_failure("Forwarder", result)
+            }
+            result = &mut handle => {
+                streamer_done = true;
+                supervisor_failure("Streamer", result)
+            }
+            result = health_handle => {
+                supervisor_failure("HealthReporter", result)
+            }
+        };
+
+        streamer_handle = Some(handle);
+    }
+
+    if hot_restart::is_requested() {
+        systemd::notify("RELOADING=1");
+    } else {
+        systemd::notify("STOPPING=1");
+    }
+    status::registry().set_connection(ConnectionState::ShuttingDown);
+    shutdown.cancel();
+
+    let mut offsets = join_collectors(collectors_handle).await;
+
+    // Collector 종료 -> Forwarder 잔여 플러시 -> Streamer 잔여 전송 순으로 드레인
+    let drain = async {
+        let forwarder = async {
+            if !forwarder_done {
+                let _ = (&mut forwarder_handle).await;
+            }
+        };
+
+        match streamer_handle.as_mut() {
+            Some(handle) => {
+                forwarder.await;
+                if !streamer_done {
+                    let _ = handle.await;
+                }
+            }
+            // 서버 연결 전 종료: Forwarder 잔여 배치까지 디스크 큐에 저장
+            None => {
+                tokio::join!(forwarder, spool_until_closed(&batch_rxs, &spool, &budget));
+            }
+        }
+
+        // 추가 Sink 는 Forwarder 가 끝나 채널이 닫히면 남은 배치를 쓰고 끝남
+        let _ = (&mut sinks_handle).await;
+    };
+
+    let drain_timeout = Duration::from_secs(settings.drain_timeout);
+    if time::timeout(drain_timeout, drain).await.is_err() {
+        warn!(timeout_secs = settings.drain_timeout, "{}", msg::DRAIN_TIMEOUT_FORCED);
+        drain_deadline.cancel();
+        forwarder_handle.abort();
+        sinks_handle.abort();
+        // Forwarder 가 쌓고 있던 라인은 버려지므로 Collector 위치 대신 마지막으로 배치에 실은 라인부터 다시 읽음
+        offsets = hot_restart::batched_offsets(offsets);
+
+        let spool_flush = async {
+            match streamer_handle {
+                Some(handle) => {
+                    let _ = handle.await;
+                }
+                None => spool_until_closed(&batch_rxs, &spool, &budget).await,
+            }
+        };
+
+        if time::timeout(SPOOL_FLUSH_TIMEOUT, spool_flush).await.is_err() {
+            error!("{}", msg::SPOOL_REMAINING_TIMEOUT);
+        }
+    }
+
+    save_handover(offsets, Some(&spool));
+
+    if let Some(e) = fatal {
+        return Err(e);
+    }
+
+    info!("{}", msg::AGENT_STOPPED);
+    Ok(())
+}
+
+/// 서버 연결/인증될 때까지 재시도하며 그 사이 모든 레인에 도착한 배치는 디스크 큐에 저장 (degraded 모드)
+///
+/// 연결 전에 `shutdown` 되면 None
+async fn connect_or_spool(
+    settings: &Settings,
+    lanes: &[SharedReceiver<LogBatch>],
+    spool: &Spool,
+    budget: &MemoryBudget,
+    shutdown: &CancellationToken,
+) -> Option<(Channel, TokenManager)> {
+    let connected = CancellationToken::new();
+    let mut spoolers = JoinSet::new();
+    for rx in lanes {
+        spoolers.spawn(spool_until_connected(
+            Arc::clone(rx),
+            spool.clone(),
+            budget.clone(),
+            connected.clone(),
+        ));
+    }
+
+    let connection = tokio::select! {
+        connection = connect_with_retry(&settings.server_addr, &settings.project_key, &settings.auth) => Some(connection),
+        _ = shutdown.cancelled() => {
+            info!("{}", msg::SHUTDOWN_SIGNAL_WHILE_DISCONNECTED);
+            None
+        }
+    };
+
+    // 수신측 잠금을 풀어야 Streamer 나 종료 시 스풀 저장이 이어받을 수 있음
+    connected.cancel();
+    while spoolers.join_next().await.is_some() {}
+
+    connection
+}
+
+async fn spool_until_connected(
+    rx: SharedReceiver<LogBatch>,
+    spool: Spool,
+    budget: MemoryBudget,
+    connected: CancellationToken,
+) {
+    let mut rx = rx.lock().await;
+    let mut stopped = false;
+
+    loop {
+        // stop_collecting 상한에 닿으면 배치를 받지 않아 채널이 차고 Collector 도 멈춤
+        if spool.is_full() {
+            if !stopped {
+                warn!("{}", msg::SPOOL_FULL_COLLECTION_PAUSED);
+                stopped = true;
+            }
+            tokio::select! {
+                _ = connected.cancelled() => break,
+                _ = time::sleep(SPOOL_FULL_RECHECK) => continue,
+            }
+        }
+        stopped = false;
+
+        tokio::select! {
+            _ = connected.cancelled() => break,
+            batch = rx.recv() => match batch {
+                Some(batch) => spool_batch(&spool, &budget, &batch),
+                None => {
+                    connected.cancelled().await;
+                    break;
+                }
+            },
+        }
+    }
+}
+
+/// 연결/인증 성공 시까지 지수 backoff 로 재시도, 재시도 예산을 다 쓰면 채워질 때까지 대기
+async fn connect_with_retry(server_addr: &str, project_key: &str, auth: &AuthSettings) -> (Channel, TokenManager) {
+    let mut backoff = Backoff::new();
+    let mut degraded = false;
+    status::registry().set_connection(ConnectionState::Connecting);
+
+    loop {
+        match connect_and_authenticate(server_addr, project_key, auth).await {
+            Ok(connection) => {
+                status::registry().set_connection(ConnectionState::Connected);
+                if degraded {
+                    info!("{}", msg::CONNECTION_RECOVERED);
+                }
+                return connection;
+            }
+            Err(e) => {
+                let mut delay = backoff.next_delay();
+                if !retry::try_acquire() {
+                    delay = delay.max(retry::until_refill());
+                }
+
+                if degraded {
+                    warn!(
+                        backoff_ms = delay.as_millis() as u64,
+                        error = %format!("{:#}", e),
+                        code = %ErrorCode::ConnectFailed,
+                        "{}",
+                        msg::CONNECT_RETRY_FAILED
+                    );
+                } else {
+                    warn!(error = %format!("{:#}", e), code = %ErrorCode::ConnectFailed, "{}", msg::CONNECT_FAILED_DEGRADED);
+                    degraded = true;
+                    status::registry().set_connection(ConnectionState::Degraded);
+                }
+
+                time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// 모든 레인의 채널이 닫힐 때까지 남은 배치를 디스크 큐에 저장
+///
+/// 레인별 Forwarder 는 서로 독립적으로 끝나므로 차례로 비워도 됨
+async fn spool_until_closed(lanes: &[SharedReceiver<LogBatch>], spool: &Spool, budget: &MemoryBudget) {
+    let mut count = 0;
+
+    for rx in lanes {
+        let mut rx = rx.lock().await;
+        while let Some(batch) = rx.recv().await {
+            spool_batch(spool, budget, &batch);
+            count += 1;
+        }
+    }
+
+    if count > 0 {
+        warn!(count = count, "{}", msg::DISCONNECTED_SHUTDOWN_SPOOLED);
+    }
+}
+
+fn spool_batch(spool: &Spool, budget: &MemoryBudget, batch: &LogBatch) {
+    if let Err(e) = spool.push(batch) {
+        error!(
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            error = %e,
+            "{}",
+            msg::BATCH_SPOOL_FAILED
+        );
+    }
+
+    budget.release(memory::batch_bytes(batch));
+}
+
+/// 서버 연결 없이 Collector → Forwarder 까지만 실행하고 배치를 `tx` 로 전달
+async fn run_local(
+    settings: Settings,
+    tx: Sender<LogBatch>,
+    budget: MemoryBudget,
+    handover: Option<Handover>,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let workers = lane_count(&settings);
+    let (event_txs, event_rxs) = lane_channels::<LogEvent>(workers, COLLECTOR_CHANNEL_CAPACITY, "collector");
+    let (batch_tx, mut batch_rx) = mpsc::channel::<LogBatch>(BATCH_CHANNEL_CAPACITY);
+    status::registry().register_queue("batch", &batch_tx);
+
+    // 라우팅 조건식, 추가 Sink, 익명화 설정 오류는 수집 시작 전에 반환
+    let router = Arc::new(Router::new(&settings.routes, &settings.sinks)?);
+    let anonymizer = Arc::new(Anonymizer::new(&settings.anonymize.clone().unwrap_or_default())?);
+    let (sink_txs, sinks_handle) = sinks::start(&settings, budget.clone())?;
+
+    let mut collectors = CollectorManager::new(event_txs, settings.read_backend, budget.clone(), shutdown.child_token());
+    collectors.start_all(settings.sources.clone(), handover.as_ref()).await?;
+    let collectors_handle = collectors.spawn_serve();
+
+    // 헬스 리포터가 없으므로 로그 기반 메트릭만 배치에 실림
+    let (_, metric_rx) = mpsc::channel::<MetricEvent>(METRIC_CHANNEL_CAPACITY);
+
+    // 모든 레인의 배치가 하나의 Sink 채널로 모임
+    let mut forwarder_handle = start_forwarders(
+        event_rxs,
+        supervisor::shared(metric_rx),
+        vec![batch_tx; workers],
+        &settings,
+        router,
+        sink_txs,
+        anonymizer,
+        budget.clone(),
+        shutdown.child_token(),
+    );
+
+    // 배치가 호출자 채널로 넘어가면 에이전트 메모리 예산에서 제외
+    let relay_handle = tokio::spawn(async move {
+        while let Some(batch) = batch_rx.recv().await {
+            budget.release(memory::batch_bytes(&batch));
+
+            if tx.send(batch).await.is_err() {
+                warn!("{}", msg::SINK_CHANNEL_CLOSED);
+                break;
+            }
+        }
+    });
+
+    // Forwarder 감독 태스크가 끝났다면 재시작 한도를 넘긴 복구 불가 상태, 종료 신호를 먼저 확인
+    let mut forwarder_done = false;
+    let fatal = tokio::select! {
+        biased;
+        _ = shutdown.cancelled() => None,
+        result = &mut forwarder_handle => {
+            forwarder_done = true;
+            supervisor_failure("Forwarder", result)
+        }
+    };
+    shutdown.cancel();
+
+    let offsets = join_collectors(collectors_handle).await;
+
+    // Collector 종료 -> Forwarder 잔여 플러시 후 tx 해제
+    if !forwarder_done {
+        let _ = forwarder_handle.await;
+    }
+    let _ = relay_handle.await;
+    let _ = sinks_handle.await;
+
+    save_handover(offsets, None);
+
+    if let Some(e) = fatal {
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// 서버 연결 후 저장된 토큰으로 인증, 토큰이 없으면 신규 등록
+async fn connect_and_authenticate(
+    server_addr: &str,
+    project_key: &str,
+    auth: &AuthSettings,
+) -> Result<(Channel, TokenManager)> {
+    let channel = endpoint::connect(server_addr, Some(CONNECT_TIMEOUT)).await?;
+
+    if auth.mode == AuthMode::OAuth2 {
+        let token_manager =
+            TokenManager::oauth2(AuthClient::new(channel.clone()), OAuth2Client::from_auth(auth)?).await?;
+        info!("{}", msg::TOKEN_LOADED);
+        return Ok((channel, token_manager));
+    }
+
+    let token_manager =
+        match TokenManager::load(AuthClient::new(channel.clone()), project_key.to_string()).await
+        {
+            Ok(token_manager) => token_manager,
+            Err(e) => {
+                warn!(error = %e, code = %ErrorCode::SavedTokenRejected, "{}", msg::SAVED_TOKEN_AUTH_FAILED);
+                TokenManager::register(AuthClient::new(channel.clone()), project_key.to_string())
+                    .await?
+            }
+        };
+
+    info!("{}", msg::TOKEN_LOADED);
+    Ok((channel, token_manager))
+}
+
+/// Collector 관리자 종료 대기 후 파일 Collector 의 마지막 위치 반환
+async fn join_collectors(handle: JoinHandle<Vec<CollectorOffset>>) -> Vec<CollectorOffset> {
+    handle.await.unwrap_or_else(|e| {
+        error!(error = ?e, code = %ErrorCode::CollectorFailed, "{}", msg::COLLECTOR_TASK_FAILED);
+        Vec::new()
+    })
+}
+
+/// 재실행 요청 시 드레인이 끝난 뒤 오프셋과 스풀 목록 기록
+fn save_handover(offsets: Vec<CollectorOffset>, spool: Option<&Spool>) {
+    if !hot_restart::is_requested() {
+        return;
+    }
+    // 상태 없는 실행은 수집 커서도 메모리에만 있어 새 프로세스가 처음부터 다시 읽음
+    if ephemeral::is_enabled() {
+        info!("{}", msg::HANDOVER_SKIPPED_EPHEMERAL);
+        return;
+    }
+
+    match Handover::new(offsets, spool).save() {
+        Ok(()) => info!("{}", msg::HANDOVER_SAVED),
+        Err(e) => error!(error = %format!("{:#}", e), "{}", msg::HANDOVER_SAVE_FAILED),
+    }
+}
+
+/// 레인마다 Forwarder 실행, 헬스 메트릭은 첫 레인 배치에 실음
+#[allow(clippy::too_many_arguments)]
+fn start_forwarders(
+    lanes: Vec<SharedReceiver<LogEvent>>,
+    metric_rx: SharedReceiver<MetricEvent>,
+    txs: Vec<Sender<LogBatch>>,
+    settings: &Settings,
+    router: Arc<Router>,
+    sinks: Vec<Sender<LogEvent>>,
+    anonymizer: Arc<Anonymizer>,
+    budget: MemoryBudget,
+    shutdown: CancellationToken,
+) -> JoinHandle<Result<()>> {
+    let log_metrics = Arc::new(LogMetrics::new(settings.log_metrics.clone()));
+    let sampler = settings.adaptive_sampling.clone().map(|s| Arc::new(Sampler::new(s)));
+    let mut forwarders = JoinSet::new();
+
+    for (lane, (rx, tx)) in lanes.into_iter().zip(txs).enumerate() {
+        let metric_rx = if lane == 0 {
+            Arc::clone(&metric_rx)
+        } else {
+            // 송신측이 없어 바로 닫히는 채널 (Forwarder 의 메트릭 분기 비활성화)
+            supervisor::shared(mpsc::channel::<MetricEvent>(1).1)
+        };
+
+        forwarders.spawn(forwarder_lane(
+            lane,
+            rx,
+            metric_rx,
+            tx,
+            settings,
+            Arc::clone(&router),
+            sinks.clone(),
+            Arc::clone(&anonymizer),
+            budget.clone(),
+            Arc::clone(&log_metrics),
+            sampler.clone(),
+            shutdown.clone(),
+        ));
+    }
+
+    join_lanes(forwarders)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn forwarder_lane(
+    lane: usize,
+    rx: SharedReceiver<LogEvent>,
+    metric_rx: SharedReceiver<MetricEvent>,
+    tx: Sender<LogBatch>,
+    settings: &Settings,
+    router: Arc<Router>,
+    sinks: Vec<Sender<LogEvent>>,
+    anonymizer: Arc<Anonymizer>,
+    budget: MemoryBudget,
+    log_metrics: Arc<LogMetrics>,
+    sampler: Option<Arc<Sampler>>,
+    shutdown: CancellationToken,
+) -> impl Future<Output = Result<()>> + Send + 'static {
+    let batch_size = settings.batch_size;
+    let flush_interval = settings.flush_interval;
+    let tags: HashMap<String, String> = settings.tags.clone().into_iter().collect();
+    let max_event_bytes = settings.max_event_bytes;
+    let dedup_window = settings.dedup_window;
+    let volume_anomaly = settings.volume_anomaly.clone();
+    // 재시작마다 이름을 새로 만들지 않도록 클로저 밖에서 한 번만 생성
+    let sink = lane_name("sink", lane);
+
+    supervisor::supervise(
+        lane_name("Forwarder", lane),
+        RestartPolicy::default(),
+        shutdown,
+        move |_| {
+            let forwarder = Forwarder::new(
+                Arc::clone(&rx),
+                Arc::clone(&metric_rx),
+                tx.clone(),
+                batch_size,
+                flush_interval,
+                budget.clone(),
+                Arc::clone(&log_metrics),
+            )
+            .with_tags(tags.clone())
+            .with_router(Arc::clone(&router), sinks.clone())
+            .with_anonymizer(Arc::clone(&anonymizer))
+            .with_max_event_bytes(max_event_bytes)
+            .with_dedup_window(dedup_window)
+            .with_sampler(sampler.clone())
+            .with_volume_anomaly(volume_anomaly.clone())
+            .with_sink_health(sink_health::lane(sink));
+
+            async move {
+                forwarder.start().await;
+                Ok(())
+            }
+        },
+    )
+}
+
+/// 레인마다 Streamer 실행, 이전 실행의 스풀은 첫 레인만 재전송
+#[allow(clippy::too_many_arguments)]
+fn start_streamers(
+    lanes: Vec<SharedReceiver<LogBatch>>,
+    server_addr: String,
+    channel: Channel,
+    token_manager: Arc<RwLock<TokenManager>>,
+    interceptor: AuthInterceptor,
+    spool: Spool,
+    budget: MemoryBudget,
+    max_event_age: u64,
+    http_fallback: Option<Arc<HttpFallback>>,
+    drain_deadline: CancellationToken,
+    shutdown: CancellationToken,
+) -> JoinHandle<Result<()>> {
+    let mut streamers = JoinSet::new();
+
+    for (lane, rx) in lanes.into_iter().enumerate() {
+        streamers.spawn(streamer_lane(
+            lane,
+            rx,
+            server_addr.clone(),
+            channel.clone(),
+            Arc::clone(&token_manager),
+            interceptor.clone(),
+            spool.clone(),
+            budget.clone(),
+            max_event_age,
+            http_fallback.clone(),
+            drain_deadline.clone(),
+            shutdown.clone(),
+        ));
+    }
+
+    join_lanes(streamers)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn streamer_lane(
+    lane: usize,
+    rx: SharedReceiver<LogBatch>,
+    server_addr: String,
+    channel: Channel,
+    token_manager: Arc<RwLock<TokenManager>>,
+    interceptor: AuthInterceptor,
+    spool: Spool,
+    budget: MemoryBudget,
+    max_event_age: u64,
+    http_fallback: Option<Arc<HttpFallback>>,
+    drain_deadline: CancellationToken,
+    shutdown: CancellationToken,
+) -> impl Future<Output = Result<()>> + Send + 'static {
+    // 재시작마다 이름을 새로 만들지 않도록 클로저 밖에서 한 번만 생성
+    let sink = lane_name("sink", lane);
+
+    supervisor::supervise(
+        lane_name("Streamer", lane),
+        RestartPolicy::default(),
+        shutdown,
+        move |attempt| {
+            let rx = Arc::clone(&rx);
+            let server_addr = server_addr.clone();
+            let channel = channel.clone();
+            let token_manager = Arc::clone(&token_manager);
+            let interceptor = interceptor.clone();
+            let spool = spool.clone();
+            let budget = budget.clone();
+            let http_fallback = http_fallback.clone();
+            let drain_deadline = drain_deadline.clone();
+
+            async move {
+                // 재시작 시 채널 재연결
+                let channel = if attempt == 0 { channel } else { redial(&server_addr)? };
+                let mut streamer = Streamer::new(rx, channel, interceptor, token_manager, spool, budget)
+                    .with_max_event_age(max_event_age)
+                    .with_sink_health(sink_health::lane(sink))
+                    .with_http_fallback(http_fallback);
+                if lane > 0 {
+                    streamer = streamer.skip_spool_resend();
+                }
+                streamer.start(drain_deadline).await;
+                Ok(())
+            }
+        },
+    )
+}
+
+/// 레인 수만큼 채널 생성, 상태 조회의 대기열 이름은 첫 레인 외에는 번호를 붙임
+fn lane_channels<T: Send + 'static>(
+    lanes: usize,
+    capacity: usize,
+    name: &'static str,
+) -> (Vec<Sender<T>>, Vec<SharedReceiver<T>>) {
+    (0..lanes)
+        .map(|lane| {
+            let (tx, rx) = mpsc::channel::<T>(capacity);
+            status::registry().register_queue(lane_name(name, lane), &tx);
+            (tx, supervisor::shared(rx))
+        })
+        .unzip()
+}
+
+/// 실행 중 관리 API 로 수집 대상이 늘어도 레인을 쓸 수 있도록 시작 시 수집 대상 수와 무관하게 `pipeline_workers` 만큼
+/// (수집 대상이 없는 레인은 Collector 관리자가 송신측을 쥐고 있어 대기만 함)
+fn lane_count(settings: &Settings) -> usize {
+    settings.pipeline_workers.max(1)
+}
+
+/// 라벨로 정해지는 레인, 같은 수집 대상의 이벤트는 항상 한 레인에서 순서대로 처리
+pub(crate) fn lane_for(label: &str, lanes: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    label.hash(&mut hasher);
+    (hasher.finish() % lanes as u64) as usize
+}
+
+/// 레인별 서브시스템/대기열 이름, 레인이 하나일 때와 같도록 첫 레인은 번호 없음
+///
+/// 이름마다 문자열을 해제하지 않으므로 레인을 만들 때만 호출 (재시작 클로저 안에서 호출하지 않음)
+fn lane_name(name: &'static str, lane: usize) -> &'static str {
+    if lane == 0 {
+        return name;
+    }
+
+    // 시작 시 레인 수만큼만 생성되므로 해제하지 않음
+    Box::leak(format!("{}-{}", name, lane).into_boxed_str())
+}
+
+/// 레인 감독 태스크를 하나로 묶어 한 서브시스템처럼 감시
+///
+/// 한 레인이 실패하면 나머지는 계속 드레인하도록 분리한 뒤 그 오류로 종료, abort 시 모든 레인 중단
+fn join_lanes(mut lanes: JoinSet<Result<()>>) -> JoinHandle<Result<()>> {
+    tokio::spawn(async move {
+        while let Some(result) = lanes.join_next().await {
+            let failure = match result {
+                Ok(Ok(())) => continue,
+                Ok(Err(e)) => e,
+                Err(e) => anyhow!("{}: {}", msg::SUPERVISOR_TASK_FAILED, e),
+            };
+
+            lanes.detach_all();
+            return Err(failure);
+        }
+
+        Ok(())
+    })
+}
+
+fn start_health_reporter(
+    server_addr: String,
+    channel: Channel,
+    token_manager: Arc<RwLock<TokenManager>>,
+    interceptor: AuthInterceptor,
+    metrics: Sender<MetricEvent>,
+    shutdown: CancellationToken,
+) -> JoinHandle<Result<()>> {
+    let reporter_shutdown = shutdown.clone();
+
+    tokio::spawn(supervisor::supervise(
+        "HealthReporter",
+        RestartPolicy::default(),
+        shutdown,
+        move |attempt| {
+            let server_addr = server_addr.clone();
+            let channel = channel.clone();
+            let token_manager = Arc::clone(&token_manager);
+            let interceptor = interceptor.clone();
+            let metrics = metrics.clone();
+            let shutdown = reporter_shutdown.clone();
+
+            async move {
+                let channel = if attempt == 0 { channel } else { redial(&server_addr)? };
+                let reporter = HealthReporter::new(channel, interceptor, token_manager, metrics);
+                reporter.start(shutdown).await;
+                Ok(())
+            }
+        },
+    ))
+}
+
+/// 재시작된 서브시스템용 채널 생성 (연결은 첫 요청 시 수립)
+fn redial(server_addr: &str) -> Result<Channel> {
+    endpoint::connect_lazy(server_addr)
+}
+
+/// 감독 태스크 종료 사유를 오류로 변환
+fn supervisor_failure(
+    name: &str,
+    result: Result<Result<()>, tokio::task::JoinError>,
+) -> Option<anyhow::Error> {
+    let e = match result {
+        Ok(Ok(())) => anyhow!("{}: {}", msg::SUPERVISOR_EXITED_UNEXPECTEDLY, name),
+        Ok(Err(e)) => e,
+        Err(e) => anyhow!("{}: {} ({})", msg::SUPERVISOR_TASK_FAILED, name, e),
+    };
+
+    error!("{:#}", e);
+    Some(e)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build() -> Result<Agent> {
+        let (tx, _rx) = mpsc::channel(1);
+        Agent::builder().sink(Sink::Channel(tx)).build()
+    }
+
+    #[test]
+    fn one_agent_per_process() {
+        let agent = build().unwrap();
+        let e = build().err().unwrap();
+        assert!(e.to_string().contains(&msg::AGENT_ALREADY_RUNNING.to_string()));
+
+        drop(agent);
+        build().unwrap();
+    }
+}
@@ -0,0 +1,173 @@
+//! 수집 대상별 로그 양 이상 탐지 (`volume_anomaly`)
+//!
+//! 구간(`window`)마다 수집 대상별 이벤트 수를 세어 지수 이동 평균으로 평소 양을 학습하고,
+//! 평소의 `spike_factor` 배를 넘으면 급증(spike), 한 건도 없으면 침묵(silence)으로 판단.
+//! 진행 중인 이상은 heartbeat 로 보고하고, `emit_events` 면 시작/해소 시 합성 이벤트도 배치에 실음
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::proto::health::VolumeAnomaly;
+use crate::settings::VolumeAnomalySettings;
+
+/// 평소 양을 학습하는 동안 판단하지 않는 구간 수
+const WARMUP_WINDOWS: u32 = 5;
+/// 지수 이동 평균에서 새 구간의 비중
+const BASELINE_ALPHA: f64 = 0.2;
+
+/// 합성 이벤트의 경로
+static ANOMALY_PATH: &str = "rlog-agent/volume-anomaly";
+
+/// 레인별 탐지기가 갱신하는 진행 중인 이상, 라벨별
+static ACTIVE: LazyLock<Mutex<BTreeMap<String, VolumeAnomaly>>> = LazyLock::new(Default::default);
+
+/// heartbeat 에 실을 진행 중인 이상
+pub fn active() -> Vec<VolumeAnomaly> {
+    lock().values().cloned().collect()
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<String, VolumeAnomaly>> {
+    ACTIVE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Spike,
+    Silence,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Spike => "spike",
+            Kind::Silence => "silence",
+        }
+    }
+}
+
+#[derive(Default)]
+struct SourceRate {
+    count: u64,
+    baseline: f64,
+    windows: u32,
+    anomaly: Option<Kind>,
+}
+
+/// Forwarder 레인 하나의 탐지기, 수집 대상은 레인에 고정되므로 레인끼리 겹치지 않음
+pub struct VolumeDetector {
+    settings: VolumeAnomalySettings,
+    sources: HashMap<String, SourceRate>,
+}
+
+impl VolumeDetector {
+    pub fn new(settings: VolumeAnomalySettings) -> Self {
+        Self {
+            settings,
+            sources: HashMap::new(),
+        }
+    }
+
+    /// 이벤트 수를 세는 구간
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.settings.window.max(1))
+    }
+
+    pub fn observe(&mut self, event: &LogEvent) {
+        match self.sources.get_mut(&event.label) {
+            Some(rate) => rate.count += 1,
+            None => {
+                self.sources.insert(event.label.clone(), SourceRate { count: 1, ..Default::default() });
+            }
+        }
+    }
+
+    /// 구간 종료, 이상이 시작/해소된 수집 대상의 합성 이벤트 반환 (`emit_events` 가 아니면 비어 있음)
+    pub fn evaluate(&mut self) -> Vec<LogEvent> {
+        let mut events = Vec::new();
+
+        for (label, rate) in self.sources.iter_mut() {
+            let count = rate.count as f64;
+            let detected = if rate.windows < WARMUP_WINDOWS || rate.baseline < self.settings.min_baseline {
+                None
+            } else if rate.count == 0 {
+                Some(Kind::Silence)
+            } else if count > rate.baseline * self.settings.spike_factor {
+                Some(Kind::Spike)
+            } else {
+                None
+            };
+
+            if detected != rate.anomaly {
+                let anomaly = VolumeAnomaly {
+                    source: label.clone(),
+                    kind: detected.map_or("recovered", Kind::as_str).to_string(),
+                    count: rate.count,
+                    baseline: rate.baseline,
+                };
+
+                match detected {
+                    Some(_) => {
+                        warn!(
+                            source = %label,
+                            kind = %anomaly.kind,
+                            count = rate.count,
+                            baseline = rate.baseline,
+                            "{}",
+                            msg::VOLUME_ANOMALY_DETECTED
+                        );
+                        lock().insert(label.clone(), anomaly.clone());
+                    }
+                    None => {
+                        info!(source = %label, count = rate.count, baseline = rate.baseline, "{}", msg::VOLUME_ANOMALY_RECOVERED);
+                        lock().remove(label);
+                    }
+                }
+                if self.settings.emit_events {
+                    events.push(synthetic_event(&anomaly));
+                }
+                rate.anomaly = detected;
+            } else if let Some(active) = lock().get_mut(label) {
+                active.count = rate.count;
+            }
+
+            // 침묵 중에는 평소 양을 유지해야 이벤트가 다시 올 때까지 침묵으로 남음
+            rate.baseline = match (rate.windows, detected) {
+                (0, _) => count,
+                (_, Some(Kind::Silence)) => rate.baseline,
+                _ => rate.baseline + BASELINE_ALPHA * (count - rate.baseline),
+            };
+            rate.windows = rate.windows.saturating_add(1);
+            rate.count = 0;
+        }
+
+        events
+    }
+}
+
+fn synthetic_event(anomaly: &VolumeAnomaly) -> LogEvent {
+    let line = format!(
+        "volume anomaly {}: {} events in window, baseline {:.1}",
+        anomaly.kind, anomaly.count, anomaly.baseline
+    );
+
+    LogEvent {
+        label: anomaly.source.clone(),
+        content: Bytes::from(line),
+        timestamp: Utc::now(),
+        path: Arc::from(ANOMALY_PATH),
+        offset: 0,
+        file_id: 0,
+        fields: vec![
+            ("anomaly".to_string(), anomaly.kind.clone()),
+            ("anomaly_count".to_string(), anomaly.count.to_string()),
+            ("anomaly_baseline".to_string(), format!("{:.1}", anomaly.baseline)),
+        ],
+    }
+}
@@ -0,0 +1,161 @@
+//! 필드 익명화 (`anonymize`)
+//!
+//! 설정한 필드(사용자 ID, IP 등)의 값을 전송 전에 솔트 해시나 형식을 유지한 마스크로 바꿈.
+//! 필드가 뽑힌 라인 원문에도 같은 값이 남아 있으므로 라인 안의 값도 함께 바꿈
+//!
+//! ```text
+//! hash  user_id=alice        → user_id=3f1c9a0e5b7d2c48a6e0f1b29c4d7e83
+//! mask  client_ip=10.1.2.3   → client_ip=10.1.2.0
+//!       email=alice@corp.io  → email=a****@corp.io
+//!       card=4111-1111-1234  → card=4***-****-***4
+//! ```
+
+use std::net::IpAddr;
+
+use anyhow::{Result, bail};
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::settings::{AnonymizeMethod, AnonymizeRule, AnonymizeSettings};
+
+/// 해시 결과로 쓰는 HMAC-SHA256 앞부분 길이 (바이트)
+const HASH_BYTES: usize = 16;
+
+pub struct Anonymizer {
+    salt: Vec<u8>,
+    rules: Vec<AnonymizeRule>,
+}
+
+impl Anonymizer {
+    /// `hash` 규칙이 있는데 솔트가 비어 있으면 오류
+    pub fn new(settings: &AnonymizeSettings) -> Result<Self> {
+        let hashes = settings.fields.iter().any(|rule| rule.method == AnonymizeMethod::Hash);
+        if hashes && settings.salt.is_empty() {
+            bail!(msg::ANONYMIZE_SALT_REQUIRED);
+        }
+
+        Ok(Self {
+            salt: settings.salt.as_bytes().to_vec(),
+            rules: settings.fields.clone(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 규칙에 맞는 필드 값과 라인 안의 같은 값을 바꿈, 크기가 달라진 만큼 메모리 예산 조정
+    pub fn apply(&self, event: &mut LogEvent, budget: &MemoryBudget) {
+        let before = memory::event_bytes(event);
+        let mut line = None::<String>;
+
+        for (key, value) in event.fields.iter_mut() {
+            let Some(rule) = self
+                .rules
+                .iter()
+                .find(|rule| rule.field == *key && rule.source.as_ref().is_none_or(|source| *source == event.label))
+            else {
+                continue;
+            };
+            if value.is_empty() {
+                continue;
+            }
+
+            let replaced = match rule.method {
+                AnonymizeMethod::Hash => self.hash(value),
+                AnonymizeMethod::Mask => mask(value),
+            };
+
+            let current = line.as_deref().unwrap_or(std::str::from_utf8(&event.content).unwrap_or_default());
+            if let Some(updated) = replace_tokens(current, value, &replaced) {
+                line = Some(updated);
+            }
+            *value = replaced;
+        }
+
+        if let Some(line) = line {
+            event.content = Bytes::from(line);
+        }
+
+        let after = memory::event_bytes(event);
+        if after > before {
+            budget.add(after - before);
+        } else if after < before {
+            budget.release(before - after);
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.salt).expect("HMAC accepts any key length");
+        mac.update(value.as_bytes());
+
+        mac.finalize().into_bytes()[..HASH_BYTES]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+/// IP 는 네트워크 부분만 (IPv4 /24, IPv6 /48), 이메일은 로컬 파트 첫 글자와 도메인만,
+/// 그 외는 첫/끝 글자와 구분 문자만 남기고 영숫자를 `*` 로 (길이 유지)
+fn mask(value: &str) -> String {
+    match value.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            let [a, b, c, _] = ip.octets();
+            return format!("{}.{}.{}.0", a, b, c);
+        }
+        Ok(IpAddr::V6(ip)) => {
+            let s = ip.segments();
+            return format!("{:x}:{:x}:{:x}::", s[0], s[1], s[2]);
+        }
+        Err(_) => {}
+    }
+
+    if let Some((local, domain)) = value.split_once('@')
+        && !local.is_empty()
+        && !domain.is_empty()
+    {
+        let mut chars = local.chars();
+        let first = chars.next().unwrap_or_default();
+        return format!("{}{}@{}", first, "*".repeat(chars.count()), domain);
+    }
+
+    let count = value.chars().count();
+    value
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let edge = count > 4 && (i == 0 || i == count - 1);
+            if edge || !c.is_alphanumeric() { c } else { '*' }
+        })
+        .collect()
+}
+
+/// `line` 안에서 앞뒤가 영숫자가 아닌 `value` 를 모두 `replacement` 로, 바꾼 곳이 없으면 None
+///
+/// 짧은 값(`1`, `ok`)이 다른 단어 일부까지 바꾸지 않도록 단어 경계만 봄
+fn replace_tokens(line: &str, value: &str, replacement: &str) -> Option<String> {
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+
+    let mut out = String::with_capacity(line.len());
+    let mut last = 0;
+    for (start, _) in line.match_indices(value) {
+        let end = start + value.len();
+        if is_word(line[..start].chars().next_back()) || is_word(line[end..].chars().next()) {
+            continue;
+        }
+        out.push_str(&line[last..start]);
+        out.push_str(replacement);
+        last = end;
+    }
+
+    if last == 0 {
+        return None;
+    }
+    out.push_str(&line[last..]);
+    Some(out)
+}
@@ -2,16 +2,26 @@ use anyhow::Result;
 use tonic::transport::Channel;
 use tracing::info;
 
+use crate::capabilities::{self, Negotiated};
+use crate::clock;
+use crate::messages as msg;
+use crate::retry;
+
 use crate::proto::auth::auth_service_client::AuthServiceClient;
 use crate::proto::auth::{RefreshRequest, RefreshResponse, RegisterRequest, RegisterResponse};
 
 pub struct AuthClient {
     client: AuthServiceClient<Channel>,
+    /// 마지막 등록/갱신 응답으로 협상한 기능
+    negotiated: Negotiated,
 }
 
 impl AuthClient {
     pub fn new(channel: Channel) -> Self {
-        Self { client: AuthServiceClient::new(channel) }
+        Self {
+            client: AuthServiceClient::new(channel),
+            negotiated: Negotiated::default(),
+        }
     }
 
     pub async fn register(
@@ -31,20 +41,48 @@ impl AuthClient {
             os,
             os_version,
             agent_uuid: agent_uuid.map(|s| s.to_string()),
+            capabilities: Some(capabilities::local()),
         };
 
-        let response = self.client.register(req).await?.into_inner();
-        info!("Agent 등록 완료");
+        let client = self.client.clone();
+        let response = retry::with_backoff(|| {
+            let mut client = client.clone();
+            let req = req.clone();
+            async move { client.register(req).await }
+        })
+        .await?;
+        clock::record(response.metadata());
+        let response = response.into_inner();
+        info!("{}", msg::AGENT_REGISTERED);
+
+        self.negotiated = capabilities::negotiate(response.capabilities.as_ref());
 
         Ok(response)
     }
 
     pub async fn refresh(&mut self, refresh_token: String) -> Result<RefreshResponse> {
-        let req = RefreshRequest { refresh_token };
+        let req = RefreshRequest {
+            refresh_token,
+            capabilities: Some(capabilities::local()),
+        };
 
-        let response = self.client.refresh(req).await?.into_inner();
-        info!("토큰 갱신 완료");
+        let client = self.client.clone();
+        let response = retry::with_backoff(|| {
+            let mut client = client.clone();
+            let req = req.clone();
+            async move { client.refresh(req).await }
+        })
+        .await?;
+        clock::record(response.metadata());
+        let response = response.into_inner();
+        info!("{}", msg::TOKEN_REFRESHED);
+
+        self.negotiated = capabilities::negotiate(response.capabilities.as_ref());
 
         Ok(response)
     }
+
+    pub fn negotiated(&self) -> Negotiated {
+        self.negotiated
+    }
 }
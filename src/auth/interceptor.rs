@@ -2,6 +2,7 @@ use tonic::service::Interceptor;
 use tonic::{Request, Status};
 
 use crate::auth::token_manager::SharedAccessToken;
+use crate::messages as msg;
 
 static AUTHORIZATION: &str = "authorization";
 
@@ -21,16 +22,16 @@ impl Interceptor for AuthInterceptor {
         let token = self
             .access_token
             .read()
-            .map_err(|_| Status::internal("토큰 읽기 실패 (RwLock poisoned)"))?
+            .map_err(|_| Status::internal(msg::TOKEN_READ_FAILED.text()))?
             .clone();
 
         if token.is_empty() {
-            return Err(Status::unauthenticated("토큰이 없습니다"));
+            return Err(Status::unauthenticated(msg::TOKEN_MISSING.text()));
         }
 
         let value = format!("Bearer {}", token)
             .parse()
-            .map_err(|_| Status::internal("토큰 형식이 잘못되었습니다."))?;
+            .map_err(|_| Status::internal(msg::TOKEN_MALFORMED.text()))?;
 
         request.metadata_mut().insert(AUTHORIZATION, value);
 
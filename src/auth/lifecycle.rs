@@ -0,0 +1,120 @@
+//! access_token 수명 통계 (발급 시각, 만료까지 남은 시간, 갱신 성공/실패, 마지막 재등록)
+//!
+//! TokenManager 가 기록하고 Heartbeat, 메트릭, 관리 API `status` 로 노출. 만료가 가까워지면 Heartbeat 주기에 경고를 남겨
+//! 전송 실패로 드러나기 전에 인증 문제를 확인할 수 있게 함
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error_code::ErrorCode;
+use crate::messages as msg;
+
+/// 만료까지 이보다 적게 남으면 경고 (토큰마다 한 번)
+const EXPIRY_WARNING_SECS: i64 = 300;
+
+static STATS: LazyLock<Stats> = LazyLock::new(Stats::default);
+
+#[derive(Default)]
+struct Stats {
+    issued_at: Mutex<Option<DateTime<Utc>>>,
+    last_register: Mutex<Option<DateTime<Utc>>>,
+    refresh_successes: AtomicU64,
+    refresh_failures: AtomicU64,
+    /// 경고를 남긴 토큰의 만료 시각
+    warned_expiry: Mutex<Option<DateTime<Utc>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 관리 API `status` 와 Heartbeat 에 실을 토큰 수명 통계
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenLifecycle {
+    pub issued_at: Option<DateTime<Utc>>,
+    /// JWT 가 아니면 None
+    pub expires_at: Option<DateTime<Utc>>,
+    pub refresh_successes: u64,
+    pub refresh_failures: u64,
+    pub last_register: Option<DateTime<Utc>>,
+}
+
+impl TokenLifecycle {
+    pub fn age_secs(&self) -> Option<i64> {
+        self.issued_at.map(|at| (Utc::now() - at).num_seconds())
+    }
+
+    pub fn expires_in_secs(&self) -> Option<i64> {
+        self.expires_at.map(|at| (at - Utc::now()).num_seconds())
+    }
+}
+
+/// 새 access_token 을 받음
+pub fn record_issued() {
+    *lock(&STATS.issued_at) = Some(Utc::now());
+}
+
+/// 등록 또는 재등록 성공
+pub fn record_register() {
+    *lock(&STATS.last_register) = Some(Utc::now());
+}
+
+pub fn record_refresh(success: bool) {
+    let counter = if success {
+        &STATS.refresh_successes
+    } else {
+        &STATS.refresh_failures
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 현재 토큰 기준 통계
+pub fn snapshot(token: Option<&str>) -> TokenLifecycle {
+    TokenLifecycle {
+        issued_at: *lock(&STATS.issued_at),
+        expires_at: token.and_then(jwt_expiry),
+        refresh_successes: STATS.refresh_successes.load(Ordering::Relaxed),
+        refresh_failures: STATS.refresh_failures.load(Ordering::Relaxed),
+        last_register: *lock(&STATS.last_register),
+    }
+}
+
+/// 만료가 `EXPIRY_WARNING_SECS` 안으로 다가왔으면 경고, 같은 토큰에는 한 번만
+pub fn warn_if_expiring(lifecycle: &TokenLifecycle) {
+    let (Some(expires_at), Some(expires_in)) = (lifecycle.expires_at, lifecycle.expires_in_secs()) else {
+        return;
+    };
+    if expires_in > EXPIRY_WARNING_SECS {
+        return;
+    }
+
+    let mut warned = lock(&STATS.warned_expiry);
+    if *warned == Some(expires_at) {
+        return;
+    }
+    *warned = Some(expires_at);
+
+    warn!(
+        expires_at = %expires_at.to_rfc3339(),
+        expires_in_secs = expires_in,
+        refresh_failures = lifecycle.refresh_failures,
+        code = %ErrorCode::TokenExpiringSoon,
+        "{}",
+        msg::TOKEN_EXPIRING_SOON
+    );
+}
+
+/// JWT access token 의 `exp` 클레임, JWT 가 아니면 None
+pub fn jwt_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+
+    Utc.timestamp_opt(claims.get("exp")?.as_i64()?, 0).single()
+}
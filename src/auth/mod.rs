@@ -1,3 +1,5 @@
 pub mod client;
 pub mod interceptor;
+pub mod lifecycle;
+pub mod oauth2;
 pub mod token_manager;
@@ -0,0 +1,157 @@
+//! OAuth2 client credentials 토큰 발급 (`auth.mode: oauth2`)
+//!
+//! 서버 앞단에 기존 인증 서버(IdP)가 있으면 rlog 등록/갱신 대신 표준 토큰 엔드포인트에서 access_token 을 받아 사용.
+//! 만료 전에 미리 다시 발급받아 교체
+
+use std::sync::{RwLock, Weak};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use tokio::time;
+use tracing::{info, warn};
+
+use crate::auth::lifecycle;
+use crate::error_code::ErrorCode;
+use crate::messages as msg;
+use crate::retry::Backoff;
+use crate::settings::{AuthSettings, OAuth2Settings};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 만료까지 남은 시간 중 이 비율이 지나면 미리 갱신
+const REFRESH_AT: f64 = 0.8;
+
+/// `expires_in` 이 없을 때 갱신 주기
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Clone)]
+pub struct OAuth2Client {
+    http: reqwest::Client,
+    settings: OAuth2Settings,
+}
+
+/// 토큰 엔드포인트 응답 (RFC 6749 5.1)
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// 토큰 엔드포인트 오류 응답 (RFC 6749 5.2)
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+pub struct AccessToken {
+    pub value: String,
+    pub expires_in: Option<Duration>,
+}
+
+impl OAuth2Client {
+    pub fn new(settings: OAuth2Settings) -> Result<Self> {
+        if settings.token_url.trim().is_empty() || settings.client_id.trim().is_empty() {
+            bail!(msg::OAUTH2_SETTINGS_REQUIRED);
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context(msg::OAUTH2_CLIENT_FAILED)?;
+
+        Ok(Self { http, settings })
+    }
+
+    /// 설정의 `auth.oauth2` 로 생성
+    pub fn from_auth(auth: &AuthSettings) -> Result<Self> {
+        Self::new(auth.oauth2.clone().context(msg::OAUTH2_SETTINGS_REQUIRED)?)
+    }
+
+    /// client_credentials 로 access_token 발급, 클라이언트 인증은 HTTP Basic
+    pub async fn fetch(&self) -> Result<AccessToken> {
+        let mut form = vec![("grant_type", "client_credentials")];
+        if let Some(scope) = &self.settings.scope {
+            form.push(("scope", scope.as_str()));
+        }
+        if let Some(audience) = &self.settings.audience {
+            form.push(("audience", audience.as_str()));
+        }
+
+        let response = self
+            .http
+            .post(&self.settings.token_url)
+            .basic_auth(&self.settings.client_id, Some(&self.settings.client_secret))
+            .form(&form)
+            .send()
+            .await
+            .context(msg::OAUTH2_REQUEST_FAILED)?;
+
+        let status = response.status();
+        let body = response.bytes().await.context(msg::OAUTH2_REQUEST_FAILED)?;
+
+        if !status.is_success() {
+            let detail = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|e| match e.error_description {
+                    Some(description) => format!("{} ({})", e.error, description),
+                    None => e.error,
+                })
+                .unwrap_or_else(|_| status.to_string());
+            bail!("{}: {}", msg::OAUTH2_TOKEN_REJECTED, detail);
+        }
+
+        let token: TokenResponse = serde_json::from_slice(&body).context(msg::OAUTH2_INVALID_RESPONSE)?;
+        info!("{}", msg::OAUTH2_TOKEN_ISSUED);
+
+        Ok(AccessToken {
+            value: token.access_token,
+            expires_in: token.expires_in.map(Duration::from_secs),
+        })
+    }
+}
+
+/// 만료 전에 다시 발급받아 공유 토큰 교체, 토큰을 쓰는 쪽(TokenManager)이 모두 사라지면 종료
+pub async fn keep_fresh(client: OAuth2Client, token: Weak<RwLock<String>>, expires_in: Option<Duration>) {
+    let mut wait = refresh_after(expires_in);
+    let mut backoff = Backoff::new();
+
+    loop {
+        time::sleep(wait).await;
+        let Some(token) = token.upgrade() else {
+            return;
+        };
+
+        let issued = client.fetch().await;
+        lifecycle::record_refresh(issued.is_ok());
+        match issued {
+            Ok(issued) => {
+                match token.write() {
+                    Ok(mut token) => {
+                        *token = issued.value;
+                        lifecycle::record_issued();
+                    }
+                    Err(e) => warn!(error = ?e, code = %ErrorCode::TokenWriteFailed, "{}", msg::ACCESS_TOKEN_WRITE_FAILED),
+                }
+                wait = refresh_after(issued.expires_in);
+                backoff.reset();
+            }
+            Err(e) => {
+                wait = backoff.next_delay();
+                warn!(
+                    backoff_ms = wait.as_millis() as u64,
+                    error = %format!("{:#}", e),
+                    code = %ErrorCode::TokenRefreshFailed,
+                    "{}",
+                    msg::OAUTH2_REFRESH_FAILED
+                );
+            }
+        }
+    }
+}
+
+fn refresh_after(expires_in: Option<Duration>) -> Duration {
+    expires_in.map_or(DEFAULT_REFRESH_INTERVAL, |expires_in| expires_in.mul_f64(REFRESH_AT))
+}
@@ -1,15 +1,23 @@
-use std::fs;
-use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 use crate::auth::client::AuthClient;
+use crate::auth::lifecycle;
+use crate::auth::oauth2::{self, OAuth2Client};
+use crate::capabilities::Negotiated;
+use crate::ephemeral;
+use crate::error_code::ErrorCode;
+use crate::messages as msg;
+use crate::state_store;
 use anyhow::{anyhow, Context, Result};
 use tracing::{error, info};
 
-static REFRESH_TOKEN_PATH: &str = "state/token";
+/// 보관소 키
+static REFRESH_TOKEN_KEY: &str = "token";
 static REFRESH_TOKEN: &str = "refresh_token";
-static AGENT_UUID_PATH: &str = "state/agent_uuid";
+static AGENT_UUID_KEY: &str = "agent_uuid";
 static AGENT_UUID: &str = "agent_uuid";
+/// 명령줄로 바꾼 서버의 토큰을 둘 보관소 키 접두어, 없으면 설정 파일 서버의 토큰
+static SERVER_SCOPE: Mutex<Option<String>> = Mutex::new(None);
 
 pub type SharedAccessToken = Arc<RwLock<String>>;
 
@@ -19,6 +27,8 @@ pub struct TokenManager {
     refresh_token: String,
     agent_uuid: String,
     project_key: String,
+    /// 있으면 rlog 등록/갱신 대신 OAuth2 토큰 엔드포인트에서 발급
+    oauth2: Option<OAuth2Client>,
 }
 
 impl TokenManager {
@@ -31,6 +41,8 @@ impl TokenManager {
     ) -> Result<Self> {
         Self::save_refresh_token(&refresh_token)?;
         Self::save_agent_uuid(&agent_uuid)?;
+        lifecycle::record_register();
+        lifecycle::record_issued();
 
         Ok(Self {
             auth_client,
@@ -38,6 +50,7 @@ impl TokenManager {
             refresh_token,
             agent_uuid,
             project_key,
+            oauth2: None,
         })
     }
 
@@ -50,11 +63,14 @@ impl TokenManager {
             match auth_client.refresh(refresh_token.clone()).await {
                 Ok(resp) if resp.success => {
                     Self::save_refresh_token(&resp.refresh_token)?;
-                    info!("저장된 토큰으로 인증 완료");
+                    lifecycle::record_refresh(true);
+                    lifecycle::record_issued();
+                    info!("{}", msg::AUTHENTICATED_WITH_SAVED_TOKEN);
                     (resp.access_token, resp.refresh_token, agent_uuid)
                 }
                 Ok(_) | Err(_) => {
-                    info!("토큰 갱신 실패, 재등록 시도");
+                    lifecycle::record_refresh(false);
+                    info!(code = %ErrorCode::TokenRefreshFailed, "{}", msg::TOKEN_REFRESH_FAILED_REREGISTER);
                     Self::do_register(&mut auth_client, &project_key).await?
                 }
             };
@@ -65,15 +81,92 @@ impl TokenManager {
             auth_client,
             agent_uuid,
             project_key,
+            oauth2: None,
+        })
+    }
+
+    /// 저장된 refresh_token 으로만 인증, 실패해도 재등록하지 않음 (자가 진단용)
+    pub async fn load_without_register(
+        mut auth_client: AuthClient,
+        project_key: String,
+    ) -> Result<Self> {
+        let refresh_token = Self::load_refresh_token().context(msg::SAVED_TOKEN_MISSING)?;
+        let agent_uuid = Self::load_agent_uuid().unwrap_or_default();
+
+        let response = auth_client
+            .refresh(refresh_token)
+            .await
+            .context(msg::TOKEN_REFRESH_REQUEST_FAILED)?;
+
+        if !response.success {
+            return Err(anyhow!(msg::TOKEN_REFRESH_REJECTED));
+        }
+
+        Self::save_refresh_token(&response.refresh_token)?;
+
+        Ok(Self {
+            access_token: Arc::new(RwLock::new(response.access_token)),
+            refresh_token: response.refresh_token,
+            auth_client,
+            agent_uuid,
+            project_key,
+            oauth2: None,
+        })
+    }
+
+    /// 저장된 토큰 없이 등록 수행 (저장된 agent_uuid 가 있으면 재등록)
+    pub async fn register(mut auth_client: AuthClient, project_key: String) -> Result<Self> {
+        let (access_token, refresh_token, agent_uuid) =
+            Self::do_register(&mut auth_client, &project_key).await?;
+
+        Ok(Self {
+            access_token: Arc::new(RwLock::new(access_token)),
+            refresh_token,
+            auth_client,
+            agent_uuid,
+            project_key,
+            oauth2: None,
+        })
+    }
+
+    /// OAuth2 client credentials 로 발급, 만료 전 갱신은 백그라운드에서 계속
+    pub async fn oauth2(auth_client: AuthClient, oauth2: OAuth2Client) -> Result<Self> {
+        let issued = oauth2.fetch().await?;
+        lifecycle::record_issued();
+        let access_token = Arc::new(RwLock::new(issued.value));
+        tokio::spawn(oauth2::keep_fresh(
+            oauth2.clone(),
+            Arc::downgrade(&access_token),
+            issued.expires_in,
+        ));
+
+        Ok(Self {
+            auth_client,
+            access_token,
+            refresh_token: String::new(),
+            agent_uuid: Self::load_agent_uuid().unwrap_or_default(),
+            project_key: String::new(),
+            oauth2: Some(oauth2),
         })
     }
 
     /// access_token 갱신
     pub async fn refresh(&mut self) -> Result<()> {
+        if let Some(oauth2) = &self.oauth2 {
+            let issued = oauth2.fetch().await;
+            lifecycle::record_refresh(issued.is_ok());
+            self.update_access_token(&issued?.value);
+            return Ok(());
+        }
+
         let response = match self.auth_client.refresh(self.refresh_token.clone()).await {
-            Ok(resp) if resp.success => resp,
+            Ok(resp) if resp.success => {
+                lifecycle::record_refresh(true);
+                resp
+            }
             Ok(_) | Err(_) => {
-                info!("토큰 갱신 실패, 재등록 시도");
+                lifecycle::record_refresh(false);
+                info!(code = %ErrorCode::TokenRefreshFailed, "{}", msg::TOKEN_REFRESH_FAILED_REREGISTER);
                 return self.re_register().await;
             }
         };
@@ -110,15 +203,17 @@ impl TokenManager {
         let response = auth_client
             .register(project_key, agent_uuid.as_deref())
             .await
-            .context("등록 중 오류")?;
+            .context(msg::REGISTER_ERROR)?;
 
         if !response.success {
-            return Err(anyhow!("등록 실패"));
+            return Err(anyhow!(msg::REGISTER_FAILED));
         }
 
         Self::save_refresh_token(&response.refresh_token)?;
         Self::save_agent_uuid(&response.agent_uuid)?;
-        info!("등록 완료");
+        lifecycle::record_register();
+        lifecycle::record_issued();
+        info!("{}", msg::REGISTERED);
 
         Ok((
             response.access_token,
@@ -130,55 +225,64 @@ impl TokenManager {
     /// access_token 업데이트
     fn update_access_token(&self, new_token: &str) {
         match self.access_token.write() {
-            Ok(mut token) => *token = new_token.to_string(),
-            Err(e) => error!("access_token 쓰기 실패 (RwLock poisoned): {:?}", e),
+            Ok(mut token) => {
+                *token = new_token.to_string();
+                lifecycle::record_issued();
+            }
+            Err(e) => error!(error = ?e, code = %ErrorCode::TokenWriteFailed, "{}", msg::ACCESS_TOKEN_WRITE_FAILED),
         }
     }
 
+    /// 마지막 인증 응답 기준 서버와 협상한 기능
+    pub fn negotiated(&self) -> Negotiated {
+        self.auth_client.negotiated()
+    }
+
     /// AuthInterceptor에 전달할 SharedAccessToken 반환
     pub fn get_shared_token(&self) -> SharedAccessToken {
         Arc::clone(&self.access_token)
     }
 
-    fn load_refresh_token() -> Result<String> { Self::load_from_file(REFRESH_TOKEN_PATH, REFRESH_TOKEN) }
+    /// 설정 파일과 다른 서버에 붙는 실행의 토큰/agent_uuid 는 서버 주소별 키(`servers/<주소>/`)에 저장,
+    /// 잠시 다른 서버로 전환해도 설정 파일 서버의 등록 정보를 덮어쓰지 않음
+    pub fn scope_to_server(server_addr: &str) {
+        let name: String = server_addr
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect();
+        *SERVER_SCOPE.lock().unwrap_or_else(|e| e.into_inner()) = Some(format!("servers/{}", name));
+    }
+
+    /// 등록 시 저장한 agent_uuid, 등록 전이면 None
+    pub fn stored_agent_uuid() -> Option<String> {
+        Self::load_agent_uuid().ok().map(|uuid| uuid.trim().to_string())
+    }
+
+    fn load_refresh_token() -> Result<String> { Self::load_value(REFRESH_TOKEN_KEY, REFRESH_TOKEN) }
 
-    fn save_refresh_token(refresh_token: &str) -> Result<()> { Self::save_to_file(REFRESH_TOKEN_PATH, refresh_token) }
+    fn save_refresh_token(refresh_token: &str) -> Result<()> { state_store::store().save(&Self::key(REFRESH_TOKEN_KEY), refresh_token) }
 
+    /// 상태 없는 실행에서 아직 등록 전이면 `AGENT_UUID` 환경 변수
     fn load_agent_uuid() -> Result<String> {
-        Self::load_from_file(AGENT_UUID_PATH, AGENT_UUID)
+        Self::load_value(AGENT_UUID_KEY, AGENT_UUID).or_else(|e| ephemeral::agent_uuid().ok_or(e))
     }
 
-    fn save_agent_uuid(agent_uuid: &str) -> Result<()> { Self::save_to_file(AGENT_UUID_PATH, agent_uuid) }
+    fn save_agent_uuid(agent_uuid: &str) -> Result<()> { state_store::store().save(&Self::key(AGENT_UUID_KEY), agent_uuid) }
 
-    fn load_from_file(file_path: &str, name: &str) -> Result<String> {
-        let path = Path::new(file_path);
-        let content = fs::read_to_string(path)?;
+    fn load_value(key: &str, name: &str) -> Result<String> {
+        let content = state_store::store().load(&Self::key(key))?.unwrap_or_default();
 
         if content.trim().is_empty() {
-            return Err(anyhow!("저장된 {}가 비어 있음", name));
+            return Err(anyhow!("{}: {}", msg::SAVED_VALUE_EMPTY, name));
         }
 
         Ok(content)
     }
 
-    fn save_to_file(file_path: &str, content: &str) -> Result<()> {
-        let path = Path::new(file_path);
-
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
-        let tmp = path.with_extension("tmp");
-        fs::write(&tmp, content)?;
-        fs::rename(&tmp, path)?;
-
-        // Unix 파일 권한 설정 (0600 - 소유자만 읽기/쓰기)
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    fn key(name: &str) -> String {
+        match SERVER_SCOPE.lock().unwrap_or_else(|e| e.into_inner()).as_deref() {
+            Some(scope) => format!("{}/{}", scope, name),
+            None => name.to_string(),
         }
-
-        Ok(())
     }
 }
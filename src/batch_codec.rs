@@ -0,0 +1,55 @@
+//! 공유 배치 전송용 gRPC 코덱
+//!
+//! 생성된 클라이언트는 [`LogBatch`] 를 값으로 받아 재시도에 대비하려면 매번 복제해야 하므로,
+//! `Arc<LogBatch>` 를 그대로 인코딩해 재전송/스풀과 같은 배치를 공유
+
+use std::sync::Arc;
+
+use prost::Message;
+use tonic::codec::{Codec, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::{GrpcMethod, Status};
+use tonic_prost::ProstDecoder;
+
+use crate::proto::log::{LogBatch, SendResponse};
+
+pub const SEND_PATH: &str = "/log.LogService/Send";
+
+/// 인터셉터와 추적에 전달되는 메서드 정보 (생성된 클라이언트와 같은 값)
+pub fn send_method() -> (PathAndQuery, GrpcMethod<'static>) {
+    (
+        PathAndQuery::from_static(SEND_PATH),
+        GrpcMethod::new("log.LogService", "Send"),
+    )
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedBatchCodec;
+
+impl Codec for SharedBatchCodec {
+    type Encode = Arc<LogBatch>;
+    type Decode = SendResponse;
+    type Encoder = SharedBatchEncoder;
+    type Decoder = ProstDecoder<SendResponse>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        SharedBatchEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        ProstDecoder::default()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SharedBatchEncoder;
+
+impl Encoder for SharedBatchEncoder {
+    type Item = Arc<LogBatch>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, buf: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(buf)
+            .map_err(|e| Status::internal(e.to_string()))
+    }
+}
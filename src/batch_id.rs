@@ -0,0 +1,74 @@
+//! 배치 식별자 (`{agent_uuid}-{순번}`)
+//!
+//! 배치마다 새 UUID 대신 에이전트 UUID 와 단조 증가 순번을 써서, 재연결 뒤 스풀에서 다시 보내는 배치도 같은 식별자를 가짐.
+//! 서버는 받았지만 응답하지 못한 배치를 이 식별자로 중복 제거할 수 있음.
+//! 순번은 상태 보관소의 `batch_seq`(기본 `state/batch_seq`)에 블록 단위로 예약해 두므로 재실행 뒤에도 겹치지 않음 (쓰지 못한 나머지는 건너뜀).
+//! 상태 없는 실행은 `AGENT_UUID` 가 고정인데 순번은 메모리에만 있으므로 실행마다 임의 구간을 붙임 (`{agent_uuid}-{실행}-{순번}`)
+
+use std::sync::{LazyLock, Mutex};
+
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::auth::token_manager::TokenManager;
+use crate::ephemeral;
+use crate::messages as msg;
+use crate::state_store;
+
+static SEQUENCE_KEY: &str = "batch_seq";
+
+/// 한 번에 예약하는 순번 수, 예약할 때만 보관소에 씀
+const RESERVE_BLOCK: u64 = 10_000;
+
+static SEQUENCE: LazyLock<Mutex<Sequence>> = LazyLock::new(|| Mutex::new(Sequence::load()));
+
+struct Sequence {
+    prefix: String,
+    next: u64,
+    reserved_until: u64,
+}
+
+impl Sequence {
+    fn load() -> Self {
+        let prefix = match TokenManager::stored_agent_uuid() {
+            // 재시작마다 순번이 0 부터 다시 시작하므로 서버가 새 배치를 중복으로 버리지 않도록 실행마다 다른 구간
+            Some(agent_uuid) if ephemeral::is_enabled() => {
+                format!("{}-{}", agent_uuid, &Uuid::new_v4().simple().to_string()[..8])
+            }
+            Some(agent_uuid) => agent_uuid,
+            // 등록 전(dry-run 등)이면 실행마다 새 접두어
+            None => Uuid::new_v4().simple().to_string(),
+        };
+        let next = state_store::store()
+            .load(SEQUENCE_KEY)
+            .ok()
+            .flatten()
+            .and_then(|content| content.trim().parse().ok())
+            .unwrap_or(0);
+
+        Self {
+            prefix,
+            next,
+            reserved_until: next,
+        }
+    }
+
+    fn reserve(&mut self) {
+        self.reserved_until = self.next + RESERVE_BLOCK;
+        if let Err(e) = state_store::store().save(SEQUENCE_KEY, &self.reserved_until.to_string()) {
+            warn!(key = SEQUENCE_KEY, error = %format!("{:#}", e), "{}", msg::BATCH_SEQUENCE_SAVE_FAILED);
+        }
+    }
+}
+
+/// 다음 배치 식별자
+pub fn next() -> String {
+    let mut sequence = SEQUENCE.lock().unwrap_or_else(|e| e.into_inner());
+    if sequence.next >= sequence.reserved_until {
+        sequence.reserve();
+    }
+
+    let id = format!("{}-{}", sequence.prefix, sequence.next);
+    sequence.next += 1;
+    id
+}
@@ -0,0 +1,42 @@
+//! 배치 로그 버퍼 재사용
+//!
+//! Forwarder 가 배치마다 새 `Vec<Log>` 를 할당하고 Sink 가 전송 후 버리는 대신,
+//! 전송이 끝난 배치의 버퍼를 비워 두었다가 다음 배치에 다시 사용
+
+use std::mem;
+use std::sync::{LazyLock, Mutex};
+
+use crate::proto::log::{Log, LogBatch};
+
+/// 보관할 최대 버퍼 수, 전송 대기 배치 수 정도면 충분
+const MAX_POOLED: usize = 8;
+
+static POOL: LazyLock<Mutex<Vec<Vec<Log>>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// 재사용 버퍼가 있으면 꺼내고 없으면 새로 할당, 최소 `capacity` 만큼 확보
+pub fn take(capacity: usize) -> Vec<Log> {
+    let pooled = POOL.lock().ok().and_then(|mut pool| pool.pop());
+
+    match pooled {
+        Some(mut logs) => {
+            logs.reserve(capacity);
+            logs
+        }
+        None => Vec::with_capacity(capacity),
+    }
+}
+
+/// 전송이 끝난 배치의 로그 버퍼를 반환 (로그 데이터는 여기서 해제)
+pub fn recycle(mut batch: LogBatch) {
+    let mut logs = mem::take(&mut batch.logs);
+    if logs.capacity() == 0 {
+        return;
+    }
+    logs.clear();
+
+    if let Ok(mut pool) = POOL.lock()
+        && pool.len() < MAX_POOLED
+    {
+        pool.push(logs);
+    }
+}
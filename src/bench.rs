@@ -0,0 +1,165 @@
+//! 파이프라인 처리량 측정 (`--bench-mode`)
+//!
+//! 임시 파일에 합성 라인을 쓰고 Collector → Forwarder 를 서버 대신 버리기만 하는 Sink 로 실행해,
+//! 마지막 라인이 배치로 나올 때까지의 처리량을 측정 (설정 파일, 서버 연결 불필요)
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use tokio::sync::mpsc;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::agent::{Agent, Sink};
+use crate::batch_pool;
+use crate::messages as msg;
+use crate::proto::log::LogBatch;
+use crate::settings::{Settings, SourceSettings};
+use crate::status;
+
+const BENCH_LABEL: &str = "bench";
+
+/// Collector 가 파일 끝으로 이동할 때까지 기다리는 최대 시간
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 마지막 배치 이후 이 시간 동안 새 배치가 없으면 측정 중단
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub lines: usize,
+    /// 개행 제외 라인 길이
+    pub line_bytes: usize,
+}
+
+#[derive(Debug)]
+pub struct BenchReport {
+    pub lines: usize,
+    pub bytes: u64,
+    pub batches: usize,
+    pub elapsed: Duration,
+}
+
+impl BenchReport {
+    pub fn print(&self) {
+        let secs = self.elapsed.as_secs_f64().max(f64::EPSILON);
+
+        println!("[bench] {}: {}", msg::BENCH_LINES, self.lines);
+        println!("[bench] {}: {}", msg::BENCH_BATCHES, self.batches);
+        println!("[bench] {}: {:.3}s", msg::BENCH_ELAPSED, secs);
+        println!(
+            "[bench] {}: {:.0} lines/s, {:.1} MiB/s",
+            msg::BENCH_THROUGHPUT,
+            self.lines as f64 / secs,
+            self.bytes as f64 / secs / (1024.0 * 1024.0)
+        );
+    }
+}
+
+pub async fn run(options: BenchOptions, shutdown: CancellationToken) -> Result<BenchReport> {
+    let dir = std::env::temp_dir().join(format!("rlog-agent-bench-{}", std::process::id()));
+    fs::create_dir_all(&dir).context(msg::BENCH_SETUP_FAILED)?;
+
+    let result = run_in(&dir, options, shutdown).await;
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+async fn run_in(dir: &Path, options: BenchOptions, shutdown: CancellationToken) -> Result<BenchReport> {
+    let path = dir.join("bench.log");
+    File::create(&path).context(msg::BENCH_SETUP_FAILED)?;
+
+    // 마지막 배치는 batch_size 를 채우지 못하면 flush_interval 만큼 늦게 나옴
+    let settings = Settings {
+        flush_interval: 1,
+        sources: vec![SourceSettings::new(BENCH_LABEL, path.to_string_lossy())],
+        ..Settings::default()
+    };
+
+    let (tx, mut rx) = mpsc::channel::<LogBatch>(16);
+    let agent = Agent::builder().settings(settings).sink(Sink::Channel(tx)).build()?;
+    let agent_shutdown = shutdown.child_token();
+    let agent_handle = tokio::spawn(agent.run(agent_shutdown.clone()));
+
+    wait_until_ready().await?;
+    info!(lines = options.lines, line_bytes = options.line_bytes, "{}", msg::BENCH_STARTED);
+
+    let started = Instant::now();
+    let writer = tokio::task::spawn_blocking({
+        let options = options.clone();
+        move || write_lines(path, &options)
+    });
+
+    let mut lines = 0;
+    let mut batches = 0;
+    while lines < options.lines {
+        let batch = tokio::select! {
+            _ = shutdown.cancelled() => break,
+            batch = time::timeout(IDLE_TIMEOUT, rx.recv()) => match batch {
+                Ok(Some(batch)) => batch,
+                Ok(None) | Err(_) => break,
+            },
+        };
+
+        lines += batch.logs.len();
+        batches += 1;
+        batch_pool::recycle(batch);
+    }
+    let elapsed = started.elapsed();
+
+    let bytes = writer.await?.context(msg::BENCH_WRITE_FAILED)?;
+    agent_shutdown.cancel();
+    // 에이전트가 종료 중 잔여 배치를 보낼 수 있도록 수신측 유지
+    while rx.recv().await.is_some() {}
+    agent_handle.await??;
+
+    if lines < options.lines {
+        bail!("{} ({}/{})", msg::BENCH_INCOMPLETE, lines, options.lines);
+    }
+
+    Ok(BenchReport {
+        lines,
+        bytes,
+        batches,
+        elapsed,
+    })
+}
+
+/// Collector 가 시작 시점의 파일 끝을 기록해야 이후 쓴 라인이 모두 수집됨
+async fn wait_until_ready() -> Result<()> {
+    let deadline = Instant::now() + READY_TIMEOUT;
+
+    while !status::registry()
+        .snapshot()
+        .sources
+        .iter()
+        .any(|source| source.label == BENCH_LABEL)
+    {
+        if Instant::now() > deadline {
+            bail!(msg::BENCH_SETUP_FAILED);
+        }
+        time::sleep(Duration::from_millis(10)).await;
+    }
+
+    Ok(())
+}
+
+/// 고정 길이 라인을 파일 끝에 기록, 쓴 바이트 수 반환
+fn write_lines(path: PathBuf, options: &BenchOptions) -> std::io::Result<u64> {
+    let file = fs::OpenOptions::new().append(true).open(path)?;
+    let mut writer = BufWriter::with_capacity(256 * 1024, file);
+
+    let mut line = "x".repeat(options.line_bytes);
+    line.push('\n');
+
+    for _ in 0..options.lines {
+        writer.write_all(line.as_bytes())?;
+    }
+    writer.flush()?;
+
+    Ok((line.len() * options.lines) as u64)
+}
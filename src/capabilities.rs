@@ -0,0 +1,117 @@
+//! 프로토콜 기능 협상
+//!
+//! 등록/토큰 갱신 요청에 에이전트가 지원하는 기능을 싣고, 응답에 담긴 서버 기능과 맞춰 전송 방식을 정함.
+//! 기능 정보를 보내지 않는 이전 서버는 협상 도입 전 동작(gzip, 4MiB, 로그 단위 거부 없음)으로 간주
+
+use tonic::codec::CompressionEncoding;
+use tracing::info;
+
+use crate::messages as msg;
+use crate::proto::auth::Capabilities;
+
+/// 에이전트가 구현한 프로토콜 버전
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// tonic 기본 수신 한도, 협상 이전 서버도 이 크기까지는 받음
+const LEGACY_MAX_MESSAGE_BYTES: u64 = 4 * 1024 * 1024;
+
+static GZIP: &str = "gzip";
+static IDENTITY: &str = "identity";
+
+/// 요청에 실을 에이전트 기능
+pub fn local() -> Capabilities {
+    Capabilities {
+        protocol_version: PROTOCOL_VERSION,
+        compression: vec![GZIP.to_string(), IDENTITY.to_string()],
+        max_message_bytes: LEGACY_MAX_MESSAGE_BYTES,
+        partial_acks: true,
+        structured_fields: true,
+        metrics: true,
+    }
+}
+
+/// 협상 결과, 양쪽이 모두 지원하는 기능만 켜짐
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Negotiated {
+    /// 0 이면 협상 이전 서버
+    pub protocol_version: u32,
+    /// `None` 이면 압축하지 않음
+    pub compression: Option<CompressionEncoding>,
+    /// 한 번에 보낼 배치의 최대 인코딩 크기
+    pub max_message_bytes: usize,
+    pub partial_acks: bool,
+    pub structured_fields: bool,
+    pub metrics: bool,
+}
+
+impl Default for Negotiated {
+    /// 협상 이전 서버 기준
+    fn default() -> Self {
+        Self {
+            protocol_version: 0,
+            compression: Some(CompressionEncoding::Gzip),
+            max_message_bytes: LEGACY_MAX_MESSAGE_BYTES as usize,
+            partial_acks: false,
+            structured_fields: false,
+            metrics: false,
+        }
+    }
+}
+
+/// 서버 기능과 에이전트 기능을 맞춤, 서버가 기능 정보를 보내지 않았으면 기본값
+pub fn negotiate(server: Option<&Capabilities>) -> Negotiated {
+    let Some(server) = server else {
+        return Negotiated::default();
+    };
+
+    let local = local();
+
+    // 에이전트 선호 순서대로 서버도 지원하는 첫 알고리즘, 목록이 비었으면 기존 gzip 유지
+    let compression = if server.compression.is_empty() {
+        Some(CompressionEncoding::Gzip)
+    } else {
+        local
+            .compression
+            .iter()
+            .find(|name| server.compression.contains(name))
+            .and_then(|name| encoding(name))
+    };
+
+    let max_message_bytes = match server.max_message_bytes {
+        0 => LEGACY_MAX_MESSAGE_BYTES,
+        bytes => bytes,
+    };
+
+    Negotiated {
+        protocol_version: server.protocol_version.min(local.protocol_version),
+        compression,
+        max_message_bytes: usize::try_from(max_message_bytes).unwrap_or(usize::MAX),
+        partial_acks: server.partial_acks && local.partial_acks,
+        structured_fields: server.structured_fields && local.structured_fields,
+        metrics: server.metrics && local.metrics,
+    }
+}
+
+fn encoding(name: &str) -> Option<CompressionEncoding> {
+    (name == GZIP).then_some(CompressionEncoding::Gzip)
+}
+
+impl Negotiated {
+    pub fn log(&self) {
+        if self.protocol_version == 0 {
+            info!("{}", msg::CAPABILITIES_LEGACY_SERVER);
+            return;
+        }
+
+        info!(
+            protocol_version = self.protocol_version,
+            compression = self.compression.map_or(IDENTITY, |_| GZIP),
+            max_message_bytes = self.max_message_bytes,
+            partial_acks = self.partial_acks,
+            structured_fields = self.structured_fields,
+            metrics = self.metrics,
+            "{}",
+            msg::CAPABILITIES_NEGOTIATED
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! 장애 주입 (숨은 `--chaos` 옵션, `RLOG_CHAOS` 환경 변수)
+//!
+//! 스테이징에서 재시도, backoff, 디스크 큐 저장 경로를 운영보다 먼저 겪어 보도록 일부러 실패를 만듦.
+//! `unauthenticated=0.05,unavailable=0.1,slow_send_ms=2000,watcher_stall=0.2,disk_full=0.05` 처럼 항목별 확률(0~1)과
+//! 전송 지연을 지정. 배치 전송은 Unauthenticated(토큰 갱신)/Unavailable(backoff 재시도)로 거부되거나 늦어지고,
+//! 파일 감시자는 변경 알림을 버려 멈춘 것처럼 보이며, 상태 쓰기(디스크 큐, 수집 커서, 토큰)는 디스크가 찬 것처럼 실패
+
+use std::sync::RwLock;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use tokio::time;
+use tonic::Status;
+use tracing::{debug, warn};
+
+use crate::messages as msg;
+
+static ENV_CHAOS: &str = "RLOG_CHAOS";
+
+static CHAOS: RwLock<Option<Chaos>> = RwLock::new(None);
+
+/// 항목별 주입 확률과 지연
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Chaos {
+    /// 배치 전송을 Unauthenticated 로 거부할 확률
+    pub unauthenticated: f64,
+    /// 배치 전송을 Unavailable 로 거부할 확률
+    pub unavailable: f64,
+    /// 배치 전송 전 지연
+    pub slow_send: Duration,
+    /// 파일 변경 알림을 버릴 확률
+    pub watcher_stall: f64,
+    /// 상태 쓰기를 디스크 부족으로 실패할 확률
+    pub disk_full: f64,
+}
+
+impl Chaos {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut chaos = Self::default();
+
+        for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (key, value) = item
+                .split_once('=')
+                .with_context(|| format!("{}: {}", msg::CHAOS_SPEC_INVALID, item))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .with_context(|| format!("{}: {}", msg::CHAOS_SPEC_INVALID, item))?;
+
+            let probability = match key.trim() {
+                "unauthenticated" => &mut chaos.unauthenticated,
+                "unavailable" => &mut chaos.unavailable,
+                "watcher_stall" => &mut chaos.watcher_stall,
+                "disk_full" => &mut chaos.disk_full,
+                "slow_send_ms" if value >= 0.0 => {
+                    chaos.slow_send = Duration::from_millis(value as u64);
+                    continue;
+                }
+                _ => bail!("{}: {}", msg::CHAOS_SPEC_INVALID, item),
+            };
+            if !(0.0..=1.0).contains(&value) {
+                bail!("{}: {}", msg::CHAOS_SPEC_INVALID, item);
+            }
+            *probability = value;
+        }
+
+        Ok(chaos)
+    }
+}
+
+/// `--chaos` 또는 `RLOG_CHAOS` 로 장애 주입 설정, 둘 다 없으면 비활성화
+pub fn configure(flag: Option<&str>) -> Result<()> {
+    let spec = flag
+        .map(str::to_string)
+        .or_else(|| std::env::var(ENV_CHAOS).ok())
+        .filter(|spec| !spec.trim().is_empty());
+
+    let chaos = spec.as_deref().map(Chaos::parse).transpose()?;
+    if let Some(spec) = &spec {
+        warn!(spec = %spec, "{}", msg::CHAOS_ENABLED);
+    }
+
+    *CHAOS.write().unwrap_or_else(|e| e.into_inner()) = chaos;
+    Ok(())
+}
+
+fn current() -> Option<Chaos> {
+    *CHAOS.read().unwrap_or_else(|e| e.into_inner())
+}
+
+fn roll(probability: f64) -> bool {
+    probability > 0.0 && fastrand::f64() < probability
+}
+
+/// 배치 전송 전 지연 후 거부할 오류, 주입하지 않으면 None
+pub async fn send_fault() -> Option<Status> {
+    let chaos = current()?;
+
+    if !chaos.slow_send.is_zero() {
+        time::sleep(chaos.slow_send).await;
+    }
+
+    if roll(chaos.unauthenticated) {
+        debug!("{}: unauthenticated", msg::CHAOS_INJECTED);
+        return Some(Status::unauthenticated(msg::CHAOS_INJECTED.to_string()));
+    }
+    if roll(chaos.unavailable) {
+        debug!("{}: unavailable", msg::CHAOS_INJECTED);
+        return Some(Status::unavailable(msg::CHAOS_INJECTED.to_string()));
+    }
+    None
+}
+
+/// 이번 파일 변경 알림을 버림
+pub fn watcher_stall() -> bool {
+    current().is_some_and(|chaos| roll(chaos.watcher_stall))
+}
+
+/// 상태 쓰기를 디스크 부족으로 실패
+pub fn disk_full() -> Result<()> {
+    if current().is_some_and(|chaos| roll(chaos.disk_full)) {
+        debug!("{}: disk_full", msg::CHAOS_INJECTED);
+        bail!("{} ({})", msg::DISK_SPACE_LOW, msg::CHAOS_INJECTED);
+    }
+    Ok(())
+}
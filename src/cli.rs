@@ -0,0 +1,230 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+use rlog_agent::auth::token_manager::TokenManager;
+use rlog_agent::messages as msg;
+use rlog_agent::loadgen::LoadRate;
+use rlog_agent::replay::ReplaySpeed;
+use rlog_agent::settings::Settings;
+use tracing::info;
+
+#[derive(Debug, Parser)]
+#[command(name = "rlog-agent", version, about = "분산 시스템을 위한 경량 로그 수집 에이전트")]
+pub struct Cli {
+    /// 서버로 전송하지 않고 배치 요약만 출력 (서버 연결/인증 불필요)
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// 합성 라인으로 Collector → Forwarder 처리량 측정 (설정 파일, 서버 연결 불필요)
+    #[arg(long)]
+    pub bench_mode: bool,
+
+    /// 벤치마크에서 쓸 라인 수
+    #[arg(long, default_value_t = 1_000_000, requires = "bench_mode")]
+    pub bench_lines: usize,
+
+    /// 벤치마크 라인 길이 (바이트, 개행 제외)
+    #[arg(long, default_value_t = 200, requires = "bench_mode")]
+    pub bench_line_bytes: usize,
+
+    /// 상태 없는 실행: 설정은 환경 변수(`RLOG_CONFIG`, `SERVER_ADDR`, `PROJECT_KEY`, `AGENT_UUID`)에서 만들고 디스크에 쓰지 않음
+    /// (기본값: `RLOG_EPHEMERAL` 환경 변수)
+    #[arg(long)]
+    pub ephemeral: bool,
+
+    /// 장애 주입 (예: `unavailable=0.1,slow_send_ms=500,watcher_stall=0.2,disk_full=0.05`), 스테이징 전용
+    /// (기본값: `RLOG_CHAOS` 환경 변수)
+    #[arg(long, global = true, hide = true)]
+    pub chaos: Option<String>,
+
+    /// 설정 파일의 `profiles` 중 적용할 프로필 (기본값: `RLOG_PROFILE` 환경 변수)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    #[command(flatten)]
+    pub overrides: Overrides,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// 저장된 설정보다 우선하는 이번 실행만의 값 (설정 파일은 바꾸지 않음)
+#[derive(Debug, Clone, Default, Args)]
+pub struct Overrides {
+    /// 서버 주소 (예: 스테이징 서버로 잠시 전환)
+    #[arg(long)]
+    pub server_addr: Option<String>,
+
+    /// 프로젝트 키, 프로세스 목록에 그대로 보이므로 공유 호스트에서는 주의
+    #[arg(long)]
+    pub project_key: Option<String>,
+
+    /// 배치당 최대 로그 수
+    #[arg(long)]
+    pub batch_size: Option<usize>,
+
+    /// 강제 플러시 주기 (초)
+    #[arg(long)]
+    pub flush_interval: Option<u64>,
+}
+
+impl Overrides {
+    /// 지정한 값으로 설정을 덮어씀, 설정 파일과 다른 서버면 토큰도 그 서버 것을 따로 보관
+    pub fn apply(&self, settings: &mut Settings) {
+        let mut applied = Vec::new();
+
+        if let Some(server_addr) = &self.server_addr {
+            if *server_addr != settings.server_addr {
+                TokenManager::scope_to_server(server_addr);
+            }
+            settings.server_addr = server_addr.clone();
+            applied.push("server_addr");
+        }
+        if let Some(project_key) = &self.project_key {
+            settings.project_key = project_key.clone();
+            applied.push("project_key");
+        }
+        if let Some(batch_size) = self.batch_size {
+            settings.batch_size = batch_size;
+            applied.push("batch_size");
+        }
+        if let Some(flush_interval) = self.flush_interval {
+            settings.flush_interval = flush_interval;
+            applied.push("flush_interval");
+        }
+
+        if !applied.is_empty() {
+            info!(fields = %applied.join(","), "{}", msg::CLI_OVERRIDES_APPLIED);
+        }
+    }
+
+    /// 서비스/unit 의 실행 인자로 넘길 `--profile` 과 지정한 값, 서비스로 실행해도 같은 설정이 되도록
+    pub fn to_args(&self, profile: Option<&str>) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if let Some(profile) = profile {
+            args.extend(["--profile".to_string(), profile.to_string()]);
+        }
+        if let Some(server_addr) = &self.server_addr {
+            args.extend(["--server-addr".to_string(), server_addr.clone()]);
+        }
+        if let Some(project_key) = &self.project_key {
+            args.extend(["--project-key".to_string(), project_key.clone()]);
+        }
+        if let Some(batch_size) = self.batch_size {
+            args.extend(["--batch-size".to_string(), batch_size.to_string()]);
+        }
+        if let Some(flush_interval) = self.flush_interval {
+            args.extend(["--flush-interval".to_string(), flush_interval.to_string()]);
+        }
+
+        args
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 실행 중인 에이전트 상태 조회 (관리 소켓)
+    Status {
+        /// 응답 JSON 그대로 출력
+        #[arg(long)]
+        json: bool,
+    },
+    /// 파이프라인을 지나는 이벤트를 실시간으로 출력 (관리 소켓)
+    Tap {
+        /// 이 라벨의 이벤트만 출력
+        #[arg(long)]
+        label: Option<String>,
+
+        /// 출력할 이벤트 수
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+
+        /// 이벤트 JSON 그대로 출력
+        #[arg(long)]
+        json: bool,
+    },
+    /// DNS, 연결, 인증, 시계 오차, 파일 권한 등 자가 진단 후 결과 출력
+    Doctor,
+    /// 기존 파일을 파싱/필터/배치/전송 파이프라인 전체로 다시 보냄 (`--dry-run` 이면 배치 요약만 출력)
+    Replay {
+        /// 재생할 파일
+        #[arg(long)]
+        file: PathBuf,
+
+        /// 수집 대상 라벨, 설정에 같은 라벨이 있으면 그 파싱 설정을 사용
+        #[arg(long)]
+        label: String,
+
+        /// 재생 속도 (`max`: 기다리지 않음, `10x`: 레코드 시각 간격의 1/10, `timestamp_format` 필요)
+        #[arg(long, default_value = "max")]
+        speed: ReplaySpeed,
+    },
+    /// 받은 배치를 로그로 출력하는 로컬 테스트 gRPC 서버 실행 (`test-server` 기능)
+    #[cfg(feature = "test-server")]
+    TestServer {
+        /// 수신 주소
+        #[arg(long, default_value = "127.0.0.1:50051")]
+        listen: std::net::SocketAddr,
+    },
+    /// 합성 이벤트를 실제 Forwarder/Streamer 로 설정의 서버에 보내 처리 한계 측정
+    Loadgen {
+        /// 초당 생성 라인 수 (예: `50k/s`, `1m/s`, `2000`)
+        #[arg(long)]
+        rate: LoadRate,
+
+        /// 라인 길이 (바이트, 개행 제외)
+        #[arg(long, default_value_t = 200)]
+        line_bytes: usize,
+
+        /// 생성 시간 (초), 생략 시 중지 신호까지
+        #[arg(long)]
+        duration: Option<u64>,
+
+        /// 합성 이벤트의 수집 대상 라벨
+        #[arg(long, default_value = "loadgen")]
+        label: String,
+    },
+    /// Windows 서비스 관리
+    Service {
+        #[command(subcommand)]
+        action: ServiceAction,
+    },
+    /// 하드닝된 systemd unit 파일을 표준 출력으로 생성
+    SystemdUnit {
+        /// ExecStart 실행 파일 경로 (기본값: 현재 실행 파일)
+        #[arg(long)]
+        exec_path: Option<PathBuf>,
+
+        /// StateDirectory 이름 (/var/lib/<이름> 을 작업 디렉터리로 사용)
+        #[arg(long, default_value = "rlog-agent")]
+        state_dir: String,
+
+        /// 실행 계정 (미지정 시 DynamicUser 사용)
+        #[arg(long)]
+        user: Option<String>,
+
+        /// WatchdogSec 값 (0이면 비활성화)
+        #[arg(long, default_value_t = 30)]
+        watchdog_sec: u64,
+
+        /// 자체 업데이트(`update`)가 실행 파일을 교체하도록 실행 파일 디렉터리를 ReadWritePaths 에 추가
+        #[arg(long)]
+        self_update: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Subcommand)]
+pub enum ServiceAction {
+    /// 서비스 등록 (자동 시작)
+    Install,
+    /// 서비스 제거
+    Uninstall,
+    /// 서비스 시작
+    Start,
+    /// 서비스 중지
+    Stop,
+    /// 서비스 컨트롤 매니저(SCM)에서 호출되는 진입점
+    #[command(hide = true)]
+    Run,
+}
@@ -0,0 +1,112 @@
+//! 서버 대비 시계 오차 감지와 타임스탬프 보정 (`clock_skew`)
+//!
+//! 등록/토큰 갱신/Heartbeat 응답의 HTTP `date` 헤더와 로컬 시각을 비교해 오차(로컬 - 서버)를 기록.
+//! 오차가 `threshold_secs` 를 넘으면 경고하고 Heartbeat 의 `clock_skew_ms`, 게이지, 관리 API `status` 로 보고.
+//! `correct_timestamps` 면 보내는 로그/메트릭 타임스탬프와 배치 `send_at` 에서 오차를 빼 서버 시각에 맞춤
+//! (`date` 헤더가 초 단위라 1초 미만 오차는 보정하지 않음)
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use tonic::metadata::MetadataMap;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::settings::ClockSkewSettings;
+
+static THRESHOLD_SECS: AtomicU64 = AtomicU64::new(5);
+static CORRECT: AtomicBool = AtomicBool::new(false);
+
+/// 마지막으로 잰 오차 (밀리초, 로컬 - 서버)
+static SKEW_MS: AtomicI64 = AtomicI64::new(0);
+static MEASURED_AT: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+static EXCEEDED: AtomicBool = AtomicBool::new(false);
+
+/// 관리 API `status` 에 실을 시계 오차
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClockSnapshot {
+    /// 로컬 - 서버 (초), 아직 재지 못했으면 None
+    pub skew_secs: Option<i64>,
+    pub measured_at: Option<DateTime<Utc>>,
+    /// 오차가 `threshold_secs` 를 넘음
+    pub exceeded: bool,
+    /// 보내는 타임스탬프를 보정 중
+    pub correcting: bool,
+}
+
+pub fn configure(settings: &ClockSkewSettings) {
+    THRESHOLD_SECS.store(settings.threshold_secs, Ordering::Relaxed);
+    CORRECT.store(settings.correct_timestamps, Ordering::Relaxed);
+}
+
+/// 응답 메타데이터의 `date` 헤더
+pub fn server_time(metadata: &MetadataMap) -> Option<DateTime<Utc>> {
+    metadata
+        .get("date")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// 서버 응답으로 오차 갱신, `date` 헤더가 없으면 무시
+pub fn record(metadata: &MetadataMap) {
+    let Some(server_time) = server_time(metadata) else {
+        return;
+    };
+
+    let now = Utc::now();
+    let skew = (now - server_time).num_milliseconds();
+    SKEW_MS.store(skew, Ordering::Relaxed);
+    *MEASURED_AT.lock().unwrap_or_else(|e| e.into_inner()) = Some(now);
+
+    // 경계를 넘을 때만 로그
+    let exceeded = exceeds(skew);
+    if exceeded != EXCEEDED.swap(exceeded, Ordering::Relaxed) {
+        let skew_secs = skew / 1000;
+        if exceeded {
+            warn!(
+                skew_secs = skew_secs,
+                threshold_secs = THRESHOLD_SECS.load(Ordering::Relaxed),
+                correcting = CORRECT.load(Ordering::Relaxed),
+                "{}",
+                msg::CLOCK_SKEW_DETECTED
+            );
+        } else {
+            info!(skew_secs = skew_secs, "{}", msg::CLOCK_SKEW_RESOLVED);
+        }
+    }
+}
+
+/// 마지막으로 잰 오차 (밀리초), 재지 못했으면 None
+pub fn skew_ms() -> Option<i64> {
+    is_measured().then(|| SKEW_MS.load(Ordering::Relaxed))
+}
+
+/// 보내는 타임스탬프에 더할 값, 보정하지 않으면 0
+pub fn correction() -> TimeDelta {
+    if !CORRECT.load(Ordering::Relaxed) || !EXCEEDED.load(Ordering::Relaxed) {
+        return TimeDelta::zero();
+    }
+
+    // 초 단위 date 헤더라 1초 미만은 버림
+    TimeDelta::seconds(-(SKEW_MS.load(Ordering::Relaxed) / 1000))
+}
+
+pub fn snapshot() -> ClockSnapshot {
+    ClockSnapshot {
+        skew_secs: skew_ms().map(|ms| ms / 1000),
+        measured_at: *MEASURED_AT.lock().unwrap_or_else(|e| e.into_inner()),
+        exceeded: EXCEEDED.load(Ordering::Relaxed),
+        correcting: correction() != TimeDelta::zero(),
+    }
+}
+
+fn is_measured() -> bool {
+    MEASURED_AT.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+fn exceeds(skew_ms: i64) -> bool {
+    (skew_ms / 1000).unsigned_abs() > THRESHOLD_SECS.load(Ordering::Relaxed)
+}
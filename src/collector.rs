@@ -1,135 +1,573 @@
+use crate::chaos;
+use crate::error_code::ErrorCode;
+use crate::governor;
+use crate::hot_restart::{self, CollectorOffset};
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
 use crate::models::LogEvent;
-use crate::settings::SourceSettings;
-use anyhow::{Context, Result};
+use crate::line_join::LineJoin;
+use crate::multiline::Multiline;
+use crate::parser::timestamp::TimestampFormat;
+use crate::parser::{self, Header};
+use crate::settings::{InvalidUtf8, ReadBackend, SourceFormat, SourceSettings};
+use crate::status;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use crate::uring;
+use crate::watch_health;
+use crate::win_path;
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use bytes::{Bytes, BytesMut};
 use chrono::Utc;
-use notify::{Watcher, recommended_watcher};
+use notify::{RecommendedWatcher, Watcher, recommended_watcher};
 use std::fs::Metadata;
-use std::io::SeekFrom;
-use std::path::PathBuf;
+use std::io::{self, SeekFrom};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use tokio::fs::{File, metadata};
-use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
 use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::watch;
+use tokio::time::{self, Instant};
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info, warn};
+use tracing::{error, field, info, info_span, warn, Instrument, Span};
 
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
 #[cfg(windows)]
 use std::os::windows::fs::MetadataExt;
 
+/// 설정된 읽기 버퍼 크기의 하한
+const MIN_READ_BUFFER_BYTES: usize = 1024;
+
+/// 파일 중간부터 읽을 때 헤더로 읽는 첫 줄의 최대 길이
+const MAX_HEADER_BYTES: u64 = 64 * 1024;
+
+/// 첫 변경 이벤트 후 이어지는 이벤트를 모아 한 번에 읽는 대기 시간
+///
+/// 작은 쓰기를 반복하는 프로그램이 만드는 이벤트마다 읽기를 반복하지 않도록 함
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(20);
+
+/// `optional` 수집 대상의 파일이 생겼는지 다시 확인하는 간격
+const OPTIONAL_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 감시자가 변경 이벤트를 계속 전달하는지 stat 으로 확인하는 간격
+const WATCH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 감시자를 다시 만든 뒤 이 시간 안에 다시 멈추면 폴링으로 전환
+const WATCH_RECREATE_WINDOW: Duration = Duration::from_secs(300);
+
+/// 감시자 대신 파일을 확인하는 간격
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `invalid_utf8: base64` 일 때 원본 바이트를 싣는 필드
+const RAW_BASE64_FIELD: &str = "raw_base64";
+
+/// 일시 중지된 수집 대상 라벨
+static PAUSED: LazyLock<watch::Sender<HashSet<String>>> =
+    LazyLock::new(|| watch::channel(HashSet::new()).0);
+
+/// 수집 일시 중지/재개, 중지 중 추가된 라인은 파일에 남아 재개 시 이어서 읽음
+pub fn set_paused(label: &str, paused: bool) {
+    PAUSED.send_modify(|labels| {
+        if paused {
+            labels.insert(label.to_string());
+        } else {
+            labels.remove(label);
+        }
+    });
+}
+
+pub fn is_paused(label: &str) -> bool {
+    PAUSED.borrow().contains(label)
+}
+
+/// 단일 파일을 감시하며 새로 추가된 라인을 [`LogEvent`] 로 전달
+///
+/// 로테이션(파일 식별자 변경)과 트런케이션(크기 감소)을 감지하여 파일을 다시 연다
 pub struct Collector {
     tx: Sender<LogEvent>,
     label: String,
+    /// 설정한 경로 (상태, 로그, 재실행 오프셋)
     path: PathBuf,
-    reader: BufReader<File>,
+    /// 파일 시스템 호출에 쓸 경로 (Windows 는 확장 길이 형식)
+    fs_path: PathBuf,
+    /// 감시자 없이 폴링 (`sources[].poll` 또는 SMB 공유)
+    force_poll: bool,
+    /// 이벤트에 실을 경로 (이벤트마다 복사하지 않도록 공유)
+    path_str: Arc<str>,
+    reader: Reader,
+    backend: ReadBackend,
+    /// 파일에서 읽었지만 아직 전달하지 않은 데이터, 맨 앞이 `position` 위치
+    buf: BytesMut,
+    /// `buf` 중 개행이 없음을 확인한 앞부분 길이 (긴 라인 재탐색 방지)
+    scanned: usize,
+    /// 줄 구분
+    delimiter: Delimiter,
+    /// UTF-8 이 아닌 레코드 처리
+    invalid_utf8: InvalidUtf8,
+    /// 읽기 버퍼 할당 단위, 한 번 할당한 영역을 여러 라인의 [`Bytes`] 가 나눠 가짐
+    read_buffer_bytes: usize,
+    format: SourceFormat,
+    /// 여러 줄 레코드 형식일 때 묶고 있는 레코드
+    multiline: Option<Multiline>,
+    /// `join_on` 설정 시 이어쓰기 표시로 나뉜 줄을 합치고 있는 상태
+    join: Option<LineJoin>,
+    /// 파일 안의 헤더가 컬럼을 정하는 형식(W3C, CSV)일 때 마지막으로 본 헤더
+    header: Option<Header>,
+    /// `timestamp_format` 설정 시 레코드 안의 시각 해석
+    timestamp_format: Option<TimestampFormat>,
     position: u64,
     file_id: u64,
+    budget: MemoryBudget,
 }
 
 impl Collector {
-    pub async fn new(tx: Sender<LogEvent>, source: SourceSettings) -> Result<Self> {
-        let path = PathBuf::from(source.path);
+    /// 파일을 열고 끝으로 이동 (기존 내용은 수집하지 않음)
+    ///
+    /// `resume` 이 주어지면 재실행 전 위치부터 이어서 읽음
+    ///
+    /// `backend` 는 [`resolve_backend`] 로 확인한 값
+    pub async fn new(
+        tx: Sender<LogEvent>,
+        source: SourceSettings,
+        budget: MemoryBudget,
+        resume: Option<&CollectorOffset>,
+        backend: ReadBackend,
+    ) -> Result<Self> {
+        Self::open(tx, source, budget, resume, backend, true).await
+    }
+
+    /// `optional` 수집 대상의 파일이 생길 때까지 기다린 뒤 처음부터 읽는 Collector 생성
+    ///
+    /// 기다리는 중 `shutdown` 이 취소되면 None
+    pub async fn wait_for_file(
+        tx: Sender<LogEvent>,
+        source: SourceSettings,
+        budget: MemoryBudget,
+        backend: ReadBackend,
+        shutdown: &CancellationToken,
+    ) -> Option<Self> {
+        let mut ticker = time::interval(OPTIONAL_RETRY_INTERVAL);
 
-        let (reader, position, file_id) = open_file(&path, true)
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => return None,
+                _ = ticker.tick() => {}
+            }
+
+            if metadata(win_path::extended(Path::new(&source.path))).await.is_err() {
+                continue;
+            }
+
+            match Self::open(tx.clone(), source.clone(), budget.clone(), None, backend, false).await {
+                Ok(collector) => {
+                    info!(source = %source.label, path = %source.path, "{}", msg::OPTIONAL_SOURCE_APPEARED);
+                    return Some(collector);
+                }
+                Err(e) => {
+                    warn!(
+                        source = %source.label,
+                        path = %source.path,
+                        error = %format!("{:#}", e),
+                        code = %ErrorCode::FileOpenFailed,
+                        "{}",
+                        msg::FILE_OPEN_FAILED
+                    );
+                }
+            }
+        }
+    }
+
+    async fn open(
+        tx: Sender<LogEvent>,
+        source: SourceSettings,
+        budget: MemoryBudget,
+        resume: Option<&CollectorOffset>,
+        backend: ReadBackend,
+        seek_to_end: bool,
+    ) -> Result<Self> {
+        let path = PathBuf::from(&source.path);
+        let fs_path = win_path::extended(&path);
+        let force_poll = source.poll || win_path::is_network(&fs_path);
+        let timestamp_format = TimestampFormat::new(&source)?;
+        let delimiter = Delimiter::new(&source)?;
+        let read_buffer_bytes = source.read_buffer_bytes.max(MIN_READ_BUFFER_BYTES);
+
+        let (mut file, mut position, file_id) = open_file(&fs_path, seek_to_end)
             .await
-            .with_context(|| format!("파일 열기 실패: {}", source.label))?;
+            .with_context(|| format!("{}: {}", msg::FILE_OPEN_FAILED, source.label))?;
+
+        if let Some(resume) = resume {
+            // 같은 파일이면 이전 위치, 재실행 사이 로테이션되었다면 새 파일 처음부터
+            let start = if resume.file_id == file_id && resume.position <= position {
+                resume.position
+            } else {
+                info!(source = %source.label, "{}", msg::FILE_REPLACED_DURING_RESTART);
+                0
+            };
+
+            file
+                .seek(SeekFrom::Start(start))
+                .await
+                .context(msg::FILE_SEEK_FAILED)?;
+            position = start;
+        }
+
+        let mut header = Header::new(&source);
+        if position > 0 && let Some(header) = header.as_mut() {
+            seed_header(header, &fs_path).await;
+        }
+
+        status::registry().update_source(&source.label, &path, position);
+        hot_restart::mark_start(&source.label, &path, file_id, position);
+        let reader = Reader::new(file, backend)
+            .await
+            .with_context(|| format!("{}: {}", msg::FILE_OPEN_FAILED, source.label))?;
 
         Ok(Self {
             tx,
             label: source.label,
+            path_str: Arc::from(path.to_string_lossy()),
             path,
+            fs_path,
+            force_poll,
             reader,
+            backend,
+            buf: BytesMut::with_capacity(read_buffer_bytes),
+            scanned: 0,
+            delimiter,
+            invalid_utf8: source.invalid_utf8,
+            read_buffer_bytes,
+            format: source.format,
+            multiline: parser::is_multiline(source.format).then(|| Multiline::new(source.format)),
+            join: source.join_on.map(LineJoin::new),
+            header,
+            timestamp_format,
             position,
             file_id,
+            budget,
         })
     }
 
+    /// 마지막으로 전달한 라인 직후의 위치
+    pub fn offset(&self) -> CollectorOffset {
+        CollectorOffset {
+            label: self.label.clone(),
+            path: self.path.clone(),
+            file_id: self.file_id,
+            position: self.position,
+        }
+    }
+
+    /// `shutdown` 취소 시까지 파일 변경을 감시하며 수집
+    ///
+    /// 감시자가 멈추면(이벤트 없이 읽지 않은 데이터가 남음) 다시 만들고, 그래도 멈추면 주기적으로 읽는 폴링으로 전환
     pub async fn start(&mut self, shutdown: CancellationToken) {
         let (watcher_tx, mut watcher_rx) = mpsc::channel::<()>(1);
+        let (removed_tx, mut removed_rx) = mpsc::channel::<()>(1);
 
-        let mut watcher = recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
-            if let Ok(event) = res
-                && event.kind.is_modify()
-            {
-                let _ = watcher_tx.try_send(());
+        watch_health::start(&self.label);
+        let mut check = WatchCheck::default();
+        let mut ticker = check_ticker(WATCH_CHECK_INTERVAL);
+
+        // 네트워크 공유이거나 감시를 설정할 수 없으면(inotify 한도 등) 처음부터 폴링
+        let mut watcher = if self.force_poll {
+            info!(source = %self.label, path = %self.path.display(), "{}", msg::WATCH_POLLING_NETWORK);
+            watch_health::set_polling(&self.label);
+            ticker = check_ticker(POLL_INTERVAL);
+            None
+        } else {
+            match watch_file(&self.fs_path, &watcher_tx, &removed_tx) {
+                Ok(watcher) => {
+                    info!(source = %self.label, "{}", msg::WATCH_STARTED);
+                    Some(watcher)
+                }
+                Err(e) => {
+                    error!(source = %self.label, error = %e, code = %ErrorCode::WatcherFailed, "{}", msg::WATCH_SETUP_FAILED);
+                    self.start_polling(&mut ticker);
+                    None
+                }
             }
-        })
-        .expect("Watcher 생성 실패");
+        };
 
-        if let Err(e) = watcher.watch(&self.path, notify::RecursiveMode::NonRecursive) {
-            error!("{} 파일 감지 설정 중 오류 {}", self.label, e);
-            return;
+        // 파일을 연 뒤 감시 시작 전까지 추가된 라인 (재실행 후 이어 읽을 라인 포함)
+        if let Err(e) = self.read_line_to_send(&shutdown).await {
+            warn!(
+                source = %self.label,
+                path = %self.path.display(),
+                error = %e,
+                code = %ErrorCode::FileReadFailed,
+                "{}",
+                msg::FILE_READ_FAILED
+            );
         }
 
-        info!("{} 파일 감지 시작", self.label);
-
-        let mut line = String::new();
+        let mut paused = PAUSED.subscribe();
         loop {
             tokio::select! {
                 _ = shutdown.cancelled() => {
-                    let _ = self.read_line_to_send(&mut line).await;
+                    let _ = self.read_line_to_send(&shutdown).await;
+                    let _ = self.flush_record().await;
                     break;
                 }
+                _ = sleep_until(self.deadline()) => {
+                    // 이어지는 줄 없이 대기 시간이 지난 줄/레코드
+                    if let Err(e) = self.flush_idle().await {
+                        warn!(source = %self.label, error = %e, code = %ErrorCode::RecordFlushFailed, "{}", msg::RECORD_FLUSH_FAILED);
+                    }
+                }
+                _ = ticker.tick() => {
+                    if watcher.is_none() {
+                        if self.poll(&shutdown).await {
+                            break;
+                        }
+                        continue;
+                    }
+                    // 읽기가 오래 걸리는 동안 도착해 아직 꺼내지 않은 이벤트
+                    check.events |= !watcher_rx.is_empty();
+                    if !self.is_watcher_stale(&mut check).await {
+                        continue;
+                    }
+
+                    watch_health::record_stale(&self.label);
+                    // 놓친 변경부터 읽음 (로테이션, 삭제 포함)
+                    if self.poll(&shutdown).await {
+                        break;
+                    }
+
+                    let recent = check.recreated_at.is_some_and(|at| at.elapsed() < WATCH_RECREATE_WINDOW);
+                    if !recent {
+                        match watch_file(&self.fs_path, &watcher_tx, &removed_tx) {
+                            Ok(recreated) => {
+                                warn!(source = %self.label, path = %self.path.display(), "{}", msg::WATCHER_RECREATED);
+                                watcher = Some(recreated);
+                                check.recreated_at = Some(Instant::now());
+                                watch_health::record_recreated(&self.label);
+                                continue;
+                            }
+                            Err(e) => warn!(
+                                source = %self.label,
+                                error = %e,
+                                code = %ErrorCode::WatcherFailed,
+                                "{}",
+                                msg::WATCH_SETUP_FAILED
+                            ),
+                        }
+                    }
+
+                    watcher = None;
+                    self.start_polling(&mut ticker);
+                }
                 recv = watcher_rx.recv() => {
                     match recv {
                         Some(()) => {
-                            if let Err(e) = self.read_line_to_send(&mut line).await {
-                                warn!("{} ({}) 파일 읽기 중 오류: {}", self.label, self.path.display(), e);
+                            // 장애 주입: 알림을 놓친 것처럼 버려 멈춘 감시자 감지와 폴링 전환을 거치게 함
+                            if chaos::watcher_stall() {
+                                continue;
+                            }
+                            check.events = true;
+                            coalesce_events(&mut watcher_rx).await;
+                            if let Err(e) = self.read_line_to_send(&shutdown).await {
+                                warn!(
+                                    source = %self.label,
+                                    path = %self.path.display(),
+                                    error = %e,
+                                    code = %ErrorCode::FileReadFailed,
+                                    "{}",
+                                    msg::FILE_READ_FAILED
+                                );
                             }
                         }
                         None => break,
                     }
                 }
+                Some(()) = removed_rx.recv() => {
+                    if self.handle_removed(&shutdown).await {
+                        break;
+                    }
+                }
+                Ok(()) = paused.changed() => {
+                    // 재개 시 중지 동안 쌓인 라인 읽기
+                    if !is_paused(&self.label)
+                        && let Err(e) = self.read_line_to_send(&shutdown).await
+                    {
+                        warn!(
+                            source = %self.label,
+                            path = %self.path.display(),
+                            error = %e,
+                            code = %ErrorCode::FileReadFailed,
+                            "{}",
+                            msg::FILE_READ_FAILED
+                        );
+                    }
+                }
             }
         }
 
-        info!("{} Collector 종료..", self.label);
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    /// 마지막 확인 이후 이벤트 없이 두 번 연속 읽지 않은 데이터(크기/파일 식별자 변화, 삭제)가 있으면 감시자가 멈춘 것
+    async fn is_watcher_stale(&self, check: &mut WatchCheck) -> bool {
+        // 일시 중지 중에는 읽지 않은 데이터가 남는 것이 정상
+        let unread = !is_paused(&self.label)
+            && match stat(&self.fs_path).await {
+                Ok((file_id, len)) => file_id != self.file_id || len != self.position + self.buf.len() as u64,
+                Err(_) => true,
+            };
+
+        let stale = unread && check.unread && !check.events;
+        check.unread = unread && !stale;
+        check.events = false;
+        stale
+    }
+
+    fn start_polling(&self, ticker: &mut time::Interval) {
+        warn!(source = %self.label, path = %self.path.display(), code = %ErrorCode::WatcherFailed, "{}", msg::WATCHER_POLLING);
+        watch_health::set_polling(&self.label);
+        *ticker = check_ticker(POLL_INTERVAL);
+    }
+
+    /// 감시자 없이 확인, 삭제 이벤트가 오지 않으므로 경로가 없어졌는지도 직접 확인. 수집을 끝내야 하면 true
+    async fn poll(&mut self, shutdown: &CancellationToken) -> bool {
+        if metadata(&self.fs_path).await.is_err() {
+            return self.handle_removed(shutdown).await;
+        }
+
+        if let Err(e) = self.read_line_to_send(shutdown).await {
+            warn!(
+                source = %self.label,
+                path = %self.path.display(),
+                error = %e,
+                code = %ErrorCode::FileReadFailed,
+                "{}",
+                msg::FILE_READ_FAILED
+            );
+        }
+        false
+    }
+
+    /// 새로 추가된 라인을 모두 읽어 전달, 한 번의 읽기를 `collect` span 으로 기록
+    async fn read_line_to_send(&mut self, shutdown: &CancellationToken) -> Result<()> {
+        let span = info_span!("collect", source = %self.label, lines = field::Empty);
+        self.read_lines(shutdown).instrument(span).await
     }
 
-    async fn read_line_to_send(&mut self, line: &mut String) -> Result<()> {
+    async fn read_lines(&mut self, shutdown: &CancellationToken) -> Result<()> {
+        let mut lines = 0u64;
+
         loop {
+            if is_paused(&self.label) {
+                break;
+            }
+
+            // 이미 읽어 둔 청크에서 완성된 라인부터 전달
+            if let Some(line) = self.next_line() {
+                // 메모리 예산 초과 시 전달 중지, 남은 데이터는 버퍼와 파일에 보존
+                self.budget.wait_for_capacity(shutdown).await;
+
+                let len = line.len() as u64;
+                self.push_line(line, self.position).await?;
+                self.position += len;
+                lines += 1;
+                continue;
+            }
+
+            // 남은 공간이 작으면 새로 할당 후 읽음 (작은 읽기 반복 방지)
+            if self.buf.capacity() - self.buf.len() < self.read_buffer_bytes / 8 {
+                self.buf.reserve(self.read_buffer_bytes);
+            }
+
+            // 버퍼에 남은 미완성 조각 바로 뒤부터 읽음
+            let offset = self.position + self.buf.len() as u64;
             let read_bytes = self
                 .reader
-                .read_line(line)
+                .read(&mut self.buf, offset)
                 .await
-                .context("라인 읽기 실패")?;
+                .context(msg::LINE_READ_FAILED)?;
 
+            // 개행 없는 마지막 조각은 버퍼에 남겨 다음 읽기에서 이어 붙임
             if read_bytes == 0 {
-                if let Ok(meta) = metadata(&self.path).await
-                    && self.check_rotation_or_truncate(meta).await?
+                if let Ok((file_id, len)) = stat(&self.fs_path).await
+                    && self.check_rotation_or_truncate(file_id, len, shutdown).await?
                 {
                     continue;
                 }
                 break;
             }
 
-            // 개행문자 없으면 무시
-            if !line.ends_with('\n') {
-                break;
-            }
-
-            self.send_event(line).await?;
-            self.position += read_bytes as u64;
-            line.clear();
+            // 호스트 자원 압박 중이면 읽기 속도 제한
+            governor::pace(read_bytes).await;
         }
 
+        Span::current().record("lines", lines);
+        status::registry().update_source(&self.label, &self.path, self.position);
         Ok(())
     }
 
-    async fn check_rotation_or_truncate(&mut self, meta: Metadata) -> Result<bool> {
-        let current_file_id = get_file_id(&meta);
-        let current_len = meta.len();
+    /// 파일 삭제 이벤트 처리, 같은 경로에 새 파일이 있으면 로테이션으로 보고 계속 수집
+    ///
+    /// 삭제된 파일도 열어 둔 핸들로는 읽을 수 있으므로 남은 내용을 모두 전달한 뒤 수집 종료 (true)
+    async fn handle_removed(&mut self, shutdown: &CancellationToken) -> bool {
+        if metadata(&self.fs_path).await.is_ok() {
+            if let Err(e) = self.read_line_to_send(shutdown).await {
+                warn!(
+                    source = %self.label,
+                    path = %self.path.display(),
+                    error = %e,
+                    code = %ErrorCode::FileReadFailed,
+                    "{}",
+                    msg::FILE_READ_FAILED
+                );
+            }
+            return false;
+        }
+
+        if let Err(e) = self.drain_rotated(shutdown).await {
+            warn!(
+                source = %self.label,
+                path = %self.path.display(),
+                error = %e,
+                code = %ErrorCode::FileReadFailed,
+                "{}",
+                msg::FILE_READ_FAILED
+            );
+        }
+        if let Err(e) = self.flush_record().await {
+            warn!(source = %self.label, error = %e, code = %ErrorCode::RecordFlushFailed, "{}", msg::RECORD_FLUSH_FAILED);
+        }
 
+        status::registry().update_source(&self.label, &self.path, self.position);
+        warn!(source = %self.label, path = %self.path.display(), position = self.position, "{}", msg::FILE_DELETED);
+        true
+    }
+
+    async fn check_rotation_or_truncate(
+        &mut self,
+        current_file_id: u64,
+        current_len: u64,
+        shutdown: &CancellationToken,
+    ) -> Result<bool> {
         if current_file_id != self.file_id {
-            info!("{} Rotation 감지", self.label);
+            info!(source = %self.label, "{}", msg::ROTATION_DETECTED);
+            // 새 파일 라인이 이전 파일 끝부분보다 먼저 전달되지 않도록 이전 파일을 끝까지 읽은 뒤 전환
+            self.drain_rotated(shutdown).await?;
             self.reopen(false).await?;
             return Ok(true);
         }
 
-        if current_len < self.position {
+        // 이미 읽은 위치보다 짧아졌으면 트런케이션
+        if current_len < self.position + self.buf.len() as u64 {
             info!(
-                "{} Truncation  감지 {} -> {}",
-                self.label, self.position, current_len
+                source = %self.label,
+                from = self.position,
+                to = current_len,
+                "{}",
+                msg::TRUNCATION_DETECTED
             );
             self.reopen(true).await?;
             return Ok(true);
@@ -138,62 +576,479 @@ impl Collector {
         Ok(false)
     }
 
-    async fn send_event(&self, line: &str) -> Result<()> {
-        if line.trim().is_empty() {
+    /// 로테이션되거나 삭제된 이전 파일을 열어 둔 핸들로 끝까지 읽어 전달
+    ///
+    /// 감지 직전까지 이전 파일에 추가된 라인과 개행 없이 끝난 마지막 조각도 전달 (이전 파일에는 더 이어지지 않음)
+    async fn drain_rotated(&mut self, shutdown: &CancellationToken) -> Result<()> {
+        loop {
+            while let Some(line) = self.next_line() {
+                self.budget.wait_for_capacity(shutdown).await;
+
+                let len = line.len() as u64;
+                self.push_line(line, self.position).await?;
+                self.position += len;
+            }
+
+            let offset = self.position + self.buf.len() as u64;
+            let read_bytes = self
+                .reader
+                .read(&mut self.buf, offset)
+                .await
+                .context(msg::LINE_READ_FAILED)?;
+            if read_bytes == 0 {
+                break;
+            }
+        }
+
+        if !self.buf.is_empty() {
+            let rest = self.buf.split().freeze();
+            let len = rest.len() as u64;
+            self.scanned = 0;
+            self.push_line(rest, self.position).await?;
+            self.position += len;
+        }
+
+        Ok(())
+    }
+
+    /// `join_on` 설정 시 나뉜 줄을 합친 뒤 다음 단계로 전달
+    async fn push_line(&mut self, line: Bytes, offset: u64) -> Result<()> {
+        let line = self.delimiter.strip(line);
+        // `skip`, `base64` 는 레코드가 완성될 때 판단하도록 원본 바이트 유지
+        let content = match self.invalid_utf8 {
+            InvalidUtf8::Lossy => to_content(line),
+            InvalidUtf8::Skip | InvalidUtf8::Base64 => trim_end(line),
+        };
+
+        match self.join.as_mut() {
+            Some(join) => join.push(content, offset),
+            None => return self.push_joined(content, offset).await,
+        }
+
+        while let Some(line) = self.join.as_mut().and_then(LineJoin::pop) {
+            self.push_joined(line.content, line.offset).await?;
+        }
+        Ok(())
+    }
+
+    /// 여러 줄 레코드 형식이면 레코드가 완성될 때 전달, 아니면 바로 전달
+    async fn push_joined(&mut self, content: Bytes, offset: u64) -> Result<()> {
+        match self.multiline.as_mut() {
+            Some(multiline) => multiline.push(content, offset),
+            None => return self.send_event(content, offset).await,
+        }
+
+        while let Some(record) = self.multiline.as_mut().and_then(Multiline::pop) {
+            self.send_event(record.content, record.offset).await?;
+        }
+        Ok(())
+    }
+
+    /// 합치고 있던 줄과 묶고 있던 레코드를 완성된 것으로 보고 전달
+    async fn flush_record(&mut self) -> Result<()> {
+        if let Some(line) = self.join.as_mut().and_then(LineJoin::take) {
+            self.push_joined(line.content, line.offset).await?;
+        }
+
+        match self.multiline.as_mut().and_then(Multiline::take) {
+            Some(record) => self.send_event(record.content, record.offset).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 대기 시간이 지난 단계만 완성, 합친 줄이 넘어간 레코드는 다시 대기 시간부터 기다림
+    async fn flush_idle(&mut self) -> Result<()> {
+        let now = Instant::now();
+
+        if self.join.as_ref().and_then(LineJoin::deadline).is_some_and(|d| d <= now)
+            && let Some(line) = self.join.as_mut().and_then(LineJoin::take)
+        {
+            self.push_joined(line.content, line.offset).await?;
+        }
+
+        if self.multiline.as_ref().and_then(Multiline::deadline).is_some_and(|d| d <= now)
+            && let Some(record) = self.multiline.as_mut().and_then(Multiline::take)
+        {
+            self.send_event(record.content, record.offset).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 합치고 있는 줄이나 묶고 있는 레코드 중 먼저 완성으로 볼 시각
+    fn deadline(&self) -> Option<Instant> {
+        let join = self.join.as_ref().and_then(LineJoin::deadline);
+        let multiline = self.multiline.as_ref().and_then(Multiline::deadline);
+        join.into_iter().chain(multiline).min()
+    }
+
+    async fn send_event(&mut self, content: Bytes, offset: u64) -> Result<()> {
+        let mut frame = parser::unframe(self.format, content);
+        if frame.content.trim_ascii().is_empty() {
             return Ok(());
         }
 
+        let mut raw = None;
+        if std::str::from_utf8(&frame.content).is_err() {
+            match self.invalid_utf8 {
+                InvalidUtf8::Skip => {
+                    status::registry().record_invalid_utf8(&self.label);
+                    warn!(source = %self.label, offset, code = %ErrorCode::InvalidUtf8, "{}", msg::INVALID_UTF8_SKIPPED);
+                    return Ok(());
+                }
+                InvalidUtf8::Base64 => raw = Some(STANDARD.encode(&frame.content)),
+                InvalidUtf8::Lossy => {}
+            }
+            frame.content = Bytes::from(String::from_utf8_lossy(&frame.content).into_owned());
+        }
+
+        let text = std::str::from_utf8(&frame.content).unwrap_or_default();
+        let mut fields = frame.fields;
+        if let Some(raw) = raw {
+            fields.push((RAW_BASE64_FIELD.to_string(), raw));
+        }
+        let mut timestamp = frame.timestamp;
+        match self.header.as_mut() {
+            Some(header) => {
+                if header.update(text, offset) {
+                    return Ok(());
+                }
+                let (row, row_timestamp) = header.parse(text);
+                fields.extend(row);
+                timestamp = timestamp.or(row_timestamp);
+            }
+            None => fields.extend(parser::parse(self.format, text)),
+        }
+        if timestamp.is_none() && let Some(timestamp_format) = &self.timestamp_format {
+            timestamp = timestamp_format.extract(text);
+        }
+
         let event = LogEvent {
             label: self.label.clone(),
-            content: line.trim_end().to_string(),
-            timestamp: Utc::now(),
+            content: frame.content,
+            timestamp: timestamp.unwrap_or_else(Utc::now),
+            path: Arc::clone(&self.path_str),
+            offset,
+            file_id: self.file_id,
+            fields,
         };
 
-        self.tx.send(event).await.context("메세지 채널 닫힘")?;
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
 
         Ok(())
     }
 
+    fn next_line(&mut self) -> Option<Bytes> {
+        self.delimiter.split(&mut self.buf, &mut self.scanned)
+    }
+
+    /// 이전 파일의 남은 데이터는 버림 (트런케이션으로 사라진 데이터, 로테이션은 [`Self::drain_rotated`] 로 먼저 비움)
     async fn reopen(&mut self, seek_to_end: bool) -> Result<()> {
-        let (reader, position, file_id) = open_file(&self.path, seek_to_end)
+        // 이전 파일의 레코드는 더 이어지지 않음
+        self.flush_record().await?;
+
+        let (file, position, file_id) = open_file(&self.fs_path, seek_to_end)
             .await
-            .context("파일 재열기 실패")?;
+            .context(msg::FILE_REOPEN_FAILED)?;
 
-        self.reader = reader;
+        self.reader = Reader::new(file, self.backend)
+            .await
+            .context(msg::FILE_REOPEN_FAILED)?;
+        self.buf.clear();
+        self.scanned = 0;
         self.position = position;
         self.file_id = file_id;
 
+        if position > 0 && let Some(header) = self.header.as_mut() {
+            seed_header(header, &self.fs_path).await;
+        }
+
         Ok(())
     }
 }
 
-async fn open_file(path: &PathBuf, seek_to_end: bool) -> Result<(BufReader<File>, u64, u64)> {
-    let file = File::open(path).await.context("파일 열기 실패")?;
+/// 파일 중간부터 읽을 때 헤더 형식(CSV 등)의 상태를 파일 첫 줄로 초기화, 읽지 못하면 그대로
+async fn seed_header(header: &mut Header, path: &Path) {
+    let Ok(file) = File::open(path).await else {
+        return;
+    };
+
+    let mut first = Vec::new();
+    if BufReader::new(file.take(MAX_HEADER_BYTES))
+        .read_until(b'\n', &mut first)
+        .await
+        .is_err()
+    {
+        return;
+    }
+    header.update(String::from_utf8_lossy(&first).trim_end(), 0);
+}
+
+/// 여러 줄 레코드의 완성 시각까지 대기, 묶고 있는 레코드가 없으면 끝나지 않음
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 감시자 상태 확인용
+#[derive(Default)]
+struct WatchCheck {
+    /// 마지막 확인 이후 변경 이벤트를 받았는지
+    events: bool,
+    /// 마지막 확인 때 읽지 않은 데이터가 있었는지
+    unread: bool,
+    /// 마지막으로 감시자를 다시 만든 시각
+    recreated_at: Option<Instant>,
+}
+
+/// 파일 변경/삭제 이벤트를 채널로 보내는 감시자 생성
+fn watch_file(path: &Path, modified: &Sender<()>, removed: &Sender<()>) -> notify::Result<RecommendedWatcher> {
+    let modified = modified.clone();
+    let removed = removed.clone();
 
-    let meta = file.metadata().await.context("파일 메타데이터 읽기 실패")?;
+    let mut watcher = recommended_watcher(move |res: Result<notify::Event, notify::Error>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if event.kind.is_modify() {
+            let _ = modified.try_send(());
+        } else if event.kind.is_remove() {
+            let _ = removed.try_send(());
+        }
+    })?;
+
+    watcher.watch(path, notify::RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// 확인 주기, 읽기가 길어져 놓친 확인은 몰아서 하지 않음
+fn check_ticker(period: Duration) -> time::Interval {
+    let mut ticker = time::interval_at(Instant::now() + period, period);
+    ticker.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+    ticker
+}
+
+/// 대기 시간 동안 들어온 이벤트를 비워 한 번의 읽기로 합침
+async fn coalesce_events(rx: &mut mpsc::Receiver<()>) {
+    time::sleep(WATCH_DEBOUNCE).await;
+    while rx.try_recv().is_ok() {}
+}
+
+/// 설정된 읽기 방식을 이 빌드/커널에서 쓸 수 있는지 확인, 쓸 수 없으면 `std`
+pub fn resolve_backend(requested: ReadBackend) -> ReadBackend {
+    match requested {
+        ReadBackend::Std => ReadBackend::Std,
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        ReadBackend::IoUring if uring::is_available() => ReadBackend::IoUring,
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        ReadBackend::IoUring => ReadBackend::Std,
+        #[cfg(not(all(target_os = "linux", feature = "io-uring")))]
+        ReadBackend::IoUring => {
+            warn!("{}", msg::READ_BACKEND_UNSUPPORTED);
+            ReadBackend::Std
+        }
+    }
+}
+
+/// 수집 파일 읽기 방식별 핸들
+enum Reader {
+    /// 커서가 항상 다음에 읽을 위치에 있음
+    Std(File),
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    Uring(uring::UringFile),
+}
+
+impl Reader {
+    /// `file` 은 다음에 읽을 위치로 이동된 상태
+    async fn new(file: File, backend: ReadBackend) -> io::Result<Self> {
+        match backend {
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            ReadBackend::IoUring => Ok(Reader::Uring(uring::UringFile::register(file.into_std().await)?)),
+            _ => Ok(Reader::Std(file)),
+        }
+    }
+
+    /// `offset` 부터 읽어 `buf` 뒤에 이어 붙임, 파일 끝이면 0
+    async fn read(&mut self, buf: &mut BytesMut, offset: u64) -> io::Result<usize> {
+        match self {
+            // 커서가 이미 `offset` 에 있음
+            Reader::Std(file) => {
+                let _ = offset;
+                file.read_buf(buf).await
+            }
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            Reader::Uring(file) => file.read_at(buf, offset).await,
+        }
+    }
+}
+
+/// 수집 파일의 줄 구분 (`sources[].line_delimiter`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delimiter {
+    /// `\n`, `\r\n` 의 `\r` 은 [`to_content`] 가 제거
+    Lf,
+    /// `\n`, `\r\n`, `\r` 단독 (`\r` 로 같은 줄을 덮어쓰는 진행률 출력, 오래된 macOS 로그)
+    Any,
+    /// 지정한 바이트열 (`\0` 등)
+    Bytes(Vec<u8>),
+}
+
+impl Delimiter {
+    pub fn new(source: &SourceSettings) -> Result<Self> {
+        match source.line_delimiter.as_deref() {
+            None | Some("lf") => Ok(Self::Lf),
+            Some("any") => Ok(Self::Any),
+            Some("nul") => Ok(Self::Bytes(vec![0])),
+            Some("") => bail!("{}: {}", msg::LINE_DELIMITER_INVALID, source.label),
+            Some(delimiter) => Ok(Self::Bytes(delimiter.as_bytes().to_vec())),
+        }
+    }
+
+    /// 버퍼에서 구분자까지 잘라낸 라인 (구분자 포함), 완성된 라인이 없으면 None
+    pub fn split(&self, buf: &mut BytesMut, scanned: &mut usize) -> Option<Bytes> {
+        match self {
+            Self::Lf => split_line(buf, scanned),
+            Self::Any => split_any(buf, scanned),
+            Self::Bytes(delimiter) => split_at(buf, scanned, delimiter),
+        }
+    }
+
+    /// 라인 끝의 구분자 제거 (`Lf`, `Any` 는 [`to_content`] 가 끝 공백과 함께 제거)
+    pub fn strip(&self, line: Bytes) -> Bytes {
+        match self {
+            Self::Bytes(delimiter) if line.ends_with(delimiter) => line.slice(..line.len() - delimiter.len()),
+            _ => line,
+        }
+    }
+}
+
+/// 버퍼에서 개행까지 잘라낸 라인 (개행 포함), 완성된 라인이 없으면 None
+///
+/// `scanned` 는 개행이 없음을 확인한 앞부분 길이로, 다음 호출에서 다시 탐색하지 않음
+pub fn split_line(buf: &mut BytesMut, scanned: &mut usize) -> Option<Bytes> {
+    match buf[*scanned..].iter().position(|&b| b == b'\n') {
+        Some(i) => {
+            let end = *scanned + i + 1;
+            *scanned = 0;
+            Some(buf.split_to(end).freeze())
+        }
+        None => {
+            *scanned = buf.len();
+            None
+        }
+    }
+}
+
+/// `\n`, `\r\n`, `\r` 중 먼저 나온 구분자까지
+fn split_any(buf: &mut BytesMut, scanned: &mut usize) -> Option<Bytes> {
+    let Some(i) = buf[*scanned..].iter().position(|&b| b == b'\n' || b == b'\r') else {
+        *scanned = buf.len();
+        return None;
+    };
+
+    let i = *scanned + i;
+    let end = match (buf[i], buf.get(i + 1)) {
+        (b'\n', _) => i + 1,
+        (_, Some(b'\n')) => i + 2,
+        (_, Some(_)) => i + 1,
+        // 버퍼 끝의 `\r` 은 다음 읽기에서 `\n` 이 이어지는지 확인
+        (_, None) => {
+            *scanned = i;
+            return None;
+        }
+    };
+    *scanned = 0;
+    Some(buf.split_to(end).freeze())
+}
+
+/// 여러 바이트 구분자까지
+fn split_at(buf: &mut BytesMut, scanned: &mut usize, delimiter: &[u8]) -> Option<Bytes> {
+    match buf[*scanned..].windows(delimiter.len()).position(|window| window == delimiter) {
+        Some(i) => {
+            let end = *scanned + i + delimiter.len();
+            *scanned = 0;
+            Some(buf.split_to(end).freeze())
+        }
+        None => {
+            // 버퍼 끝에 걸친 구분자 앞부분은 다음 읽기에서 다시 확인
+            *scanned = buf.len().saturating_sub(delimiter.len() - 1);
+            None
+        }
+    }
+}
+
+/// 끝 공백/개행을 잘라낸 조각, UTF-8 이 아니면 대체 문자로 바꾼 사본
+pub fn to_content(line: Bytes) -> Bytes {
+    let trimmed = trim_end(line);
+
+    match std::str::from_utf8(&trimmed) {
+        Ok(_) => trimmed,
+        Err(_) => Bytes::from(String::from_utf8_lossy(&trimmed).into_owned()),
+    }
+}
+
+/// 끝 공백/개행을 잘라낸 조각 (원본 바이트 그대로)
+fn trim_end(line: Bytes) -> Bytes {
+    line.slice(..line.trim_ascii_end().len())
+}
+
+async fn open_file(path: &Path, seek_to_end: bool) -> Result<(File, u64, u64)> {
+    let mut file = File::open(path).await.context(msg::FILE_OPEN_FAILED)?;
+
+    let meta = file.metadata().await.context(msg::FILE_METADATA_FAILED)?;
 
     let position = if seek_to_end { meta.len() } else { 0 };
+    #[cfg(unix)]
     let file_id = get_file_id(&meta);
+    #[cfg(windows)]
+    let file_id = get_file_id(&file, &meta);
 
-    let mut reader = BufReader::new(file);
-    reader
-        .seek(SeekFrom::Start(position))
+    file.seek(SeekFrom::Start(position))
         .await
-        .context("파일 포인터 이동 실패")?;
+        .context(msg::FILE_SEEK_FAILED)?;
 
-    Ok((reader, position, file_id))
+    Ok((file, position, file_id))
 }
 
-fn get_file_id(meta: &Metadata) -> u64 {
+/// 경로에 있는 파일의 식별자와 크기
+async fn stat(path: &Path) -> io::Result<(u64, u64)> {
     #[cfg(unix)]
     {
-        meta.ino()
+        let meta = metadata(path).await?;
+        Ok((get_file_id(&meta), meta.len()))
     }
 
+    // 파일 인덱스는 핸들로만 얻을 수 있음
     #[cfg(windows)]
     {
-        // NOTE: file_index()가 unstable 이라서 creation_time 사용
-        // meta.file_index().unwrap_or(0)
-        meta.creation_time()
+        let file = File::open(path).await?;
+        let meta = file.metadata().await?;
+        Ok((get_file_id(&file, &meta), meta.len()))
     }
 }
+
+#[cfg(unix)]
+fn get_file_id(meta: &Metadata) -> u64 {
+    meta.ino()
+}
+
+/// 볼륨 안의 파일 인덱스, SMB 서버가 주지 않으면(0) 생성 시각
+///
+/// NOTE: `MetadataExt::file_index()` 가 unstable 이라 핸들로 직접 조회.
+/// 생성 시각은 같은 이름으로 바로 다시 만든 파일에 이전 값이 이어지므로(터널링) 로테이션을 놓칠 수 있음
+#[cfg(windows)]
+fn get_file_id(file: &File, meta: &Metadata) -> u64 {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{BY_HANDLE_FILE_INFORMATION, GetFileInformationByHandle};
+
+    // SAFETY: 열린 파일 핸들과 쓰기 가능한 `info`
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    let ok = unsafe { GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) } != 0;
+    let index = (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow);
+
+    if ok && index != 0 { index } else { meta.creation_time() }
+}
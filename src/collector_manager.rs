@@ -0,0 +1,348 @@
+//! 실행 중 수집 대상 추가/제거
+//!
+//! 수집 대상별 Collector 태스크를 라벨로 관리해, 파이프라인(Forwarder, Streamer)을 다시 시작하지 않고 개별 수집 대상만
+//! 시작/중지. 관리 API 의 `add_source`, `remove_source`, `reload_sources` 가 [`request`] 로 요청하며,
+//! 종료 시 파일 Collector 의 마지막 위치를 모아 재실행 핸드오버에 넘김
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result, anyhow, bail};
+use tokio::sync::mpsc::{self, Sender};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::agent::lane_for;
+use crate::collector::{self, Collector};
+use crate::crash;
+use crate::error_code::ErrorCode;
+use crate::gelf::GelfCollector;
+use crate::hot_restart::{CollectorOffset, Handover};
+use crate::memory::MemoryBudget;
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::oslog::OsLogCollector;
+use crate::redis_stream::RedisStreamCollector;
+use crate::settings::{ReadBackend, SourceKind, SourceSettings};
+use crate::snmp_trap::SnmpTrapCollector;
+use crate::sql_poll::SqlCollector;
+use crate::status;
+use crate::schedule;
+use crate::self_log::SelfLogCollector;
+use crate::watch_health;
+use crate::win_path;
+
+const REQUEST_CHANNEL_CAPACITY: usize = 16;
+
+/// 실행 중인 관리자의 요청 채널, 에이전트가 실행 중이 아니면 None
+static REQUESTS: Mutex<Option<Sender<Envelope>>> = Mutex::new(None);
+
+/// 관리자에 보내는 요청
+pub enum Request {
+    Add(Box<SourceSettings>),
+    Remove(String),
+    /// 목록에 맞춰 없어진/바뀐 수집 대상은 중지하고 새 수집 대상은 시작
+    Sync(Vec<SourceSettings>),
+    List,
+    /// 읽기만 멈추고 위치는 유지, 재개하면 멈춘 동안 쌓인 라인부터 읽음
+    SetPaused { label: String, paused: bool },
+}
+
+/// 실행 중인 관리자에 요청하고 결과 설명을 받음
+pub async fn request(request: Request) -> Result<String> {
+    let tx = REQUESTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .context(msg::COLLECTOR_MANAGER_NOT_RUNNING)?;
+
+    let (reply_tx, reply_rx) = oneshot::channel();
+    tx.send(Envelope { request, reply: reply_tx })
+        .await
+        .map_err(|_| anyhow!(msg::COLLECTOR_MANAGER_NOT_RUNNING))?;
+
+    reply_rx.await.map_err(|_| anyhow!(msg::COLLECTOR_MANAGER_NOT_RUNNING))?
+}
+
+struct Envelope {
+    request: Request,
+    reply: oneshot::Sender<Result<String>>,
+}
+
+struct Running {
+    source: SourceSettings,
+    shutdown: CancellationToken,
+    handle: JoinHandle<Option<CollectorOffset>>,
+}
+
+/// 수집 대상별 Collector 태스크를 라벨로 관리
+pub struct CollectorManager {
+    /// 레인별 수집 채널, 이벤트는 라벨로 정해진 레인으로
+    lanes: Vec<Sender<LogEvent>>,
+    backend: ReadBackend,
+    budget: MemoryBudget,
+    shutdown: CancellationToken,
+    running: BTreeMap<String, Running>,
+}
+
+impl CollectorManager {
+    pub fn new(
+        lanes: Vec<Sender<LogEvent>>,
+        read_backend: ReadBackend,
+        budget: MemoryBudget,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            lanes,
+            backend: collector::resolve_backend(read_backend),
+            budget,
+            shutdown,
+            running: BTreeMap::new(),
+        }
+    }
+
+    /// 시작 시 설정의 수집 대상 실행, 하나라도 실패하면 오류
+    pub async fn start_all(&mut self, sources: Vec<SourceSettings>, handover: Option<&Handover>) -> Result<()> {
+        for source in sources {
+            let resume = handover.and_then(|h| h.offset_for(&source.label, Path::new(&source.path)));
+            self.start(source, resume).await?;
+        }
+        Ok(())
+    }
+
+    /// 수집 대상 하나 시작, 같은 라벨이 실행 중이면 오류
+    pub async fn start(&mut self, source: SourceSettings, resume: Option<&CollectorOffset>) -> Result<()> {
+        if self.running.contains_key(&source.label) {
+            bail!("{}: {}", msg::SOURCE_ALREADY_RUNNING, source.label);
+        }
+
+        let label = source.label.clone();
+        schedule::register(&source)?;
+        let shutdown = self.shutdown.child_token();
+        let handle = self
+            .spawn(source.clone(), resume, shutdown.clone())
+            .await
+            .inspect_err(|_| schedule::remove(&label))?;
+
+        self.running.insert(label, Running { source, shutdown, handle });
+        Ok(())
+    }
+
+    /// 수집 대상 하나 중지, 남은 라인을 전달하고 끝날 때까지 대기
+    pub async fn stop(&mut self, label: &str) -> Result<()> {
+        let running = self
+            .running
+            .remove(label)
+            .with_context(|| format!("{}: {}", msg::CONTROL_UNKNOWN_SOURCE, label))?;
+
+        running.shutdown.cancel();
+        if let Err(e) = running.handle.await {
+            error!(source = label, error = ?e, code = %ErrorCode::CollectorFailed, "{}", msg::COLLECTOR_TASK_FAILED);
+        }
+
+        collector::set_paused(label, false);
+        status::registry().remove_source(label);
+        watch_health::remove(label);
+        schedule::remove(label);
+        Ok(())
+    }
+
+    /// 목록과 맞춤, 설정이 바뀐 수집 대상은 다시 시작 (파일은 끝부터 다시 읽음)
+    pub async fn sync(&mut self, sources: Vec<SourceSettings>) -> Result<String> {
+        let wanted: BTreeMap<String, SourceSettings> =
+            sources.into_iter().map(|source| (source.label.clone(), source)).collect();
+
+        let stale: Vec<String> = self
+            .running
+            .iter()
+            .filter(|(label, running)| wanted.get(*label).is_none_or(|source| !same(source, &running.source)))
+            .map(|(label, _)| label.clone())
+            .collect();
+
+        for label in &stale {
+            self.stop(label).await?;
+        }
+
+        let mut started = Vec::new();
+        for (label, source) in wanted {
+            if self.running.contains_key(&label) {
+                continue;
+            }
+            self.start(source, None).await?;
+            started.push(label);
+        }
+
+        Ok(format!("stopped=[{}] started=[{}]", stale.join(","), started.join(",")))
+    }
+
+    /// 요청 채널을 열고 `shutdown` 까지 요청 처리, 종료 후 모든 Collector 를 기다려 파일 Collector 의 마지막 위치 반환
+    pub fn spawn_serve(mut self) -> JoinHandle<Vec<CollectorOffset>> {
+        let (tx, mut rx) = mpsc::channel::<Envelope>(REQUEST_CHANNEL_CAPACITY);
+        *REQUESTS.lock().unwrap_or_else(|e| e.into_inner()) = Some(tx);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = self.shutdown.cancelled() => break,
+                    Some(envelope) = rx.recv() => {
+                        let result = self.handle(envelope.request).await;
+                        if let Err(e) = &result {
+                            warn!(error = %format!("{:#}", e), "{}", msg::COLLECTOR_MANAGER_REQUEST_FAILED);
+                        }
+                        let _ = envelope.reply.send(result);
+                    }
+                }
+            }
+
+            REQUESTS.lock().unwrap_or_else(|e| e.into_inner()).take();
+            self.join().await
+        })
+    }
+
+    async fn handle(&mut self, request: Request) -> Result<String> {
+        match request {
+            Request::Add(source) => {
+                let label = source.label.clone();
+                self.start(*source, None).await?;
+                info!(source = %label, "{}", msg::SOURCE_ADDED);
+                Ok(format!("started=[{}]", label))
+            }
+            Request::Remove(label) => {
+                self.stop(&label).await?;
+                info!(source = %label, "{}", msg::SOURCE_REMOVED);
+                Ok(format!("stopped=[{}]", label))
+            }
+            Request::Sync(sources) => {
+                let summary = self.sync(sources).await?;
+                info!(summary = %summary, "{}", msg::SOURCES_SYNCED);
+                Ok(summary)
+            }
+            Request::List => Ok(self.running.keys().cloned().collect::<Vec<_>>().join(",")),
+            Request::SetPaused { label, paused } => {
+                if !self.running.contains_key(&label) {
+                    bail!("{}: {}", msg::CONTROL_UNKNOWN_SOURCE, label);
+                }
+
+                collector::set_paused(&label, paused);
+                let message = if paused {
+                    msg::CONTROL_SOURCE_PAUSED
+                } else {
+                    msg::CONTROL_SOURCE_RESUMED
+                };
+                info!(source = %label, "{}", message);
+                Ok(message.to_string())
+            }
+        }
+    }
+
+    /// Collector 종료 대기 후 파일 Collector 의 마지막 위치 반환
+    async fn join(self) -> Vec<CollectorOffset> {
+        let mut offsets = Vec::with_capacity(self.running.len());
+
+        for (label, running) in self.running {
+            match running.handle.await {
+                Ok(offset) => offsets.extend(offset),
+                Err(e) => error!(source = %label, error = ?e, code = %ErrorCode::CollectorFailed, "{}", msg::COLLECTOR_TASK_FAILED),
+            }
+        }
+
+        offsets
+    }
+
+    /// 수집 대상 종류에 맞는 Collector 태스크 실행
+    async fn spawn(
+        &self,
+        source: SourceSettings,
+        resume: Option<&CollectorOffset>,
+        shutdown: CancellationToken,
+    ) -> Result<JoinHandle<Option<CollectorOffset>>> {
+        let tx = self.lanes[lane_for(&source.label, self.lanes.len())].clone();
+        let budget = self.budget.clone();
+        let backend = self.backend;
+
+        let handle = match source.kind {
+            // 파일이 생길 때까지 백그라운드에서 기다림
+            SourceKind::File if source.optional && !win_path::extended(Path::new(&source.path)).exists() => {
+                warn!(source = %source.label, path = %source.path, "{}", msg::OPTIONAL_SOURCE_MISSING);
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    let mut collector = Collector::wait_for_file(tx, source, budget, backend, &shutdown).await?;
+                    collector.start(shutdown).await;
+                    Some(collector.offset())
+                }))
+            }
+            SourceKind::File => {
+                let mut collector = Collector::new(tx, source, budget, resume, backend).await?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    Some(collector.offset())
+                }))
+            }
+            // 이어 읽을 위치가 없으므로 재실행 핸드오버 대상 아님
+            SourceKind::Oslog => {
+                let mut collector = OsLogCollector::new(tx, source, budget)?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+            SourceKind::GelfUdp => {
+                let mut collector = GelfCollector::bind(tx, source, budget).await?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+            // 위치는 Redis 컨슈머 그룹과 상태 파일이 관리
+            SourceKind::RedisStream => {
+                let mut collector = RedisStreamCollector::new(tx, source, budget)?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+            // 위치는 커서 상태 파일이 관리
+            SourceKind::Sql => {
+                let mut collector = SqlCollector::new(tx, source, budget)?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+            SourceKind::SnmpTrap => {
+                let mut collector = SnmpTrapCollector::bind(tx, source, budget).await?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+            SourceKind::SelfLog => {
+                let mut collector = SelfLogCollector::new(tx, source, budget)?;
+
+                tokio::spawn(crash::in_subsystem("Collector", async move {
+                    collector.start(shutdown).await;
+                    None
+                }))
+            }
+        };
+
+        Ok(handle)
+    }
+}
+
+/// 설정이 같은 수집 대상인지 (필드가 많아 직렬화한 값으로 비교)
+fn same(a: &SourceSettings, b: &SourceSettings) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,294 @@
+//! 서버 → 에이전트 명령 채널
+//!
+//! `ControlService.Subscribe` 양방향 스트림으로 서버가 내려보낸 명령(설정 재적용, 수집 일시 중지/재개,
+//! 즉시 플러시, 로그 레벨 변경, 자가 진단, 자체 업데이트)을 실행하고 명령마다 결과를 응답.
+//! ControlService 를 구현하지 않은 서버와는 명령 채널 없이 동작
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use tokio::sync::{mpsc, RwLock};
+use tokio::time;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::Channel;
+use tonic::{Code, Status, Streaming};
+use tracing::{info, warn};
+
+use crate::auth::interceptor::AuthInterceptor;
+use crate::auth::token_manager::TokenManager;
+use crate::collector_manager::{self, Request};
+use crate::doctor::{self, Status as CheckStatus};
+use crate::error_code::ErrorCode;
+use crate::forwarder;
+use crate::hot_restart;
+use crate::log_level;
+use crate::messages as msg;
+use crate::proto::control::command::Action;
+use crate::proto::control::control_service_client::ControlServiceClient;
+use crate::proto::control::{Command, CommandResult, CommandStatus, SelfTestCheck, UpdateAgent};
+use crate::retry::Backoff;
+use crate::settings::Settings;
+use crate::updater::{self, Release};
+
+type ControlClient = ControlServiceClient<InterceptedService<Channel, AuthInterceptor>>;
+
+/// 종료 시 마지막 결과가 서버에 전달되도록 스트림 정리를 기다리는 시간
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+const RESULT_CHANNEL_CAPACITY: usize = 16;
+
+pub struct ControlChannel {
+    client: ControlClient,
+    interceptor: AuthInterceptor,
+    token_manager: Arc<RwLock<TokenManager>>,
+    settings: Settings,
+    /// 에이전트 종료 신호, 설정 재적용 시 재실행을 위해 직접 취소
+    shutdown: CancellationToken,
+    backoff: Backoff,
+    reload_requested: bool,
+}
+
+impl ControlChannel {
+    pub fn new(
+        channel: Channel,
+        interceptor: AuthInterceptor,
+        token_manager: Arc<RwLock<TokenManager>>,
+        settings: Settings,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            client: ControlServiceClient::with_interceptor(channel, interceptor.clone()),
+            interceptor,
+            token_manager,
+            settings,
+            shutdown,
+            backoff: Backoff::new(),
+            reload_requested: false,
+        }
+    }
+
+    /// `shutdown` 취소 시까지 연결 유지, 끊기면 backoff 후 재연결
+    pub async fn start(mut self) {
+        loop {
+            let result = self.run_stream().await;
+            let delay = self.backoff.next_delay();
+
+            match result {
+                Ok(()) => info!("{}", msg::CONTROL_STREAM_CLOSED),
+                Err(status) if status.code() == Code::Unimplemented => {
+                    info!("{}", msg::CONTROL_UNSUPPORTED);
+                    return;
+                }
+                Err(status) if status.code() == Code::Unauthenticated => {
+                    warn!(code = %ErrorCode::TokenExpired, "{}", msg::TOKEN_EXPIRED_RETRY);
+
+                    let mut tm = self.token_manager.write().await;
+                    if let Err(e) = tm.refresh().await {
+                        warn!(error = %format!("{:#}", e), "{}", msg::CONTROL_DISCONNECTED);
+                    }
+                }
+                Err(status) => warn!(
+                    backoff_ms = delay.as_millis() as u64,
+                    error = %status,
+                    "{}",
+                    msg::CONTROL_DISCONNECTED
+                ),
+            }
+
+            if self.shutdown.is_cancelled() {
+                break;
+            }
+
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = time::sleep(delay) => {}
+            }
+        }
+
+        info!("{}", msg::CONTROL_STOPPED);
+    }
+
+    /// 스트림 하나를 열어 명령을 차례로 실행, 서버가 닫거나 종료 신호를 받으면 반환
+    async fn run_stream(&mut self) -> Result<(), Status> {
+        let (tx, rx) = mpsc::channel::<CommandResult>(RESULT_CHANNEL_CAPACITY);
+
+        let mut commands = tokio::select! {
+            _ = self.shutdown.cancelled() => return Ok(()),
+            response = self.client.subscribe(ReceiverStream::new(rx)) => response?.into_inner(),
+        };
+
+        self.backoff.reset();
+        info!("{}", msg::CONTROL_CONNECTED);
+
+        loop {
+            let command = tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                command = commands.message() => match command? {
+                    Some(command) => command,
+                    None => return Ok(()),
+                },
+            };
+
+            let result = self.execute(command).await;
+            if tx.send(result).await.is_err() {
+                break;
+            }
+
+            if self.reload_requested {
+                info!("{}", msg::CONTROL_RELOADING);
+                hot_restart::request();
+                self.shutdown.cancel();
+            }
+        }
+
+        // 요청 스트림을 닫고 서버가 응답을 마칠 때까지 대기 (전송 대기 중인 결과 전달)
+        drop(tx);
+        let _ = time::timeout(CLOSE_TIMEOUT, drain(&mut commands)).await;
+        Ok(())
+    }
+
+    async fn execute(&mut self, command: Command) -> CommandResult {
+        let command_id = command.command_id;
+
+        // 이후 버전 서버의 새 명령은 oneof 를 해석하지 못해 비어 있음
+        let Some(action) = command.action else {
+            warn!(command_id = %command_id, "{}", msg::CONTROL_UNKNOWN_COMMAND);
+            return CommandResult {
+                command_id,
+                status: CommandStatus::Unsupported as i32,
+                message: msg::CONTROL_UNKNOWN_COMMAND.to_string(),
+                checks: Vec::new(),
+            };
+        };
+
+        info!(command_id = %command_id, command = action_name(&action), "{}", msg::CONTROL_COMMAND_RECEIVED);
+
+        let (outcome, checks) = match action {
+            Action::ReloadConfig(_) => (self.reload(), Vec::new()),
+            Action::SetSourcePaused(request) => {
+                (set_source_paused(request.label, request.paused).await, Vec::new())
+            }
+            Action::FlushNow(_) => {
+                forwarder::request_flush();
+                (Ok(msg::CONTROL_FLUSH_REQUESTED.to_string()), Vec::new())
+            }
+            Action::SetLogLevel(request) => {
+                (set_log_level(&request.filter, request.duration_sec), Vec::new())
+            }
+            Action::RunSelfTest(_) => self.self_test().await,
+            Action::UpdateAgent(request) => (self.update(request).await, Vec::new()),
+        };
+
+        let (status, message) = match outcome {
+            Ok(message) => (CommandStatus::Ok, message),
+            Err(e) => {
+                let message = format!("{:#}", e);
+                warn!(command_id = %command_id, error = %message, "{}", msg::CONTROL_COMMAND_FAILED);
+                (CommandStatus::Failed, message)
+            }
+        };
+
+        CommandResult {
+            command_id,
+            status: status as i32,
+            message,
+            checks,
+        }
+    }
+
+    /// 응답을 보낸 뒤 재실행해 설정 파일을 다시 읽음 (수집 위치는 핸드오버로 이어감)
+    fn reload(&mut self) -> Result<String> {
+        if !cfg!(unix) {
+            bail!(msg::RESTART_UNIX_ONLY);
+        }
+
+        self.reload_requested = true;
+        Ok(msg::CONTROL_RELOAD_SCHEDULED.to_string())
+    }
+
+    /// 서명을 검증해 실행 파일을 교체한 뒤 응답을 보내고 재실행
+    async fn update(&mut self, request: UpdateAgent) -> Result<String> {
+        let settings = self.settings.update.as_ref().ok_or_else(|| anyhow!(msg::UPDATE_NOT_CONFIGURED))?;
+        let release = Release {
+            version: request.version,
+            url: request.url,
+            signature: request.signature,
+        };
+
+        if !updater::apply(settings, &release).await? {
+            return Ok(msg::UPDATE_ALREADY_CURRENT.to_string());
+        }
+
+        self.reload_requested = true;
+        Ok(msg::UPDATE_RESTARTING.to_string())
+    }
+
+    async fn self_test(&self) -> (Result<String>, Vec<SelfTestCheck>) {
+        let checks = doctor::run_in_session(&self.settings, self.interceptor.clone()).await;
+        doctor::log_report(&checks);
+
+        let failed = checks.iter().filter(|c| c.status == CheckStatus::Fail).count();
+        let outcome = if failed > 0 {
+            Err(anyhow!("{}: {}", msg::CONTROL_SELF_TEST_FAILED, failed))
+        } else {
+            Ok(msg::CONTROL_SELF_TEST_PASSED.to_string())
+        };
+
+        let checks = checks
+            .into_iter()
+            .map(|c| SelfTestCheck {
+                id: c.id,
+                status: c.status.to_string(),
+                detail: c.detail,
+            })
+            .collect();
+
+        (outcome, checks)
+    }
+}
+
+/// 실행 중인 수집 대상(관리 API 로 추가한 것 포함) 일시 중지/재개
+async fn set_source_paused(label: String, paused: bool) -> Result<String> {
+    collector_manager::request(Request::SetPaused { label, paused }).await
+}
+
+/// 필터 변경, `duration_sec` 이 0 이 아니면 지난 뒤 이전 필터로 복원
+fn set_log_level(filter: &str, duration_sec: u32) -> Result<String> {
+    let controller = log_level::controller().ok_or_else(|| anyhow!(msg::LOG_LEVEL_UNAVAILABLE))?;
+    let previous = controller.current();
+    controller.set(filter)?;
+
+    if duration_sec > 0 {
+        let filter = filter.to_string();
+        tokio::spawn(async move {
+            time::sleep(Duration::from_secs(duration_sec.into())).await;
+
+            // 그 사이 다른 명령이나 시그널로 바뀌었으면 유지
+            if controller.current() == filter
+                && let Err(e) = controller.set(&previous)
+            {
+                warn!(error = %e, "{}", msg::LOG_LEVEL_TOGGLE_FAILED);
+            }
+        });
+    }
+
+    Ok(msg::LOG_LEVEL_CHANGED.to_string())
+}
+
+async fn drain(commands: &mut Streaming<Command>) {
+    while let Ok(Some(_)) = commands.message().await {}
+}
+
+fn action_name(action: &Action) -> &'static str {
+    match action {
+        Action::ReloadConfig(_) => "reload_config",
+        Action::SetSourcePaused(_) => "set_source_paused",
+        Action::FlushNow(_) => "flush_now",
+        Action::SetLogLevel(_) => "set_log_level",
+        Action::RunSelfTest(_) => "run_self_test",
+        Action::UpdateAgent(_) => "update_agent",
+    }
+}
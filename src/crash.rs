@@ -0,0 +1,245 @@
+//! 비정상 종료 보고
+//!
+//! panic 훅과 서브시스템 감독에서 크래시 보고서를 `state/crash` 에 남기고,
+//! 다음 실행 시 서버 인증 직후 CrashReport RPC 로 전송 (호스트 접근 없이 크래시 파악)
+
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::future::Future;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use prost::Message;
+use tonic::transport::Channel;
+use tracing::field::{Field, Visit};
+use tracing::{info, warn, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context as LayerContext, Layer};
+
+use crate::auth::interceptor::AuthInterceptor;
+use crate::ephemeral;
+use crate::messages as msg;
+use crate::proto::health::health_service_client::HealthServiceClient;
+use crate::proto::health::CrashReportRequest;
+
+pub static CRASH_DIR: &str = "state/crash";
+static CRASH_EXTENSION: &str = "pb";
+
+/// 보고서에 포함할 최근 경고/오류 로그 개수
+const RECENT_ERRORS_CAPACITY: usize = 20;
+
+/// 서브시스템 밖(메인 스레드 등)에서 발생한 크래시의 서브시스템 이름
+static UNKNOWN_SUBSYSTEM: &str = "main";
+
+static RECENT_ERRORS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+tokio::task_local! {
+    static SUBSYSTEM: &'static str;
+}
+
+/// `future` 실행 중 발생한 panic 을 `name` 서브시스템의 크래시로 기록
+pub fn in_subsystem<F: Future>(name: &'static str, future: F) -> impl Future<Output = F::Output> {
+    SUBSYSTEM.scope(name, future)
+}
+
+fn current_subsystem() -> &'static str {
+    SUBSYSTEM.try_with(|name| *name).unwrap_or(UNKNOWN_SUBSYSTEM)
+}
+
+/// panic 발생 시 크래시 보고서를 저장한 뒤 기존 훅(표준 에러 출력) 실행
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        let report = panic_report(info);
+
+        if let Err(e) = save(&report) {
+            eprintln!("{}: {:#}", msg::CRASH_REPORT_SAVE_FAILED, e);
+        }
+
+        previous(info);
+    }));
+}
+
+fn panic_report(info: &PanicHookInfo) -> CrashReportRequest {
+    let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        msg::UNKNOWN_PANIC.to_string()
+    };
+
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    new_report(
+        current_subsystem(),
+        message,
+        location,
+        Backtrace::force_capture().to_string(),
+    )
+}
+
+/// panic 이 아닌 복구 불가 종료 (재시작 한도 초과 등) 기록
+pub fn record_failure(subsystem: &str, reason: &str) {
+    let report = new_report(subsystem, reason.to_string(), String::new(), String::new());
+
+    if let Err(e) = save(&report) {
+        warn!(error = %format!("{:#}", e), "{}", msg::CRASH_REPORT_SAVE_FAILED);
+    }
+}
+
+fn new_report(
+    subsystem: &str,
+    message: String,
+    location: String,
+    backtrace: String,
+) -> CrashReportRequest {
+    CrashReportRequest {
+        timestamp: Some(prost_types::Timestamp::from(SystemTime::from(Utc::now()))),
+        agent_version: env!("CARGO_PKG_VERSION").to_string(),
+        subsystem: subsystem.to_string(),
+        message,
+        location,
+        backtrace,
+        recent_errors: recent_errors(),
+    }
+}
+
+fn save(report: &CrashReportRequest) -> Result<()> {
+    if ephemeral::is_enabled() {
+        bail!(msg::EPHEMERAL_NOT_PERSISTED);
+    }
+
+    let dir = Path::new(CRASH_DIR);
+    fs::create_dir_all(dir).context(msg::CRASH_DIR_CREATE_FAILED)?;
+
+    let path = dir.join(format!(
+        "{:013}-{}.{}",
+        Utc::now().timestamp_millis(),
+        report.subsystem,
+        CRASH_EXTENSION
+    ));
+    let tmp = path.with_extension("tmp");
+
+    fs::write(&tmp, report.encode_to_vec()).context(msg::CRASH_REPORT_WRITE_FAILED)?;
+    fs::rename(&tmp, &path).context(msg::CRASH_REPORT_RENAME_FAILED)?;
+
+    Ok(())
+}
+
+fn pending() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(CRASH_DIR) else {
+        return Vec::new();
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == CRASH_EXTENSION))
+        .collect();
+
+    paths.sort();
+    paths
+}
+
+/// 이전 실행에서 남은 크래시 보고서를 서버로 전송, 성공한 보고서는 삭제
+pub async fn report_pending(channel: Channel, interceptor: AuthInterceptor) {
+    let paths = pending();
+    if paths.is_empty() {
+        return;
+    }
+
+    let mut client = HealthServiceClient::with_interceptor(channel, interceptor);
+
+    for path in paths {
+        let report = match fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| Ok(CrashReportRequest::decode(bytes.as_slice())?))
+        {
+            Ok(report) => report,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "{}", msg::CRASH_REPORT_CORRUPT_REMOVED);
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+        };
+
+        let subsystem = report.subsystem.clone();
+
+        match client.crash_report(report).await {
+            Ok(_) => {
+                info!(subsystem = %subsystem, "{}", msg::CRASH_REPORT_SENT);
+                let _ = fs::remove_file(&path);
+            }
+            Err(e) => {
+                // 다음 실행 시 재시도
+                warn!(error = %e, "{}", msg::CRASH_REPORT_SEND_FAILED);
+                return;
+            }
+        }
+    }
+}
+
+/// 최근 경고/오류 로그 (오래된 순)
+pub fn recent_errors() -> Vec<String> {
+    let errors = RECENT_ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+    errors.iter().cloned().collect()
+}
+
+/// 최근 경고/오류 로그를 보관해 크래시 보고서에 첨부하는 tracing 레이어
+pub fn recent_errors_layer() -> RecentErrorsLayer {
+    RecentErrorsLayer
+}
+
+pub struct RecentErrorsLayer;
+
+impl<S: Subscriber> Layer<S> for RecentErrorsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        let level = *event.metadata().level();
+        if level > Level::WARN {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let line = format!(
+            "{} {} {}: {}",
+            Utc::now().to_rfc3339(),
+            level,
+            event.metadata().target(),
+            visitor.0
+        );
+
+        let mut errors = RECENT_ERRORS.lock().unwrap_or_else(|e| e.into_inner());
+        if errors.len() >= RECENT_ERRORS_CAPACITY {
+            errors.pop_front();
+        }
+        errors.push_back(line);
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+
+        if field.name() == "message" {
+            self.0.push_str(&format!("{:?}", value));
+        } else {
+            self.0.push_str(&format!("{}={:?}", field.name(), value));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! 재시작 반복(crash loop) 감지
+//!
+//! 정상 종료 없이 다시 시작된 시각을 `state/restarts` 에 남겨, `WINDOW` 안에서 `THRESHOLD` 번을 넘게 재시작되면
+//! 시작 전 대기 시간을 두 배씩 늘림 (서비스 관리자가 곧바로 다시 띄워도 CPU 를 태우며 재시작을 반복하지 않도록).
+//! 서브시스템은 감독(supervisor)이 `window` 안의 재시작 횟수를 알려줌. 상태는 Heartbeat 의 `crash_loop` 와 관리 API `status` 로 보고
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::ephemeral;
+use crate::messages as msg;
+
+static RESTARTS_PATH: &str = "state/restarts";
+
+/// 재시작 횟수를 세는 구간
+const WINDOW: Duration = Duration::from_secs(600);
+
+/// 구간 안에서 이보다 많이 시작되면 crash loop
+const THRESHOLD: u32 = 3;
+
+/// 서브시스템이 구간 안에서 이만큼 재시작되면 crash loop
+const SUBSYSTEM_THRESHOLD: u32 = 3;
+
+const INITIAL_DELAY: Duration = Duration::from_secs(5);
+const MAX_DELAY: Duration = Duration::from_secs(300);
+
+static STARTED_AT: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+/// 이번 실행까지 포함한 구간 안의 시작 횟수
+static RECENT_STARTS: AtomicU32 = AtomicU32::new(0);
+
+/// crash loop 중인 서브시스템
+static SUBSYSTEMS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+fn subsystems() -> MutexGuard<'static, BTreeSet<String>> {
+    SUBSYSTEMS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 관리 API `status` 와 Heartbeat 에 실을 crash loop 상태
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrashLoopSnapshot {
+    /// 에이전트 프로세스가 재시작을 반복 중 (이번 실행이 `WINDOW` 동안 버티면 해제)
+    pub process: bool,
+    /// 구간 안에서 정상 종료 없이 시작된 횟수 (이번 실행 포함)
+    pub recent_restarts: u32,
+    /// 재시작을 반복 중인 서브시스템
+    pub subsystems: Vec<String>,
+}
+
+impl CrashLoopSnapshot {
+    pub fn is_looping(&self) -> bool {
+        self.process || !self.subsystems.is_empty()
+    }
+}
+
+/// 시작 시각을 기록하고, crash loop 이면 늘어난 대기 시간만큼 기다림 (`shutdown` 취소 시 즉시 반환)
+pub async fn on_start(shutdown: &CancellationToken) {
+    LazyLock::force(&STARTED_AT);
+
+    // 재시작 기록을 남길 곳이 없으면 반복을 셀 수 없음 (컨테이너 런타임의 재시작 정책에 맡김)
+    if ephemeral::is_enabled() {
+        return;
+    }
+
+    let now = Utc::now().timestamp();
+    let mut starts = load();
+    starts.retain(|at| now - at < WINDOW.as_secs() as i64);
+    starts.push(now);
+
+    if let Err(e) = save(&starts) {
+        warn!(path = RESTARTS_PATH, error = %format!("{:#}", e), "{}", msg::RESTARTS_SAVE_FAILED);
+    }
+
+    let count = starts.len() as u32;
+    RECENT_STARTS.store(count, Ordering::Relaxed);
+    if count <= THRESHOLD {
+        return;
+    }
+
+    let delay = restart_delay(count - THRESHOLD);
+    warn!(
+        restarts = count,
+        window_secs = WINDOW.as_secs(),
+        delay_secs = delay.as_secs(),
+        "{}",
+        msg::CRASH_LOOP_DETECTED
+    );
+
+    tokio::select! {
+        _ = shutdown.cancelled() => {}
+        _ = time::sleep(delay) => {}
+    }
+}
+
+/// 정상 종료, 다음 시작은 재시작 반복으로 세지 않음
+pub fn on_clean_exit() {
+    if ephemeral::is_enabled() {
+        return;
+    }
+    if let Err(e) = fs::remove_file(RESTARTS_PATH)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!(path = RESTARTS_PATH, error = %e, "{}", msg::RESTARTS_SAVE_FAILED);
+    }
+}
+
+/// 감독이 알려주는 서브시스템의 구간 안 재시작 횟수, 0 이면 안정
+pub fn record_subsystem(name: &str, restarts: u32) {
+    let mut looping = subsystems();
+
+    if restarts >= SUBSYSTEM_THRESHOLD {
+        if looping.insert(name.to_string()) {
+            warn!(subsystem = name, restarts = restarts, "{}", msg::SUBSYSTEM_CRASH_LOOP);
+        }
+    } else if looping.remove(name) {
+        info!(subsystem = name, "{}", msg::SUBSYSTEM_CRASH_LOOP_CLEARED);
+    }
+}
+
+pub fn snapshot() -> CrashLoopSnapshot {
+    let recent_restarts = RECENT_STARTS.load(Ordering::Relaxed);
+
+    CrashLoopSnapshot {
+        process: recent_restarts > THRESHOLD && STARTED_AT.elapsed() < WINDOW,
+        recent_restarts,
+        subsystems: subsystems().iter().cloned().collect(),
+    }
+}
+
+/// 한도를 넘은 횟수마다 두 배, `MAX_DELAY` 까지
+fn restart_delay(over: u32) -> Duration {
+    INITIAL_DELAY
+        .saturating_mul(1 << (over - 1).min(16))
+        .min(MAX_DELAY)
+}
+
+/// 기록된 시작 시각 (유닉스 초, 줄마다 하나)
+fn load() -> Vec<i64> {
+    fs::read_to_string(RESTARTS_PATH)
+        .map(|content| content.lines().filter_map(|line| line.trim().parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+fn save(starts: &[i64]) -> Result<()> {
+    let path = Path::new(RESTARTS_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+    }
+
+    let content: String = starts.iter().map(|at| format!("{}\n", at)).collect();
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
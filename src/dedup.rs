@@ -0,0 +1,119 @@
+//! 오류 폭주 중복 제거 (`dedup_window`)
+//!
+//! 장애 중 같은 ERROR 라인이 쏟아지면 대역폭과 서버 저장소를 함께 소모함.
+//! 같은 수집 대상의 같은 ERROR 이상 라인은 처음 본 시점부터 구간 동안 첫 이벤트만 붙잡고 나머지는 개수만 셈.
+//! 구간이 끝나면 첫 이벤트를 내보내고, 반복되었으면 `repeat_count`, `first_timestamp`, `last_timestamp` 필드를 붙임
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::time::Instant;
+
+use crate::memory::{self, MemoryBudget};
+use crate::models::LogEvent;
+use crate::routing::expr::{self, LEVEL_ERROR};
+
+/// 동시에 붙잡는 라인 종류 상한, 넘으면 새 라인은 중복 제거 없이 바로 전달
+const MAX_GROUPS: usize = 1024;
+
+struct Group {
+    event: LogEvent,
+    count: u64,
+    last: DateTime<Utc>,
+    deadline: Instant,
+}
+
+pub struct Dedup {
+    window: Duration,
+    budget: MemoryBudget,
+    groups: HashMap<(String, Bytes), Group>,
+}
+
+impl Dedup {
+    pub fn new(window: Duration, budget: MemoryBudget) -> Self {
+        Self {
+            window,
+            budget,
+            groups: HashMap::new(),
+        }
+    }
+
+    /// 바로 보낼 이벤트면 그대로 반환, 붙잡거나 중복으로 센 이벤트면 None
+    pub fn push(&mut self, event: LogEvent) -> Option<LogEvent> {
+        if expr::event_level(&event).is_none_or(|level| level < LEVEL_ERROR) {
+            return Some(event);
+        }
+
+        let key = (event.label.clone(), event.content.clone());
+        if let Some(group) = self.groups.get_mut(&key) {
+            group.count += 1;
+            group.last = group.last.max(event.timestamp);
+            self.budget.release(memory::event_bytes(&event));
+            return None;
+        }
+
+        if self.groups.len() >= MAX_GROUPS {
+            return Some(event);
+        }
+
+        self.groups.insert(
+            key,
+            Group {
+                last: event.timestamp,
+                event,
+                count: 1,
+                deadline: Instant::now() + self.window,
+            },
+        );
+        None
+    }
+
+    /// 가장 먼저 끝나는 구간의 종료 시각, 붙잡은 이벤트가 없으면 None
+    pub fn deadline(&self) -> Option<Instant> {
+        self.groups.values().map(|group| group.deadline).min()
+    }
+
+    /// 구간이 끝난 이벤트를 처음 본 순서로 꺼냄
+    pub fn take_expired(&mut self) -> Vec<LogEvent> {
+        let now = Instant::now();
+        self.take_where(|group| group.deadline <= now)
+    }
+
+    /// 붙잡은 이벤트 모두 꺼냄 (종료 시)
+    pub fn take_all(&mut self) -> Vec<LogEvent> {
+        self.take_where(|_| true)
+    }
+
+    fn take_where(&mut self, done: impl Fn(&Group) -> bool) -> Vec<LogEvent> {
+        let keys: Vec<_> = self
+            .groups
+            .iter()
+            .filter(|(_, group)| done(group))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        let mut groups: Vec<Group> = keys.iter().filter_map(|key| self.groups.remove(key)).collect();
+        groups.sort_by_key(|group| group.deadline);
+
+        groups.into_iter().map(|group| self.finish(group)).collect()
+    }
+
+    /// 반복된 이벤트에 반복 횟수와 처음/마지막 시각을 붙이고 늘어난 크기를 예산에 반영
+    fn finish(&self, group: Group) -> LogEvent {
+        let mut event = group.event;
+        if group.count == 1 {
+            return event;
+        }
+
+        let before = memory::event_bytes(&event);
+        let timestamp = |t: DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::Millis, true);
+        event.fields.push(("repeat_count".to_string(), group.count.to_string()));
+        event.fields.push(("first_timestamp".to_string(), timestamp(event.timestamp)));
+        event.fields.push(("last_timestamp".to_string(), timestamp(group.last)));
+
+        self.budget.add(memory::event_bytes(&event) - before);
+        event
+    }
+}
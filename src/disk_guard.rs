@@ -0,0 +1,116 @@
+//! 상태 디렉터리 여유 공간 감시 (`min_free_disk_mb`)
+//!
+//! `state/` 가 있는 디스크의 여유 공간이 기준 아래로 내려가면 디스크 큐(스풀, dead-letter)와 체크포인트(`state/redis`,
+//! `state/sql`) 쓰기를 멈추고 메모리에서만 처리 (채널이 차면 배치를 버림). 디스크를 가득 채워 호스트의 다른 프로세스까지
+//! 멈추게 하지 않기 위함. 상태는 오류 로그, heartbeat 의 `disk_space_low`, 게이지, 관리 API `status` 로 보고
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use sysinfo::Disks;
+use tracing::{error, info};
+
+use crate::chaos;
+use crate::error_code::ErrorCode;
+use crate::messages as msg;
+
+static STATE_DIR: &str = "state";
+
+/// 여유 공간을 다시 재는 최소 간격, 쓰기마다 디스크 목록을 읽지 않도록
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 0 이면 감시 안 함
+static MIN_FREE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+static GUARD: LazyLock<Mutex<Guard>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Guard {
+    checked_at: Option<Instant>,
+    free_bytes: Option<u64>,
+    low: bool,
+}
+
+fn lock() -> MutexGuard<'static, Guard> {
+    GUARD.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 관리 API `status` 에 실을 여유 공간 상태
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiskSnapshot {
+    /// 마지막으로 잰 `state/` 디스크의 여유 공간, 재지 못했으면 None
+    pub free_bytes: Option<u64>,
+    pub min_free_bytes: u64,
+    /// 기준 아래라 디스크 쓰기를 멈춤
+    pub low: bool,
+}
+
+/// 설정의 최소 여유 공간 적용
+pub fn configure(min_free_mb: u64) {
+    MIN_FREE_BYTES.store(min_free_mb * 1024 * 1024, Ordering::Relaxed);
+}
+
+/// 상태 파일을 써도 되는지, 여유 공간이 기준 아래면 오류
+pub fn ensure_room() -> Result<()> {
+    if is_low() {
+        bail!(msg::DISK_SPACE_LOW);
+    }
+    chaos::disk_full()
+}
+
+/// 여유 공간이 기준 아래인지, 마지막으로 잰 지 `CHECK_INTERVAL` 이 지났으면 다시 잼
+pub fn is_low() -> bool {
+    let min_free = MIN_FREE_BYTES.load(Ordering::Relaxed);
+    if min_free == 0 {
+        return false;
+    }
+
+    let mut guard = lock();
+    if guard.checked_at.is_some_and(|at| at.elapsed() < CHECK_INTERVAL) {
+        return guard.low;
+    }
+    guard.checked_at = Some(Instant::now());
+    guard.free_bytes = available_space(Path::new(STATE_DIR));
+
+    // 재지 못하면 쓰기를 막지 않음
+    let low = guard.free_bytes.is_some_and(|free| free < min_free);
+    if low && !guard.low {
+        error!(
+            free_bytes = guard.free_bytes,
+            min_free_bytes = min_free,
+            code = %ErrorCode::DiskSpaceLow,
+            "{}",
+            msg::DISK_SPACE_LOW_REDUCED_MODE
+        );
+    } else if !low && guard.low {
+        info!(free_bytes = guard.free_bytes, "{}", msg::DISK_SPACE_RECOVERED);
+    }
+    guard.low = low;
+    low
+}
+
+pub fn snapshot() -> DiskSnapshot {
+    let low = is_low();
+    DiskSnapshot {
+        free_bytes: lock().free_bytes,
+        min_free_bytes: MIN_FREE_BYTES.load(Ordering::Relaxed),
+        low,
+    }
+}
+
+/// 경로를 담은 마운트 중 가장 깊은 것의 여유 공간
+fn available_space(path: &Path) -> Option<u64> {
+    let path = path.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
@@ -0,0 +1,464 @@
+//! 연결/환경 자가 진단
+//!
+//! DNS, TCP, TLS(gRPC), 인증, 시계 오차, 수집 파일 권한, 상태 디렉터리 쓰기 가능 여부를
+//! 차례로 확인하고 항목별 결과를 반환
+
+use std::fmt;
+use std::fs::{self, File};
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use tokio::net::{self, TcpStream};
+use tokio::time;
+use tonic::transport::{Channel, Uri};
+use tracing::{info, warn};
+
+use crate::auth::client::AuthClient;
+use crate::auth::interceptor::AuthInterceptor;
+use crate::auth::oauth2::OAuth2Client;
+use crate::auth::token_manager::TokenManager;
+use crate::clock;
+use crate::endpoint;
+use crate::messages::{self as msg, Msg};
+use crate::gelf;
+use crate::oslog;
+use crate::redis_stream;
+use crate::self_log;
+use crate::sql_poll;
+use crate::snmp_trap;
+use crate::spiffe;
+use crate::state_meta;
+use crate::win_path;
+use crate::proto::health::health_service_client::HealthServiceClient;
+use crate::proto::health::HeartbeatRequest;
+use crate::settings::{AuthMode, Settings, SourceKind, SourceSettings};
+
+static STATE_DIR: &str = "state";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 허용 시계 오차, 초과 시 토큰 만료 판단 및 로그 타임스탬프가 어긋날 수 있음
+const MAX_CLOCK_SKEW_SECS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+            Status::Skip => "SKIP",
+        };
+        f.write_str(s)
+    }
+}
+
+/// 진단 항목 하나의 결과
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// 언어와 무관한 항목 식별자 (로그 필드용)
+    pub id: String,
+    pub name: String,
+    pub status: Status,
+    pub detail: String,
+}
+
+/// 진단 항목 종류, 식별자와 표시 이름
+#[derive(Clone, Copy)]
+struct Kind {
+    id: &'static str,
+    name: Msg,
+}
+
+const STATE_DIR_CHECK: Kind = Kind { id: "state_dir", name: msg::DOCTOR_STATE_DIR };
+const SOURCE_CHECK: Kind = Kind { id: "source", name: msg::DOCTOR_SOURCE };
+const NETWORK_CHECKS: [Kind; 5] = [
+    Kind { id: "dns", name: msg::DOCTOR_DNS },
+    Kind { id: "tcp", name: msg::DOCTOR_TCP },
+    Kind { id: "grpc", name: msg::DOCTOR_GRPC },
+    Kind { id: "auth", name: msg::DOCTOR_AUTH },
+    Kind { id: "clock_skew", name: msg::DOCTOR_CLOCK_SKEW },
+];
+
+impl Check {
+    fn new(kind: Kind, status: Status, detail: impl Into<String>) -> Self {
+        Self {
+            id: kind.id.to_string(),
+            name: kind.name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    fn from_result(kind: Kind, result: Result<String>) -> Self {
+        match result {
+            Ok(detail) => Self::new(kind, Status::Pass, detail),
+            Err(e) => Self::new(kind, Status::Fail, format!("{:#}", e)),
+        }
+    }
+
+    /// 수집 대상별 항목은 식별자와 이름에 라벨을 덧붙임
+    fn with_label(mut self, label: &str) -> Self {
+        self.id = format!("{}:{}", self.id, label);
+        self.name = format!("{} ({})", self.name, label);
+        self
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {} - {}", self.status, self.name, self.detail)
+    }
+}
+
+/// 전체 진단 실행, 앞 단계가 실패하면 의존하는 뒤 단계는 SKIP
+pub async fn run(settings: &Settings) -> Vec<Check> {
+    endpoint::configure(&settings.tls);
+    // 받지 못하면 서버 연결 단계가 SVID 대기로 실패
+    if settings.auth.mode == AuthMode::Spiffe
+        && let Err(e) = spiffe::fetch(&spiffe::socket(&settings.auth)).await
+    {
+        warn!(error = %format!("{:#}", e), "{}", msg::SPIFFE_FETCH_FAILED);
+    }
+    run_checks(settings, None).await
+}
+
+/// 실행 중인 에이전트 안에서 진단, 인증은 에이전트 세션을 그대로 사용
+///
+/// 저장된 refresh_token 으로 다시 인증하면 토큰이 교체되어 실행 중인 세션과 어긋날 수 있음
+pub async fn run_in_session(settings: &Settings, interceptor: AuthInterceptor) -> Vec<Check> {
+    run_checks(settings, Some(interceptor)).await
+}
+
+async fn run_checks(settings: &Settings, session: Option<AuthInterceptor>) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    checks.push(Check::from_result(STATE_DIR_CHECK, check_state_dir()));
+
+    for source in &settings.sources {
+        // 파일이 없어도 실행은 계속되므로 경고
+        if source.kind == SourceKind::File && source.optional && !win_path::extended(Path::new(&source.path)).exists() {
+            let detail = format!("{}: {}", msg::OPTIONAL_SOURCE_MISSING, source.path);
+            checks.push(Check::new(SOURCE_CHECK, Status::Warn, detail).with_label(&source.label));
+            continue;
+        }
+
+        let result = match source.kind {
+            SourceKind::File => check_source(&win_path::extended(Path::new(&source.path))),
+            SourceKind::Oslog => check_oslog(),
+            SourceKind::GelfUdp => check_gelf(source.listen.as_deref()),
+            SourceKind::RedisStream => check_redis(source).await,
+            SourceKind::Sql => check_sql(source).await,
+            SourceKind::SnmpTrap => check_snmp(source),
+            SourceKind::SelfLog => Ok(format!("{}: {}", msg::DOCTOR_SELF_LOG_LEVEL, self_log::threshold(source))),
+        };
+        checks.push(Check::from_result(SOURCE_CHECK, result).with_label(&source.label));
+    }
+
+    run_network(settings, session, &mut checks).await;
+
+    checks
+}
+
+async fn run_network(settings: &Settings, session: Option<AuthInterceptor>, checks: &mut Vec<Check>) {
+    let skip_rest = |checks: &mut Vec<Check>, from: usize| {
+        for kind in &NETWORK_CHECKS[from..] {
+            checks.push(Check::new(*kind, Status::Skip, msg::DOCTOR_PREVIOUS_FAILED.text()));
+        }
+    };
+
+    let tcp = match endpoint::unix_path(&settings.server_addr) {
+        // Unix 소켓은 이름 해석 없이 경로로 바로 연결
+        Some(path) => {
+            checks.push(Check::new(NETWORK_CHECKS[0], Status::Skip, msg::DOCTOR_UNIX_SOCKET.text()));
+            connect_unix(path).await
+        }
+        None => match resolve(&settings.server_addr).await {
+            Ok(addrs) => {
+                let list: Vec<String> = addrs.iter().map(|a| a.to_string()).collect();
+                checks.push(Check::new(NETWORK_CHECKS[0], Status::Pass, list.join(", ")));
+                connect_tcp(&addrs).await
+            }
+            Err(e) => {
+                checks.push(Check::new(NETWORK_CHECKS[0], Status::Fail, format!("{:#}", e)));
+                return skip_rest(checks, 1);
+            }
+        },
+    };
+
+    let tcp_ok = tcp.is_ok();
+    checks.push(Check::from_result(NETWORK_CHECKS[1], tcp));
+    if !tcp_ok {
+        return skip_rest(checks, 2);
+    }
+
+    let channel = match connect_grpc(&settings.server_addr).await {
+        Ok(channel) => {
+            let detail = if settings.server_addr.starts_with("https") {
+                msg::DOCTOR_TLS_OK
+            } else {
+                msg::DOCTOR_PLAINTEXT_OK
+            };
+            checks.push(Check::new(NETWORK_CHECKS[2], Status::Pass, detail.text()));
+            channel
+        }
+        Err(e) => {
+            checks.push(Check::new(NETWORK_CHECKS[2], Status::Fail, format!("{:#}", e)));
+            return skip_rest(checks, 3);
+        }
+    };
+
+    if let Some(interceptor) = session {
+        checks.push(Check::new(NETWORK_CHECKS[3], Status::Pass, msg::DOCTOR_AUTH_SESSION.text()));
+        checks.push(check_clock_skew(channel, interceptor).await);
+        return;
+    }
+
+    let authenticated = match settings.auth.mode {
+        AuthMode::OAuth2 => match OAuth2Client::from_auth(&settings.auth) {
+            Ok(oauth2) => TokenManager::oauth2(AuthClient::new(channel.clone()), oauth2).await,
+            Err(e) => Err(e),
+        },
+        _ => TokenManager::load_without_register(AuthClient::new(channel.clone()), settings.project_key.clone()).await,
+    };
+
+    let token_manager = match authenticated {
+        Ok(tm) => {
+            checks.push(Check::new(NETWORK_CHECKS[3], Status::Pass, msg::DOCTOR_AUTH_OK.text()));
+            tm
+        }
+        Err(e) => {
+            checks.push(Check::new(NETWORK_CHECKS[3], Status::Fail, format!("{:#}", e)));
+            return skip_rest(checks, 4);
+        }
+    };
+
+    let interceptor = AuthInterceptor::new(token_manager.get_shared_token());
+    checks.push(check_clock_skew(channel, interceptor).await);
+}
+
+fn check_state_dir() -> Result<String> {
+    let dir = Path::new(STATE_DIR);
+    fs::create_dir_all(dir).context(msg::DOCTOR_DIR_CREATE_FAILED)?;
+
+    let probe = dir.join(".doctor");
+    fs::write(&probe, b"ok").context(msg::DOCTOR_FILE_WRITE_FAILED)?;
+    fs::remove_file(&probe).context(msg::DOCTOR_FILE_REMOVE_FAILED)?;
+
+    // 더 새 에이전트가 만든 상태면 실행 시 시작하지 않음
+    if let Some(meta) = state_meta::load()?
+        && meta.schema_version > state_meta::SCHEMA_VERSION
+    {
+        bail!(
+            "{}: state={} agent={}",
+            msg::STATE_SCHEMA_TOO_NEW,
+            meta.schema_version,
+            state_meta::SCHEMA_VERSION
+        );
+    }
+
+    Ok(format!("{}: {}", msg::DOCTOR_WRITABLE, dir.display()))
+}
+
+fn check_source(path: &Path) -> Result<String> {
+    File::open(path).with_context(|| format!("{}: {}", msg::DOCTOR_UNREADABLE, path.display()))?;
+
+    // 파일 교체(로테이션) 감지를 위해 상위 디렉터리 감시 권한도 필요
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::read_dir(parent).with_context(|| format!("{}: {}", msg::DOCTOR_DIR_UNREADABLE, parent.display()))?;
+    }
+
+    Ok(format!("{}: {}", msg::DOCTOR_READABLE, path.display()))
+}
+
+fn check_oslog() -> Result<String> {
+    if !Path::new(oslog::LOG_COMMAND).exists() {
+        bail!(msg::OSLOG_UNAVAILABLE);
+    }
+
+    Ok(format!("{}: {}", msg::DOCTOR_OSLOG_AVAILABLE, oslog::LOG_COMMAND))
+}
+
+/// 주소 형식만 확인, 실행 중인 에이전트가 이미 바인드하고 있을 수 있어 직접 바인드하지 않음
+fn check_gelf(listen: Option<&str>) -> Result<String> {
+    let listen = listen.unwrap_or(gelf::DEFAULT_LISTEN);
+    listen
+        .to_socket_addrs()
+        .with_context(|| format!("{}: {}", msg::GELF_INVALID_LISTEN, listen))?;
+
+    Ok(format!("{}: {}", msg::DOCTOR_GELF_LISTEN, listen))
+}
+
+async fn check_redis(source: &SourceSettings) -> Result<String> {
+    let Some(redis) = source.redis.as_ref().filter(|r| !r.streams.is_empty()) else {
+        bail!(msg::REDIS_NOT_CONFIGURED);
+    };
+    redis_stream::ping(redis).await?;
+
+    Ok(format!("{}: {}", msg::DOCTOR_REDIS_REACHABLE, redis.addr))
+}
+
+async fn check_sql(source: &SourceSettings) -> Result<String> {
+    let Some(sql) = source.sql.as_ref() else {
+        bail!(msg::SQL_NOT_CONFIGURED);
+    };
+    sql_poll::ping(sql).await?;
+
+    Ok(format!("{}: {}", msg::DOCTOR_SQL_REACHABLE, sql_poll::redact(&sql.url)))
+}
+
+/// 커뮤니티/사용자 설정과 주소 형식만 확인, 직접 바인드하지 않음
+fn check_snmp(source: &SourceSettings) -> Result<String> {
+    snmp_trap::validate(&source.snmp.clone().unwrap_or_default())?;
+
+    let listen = source.listen.as_deref().unwrap_or(snmp_trap::DEFAULT_LISTEN);
+    listen
+        .to_socket_addrs()
+        .with_context(|| format!("{}: {}", msg::SNMP_INVALID_LISTEN, listen))?;
+
+    Ok(format!("{}: {}", msg::DOCTOR_SNMP_LISTEN, listen))
+}
+
+async fn resolve(server_addr: &str) -> Result<Vec<SocketAddr>> {
+    let uri: Uri = server_addr.parse().context(msg::DOCTOR_INVALID_SERVER_ADDR)?;
+    let host = uri.host().ok_or_else(|| anyhow!(msg::DOCTOR_NO_HOST))?;
+    let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+        Some("https") => 443,
+        _ => 80,
+    });
+
+    let addrs: Vec<SocketAddr> = time::timeout(CONNECT_TIMEOUT, net::lookup_host((host, port)))
+        .await
+        .context(msg::DOCTOR_DNS_TIMEOUT)?
+        .with_context(|| format!("{}: {}", msg::DOCTOR_DNS_FAILED, host))?
+        .collect();
+
+    if addrs.is_empty() {
+        bail!("{}: {}", msg::DOCTOR_NO_ADDRESS, host);
+    }
+
+    Ok(addrs)
+}
+
+async fn connect_tcp(addrs: &[SocketAddr]) -> Result<String> {
+    let mut last_error = None;
+
+    for addr in addrs {
+        match time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(_)) => return Ok(format!("{}: {}", msg::DOCTOR_CONNECTED, addr)),
+            Ok(Err(e)) => last_error = Some(anyhow!("{}: {}", addr, e)),
+            Err(_) => last_error = Some(anyhow!("{}: {}", addr, msg::DOCTOR_CONNECT_TIMEOUT)),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow!(msg::DOCTOR_NO_ADDRESS)))
+}
+
+#[cfg(unix)]
+async fn connect_unix(path: &str) -> Result<String> {
+    match time::timeout(CONNECT_TIMEOUT, net::UnixStream::connect(path)).await {
+        Ok(Ok(_)) => Ok(format!("{}: {}", msg::DOCTOR_CONNECTED, path)),
+        Ok(Err(e)) => Err(anyhow!("{}: {}", path, e)),
+        Err(_) => Err(anyhow!("{}: {}", path, msg::DOCTOR_CONNECT_TIMEOUT)),
+    }
+}
+
+#[cfg(not(unix))]
+async fn connect_unix(_path: &str) -> Result<String> {
+    bail!(msg::UNIX_SOCKET_UNSUPPORTED)
+}
+
+async fn connect_grpc(server_addr: &str) -> Result<Channel> {
+    endpoint::connect(server_addr, Some(CONNECT_TIMEOUT))
+        .await
+        .context(msg::DOCTOR_GRPC_FAILED)
+}
+
+/// Heartbeat 응답의 HTTP `date` 헤더와 로컬 시각 비교
+async fn check_clock_skew(channel: Channel, interceptor: AuthInterceptor) -> Check {
+    const NAME: Kind = NETWORK_CHECKS[4];
+
+    let mut client = HealthServiceClient::with_interceptor(channel, interceptor);
+    let request = HeartbeatRequest {
+        timestamp: Some(prost_types::Timestamp::from(SystemTime::from(Utc::now()))),
+        cpu: 0.0,
+        memory: 0.0,
+        sampling_rate: 1.0,
+        anomalies: Vec::new(),
+        egress: Vec::new(),
+        token: None,
+        disk_space_low: false,
+        disk_free_bytes: 0,
+        crash_loop: None,
+        clock_skew_ms: 0,
+        clock_skew_exceeded: false,
+        errors: Vec::new(),
+        backlog: None,
+    };
+
+    let response = match client.heartbeat(request).await {
+        Ok(response) => response,
+        Err(e) => return Check::new(NAME, Status::Fail, format!("{}: {}", msg::HEARTBEAT_FAILED, e)),
+    };
+
+    let Some(server_time) = clock::server_time(response.metadata()) else {
+        return Check::new(NAME, Status::Warn, msg::DOCTOR_NO_DATE_HEADER.text());
+    };
+
+    // date 헤더는 초 단위이므로 1초 미만 오차는 무시
+    let skew = (Utc::now() - server_time).num_seconds();
+    let detail = format!("{} {:+}s", msg::DOCTOR_SKEW_VS_SERVER, skew);
+
+    if skew.abs() > MAX_CLOCK_SKEW_SECS {
+        Check::new(NAME, Status::Fail, format!("{} (max ±{}s)", detail, MAX_CLOCK_SKEW_SECS))
+    } else {
+        Check::new(NAME, Status::Pass, detail)
+    }
+}
+
+/// 결과를 표준 출력으로 출력, 실패 항목이 있으면 Err
+pub fn print_report(checks: &[Check]) -> Result<()> {
+    for check in checks {
+        println!("{}", check);
+    }
+
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    if failed > 0 {
+        bail!("{}: {}", msg::DOCTOR_FAILED_COUNT, failed);
+    }
+
+    println!("{}", msg::DOCTOR_ALL_PASSED);
+    Ok(())
+}
+
+/// 시작 시 진단 결과를 로그로 남김 (실패해도 에이전트 실행은 계속)
+pub fn log_report(checks: &[Check]) {
+    for check in checks {
+        match check.status {
+            Status::Fail | Status::Warn => warn!(
+                check = %check.id,
+                status = %check.status,
+                detail = %check.detail,
+                "{}",
+                msg::DOCTOR_RESULT
+            ),
+            Status::Pass | Status::Skip => info!(
+                check = %check.id,
+                status = %check.status,
+                detail = %check.detail,
+                "{}",
+                msg::DOCTOR_RESULT
+            ),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use tokio::sync::mpsc::Receiver;
+use tracing::info;
+
+use crate::batch_pool;
+use crate::messages as msg;
+use crate::proto::log::LogBatch;
+
+/// 서버 대신 배치 요약을 표준 출력으로 내보내는 Sink
+pub struct DryRunSink {
+    rx: Receiver<LogBatch>,
+}
+
+impl DryRunSink {
+    pub fn new(rx: Receiver<LogBatch>) -> Self {
+        Self { rx }
+    }
+
+    pub async fn start(mut self) {
+        info!("{}", msg::DRY_RUN_SINK_STARTED);
+
+        let mut total_batches = 0usize;
+        let mut total_logs = 0usize;
+        let mut total_metrics = 0usize;
+
+        while let Some(batch) = self.rx.recv().await {
+            total_batches += 1;
+            total_logs += batch.logs.len();
+            total_metrics += batch.metrics.len();
+            print_summary(&batch);
+            batch_pool::recycle(batch);
+        }
+
+        println!(
+            "[dry-run] {}: batches={} logs={} metrics={}",
+            msg::DRY_RUN_TOTAL,
+            total_batches,
+            total_logs,
+            total_metrics
+        );
+        info!("{}", msg::DRY_RUN_SINK_STOPPED);
+    }
+}
+
+fn print_summary(batch: &LogBatch) {
+    let mut per_label: BTreeMap<&str, usize> = BTreeMap::new();
+    for log in &batch.logs {
+        *per_label.entry(log.label.as_str()).or_default() += 1;
+    }
+
+    println!(
+        "[dry-run] batch_id={} trace_id={} count={} metrics={}",
+        batch.batch_id,
+        batch.trace_id,
+        batch.logs.len(),
+        batch.metrics.len()
+    );
+
+    for (label, count) in per_label {
+        println!("  {}: {}", label, count);
+    }
+
+    for metric in &batch.metrics {
+        println!("  metric: {} = {} {:?}", metric.name, metric.value, metric.labels);
+    }
+
+    if let Some(first) = batch.logs.first() {
+        println!(
+            "  sample: [{}] {} ({}@{})",
+            first.label,
+            String::from_utf8_lossy(&first.line),
+            first.path,
+            first.offset
+        );
+
+        let fields: BTreeMap<_, _> = first.fields.iter().collect();
+        if !fields.is_empty() {
+            println!("  fields: {:?}", fields);
+        }
+    }
+}
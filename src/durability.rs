@@ -0,0 +1,99 @@
+//! 디스크 큐와 체크포인트의 fsync 시점 (`durability`)
+//!
+//! 임시 파일에 쓰고 이름을 바꾸는 방식만으로는 전원이 나가면 페이지 캐시에 남은 내용과 디렉터리 항목이 사라져
+//! 빈 배치 파일이나 이전 커서가 남을 수 있음. 이름을 바꾸기 전 파일을, 바꾼 뒤 디렉터리를 fsync 해 저장 완료를 보장.
+//! `batch` 는 배치/체크포인트마다, `shutdown` 은 쓴 파일을 모아 두었다가 종료 시 한 번에, `off` 는 OS 에 맡김.
+//! 전송 후 지운 배치는 fsync 하지 않음 (전원이 나가면 다시 보낼 뿐, 최소 한 번 전달)
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use tracing::warn;
+
+use crate::messages as msg;
+use crate::settings::Durability;
+
+static LEVEL: Mutex<Durability> = Mutex::new(Durability::Batch);
+/// `shutdown` 에서 종료 시 fsync 할 파일과 디렉터리
+static DIRTY: LazyLock<Mutex<Dirty>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct Dirty {
+    files: BTreeSet<PathBuf>,
+    dirs: BTreeSet<PathBuf>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 설정의 `durability` 적용
+pub fn configure(level: Durability) {
+    *lock(&LEVEL) = level;
+}
+
+/// 다 쓴 `tmp` 를 `path` 로 바꿈, 수준에 따라 파일과 양쪽 디렉터리를 fsync 하거나 종료 시로 미룸
+pub fn persist(tmp: &Path, path: &Path) -> io::Result<()> {
+    let level = *lock(&LEVEL);
+
+    if level == Durability::Batch {
+        File::open(tmp)?.sync_all()?;
+    }
+    fs::rename(tmp, path)?;
+
+    let dirs = parents(tmp, path);
+    match level {
+        Durability::Batch => dirs.iter().try_for_each(|dir| sync_dir(dir)),
+        Durability::Shutdown => {
+            let mut dirty = lock(&DIRTY);
+            dirty.files.insert(path.to_path_buf());
+            dirty.dirs.extend(dirs);
+            Ok(())
+        }
+        Durability::Off => Ok(()),
+    }
+}
+
+/// 종료 시 `shutdown` 에서 미뤄 둔 파일, 디렉터리 순으로 fsync, 그새 지워진 파일은 건너뜀
+pub fn flush() {
+    let Dirty { files, dirs } = std::mem::take(&mut *lock(&DIRTY));
+
+    for file in &files {
+        match File::open(file).and_then(|file| file.sync_all()) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                warn!(path = %file.display(), error = %e, "{}", msg::DURABILITY_SYNC_FAILED);
+            }
+            _ => {}
+        }
+    }
+    for dir in &dirs {
+        if let Err(e) = sync_dir(dir) {
+            warn!(path = %dir.display(), error = %e, "{}", msg::DURABILITY_SYNC_FAILED);
+        }
+    }
+}
+
+/// 이름을 바꾼 양쪽 디렉터리 (같으면 하나)
+fn parents(from: &Path, to: &Path) -> Vec<PathBuf> {
+    let dir = |path: &Path| match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+
+    let (from, to) = (dir(from), dir(to));
+    if from == to { vec![to] } else { vec![to, from] }
+}
+
+/// 디렉터리 항목 fsync, Windows 는 디렉터리를 열 수 없어 건너뜀
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! 수집 대상별 전송량 집계
+//!
+//! 서버가 받은 배치의 로그를 라벨별로 나눠 이벤트 수와 바이트를 누적 (필터/샘플링 이후 실제 전송분).
+//! 압축은 배치 단위라 라벨별 압축 크기는 알 수 없으므로, 주기적으로 배치를 직접 압축해 잰 압축률로 환산.
+//! 누적값은 관리 API `status`, heartbeat, 헬스 리포터의 카운터 메트릭으로 노출
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::proto::log::LogBatch;
+
+static EGRESS: LazyLock<Mutex<BTreeMap<String, SourceEgress>>> = LazyLock::new(Default::default);
+
+/// 수집 대상 하나의 누적 전송량
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SourceEgress {
+    pub events: u64,
+    /// 압축 후 바이트 (압축하지 않는 서버면 인코딩 크기 그대로)
+    pub bytes: u64,
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<String, SourceEgress>> {
+    EGRESS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 전송한 배치의 로그를 라벨별로 누적, `compression_ratio` 는 압축 후/전 크기 비율
+pub fn record(batch: &LogBatch, compression_ratio: f64) {
+    let mut egress = lock();
+
+    for log in &batch.logs {
+        let bytes = (log.encoded_len() as f64 * compression_ratio).round() as u64;
+        match egress.get_mut(&log.label) {
+            Some(source) => {
+                source.events += 1;
+                source.bytes += bytes;
+            }
+            None => {
+                egress.insert(log.label.clone(), SourceEgress { events: 1, bytes });
+            }
+        }
+    }
+}
+
+/// 라벨별 누적 전송량
+pub fn snapshot() -> BTreeMap<String, SourceEgress> {
+    lock().clone()
+}
+
+/// 배치를 gzip 으로 압축해 잰 압축률 (압축 후/전), 빈 배치면 1.0
+pub fn compression_ratio(batch: &LogBatch) -> f64 {
+    let encoded = batch.encode_to_vec();
+    if encoded.is_empty() {
+        return 1.0;
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    match encoder.write_all(&encoded).and_then(|_| encoder.finish()) {
+        Ok(compressed) => compressed.len() as f64 / encoded.len() as f64,
+        Err(_) => 1.0,
+    }
+}
@@ -0,0 +1,181 @@
+//! `server_addr` 로 gRPC 채널 생성
+//!
+//! `http://`, `https://` 는 tonic 기본 연결, `unix:///run/rlog/server.sock` 은 Unix 도메인 소켓으로 연결.
+//! 같은 호스트의 게이트웨이/사이드카가 실제 서버로 중계할 때 사용 (인증, 로그, 헬스, 명령 채널 모두 같은 채널).
+//! `https://` 는 시스템 루트 인증서로 검증하고, `tls.server_name`/`tls.authority` 로 IP 주소나 TCP 로드 밸런서를 거쳐도
+//! 인증서의 이름으로 검증. SPIFFE 모드에서는 Workload API 로 받은 SVID 를 클라이언트 인증서로 제시 (mTLS)
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity, Uri};
+
+use crate::messages as msg;
+use crate::settings::TlsSettings;
+
+static UNIX_SCHEME: &str = "unix://";
+static HTTPS_SCHEME: &str = "https://";
+
+static TLS: LazyLock<RwLock<TlsSettings>> = LazyLock::new(Default::default);
+static CLIENT_IDENTITY: LazyLock<RwLock<Option<ClientIdentity>>> = LazyLock::new(Default::default);
+static REQUIRE_CLIENT_IDENTITY: AtomicBool = AtomicBool::new(false);
+
+/// mTLS 로 제시할 클라이언트 인증서와 서버 인증서를 검증할 신뢰 번들
+#[derive(Clone)]
+pub struct ClientIdentity {
+    pub identity: Identity,
+    pub trust_bundle: Certificate,
+}
+
+/// Unix 소켓 연결 시 HTTP/2 `:authority` 로 쓰는 자리표시 URI (실제 연결에는 쓰이지 않음)
+static UNIX_PLACEHOLDER_URI: &str = "http://localhost";
+
+/// 설정의 TLS 이름 재지정 적용, 이후 만드는 채널에 반영
+pub fn configure(settings: &TlsSettings) {
+    *TLS.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+}
+
+fn tls() -> TlsSettings {
+    TLS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 이후 만드는 서버 채널은 클라이언트 인증서 필수, 아직 받지 못했으면 연결 실패
+pub fn require_client_identity() {
+    REQUIRE_CLIENT_IDENTITY.store(true, Ordering::Relaxed);
+}
+
+/// 클라이언트 인증서 교체, 이미 맺은 연결은 그대로이고 새 연결부터 적용
+pub fn set_client_identity(identity: ClientIdentity) {
+    *CLIENT_IDENTITY.write().unwrap_or_else(|e| e.into_inner()) = Some(identity);
+}
+
+fn client_identity() -> Option<ClientIdentity> {
+    CLIENT_IDENTITY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// `unix://` 주소면 소켓 경로
+pub fn unix_path(server_addr: &str) -> Option<&str> {
+    server_addr.strip_prefix(UNIX_SCHEME)
+}
+
+/// 연결 수립까지 대기, `timeout` 은 연결 제한 시간
+pub async fn connect(server_addr: &str, timeout: Option<Duration>) -> Result<Channel> {
+    let endpoint = endpoint(server_addr, timeout)?;
+
+    match unix_path(server_addr) {
+        Some(path) => Ok(endpoint.connect_with_connector(unix::connector(path)?).await?),
+        None => Ok(endpoint.connect().await?),
+    }
+}
+
+/// 연결은 첫 요청 시 수립
+pub fn connect_lazy(server_addr: &str) -> Result<Channel> {
+    let endpoint = endpoint(server_addr, None)?;
+
+    match unix_path(server_addr) {
+        Some(path) => Ok(endpoint.connect_with_connector_lazy(unix::connector(path)?)),
+        None => Ok(endpoint.connect_lazy()),
+    }
+}
+
+/// 같은 호스트의 Unix 소켓 서비스 연결 (SPIFFE Workload API 등), 서버용 TLS 설정과 클라이언트 인증서는 쓰지 않음
+pub async fn connect_local(socket_addr: &str) -> Result<Channel> {
+    let path = unix_path(socket_addr).unwrap_or(socket_addr);
+    Ok(Endpoint::from_static(UNIX_PLACEHOLDER_URI)
+        .connect_with_connector(unix::connector(path)?)
+        .await?)
+}
+
+fn endpoint(server_addr: &str, timeout: Option<Duration>) -> Result<Endpoint> {
+    let tls = tls();
+    let https = server_addr.starts_with(HTTPS_SCHEME);
+
+    let identity = client_identity();
+    if REQUIRE_CLIENT_IDENTITY.load(Ordering::Relaxed) {
+        if !https {
+            bail!("{}: {}", msg::CLIENT_IDENTITY_REQUIRES_HTTPS, server_addr);
+        }
+        if identity.is_none() {
+            bail!(msg::CLIENT_IDENTITY_NOT_READY);
+        }
+    }
+
+    let mut endpoint = match unix_path(server_addr) {
+        Some(_) => Endpoint::from_static(UNIX_PLACEHOLDER_URI),
+        None => Endpoint::from_shared(server_addr.to_string())?,
+    };
+
+    if https {
+        let mut config = ClientTlsConfig::new().with_native_roots();
+        if let Some(server_name) = &tls.server_name {
+            config = config.domain_name(server_name.clone());
+        }
+        if let Some(identity) = identity {
+            config = config.identity(identity.identity).ca_certificate(identity.trust_bundle);
+        }
+        endpoint = endpoint.tls_config(config).context(msg::TLS_CONFIG_FAILED)?;
+    }
+
+    // 요청의 `:authority` 만 바꾸며 연결 대상은 그대로 `server_addr`
+    if let Some(authority) = &tls.authority {
+        let scheme = if https { "https" } else { "http" };
+        let origin: Uri = format!("{}://{}", scheme, authority)
+            .parse()
+            .with_context(|| format!("{}: {}", msg::TLS_INVALID_AUTHORITY, authority))?;
+        endpoint = endpoint.origin(origin);
+    }
+
+    Ok(match timeout {
+        Some(timeout) => endpoint.connect_timeout(timeout),
+        None => endpoint,
+    })
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::io;
+    use std::path::PathBuf;
+
+    use anyhow::{Result, bail};
+    use hyper_util::rt::TokioIo;
+    use tokio::net::UnixStream;
+    use tonic::transport::Uri;
+    use tower::service_fn;
+    use tower::util::ServiceFn;
+
+    use crate::messages as msg;
+
+    type Connecting = std::pin::Pin<Box<dyn Future<Output = io::Result<TokioIo<UnixStream>>> + Send>>;
+
+    /// 요청 URI 와 무관하게 항상 같은 소켓 경로로 연결
+    pub fn connector(path: &str) -> Result<ServiceFn<impl FnMut(Uri) -> Connecting + Clone + use<>>> {
+        if path.is_empty() {
+            bail!(msg::UNIX_SOCKET_PATH_REQUIRED);
+        }
+
+        let path = PathBuf::from(path);
+        Ok(service_fn(move |_: Uri| -> Connecting {
+            let path = path.clone();
+            Box::pin(async move { Ok(TokioIo::new(UnixStream::connect(path).await?)) })
+        }))
+    }
+}
+
+#[cfg(not(unix))]
+mod unix {
+    use anyhow::{Result, bail};
+    use hyper_util::rt::TokioIo;
+    use tokio::net::TcpStream;
+    use tonic::transport::Uri;
+    use tower::util::ServiceFn;
+
+    use crate::messages as msg;
+
+    type Connecting = std::future::Ready<std::io::Result<TokioIo<TcpStream>>>;
+
+    pub fn connector(_path: &str) -> Result<ServiceFn<fn(Uri) -> Connecting>> {
+        bail!(msg::UNIX_SOCKET_UNSUPPORTED)
+    }
+}
@@ -0,0 +1,70 @@
+//! 상태 없는 실행 (`--ephemeral`, `RLOG_EPHEMERAL=1`)
+//!
+//! 볼륨 없는 읽기 전용 컨테이너용. 설정은 `RLOG_CONFIG`(agent.yaml 내용)와 `SERVER_ADDR`, `PROJECT_KEY` 환경 변수에서
+//! 만들고 `config/`, `state/` 에 아무것도 쓰지 않음. 토큰은 메모리에만 두고 시작할 때마다 `AGENT_UUID` 로 재등록해
+//! 서버에서는 같은 에이전트로 이어짐. 디스크 큐, 수집 커서, 재시작 기록, 크래시 보고서는 남기지 않으므로
+//! 서버 장애가 메모리 큐를 넘게 길어지면 배치를 버림
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+
+use crate::settings::Settings;
+
+static ENV_EPHEMERAL: &str = "RLOG_EPHEMERAL";
+static ENV_CONFIG: &str = "RLOG_CONFIG";
+static ENV_SERVER_ADDR: &str = "SERVER_ADDR";
+static ENV_PROJECT_KEY: &str = "PROJECT_KEY";
+static ENV_AGENT_UUID: &str = "AGENT_UUID";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `--ephemeral` 이거나 `RLOG_EPHEMERAL` 이 `1`/`true` 면 상태 없는 실행
+pub fn configure(flag: bool) {
+    let env = std::env::var(ENV_EPHEMERAL).is_ok_and(|value| matches!(value.trim(), "1" | "true"));
+    ENABLED.store(flag || env, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// `RLOG_CONFIG` 의 YAML, 상태 없는 실행이 아니거나 비어 있으면 None
+pub fn config_yaml() -> Option<String> {
+    if !is_enabled() {
+        return None;
+    }
+    std::env::var(ENV_CONFIG).ok().filter(|content| !content.trim().is_empty())
+}
+
+/// `RLOG_CONFIG` 로 만든 설정 (`SERVER_ADDR` 가 있으면 우선, 비어 있는 project_key 는 `PROJECT_KEY`),
+/// 없으면 None (서버 등록으로 설정을 받음)
+pub fn settings() -> Result<Option<Settings>> {
+    let Some(content) = config_yaml() else {
+        return Ok(None);
+    };
+
+    let mut settings = Settings::from_yaml(&content)?;
+    if let Ok(server_addr) = std::env::var(ENV_SERVER_ADDR)
+        && !server_addr.trim().is_empty()
+    {
+        settings.server_addr = server_addr;
+    }
+    if settings.project_key.is_empty()
+        && let Ok(project_key) = std::env::var(ENV_PROJECT_KEY)
+    {
+        settings.project_key = project_key.trim().to_string();
+    }
+    Ok(Some(settings))
+}
+
+/// 재등록에 쓸 `AGENT_UUID`, 상태 없는 실행이 아니거나 없으면 None
+pub fn agent_uuid() -> Option<String> {
+    if !is_enabled() {
+        return None;
+    }
+    std::env::var(ENV_AGENT_UUID)
+        .ok()
+        .map(|uuid| uuid.trim().to_string())
+        .filter(|uuid| !uuid.is_empty())
+}
@@ -0,0 +1,167 @@
+//! 진단용 오류 코드
+//!
+//! 경고/오류 로그에 `code` 필드로 붙이는 고정 코드 (`AUTH_xx` 인증, `COLLECT_xx` 수집, `SEND_xx` 전송).
+//! 문구는 언어 설정과 버전에 따라 바뀌므로 알림 규칙과 대응 절차는 코드를 기준으로 작성.
+//! 코드별 발생 횟수와 마지막 문구를 모아 관리 API `status`, heartbeat, 헬스 리포터의 카운터 메트릭으로 노출.
+//! 이미 쓰인 코드의 번호와 의미는 바꾸지 않고 새 코드는 번호를 이어서 추가
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// 로그 이벤트에서 코드를 담는 필드 이름
+const CODE_FIELD: &str = "code";
+
+static COUNTS: LazyLock<Mutex<BTreeMap<String, ErrorCodeStats>>> = LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// 저장된 토큰으로 인증하지 못해 새로 등록
+    SavedTokenRejected,
+    /// 토큰 갱신 실패
+    TokenRefreshFailed,
+    /// 서버가 access_token 을 거부 (만료)
+    TokenExpired,
+    /// access_token 만료 임박
+    TokenExpiringSoon,
+    /// access_token 저장 실패
+    TokenWriteFailed,
+    /// 수집 대상 파일 열기 실패
+    FileOpenFailed,
+    /// 수집 대상 파일 읽기 실패
+    FileReadFailed,
+    /// 파일 감시자 설정 실패 또는 폴링 전환
+    WatcherFailed,
+    /// UTF-8 이 아닌 레코드를 버림
+    InvalidUtf8,
+    /// 레코드 플러시 실패
+    RecordFlushFailed,
+    /// Collector 작업 비정상 종료
+    CollectorFailed,
+    /// 일시적 오류로 배치 재전송 대기
+    SendRetrying,
+    /// 재시도 예산 소진
+    RetryBudgetExhausted,
+    /// 배치 전송 실패
+    SendFailed,
+    /// 배치를 디스크 큐에 저장하지 못함
+    SpoolWriteFailed,
+    /// 서버 연결 실패 (디스크 큐에 저장하며 재시도)
+    ConnectFailed,
+    /// 서버가 거부했거나 너무 큰 로그를 dead-letter 로 보냄
+    DeadLettered,
+    /// `state/` 디스크 여유 공간 부족
+    DiskSpaceLow,
+    /// heartbeat 전송 실패
+    HeartbeatFailed,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SavedTokenRejected => "AUTH_01",
+            Self::TokenRefreshFailed => "AUTH_02",
+            Self::TokenExpired => "AUTH_03",
+            Self::TokenExpiringSoon => "AUTH_04",
+            Self::TokenWriteFailed => "AUTH_05",
+            Self::FileOpenFailed => "COLLECT_01",
+            Self::FileReadFailed => "COLLECT_02",
+            Self::WatcherFailed => "COLLECT_03",
+            Self::InvalidUtf8 => "COLLECT_04",
+            Self::RecordFlushFailed => "COLLECT_05",
+            Self::CollectorFailed => "COLLECT_06",
+            Self::SendRetrying => "SEND_01",
+            Self::RetryBudgetExhausted => "SEND_02",
+            Self::SendFailed => "SEND_03",
+            Self::SpoolWriteFailed => "SEND_04",
+            Self::ConnectFailed => "SEND_05",
+            Self::DeadLettered => "SEND_06",
+            Self::DiskSpaceLow => "SEND_07",
+            Self::HeartbeatFailed => "SEND_08",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 코드 하나의 누적 발생
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorCodeStats {
+    pub count: u64,
+    pub last_at: DateTime<Utc>,
+    pub last_message: String,
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<String, ErrorCodeStats>> {
+    COUNTS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 코드별 누적 발생
+pub fn snapshot() -> BTreeMap<String, ErrorCodeStats> {
+    lock().clone()
+}
+
+/// `code` 필드가 있는 로그 이벤트를 코드별로 세는 tracing 레이어, 억제된 반복 로그도 셈
+pub fn layer() -> ErrorCodeLayer {
+    ErrorCodeLayer
+}
+
+pub struct ErrorCodeLayer;
+
+impl<S: Subscriber> Layer<S> for ErrorCodeLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().fields().field(CODE_FIELD).is_none() {
+            return;
+        }
+
+        let mut visitor = CodeVisitor::default();
+        event.record(&mut visitor);
+        let Some(code) = visitor.code else {
+            return;
+        };
+
+        let mut counts = lock();
+        let stats = counts.entry(code).or_insert_with(|| ErrorCodeStats {
+            count: 0,
+            last_at: Utc::now(),
+            last_message: String::new(),
+        });
+        stats.count += 1;
+        stats.last_at = Utc::now();
+        stats.last_message = visitor.message;
+    }
+}
+
+#[derive(Default)]
+struct CodeVisitor {
+    code: Option<String>,
+    message: String,
+}
+
+impl Visit for CodeVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            CODE_FIELD => self.code = Some(value.to_string()),
+            "message" => self.message = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            CODE_FIELD => self.code = Some(format!("{:?}", value)),
+            "message" => self.message = format!("{:?}", value),
+            _ => {}
+        }
+    }
+}
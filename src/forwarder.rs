@@ -1,104 +1,521 @@
-use crate::models::LogEvent;
-use crate::proto::log::{Log, LogBatch};
+use crate::anomaly::VolumeDetector;
+use crate::anonymize::Anonymizer;
+use crate::batch_id;
+use crate::batch_pool;
+use crate::clock;
+use crate::dedup::Dedup;
+use crate::hot_restart::BatchedLines;
+use crate::log_metrics::LogMetrics;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::{LogEvent, MetricEvent, MetricKind};
+use crate::oversize;
+use crate::proto::log::{self, Log, LogBatch, Metric};
+use crate::routing::{Route, Router};
+use crate::sampling::{self, Sampler};
+use crate::schedule;
+use crate::settings::VolumeAnomalySettings;
+use crate::sink_health::SinkHealth;
+use crate::supervisor::SharedReceiver;
+use crate::tap;
+use chrono::{DateTime, Utc};
 use prost_types::Timestamp;
+use std::collections::HashMap;
 use std::mem;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
 use tokio::time;
-use tracing::{error, info};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
+/// 플러시 요청 횟수, 모든 레인의 Forwarder 가 마지막으로 본 값과 비교
+static FLUSH_REQUESTED: LazyLock<watch::Sender<u64>> = LazyLock::new(|| watch::channel(0).0);
+
+/// `flush_interval` 을 기다리지 않고 쌓인 로그를 바로 배치로 전달
+///
+/// Forwarder 가 배치를 넘기는 중이어도 요청을 잃지 않도록 횟수로 남김 (다음 대기에서 바로 플러시)
+pub fn request_flush() {
+    FLUSH_REQUESTED.send_modify(|count| *count += 1);
+}
+
+/// [`LogEvent`] 를 `batch_size` 또는 `flush_interval` 기준으로 묶어 [`LogBatch`] 생성
+///
+/// 그 사이 들어온 [`MetricEvent`] 와 로그 기반 카운터도 같은 배치에 실음
 pub struct Forwarder {
-    rx: Receiver<LogEvent>,
+    rx: SharedReceiver<LogEvent>,
+    metric_rx: SharedReceiver<MetricEvent>,
     tx: Sender<LogBatch>,
     batch_size: usize,
     flush_interval: Duration,
+    budget: MemoryBudget,
+    /// 모든 레인이 공유하는 로그 기반 카운터
+    log_metrics: Arc<LogMetrics>,
+    /// 배치마다 붙이는 전역 태그
+    tags: HashMap<String, String>,
+    /// 이벤트별 전송 대상 규칙, 비어 있으면 모두 Sink 로
+    router: Option<Arc<Router>>,
+    /// 규칙이 이름으로 고른 추가 Sink 채널 (설정의 `sinks` 순서)
+    sinks: Vec<Sender<LogEvent>>,
+    /// 전송 전 필드 익명화, 규칙이 없으면 None
+    anonymizer: Option<Arc<Anonymizer>>,
+    /// 이벤트 크기 상한 (바이트), 0 이면 무제한
+    max_event_bytes: usize,
+    /// 오류 폭주 중복 제거, 구간이 0 이면 None
+    dedup: Option<Dedup>,
+    /// 모든 레인이 공유하는 역압 샘플링, 설정이 없으면 None
+    sampler: Option<Arc<Sampler>>,
+    /// 이 레인 수집 대상의 로그 양 이상 탐지, 설정이 없으면 None
+    anomaly: Option<VolumeDetector>,
+    /// 이 레인 Sink 의 상태, 실패 중이면 대기 배치가 빠질 때까지 새 이벤트를 받지 않음
+    sink_health: Option<SinkHealth>,
+    /// `schedule` 구간 밖이라 전송을 보류한 라벨별 로그와 예산 사용량
+    deferred: HashMap<String, (Vec<Log>, usize)>,
 }
 
 impl Forwarder {
     pub fn new(
-        rx: Receiver<LogEvent>,
+        rx: SharedReceiver<LogEvent>,
+        metric_rx: SharedReceiver<MetricEvent>,
         tx: Sender<LogBatch>,
         batch_size: usize,
         flush_interval: u64,
+        budget: MemoryBudget,
+        log_metrics: Arc<LogMetrics>,
     ) -> Self {
         Self {
             rx,
+            metric_rx,
             tx,
             batch_size,
             flush_interval: Duration::from_secs(flush_interval),
+            budget,
+            log_metrics,
+            tags: HashMap::new(),
+            router: None,
+            sinks: Vec::new(),
+            anonymizer: None,
+            max_event_bytes: 0,
+            dedup: None,
+            sampler: None,
+            anomaly: None,
+            sink_health: None,
+            deferred: HashMap::new(),
         }
     }
 
+    /// 배치마다 붙일 전역 태그 (설정의 `tags`)
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// 이벤트별 전송 대상 규칙 (설정의 `routes`)과 규칙이 고르는 추가 Sink 채널 (설정의 `sinks` 순서)
+    pub fn with_router(mut self, router: Arc<Router>, sinks: Vec<Sender<LogEvent>>) -> Self {
+        self.router = (!router.is_empty()).then_some(router);
+        self.sinks = sinks;
+        self
+    }
+
+    /// 전송 전 필드 익명화 (설정의 `anonymize`)
+    pub fn with_anonymizer(mut self, anonymizer: Arc<Anonymizer>) -> Self {
+        self.anonymizer = (!anonymizer.is_empty()).then_some(anonymizer);
+        self
+    }
+
+    /// 이벤트 크기 상한 (설정의 `max_event_bytes`)
+    pub fn with_max_event_bytes(mut self, max_event_bytes: usize) -> Self {
+        self.max_event_bytes = max_event_bytes;
+        self
+    }
+
+    /// 같은 ERROR 라인을 묶는 구간 (설정의 `dedup_window`, 초)
+    pub fn with_dedup_window(mut self, secs: u64) -> Self {
+        self.dedup = (secs > 0).then(|| Dedup::new(Duration::from_secs(secs), self.budget.clone()));
+        self
+    }
+
+    /// 역압 시 낮은 레벨 이벤트 샘플링 (설정의 `adaptive_sampling`)
+    pub fn with_sampler(mut self, sampler: Option<Arc<Sampler>>) -> Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// 수집 대상별 로그 양 이상 탐지 (설정의 `volume_anomaly`)
+    pub fn with_volume_anomaly(mut self, settings: Option<VolumeAnomalySettings>) -> Self {
+        self.anomaly = settings.map(VolumeDetector::new);
+        self
+    }
+
+    /// Sink 상태에 따른 역압, 배치 채널의 대기 수도 이 상태로 노출
+    pub fn with_sink_health(mut self, health: SinkHealth) -> Self {
+        health.watch_queue(&self.tx);
+        self.sink_health = Some(health);
+        self
+    }
+
+    /// 수신 채널이 닫힐 때까지 배치 생성, 종료 시 잔여 로그 플러시
     pub async fn start(mut self) {
-        let mut logs: Vec<Log> = Vec::with_capacity(self.batch_size);
+        let rx = Arc::clone(&self.rx);
+        let mut rx = rx.lock().await;
+        let metric_rx = Arc::clone(&self.metric_rx);
+        let mut metric_rx = metric_rx.lock().await;
+
+        let mut logs: Vec<Log> = batch_pool::take(self.batch_size);
+        let mut metrics: Vec<Metric> = Vec::new();
+        // logs 에 쌓인 이벤트의 예산 사용량, 플러시 시 배치 크기로 전환
+        let mut pending_bytes = 0usize;
         let mut interval = time::interval(self.flush_interval);
 
+        let mut sampling_interval = time::interval(sampling::ADJUST_INTERVAL);
+        // 탐지를 쓰지 않으면 분기가 비활성화되므로 주기는 쓰이지 않음
+        let anomaly_window = self.anomaly.as_ref().map_or(self.flush_interval, VolumeDetector::window);
+        let mut anomaly_interval = time::interval_at(time::Instant::now() + anomaly_window, anomaly_window);
+
         interval.tick().await;
 
+        let sink_health = self.sink_health.clone();
+        let mut paused = false;
+        let mut flush_requested = FLUSH_REQUESTED.subscribe();
+
         loop {
+            let dedup_deadline = self.dedup.as_ref().and_then(Dedup::deadline);
+
+            let sink_failing = self.sink_failing();
+            if sink_failing != paused {
+                paused = sink_failing;
+                if paused {
+                    warn!("{}", msg::FORWARDER_PAUSED_SINK_UNHEALTHY);
+                } else {
+                    info!("{}", msg::FORWARDER_RESUMED);
+                }
+            }
+
             tokio::select! {
-                msg = self.rx.recv() => {
+                // Sink 가 실패 중이고 대기 배치가 남아 있으면 수집 채널을 읽지 않음 (Collector 는 채널이 차면 대기)
+                msg = rx.recv(), if !paused => {
                     match msg {
-                        Some(event) => {
-                            logs.push(event_to_log(event));
+                        Some(mut event) => {
+                            // 탭, 카운터, 라우팅 조건도 익명화된 값을 봄
+                            if let Some(anonymizer) = &self.anonymizer {
+                                anonymizer.apply(&mut event, &self.budget);
+                            }
+                            if self.max_event_bytes > 0 {
+                                self.truncate_oversized(&mut event);
+                            }
+
+                            tap::publish(&event);
+                            self.log_metrics.observe(&event);
+                            if let Some(anomaly) = &mut self.anomaly {
+                                anomaly.observe(&event);
+                            }
+
+                            match self.router.as_ref().map_or(Route::Sink, |router| router.route(&event)) {
+                                Route::Sink => {}
+                                Route::Drop => {
+                                    self.budget.release(memory::event_bytes(&event));
+                                    continue;
+                                }
+                                Route::Named(index) => {
+                                    self.dispatch(index, event).await;
+                                    continue;
+                                }
+                            }
+
+                            if let Some(sampler) = &self.sampler
+                                && !sampler.keep(&event)
+                            {
+                                self.budget.release(memory::event_bytes(&event));
+                                continue;
+                            }
+
+                            let event = match &mut self.dedup {
+                                Some(dedup) => match dedup.push(event) {
+                                    Some(event) => event,
+                                    None => continue,
+                                },
+                                None => event,
+                            };
+
+                            if schedule::is_deferred(&event.label) {
+                                self.defer(event).await;
+                                continue;
+                            }
+                            push_log(&mut logs, &mut pending_bytes, event);
 
                             if logs.len() >= self.batch_size {
-                                self.flush(&mut logs).await;
+                                self.flush(&mut logs, &mut metrics, &mut pending_bytes).await;
                                 interval.reset();
                             }
                         }
                         None => {
-                            info!("모든 Collector 종료, 잔여 데이터 전송 중..");
-                            self.flush(&mut logs).await;
+                            info!("{}", msg::COLLECTORS_DONE_FLUSHING);
+                            for label in self.deferred.keys().cloned().collect::<Vec<_>>() {
+                                self.store_deferred(&label).await;
+                            }
+                            for event in self.dedup.as_mut().map(Dedup::take_all).unwrap_or_default() {
+                                push_log(&mut logs, &mut pending_bytes, event);
+                            }
+                            self.flush(&mut logs, &mut metrics, &mut pending_bytes).await;
                             break;
                         }
                     }
                 }
+                // 모든 송신측이 사라지면 (None) 이 분기는 비활성화
+                Some(metric) = metric_rx.recv() => {
+                    metrics.push(metric_to_proto(metric));
+                }
+                // 붙잡은 오류 라인이 없으면 비활성화
+                _ = time::sleep_until(dedup_deadline.unwrap_or_else(time::Instant::now)), if dedup_deadline.is_some() => {
+                    for event in self.dedup.as_mut().map(Dedup::take_expired).unwrap_or_default() {
+                        push_log(&mut logs, &mut pending_bytes, event);
+                    }
+
+                    if logs.len() >= self.batch_size {
+                        self.flush(&mut logs, &mut metrics, &mut pending_bytes).await;
+                        interval.reset();
+                    }
+                }
                 _ = interval.tick() => {
-                    self.flush(&mut logs).await;
+                    self.flush(&mut logs, &mut metrics, &mut pending_bytes).await;
+                }
+                _ = sampling_interval.tick(), if self.sampler.is_some() => {
+                    self.adjust_sampling();
+                }
+                _ = anomaly_interval.tick(), if self.anomaly.is_some() => {
+                    for event in self.anomaly.as_mut().map(VolumeDetector::evaluate).unwrap_or_default() {
+                        // 수집 대상이 아닌 에이전트가 만든 이벤트라 예산에 새로 더함
+                        self.budget.add(memory::event_bytes(&event));
+                        push_log(&mut logs, &mut pending_bytes, event);
+                    }
+                }
+                // 전송 결과가 기록되면 멈춤 여부를 다시 판단
+                _ = sink_changed(sink_health.as_ref()), if paused => {}
+                Ok(()) = flush_requested.changed() => {
+                    self.flush(&mut logs, &mut metrics, &mut pending_bytes).await;
+                    interval.reset();
                 }
             }
         }
 
-        info!("Forwarder 종료..");
+        info!("{}", msg::FORWARDER_STOPPED);
     }
 
-    async fn flush(&self, logs: &mut Vec<Log>) {
-        if logs.is_empty() {
+    /// 추가 Sink 로 넘김, 예산은 해당 Sink 가 쓰고 나서 해제
+    async fn dispatch(&self, index: usize, event: LogEvent) {
+        let Some(sink) = self.sinks.get(index) else {
+            self.budget.release(memory::event_bytes(&event));
             return;
+        };
+
+        if let Err(e) = sink.send(event).await {
+            warn!(label = %e.0.label, "{}", msg::NAMED_SINK_CLOSED);
+            self.budget.release(memory::event_bytes(&e.0));
         }
+    }
 
-        let send_logs = mem::take(logs);
-        let batch_id = Uuid::new_v4().to_string();
+    /// Sink 가 연속으로 실패 중이고 아직 넘기지 못한 배치가 있음
+    fn sink_failing(&self) -> bool {
+        self.sink_health
+            .as_ref()
+            .is_some_and(|health| !health.is_healthy() && health.queue_depth() > 0)
+    }
+
+    /// 배치 채널과 메모리 예산 중 더 찬 쪽의 사용률로 샘플링 조절
+    fn adjust_sampling(&self) {
+        let Some(sampler) = &self.sampler else {
+            return;
+        };
+
+        let queue = 1.0 - self.tx.capacity() as f64 / self.tx.max_capacity() as f64;
+        let memory = match self.budget.limit() {
+            0 => 0.0,
+            limit => self.budget.used() as f64 / limit as f64,
+        };
+        sampler.adjust(queue.max(memory));
+    }
+
+    /// 상한을 넘는 이벤트를 자르고 줄어든 만큼 메모리 예산 반환
+    fn truncate_oversized(&self, event: &mut LogEvent) {
+        let before = memory::event_bytes(event);
+        if !oversize::truncate(event, self.max_event_bytes) {
+            return;
+        }
+
+        warn!(label = %event.label, offset = event.offset, limit = self.max_event_bytes, "{}", msg::EVENT_TRUNCATED);
+        self.budget.release(before.saturating_sub(memory::event_bytes(event)));
+    }
+
+    /// 전송을 보류한 이벤트를 라벨별로 모음, `batch_size` 가 차면 보류 큐에 저장
+    async fn defer(&mut self, event: LogEvent) {
+        let label = event.label.clone();
+        let (logs, bytes) = self.deferred.entry(label.clone()).or_default();
+        push_log(logs, bytes, event);
+
+        if logs.len() >= self.batch_size {
+            self.store_deferred(&label).await;
+        }
+    }
+
+    /// 모은 이벤트를 배치로 보류 큐에 저장, 저장할 수 없으면(상태 없는 실행, 디스크 부족) 바로 전송
+    async fn store_deferred(&mut self, label: &str) {
+        let Some((logs, bytes)) = self.deferred.remove(label) else {
+            return;
+        };
+
+        let batch = LogBatch {
+            batch_id: batch_id::next(),
+            send_at: Some(now()),
+            logs,
+            trace_id: new_trace_id(),
+            metrics: Vec::new(),
+            tags: self.tags.clone(),
+        };
+
+        match schedule::defer(label, &batch) {
+            Ok(()) => {
+                BatchedLines::of(&batch.logs).record();
+                self.budget.release(bytes);
+                // 저장하는 사이 구간이 열렸으면 바로 내보냄
+                if !schedule::is_deferred(label) {
+                    schedule::release(label);
+                }
+            }
+            Err(e) => {
+                warn!(source = label, batch_id = %batch.batch_id, error = %e, "{}", msg::SCHEDULE_DEFER_FAILED);
+                self.budget.release(bytes);
+                self.send(batch).await;
+            }
+        }
+    }
+
+    async fn flush(&mut self, logs: &mut Vec<Log>, metrics: &mut Vec<Metric>, pending_bytes: &mut usize) {
+        // 구간이 열린 수집 대상의 남은 이벤트는 이번 배치에 실음
+        let opened: Vec<String> = self
+            .deferred
+            .keys()
+            .filter(|label| !schedule::is_deferred(label))
+            .cloned()
+            .collect();
+        for label in opened {
+            if let Some((deferred, bytes)) = self.deferred.remove(&label) {
+                logs.extend(deferred);
+                *pending_bytes += bytes;
+            }
+        }
+
+        metrics.extend(self.log_metrics.collect().into_iter().map(metric_to_proto));
+
+        if logs.is_empty() && metrics.is_empty() {
+            return;
+        }
+
+        let batch_id = batch_id::next();
 
         let batch = LogBatch {
             batch_id,
             send_at: Some(now()),
-            logs: send_logs,
+            // 전송이 끝난 배치의 버퍼를 이어 받아 다음 배치를 쌓음
+            logs: mem::replace(logs, batch_pool::take(self.batch_size)),
+            trace_id: new_trace_id(),
+            metrics: mem::take(metrics),
+            tags: self.tags.clone(),
         };
 
-        if let Err(e) = self.tx.send(batch).await {
-            error!("배치 전송 실패: {:?}", e);
+        // 이벤트 단위 예산을 배치 단위로 전환 (이후 Sink 가 전송/저장 후 해제)
+        self.budget.release(mem::take(pending_bytes));
+        self.send(batch).await;
+    }
+
+    /// 배치를 Sink 채널로 넘기고 배치 크기만큼 예산에 더함
+    async fn send(&mut self, batch: LogBatch) {
+        let bytes = memory::batch_bytes(&batch);
+        self.budget.add(bytes);
+
+        // Sink 채널이 가득 차 대기한 시간까지 포함
+        let span = info_span!(
+            "batch",
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            count = batch.logs.len(),
+            bytes = bytes
+        );
+
+        debug!(
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            count = batch.logs.len(),
+            metrics = batch.metrics.len(),
+            "{}",
+            msg::BATCH_CREATED
+        );
+
+        let lines = BatchedLines::of(&batch.logs);
+        match self.tx.send(batch).instrument(span).await {
+            Ok(()) => lines.record(),
+            Err(e) => {
+                error!(batch_id = %e.0.batch_id, trace_id = %e.0.trace_id, "{}", msg::BATCH_FORWARD_FAILED);
+                self.budget.release(bytes);
+            }
         }
     }
 }
 
-fn event_to_log(event: LogEvent) -> Log {
+async fn sink_changed(health: Option<&SinkHealth>) {
+    match health {
+        Some(health) => health.changed().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 이벤트를 배치에 쌓고 예산 사용량 누적
+fn push_log(logs: &mut Vec<Log>, pending_bytes: &mut usize, event: LogEvent) {
+    *pending_bytes += memory::event_bytes(&event);
+    logs.push(event_to_log(event));
+}
+
+pub fn event_to_log(event: LogEvent) -> Log {
     Log {
         label: event.label,
         line: event.content,
-        timestamp: Some(Timestamp {
-            seconds: event.timestamp.timestamp(),
-            nanos: event.timestamp.timestamp_subsec_nanos() as i32,
-        }),
+        timestamp: Some(timestamp(event.timestamp)),
+        path: event.path.to_string(),
+        offset: event.offset,
+        file_id: event.file_id,
+        fields: event.fields.into_iter().collect(),
     }
 }
 
+fn metric_to_proto(metric: MetricEvent) -> Metric {
+    let kind = match metric.kind {
+        MetricKind::Gauge => log::MetricKind::Gauge,
+        MetricKind::Counter => log::MetricKind::Counter,
+    };
+
+    Metric {
+        name: metric.name,
+        value: metric.value,
+        kind: kind as i32,
+        labels: metric.labels.into_iter().collect(),
+        timestamp: Some(timestamp(metric.timestamp)),
+    }
+}
+
+/// W3C trace-context 와 같은 32자리 16진수 추적 식별자
+fn new_trace_id() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
 fn now() -> Timestamp {
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    timestamp(Utc::now())
+}
+
+/// 서버 대비 시계 오차 보정(`clock_skew.correct_timestamps`)을 적용한 타임스탬프
+fn timestamp(at: DateTime<Utc>) -> Timestamp {
+    let at = at + clock::correction();
     Timestamp {
-        seconds: now.as_secs() as i64,
-        nanos: now.subsec_nanos() as i32,
+        seconds: at.timestamp(),
+        nanos: at.timestamp_subsec_nanos() as i32,
     }
 }
@@ -0,0 +1,384 @@
+//! GELF UDP 수신 (`kind: gelf_udp`)
+//!
+//! Graylog GELF UDP 입력과 같은 형식의 데이터그램을 받아 [`LogEvent`] 로 변환.
+//! 청크로 나뉜 메시지는 메시지 ID 별로 모아 합치고, gzip/zlib 압축은 풀어서 처리.
+//! `short_message` 가 본문이고 `host`, `level`, `full_message` 와 `_` 로 시작하는 추가 필드는 구조화 필드로 실음
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc::Sender;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::{self, Fields};
+use crate::settings::{SourceFormat, SourceSettings};
+use crate::status;
+
+/// `listen` 생략 시 수신 주소 (GELF 기본 포트)
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:12201";
+
+/// 청크 데이터그램 머리말: 매직 2바이트, 메시지 ID 8바이트, 순번, 전체 청크 수
+const CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+const CHUNK_HEADER_LEN: usize = 12;
+
+/// GELF 규격의 메시지당 최대 청크 수
+const MAX_CHUNKS: u8 = 128;
+
+/// 청크가 모두 도착하기까지 기다리는 시간, 넘으면 버림
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 동시에 조립 중인 청크 메시지 수 상한 (유실이 많을 때 메모리 보호)
+const MAX_PARTIALS: usize = 1024;
+
+/// UDP 데이터그램 최대 크기
+const MAX_DATAGRAM: usize = 65536;
+
+/// 압축 해제 후 메시지 크기 상한 (압축 폭탄 방지)
+const MAX_MESSAGE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// GELF 1.1 메시지 (사용하는 필드만, 나머지는 `extra`)
+#[derive(Debug, Deserialize)]
+struct Message {
+    #[serde(default)]
+    host: String,
+    #[serde(default)]
+    short_message: String,
+    #[serde(default)]
+    full_message: Option<String>,
+    /// 초 단위 유닉스 시각 (소수부는 밀리초)
+    #[serde(default)]
+    timestamp: Option<f64>,
+    /// syslog 심각도 (0 emerg ~ 7 debug)
+    #[serde(default)]
+    level: Option<u8>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+/// 조립 중인 청크 메시지
+struct Partial {
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+    started: Instant,
+}
+
+pub struct GelfCollector {
+    tx: Sender<LogEvent>,
+    label: String,
+    socket: UdpSocket,
+    format: SourceFormat,
+    path: Arc<str>,
+    /// 지금까지 전달한 메시지 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+    partials: HashMap<[u8; 8], Partial>,
+}
+
+impl GelfCollector {
+    pub async fn bind(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        let listen = source.listen.as_deref().unwrap_or(DEFAULT_LISTEN);
+        let socket = UdpSocket::bind(listen)
+            .await
+            .with_context(|| format!("{}: {}", msg::GELF_BIND_FAILED, listen))?;
+
+        let path = format!("udp://{}", listen);
+        status::registry().update_source(&source.label, Path::new(&path), 0);
+
+        Ok(Self {
+            tx,
+            label: source.label,
+            socket,
+            format: source.format,
+            path: Arc::from(path),
+            forwarded: 0,
+            budget,
+            partials: HashMap::new(),
+        })
+    }
+
+    /// `shutdown` 취소 시까지 데이터그램을 받아 전달
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, path = %self.path, "{}", msg::GELF_STARTED);
+
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+        let mut expire = time::interval(CHUNK_TIMEOUT);
+
+        loop {
+            let received = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = expire.tick() => None,
+                received = self.socket.recv_from(&mut buf) => Some(received),
+            };
+            let Some(received) = received else {
+                self.expire_partials();
+                continue;
+            };
+
+            let len = match received {
+                Ok((len, _)) => len,
+                Err(e) => {
+                    warn!(source = %self.label, error = %e, "{}", msg::GELF_RECEIVE_FAILED);
+                    continue;
+                }
+            };
+            let Some(payload) = self.assemble(&buf[..len]) else {
+                continue;
+            };
+            // 파일과 달리 다시 받을 수 없으므로 일시 중지 중 메시지는 버림
+            if collector::is_paused(&self.label) {
+                continue;
+            }
+
+            let message = match decode(&payload) {
+                Ok(message) => message,
+                Err(e) => {
+                    warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::GELF_INVALID_MESSAGE);
+                    continue;
+                }
+            };
+
+            self.budget.wait_for_capacity(&shutdown).await;
+            if let Err(e) = self.send_event(message).await {
+                warn!(source = %self.label, error = %e, "{}", msg::GELF_RECEIVE_FAILED);
+                break;
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    /// 청크가 아니면 그대로, 청크면 모두 모였을 때 합친 메시지 반환
+    fn assemble<'a>(&mut self, datagram: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+        if !datagram.starts_with(&CHUNK_MAGIC) {
+            return Some(Cow::Borrowed(datagram));
+        }
+        if datagram.len() < CHUNK_HEADER_LEN {
+            return None;
+        }
+
+        let id: [u8; 8] = datagram[2..10].try_into().ok()?;
+        let (seq, count) = (datagram[10], datagram[11]);
+        if count == 0 || count > MAX_CHUNKS || seq >= count {
+            return None;
+        }
+        if !self.partials.contains_key(&id) && self.partials.len() >= MAX_PARTIALS {
+            return None;
+        }
+
+        let partial = self.partials.entry(id).or_insert_with(|| Partial {
+            chunks: vec![None; count as usize],
+            received: 0,
+            started: Instant::now(),
+        });
+        // 같은 ID 인데 청크 수가 다르면 잘못된 청크
+        if partial.chunks.len() != count as usize {
+            return None;
+        }
+
+        let slot = &mut partial.chunks[seq as usize];
+        if slot.is_none() {
+            *slot = Some(datagram[CHUNK_HEADER_LEN..].to_vec());
+            partial.received += 1;
+        }
+        if partial.received < partial.chunks.len() {
+            return None;
+        }
+
+        let partial = self.partials.remove(&id)?;
+        Some(Cow::Owned(partial.chunks.into_iter().flatten().flatten().collect()))
+    }
+
+    /// 대기 시간이 지나도록 청크가 다 오지 않은 메시지 버림
+    fn expire_partials(&mut self) {
+        let before = self.partials.len();
+        self.partials.retain(|_, partial| partial.started.elapsed() < CHUNK_TIMEOUT);
+
+        let expired = before - self.partials.len();
+        if expired > 0 {
+            warn!(source = %self.label, count = expired, "{}", msg::GELF_CHUNKS_EXPIRED);
+        }
+    }
+
+    async fn send_event(&mut self, message: Message) -> Result<()> {
+        if message.short_message.trim().is_empty() {
+            return Ok(());
+        }
+
+        let mut fields = message_fields(&message);
+        fields.extend(parser::parse(self.format, &message.short_message));
+
+        let timestamp = message
+            .timestamp
+            .and_then(|secs| DateTime::from_timestamp_millis((secs * 1000.0).round() as i64));
+
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(message.short_message),
+            timestamp: timestamp.unwrap_or_else(Utc::now),
+            path: Arc::clone(&self.path),
+            offset: self.forwarded,
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        self.forwarded += 1;
+        status::registry().update_source(&self.label, Path::new(self.path.as_ref()), self.forwarded);
+
+        Ok(())
+    }
+}
+
+/// 압축을 풀고(gzip `1f 8b`, zlib `78`) JSON 메시지로 해석
+fn decode(payload: &[u8]) -> Result<Message> {
+    let mut json = Vec::new();
+    let decompressed = match payload {
+        [0x1f, 0x8b, ..] => GzDecoder::new(payload).take(MAX_MESSAGE_BYTES).read_to_end(&mut json),
+        [0x78, ..] => ZlibDecoder::new(payload).take(MAX_MESSAGE_BYTES).read_to_end(&mut json),
+        _ => return serde_json::from_slice(payload).context(msg::GELF_INVALID_JSON),
+    };
+    decompressed.context(msg::GELF_DECOMPRESS_FAILED)?;
+
+    serde_json::from_slice(&json).context(msg::GELF_INVALID_JSON)
+}
+
+/// `host`, `level`, `full_message` 와 추가 필드 (`_user_id` → `user_id`, 규격상 금지된 `_id` 와 `version` 은 제외)
+fn message_fields(message: &Message) -> Fields {
+    let mut fields = Fields::new();
+
+    if !message.host.is_empty() {
+        fields.push(("host".to_string(), message.host.clone()));
+    }
+    if let Some(level) = message.level {
+        fields.push(("level".to_string(), level.to_string()));
+    }
+    if let Some(full_message) = message.full_message.as_ref().filter(|m| !m.is_empty()) {
+        fields.push(("full_message".to_string(), full_message.clone()));
+    }
+
+    for (key, value) in &message.extra {
+        if key == "_id" || key == "version" {
+            continue;
+        }
+        let value = match value {
+            Value::Null => continue,
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        let key = key.strip_prefix('_').unwrap_or(key);
+        fields.push((key.to_string(), value));
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::parser::field;
+
+    fn chunk(id: u8, seq: u8, count: u8, data: &[u8]) -> Vec<u8> {
+        let mut datagram = CHUNK_MAGIC.to_vec();
+        datagram.extend([id; 8]);
+        datagram.extend([seq, count]);
+        datagram.extend_from_slice(data);
+        datagram
+    }
+
+    async fn collector() -> GelfCollector {
+        let mut source = SourceSettings::new("gelf", "");
+        source.listen = Some("127.0.0.1:0".to_string());
+
+        let (tx, _rx) = mpsc::channel(1);
+        GelfCollector::bind(tx, source, MemoryBudget::new(0)).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn assembles_chunks_in_any_order() {
+        let mut collector = collector().await;
+
+        assert!(collector.assemble(&chunk(1, 2, 3, b"!\"}")).is_none());
+        assert!(collector.assemble(&chunk(1, 0, 3, b"{\"short_message\":")).is_none());
+        // 같은 순번이 다시 와도 한 번만 셈
+        assert!(collector.assemble(&chunk(1, 0, 3, b"ignored")).is_none());
+        let last = chunk(1, 1, 3, b"\"hello");
+        let payload = collector.assemble(&last).unwrap();
+
+        assert_eq!(payload.as_ref(), br#"{"short_message":"hello!"}"#);
+        assert!(collector.partials.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_chunks() {
+        let mut collector = collector().await;
+
+        assert!(collector.assemble(&chunk(2, 0, 0, b"x")).is_none());
+        assert!(collector.assemble(&chunk(2, 3, 3, b"x")).is_none());
+        assert!(collector.assemble(&chunk(2, 0, MAX_CHUNKS + 1, b"x")).is_none());
+        assert!(collector.assemble(&CHUNK_MAGIC).is_none());
+        assert!(collector.partials.is_empty());
+
+        // 같은 ID 인데 청크 수가 다르면 조립 중인 메시지에 넣지 않음
+        assert!(collector.assemble(&chunk(3, 0, 2, b"a")).is_none());
+        assert!(collector.assemble(&chunk(3, 1, 3, b"b")).is_none());
+        assert_eq!(collector.partials[&[3; 8]].received, 1);
+    }
+
+    #[tokio::test]
+    async fn passes_plain_datagrams_through() {
+        let mut collector = collector().await;
+
+        let datagram = br#"{"short_message":"plain"}"#;
+        assert!(matches!(collector.assemble(datagram), Some(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn decodes_compressed_messages() {
+        let json = br#"{"version":"1.1","host":"web01","short_message":"hi","level":3,"_user_id":42,"_id":"x"}"#;
+
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(json).unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(json).unwrap();
+
+        for payload in [json.to_vec(), gzip.finish().unwrap(), zlib.finish().unwrap()] {
+            let message = decode(&payload).unwrap();
+            let fields = message_fields(&message);
+
+            assert_eq!(message.short_message, "hi");
+            assert_eq!(field(&fields, "host"), Some("web01"));
+            assert_eq!(field(&fields, "level"), Some("3"));
+            assert_eq!(field(&fields, "user_id"), Some("42"));
+            assert_eq!(field(&fields, "id"), None);
+            assert_eq!(field(&fields, "version"), None);
+        }
+
+        assert!(decode(b"\x1f\x8bnot gzip").is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! 호스트 자원 압박 시 자체 감속 (`resource_governor`)
+//!
+//! 호스트 CPU 사용률이나 코어당 load average(Linux 에서는 디스크 I/O 대기 중인 프로세스 포함)가 `*_high` 를 넘으면
+//! 파일 읽기 속도를 `throttled_read_bytes_per_sec` 로 제한하고 전송 압축을 끄거나 가장 빠른 단계로 낮춤.
+//! 둘 다 `*_low` 아래로 내려가면 되돌림. 모니터링 대상 애플리케이션과 자원을 다투지 않기 위함
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use sysinfo::System;
+use tokio::time::{self, interval};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::settings::ResourceGovernorSettings;
+
+/// 감속 중인지
+static THROTTLED: AtomicBool = AtomicBool::new(false);
+
+/// 감속 중 파일 읽기 속도 상한 (초당 바이트)
+static READ_BYTES_PER_SEC: AtomicU64 = AtomicU64::new(u64::MAX);
+
+pub fn is_throttled() -> bool {
+    THROTTLED.load(Ordering::Relaxed)
+}
+
+/// 감속 중이면 방금 읽은 바이트만큼 대기해 읽기 속도를 제한
+pub async fn pace(read_bytes: usize) {
+    if !is_throttled() || read_bytes == 0 {
+        return;
+    }
+
+    let rate = READ_BYTES_PER_SEC.load(Ordering::Relaxed).max(1);
+    time::sleep(Duration::from_secs_f64(read_bytes as f64 / rate as f64)).await;
+}
+
+pub struct Governor {
+    settings: ResourceGovernorSettings,
+    system: System,
+}
+
+impl Governor {
+    pub fn new(settings: ResourceGovernorSettings) -> Self {
+        READ_BYTES_PER_SEC.store(settings.throttled_read_bytes_per_sec.max(1), Ordering::Relaxed);
+
+        Self {
+            settings,
+            system: System::new(),
+        }
+    }
+
+    pub async fn start(mut self, shutdown: CancellationToken) {
+        let mut ticker = interval(Duration::from_secs(self.settings.check_interval.max(1)));
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = ticker.tick() => self.check(),
+            }
+        }
+
+        THROTTLED.store(false, Ordering::Relaxed);
+    }
+
+    fn check(&mut self) {
+        self.system.refresh_cpu_usage();
+
+        let cpu = self.system.global_cpu_usage() as f64;
+        let cores = self.system.cpus().len().max(1) as f64;
+        let load = System::load_average().one / cores;
+
+        let throttled = is_throttled();
+        if !throttled && (cpu >= self.settings.cpu_high || load >= self.settings.load_per_cpu_high) {
+            THROTTLED.store(true, Ordering::Relaxed);
+            warn!(cpu = cpu, load_per_cpu = load, "{}", msg::GOVERNOR_THROTTLING);
+        } else if throttled && cpu <= self.settings.cpu_low && load <= self.settings.load_per_cpu_low {
+            THROTTLED.store(false, Ordering::Relaxed);
+            info!(cpu = cpu, load_per_cpu = load, "{}", msg::GOVERNOR_RELAXED);
+        }
+    }
+}
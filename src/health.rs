@@ -2,9 +2,10 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sysinfo::System;
 use tokio::sync::RwLock;
+use tokio::sync::mpsc::Sender;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 use tonic::Code;
@@ -13,18 +14,58 @@ use tonic::transport::Channel;
 use tracing::{debug, error, info, warn};
 
 use crate::auth::interceptor::AuthInterceptor;
+use crate::auth::lifecycle::{self, TokenLifecycle};
 use crate::auth::token_manager::TokenManager;
-use crate::proto::health::HeartbeatRequest;
+use crate::anomaly;
+use crate::clock;
+use crate::crash_loop;
+use crate::disk_guard;
+use crate::egress;
+use crate::error_code::{self, ErrorCode};
+use crate::governor;
+use crate::messages as msg;
+use crate::models::MetricEvent;
+use crate::proto::health::{BacklogStatus, CrashLoopStatus, EgressTotal, ErrorCodeCount, HeartbeatRequest, TokenStatus};
+use crate::sampling;
+use crate::spool;
+use crate::status;
+use crate::watch_health;
 use crate::proto::health::health_service_client::HealthServiceClient;
 
 type HealthClient = HealthServiceClient<InterceptedService<Channel, AuthInterceptor>>;
 
 static HEARTBEAT_INTERVAL_SECS: u64 = 10;
 
+static CPU_METRIC: &str = "host_cpu_usage_percent";
+static MEMORY_METRIC: &str = "host_memory_usage_percent";
+static SAMPLING_METRIC: &str = "agent_sampling_rate";
+static EGRESS_EVENTS_METRIC: &str = "agent_egress_events";
+static EGRESS_BYTES_METRIC: &str = "agent_egress_bytes";
+static SPOOL_BYTES_METRIC: &str = "agent_spool_bytes";
+static SPOOL_EVICTED_METRIC: &str = "agent_spool_evicted_batches";
+static SPOOL_REJECTED_METRIC: &str = "agent_spool_rejected_batches";
+static GOVERNOR_THROTTLED_METRIC: &str = "agent_governor_throttled";
+static DISK_FREE_METRIC: &str = "agent_disk_free_bytes";
+static DISK_LOW_METRIC: &str = "agent_disk_space_low";
+static CRASH_LOOP_METRIC: &str = "agent_crash_loop";
+static CLOCK_SKEW_METRIC: &str = "agent_clock_skew_seconds";
+static WATCHER_STALE_METRIC: &str = "agent_watcher_stale_incidents";
+static WATCHER_POLLING_METRIC: &str = "agent_watcher_polling";
+static INVALID_UTF8_METRIC: &str = "agent_invalid_utf8_skipped";
+static ERROR_CODE_METRIC: &str = "agent_errors";
+static BACKLOG_BATCHES_METRIC: &str = "agent_backlog_batches";
+static BACKLOG_AGE_METRIC: &str = "agent_backlog_oldest_seconds";
+static TOKEN_AGE_METRIC: &str = "agent_token_age_seconds";
+static TOKEN_EXPIRES_IN_METRIC: &str = "agent_token_expires_in_seconds";
+static TOKEN_REFRESH_METRIC: &str = "agent_token_refreshes";
+static TOKEN_LAST_REGISTER_METRIC: &str = "agent_token_last_register_timestamp";
+
 pub struct HealthReporter {
     client: HealthClient,
     token_manager: Arc<RwLock<TokenManager>>,
     system: System,
+    /// Heartbeat 와 같은 값을 로그 배치에도 게이지로 실음 (서버 장애 중에도 스풀로 보존)
+    metrics: Sender<MetricEvent>,
 }
 
 impl HealthReporter {
@@ -32,6 +73,7 @@ impl HealthReporter {
         channel: Channel,
         interceptor: AuthInterceptor,
         token_manager: Arc<RwLock<TokenManager>>,
+        metrics: Sender<MetricEvent>,
     ) -> Self {
         let client = HealthServiceClient::with_interceptor(channel, interceptor);
         let system = System::new_all();
@@ -40,23 +82,24 @@ impl HealthReporter {
             client,
             token_manager,
             system,
+            metrics,
         }
     }
 
     pub async fn start(mut self, shutdown: CancellationToken) {
-        info!("HealthReporter 시작");
+        info!("{}", msg::HEALTH_REPORTER_STARTED);
 
         let mut ticker = interval(Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
 
         loop {
             tokio::select! {
                 _ = shutdown.cancelled() => {
-                    info!("HealthReporter 종료");
+                    info!("{}", msg::HEALTH_REPORTER_STOPPED);
                     break;
                 }
                 _ = ticker.tick() => {
                     if let Err(e) = self.send_heartbeat().await {
-                        error!("Heartbeat 전송 실패: {}", e);
+                        error!(error = %e, code = %ErrorCode::HeartbeatFailed, "{}", msg::HEARTBEAT_FAILED);
                     }
                 }
             }
@@ -69,21 +112,126 @@ impl HealthReporter {
 
         let cpu = self.system.global_cpu_usage() as f64;
         let memory = self.calculate_memory_usage();
+        let sampling_rate = sampling::rate();
         let sys_time = SystemTime::from(Utc::now());
 
+        self.emit(MetricEvent::gauge(CPU_METRIC, cpu));
+        self.emit(MetricEvent::gauge(MEMORY_METRIC, memory));
+        self.emit(MetricEvent::gauge(SAMPLING_METRIC, sampling_rate));
+        self.emit(MetricEvent::gauge(GOVERNOR_THROTTLED_METRIC, if governor::is_throttled() { 1.0 } else { 0.0 }));
+
+        let egress = egress::snapshot();
+        for (source, total) in &egress {
+            self.emit(MetricEvent::counter(EGRESS_EVENTS_METRIC, total.events as f64).label("source", source));
+            self.emit(MetricEvent::counter(EGRESS_BYTES_METRIC, total.bytes as f64).label("source", source));
+        }
+
+        for (queue, stats) in spool::stats() {
+            self.emit(MetricEvent::gauge(SPOOL_BYTES_METRIC, stats.bytes as f64).label("queue", &queue));
+            self.emit(MetricEvent::counter(SPOOL_EVICTED_METRIC, stats.evicted_batches as f64).label("queue", &queue));
+            self.emit(MetricEvent::counter(SPOOL_REJECTED_METRIC, stats.rejected_batches as f64).label("queue", &queue));
+        }
+
+        let disk = disk_guard::snapshot();
+        if let Some(free) = disk.free_bytes {
+            self.emit(MetricEvent::gauge(DISK_FREE_METRIC, free as f64));
+        }
+        self.emit(MetricEvent::gauge(DISK_LOW_METRIC, if disk.low { 1.0 } else { 0.0 }));
+
+        let crash_loop = crash_loop::snapshot();
+        self.emit(MetricEvent::gauge(CRASH_LOOP_METRIC, if crash_loop.is_looping() { 1.0 } else { 0.0 }));
+
+        let clock = clock::snapshot();
+        if let Some(skew) = clock.skew_secs {
+            self.emit(MetricEvent::gauge(CLOCK_SKEW_METRIC, skew as f64));
+        }
+
+        for (source, watcher) in watch_health::snapshot() {
+            self.emit(MetricEvent::counter(WATCHER_STALE_METRIC, watcher.stale_incidents as f64).label("source", &source));
+            self.emit(MetricEvent::gauge(WATCHER_POLLING_METRIC, if watcher.polling { 1.0 } else { 0.0 }).label("source", &source));
+        }
+
+        for (source, skipped) in status::registry().invalid_utf8() {
+            self.emit(MetricEvent::counter(INVALID_UTF8_METRIC, skipped as f64).label("source", &source));
+        }
+
+        let backlog = status::registry().backlog();
+        let backlog_age = backlog.oldest_event_age_secs.unwrap_or(0).max(0) as u64;
+        self.emit(MetricEvent::gauge(BACKLOG_BATCHES_METRIC, backlog.spooled_batches as f64).label("queue", "spool"));
+        self.emit(MetricEvent::gauge(BACKLOG_BATCHES_METRIC, backlog.queued_batches as f64).label("queue", "memory"));
+        self.emit(MetricEvent::gauge(BACKLOG_AGE_METRIC, backlog_age as f64));
+
+        let errors = error_code::snapshot();
+        for (code, stats) in &errors {
+            self.emit(MetricEvent::counter(ERROR_CODE_METRIC, stats.count as f64).label("code", code));
+        }
+
+        let token = self.token_manager.read().await.get_shared_token();
+        let token = token.read().ok().map(|t| t.clone());
+        let token = lifecycle::snapshot(token.as_deref());
+        lifecycle::warn_if_expiring(&token);
+        self.emit_token(&token);
+
         let request = HeartbeatRequest {
             timestamp: Some(prost_types::Timestamp::from(sys_time)),
             cpu,
             memory,
+            sampling_rate,
+            anomalies: anomaly::active(),
+            egress: egress
+                .into_iter()
+                .map(|(source, total)| EgressTotal {
+                    source,
+                    events: total.events,
+                    bytes: total.bytes,
+                })
+                .collect(),
+            token: Some(TokenStatus {
+                issued_at: token.issued_at.map(timestamp),
+                expires_at: token.expires_at.map(timestamp),
+                refresh_successes: token.refresh_successes,
+                refresh_failures: token.refresh_failures,
+                last_register: token.last_register.map(timestamp),
+            }),
+            disk_space_low: disk.low,
+            disk_free_bytes: disk.free_bytes.unwrap_or(0),
+            crash_loop: Some(CrashLoopStatus {
+                process: crash_loop.process,
+                recent_restarts: crash_loop.recent_restarts,
+                subsystems: crash_loop.subsystems,
+            }),
+            clock_skew_ms: clock::skew_ms().unwrap_or(0),
+            clock_skew_exceeded: clock.exceeded,
+            errors: errors
+                .into_iter()
+                .map(|(code, stats)| ErrorCodeCount {
+                    code,
+                    count: stats.count,
+                    last_at: Some(timestamp(stats.last_at)),
+                    last_message: stats.last_message,
+                })
+                .collect(),
+            backlog: Some(BacklogStatus {
+                spooled_batches: backlog.spooled_batches as u64,
+                queued_batches: backlog.queued_batches as u64,
+                oldest_event_age_secs: backlog_age,
+                consecutive_failures: backlog.retry.consecutive_failures,
+                failing_since: backlog.retry.failing_since.map(timestamp),
+                backoff_remaining_ms: backlog
+                    .retry
+                    .backoff_until
+                    .map_or(0, |until| (until - Utc::now()).num_milliseconds().max(0) as u64),
+                retry_budget_exhausted: backlog.retry.budget_exhausted,
+            }),
         };
 
-        match self.send_request(request).await {
+        match self.send_request(request.clone()).await {
             Ok(_) => {
-                debug!(cpu = %cpu, memory = %memory, "Heartbeat 전송 완료");
+                debug!(cpu = %cpu, memory = %memory, "{}", msg::HEARTBEAT_SENT);
                 Ok(())
             }
             Err(status) if status.code() == Code::Unauthenticated => {
-                warn!("토큰 만료, 재발급 시도");
+                warn!(code = %ErrorCode::TokenExpired, "{}", msg::TOKEN_EXPIRED_RETRY);
 
                 {
                     let mut tm = self.token_manager.write().await;
@@ -91,7 +239,7 @@ impl HealthReporter {
                 }
 
                 self.send_request(request).await?;
-                debug!("재시도 후 Heartbeat 전송 완료");
+                debug!("{}", msg::HEARTBEAT_SENT_AFTER_RETRY);
                 Ok(())
             }
             Err(e) => Err(e.into()),
@@ -99,10 +247,32 @@ impl HealthReporter {
     }
 
     async fn send_request(&mut self, request: HeartbeatRequest) -> Result<(), tonic::Status> {
-        self.client.heartbeat(request).await?;
+        let response = self.client.heartbeat(request).await?;
+        clock::record(response.metadata());
         Ok(())
     }
 
+    fn emit_token(&self, token: &TokenLifecycle) {
+        if let Some(age) = token.age_secs() {
+            self.emit(MetricEvent::gauge(TOKEN_AGE_METRIC, age as f64));
+        }
+        if let Some(expires_in) = token.expires_in_secs() {
+            self.emit(MetricEvent::gauge(TOKEN_EXPIRES_IN_METRIC, expires_in as f64));
+        }
+        self.emit(MetricEvent::counter(TOKEN_REFRESH_METRIC, token.refresh_successes as f64).label("result", "success"));
+        self.emit(MetricEvent::counter(TOKEN_REFRESH_METRIC, token.refresh_failures as f64).label("result", "failure"));
+        if let Some(at) = token.last_register {
+            self.emit(MetricEvent::gauge(TOKEN_LAST_REGISTER_METRIC, at.timestamp() as f64));
+        }
+    }
+
+    /// 채널이 가득 차면 이번 측정값은 버림 (다음 주기에 다시 측정)
+    fn emit(&self, metric: MetricEvent) {
+        if self.metrics.try_send(metric).is_err() {
+            debug!("{}", msg::METRIC_DROPPED);
+        }
+    }
+
     fn calculate_memory_usage(&self) -> f64 {
         let total = self.system.total_memory();
         let used = self.system.used_memory();
@@ -114,3 +284,7 @@ impl HealthReporter {
         (used as f64 / total as f64) * 100.0
     }
 }
+
+fn timestamp(at: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp::from(SystemTime::from(at))
+}
@@ -0,0 +1,269 @@
+//! 무중단 업그레이드를 위한 재실행(re-exec)
+//!
+//! SIGUSR2 수신 시 정상 종료 절차로 파이프라인을 드레인한 뒤 Collector 오프셋과 스풀 목록을
+//! `state/handover.yaml` 에 기록하고 같은 경로의 (새) 바이너리를 exec,
+//! 새 프로세스는 기록된 오프셋부터 이어서 읽어 유실/중복 없이 수집을 재개.
+//! 드레인 제한 시간을 넘겨 Forwarder 를 중단하면 Collector 위치 대신 마지막으로 배치에 실은 라인을 기록
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::durability;
+use crate::ephemeral;
+use crate::messages as msg;
+use crate::proto::log::Log;
+use crate::spool::Spool;
+
+pub static HANDOVER_PATH: &str = "state/handover.yaml";
+
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// (라벨, 경로)
+type SourceKey = (String, String);
+/// 라인의 (file_id, 시작 위치)
+type LinePosition = (u64, u64);
+
+/// 수집 대상별로 배치에 실어 넘긴 마지막 라인, 수집 시작 위치로 초기화
+static BATCHED: LazyLock<Mutex<HashMap<SourceKey, LinePosition>>> = LazyLock::new(Default::default);
+
+fn batched() -> MutexGuard<'static, HashMap<SourceKey, LinePosition>> {
+    BATCHED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 재실행 전후로 넘겨주는 파이프라인 상태
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Handover {
+    pub created_at: DateTime<Utc>,
+    pub offsets: Vec<CollectorOffset>,
+    /// 재실행 시점에 남아 있던 스풀 파일 이름 (전송 순서)
+    pub spool: Vec<String>,
+}
+
+/// Collector 가 마지막으로 전달한 라인 직후의 파일 위치
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectorOffset {
+    pub label: String,
+    pub path: PathBuf,
+    pub file_id: u64,
+    pub position: u64,
+}
+
+impl Handover {
+    pub fn new(offsets: Vec<CollectorOffset>, spool: Option<&Spool>) -> Self {
+        let spool = spool
+            .and_then(|s| s.list().ok())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .collect();
+
+        Self {
+            created_at: Utc::now(),
+            offsets,
+            spool,
+        }
+    }
+
+    pub fn offset_for(&self, label: &str, path: &Path) -> Option<&CollectorOffset> {
+        self.offsets
+            .iter()
+            .find(|o| o.label == label && o.path == path)
+    }
+
+    /// 기록된 스풀 파일이 모두 남아 있는지 확인 (재전송 대상 누락 감지)
+    pub fn check_spool(&self, spool: &Spool) {
+        let present: Vec<String> = spool
+            .list()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .collect();
+
+        let missing = self.spool.iter().filter(|name| !present.contains(name)).count();
+
+        if missing > 0 {
+            warn!(missing = missing, "{}", msg::HANDOVER_SPOOL_MISSING);
+        } else if !self.spool.is_empty() {
+            info!(count = self.spool.len(), "{}", msg::HANDOVER_SPOOL_RESUMING);
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if ephemeral::is_enabled() {
+            bail!(msg::EPHEMERAL_NOT_PERSISTED);
+        }
+
+        let path = Path::new(HANDOVER_PATH);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+        }
+
+        let yaml = serde_yaml::to_string(self).context(msg::HANDOVER_SERIALIZE_FAILED)?;
+        let tmp = path.with_extension("tmp");
+
+        fs::write(&tmp, yaml).context(msg::HANDOVER_WRITE_FAILED)?;
+        durability::persist(&tmp, path).context(msg::HANDOVER_RENAME_FAILED)?;
+
+        Ok(())
+    }
+
+    /// 이전 프로세스가 남긴 핸드오버 로드 후 삭제 (한 번만 적용)
+    pub fn take() -> Option<Self> {
+        let path = Path::new(HANDOVER_PATH);
+        let content = fs::read_to_string(path).ok()?;
+        let _ = fs::remove_file(path);
+
+        match serde_yaml::from_str::<Handover>(&content) {
+            Ok(handover) => {
+                info!(
+                    collectors = handover.offsets.len(),
+                    created_at = %handover.created_at,
+                    "{}",
+                    msg::HANDOVER_LOADED
+                );
+                Some(handover)
+            }
+            Err(e) => {
+                warn!(error = %e, "{}", msg::HANDOVER_PARSE_FAILED);
+                None
+            }
+        }
+    }
+}
+
+/// 수집 시작 위치 등록, 이 실행에서 배치로 넘긴 라인이 없으면 이 위치부터 다시 읽음
+pub fn mark_start(label: &str, path: &Path, file_id: u64, position: u64) {
+    batched().insert((label.to_string(), path.to_string_lossy().into_owned()), (file_id, position));
+}
+
+/// 배치에 실은 수집 대상별 마지막 라인
+pub struct BatchedLines(Vec<(SourceKey, LinePosition)>);
+
+impl BatchedLines {
+    pub fn of(logs: &[Log]) -> Self {
+        let mut last: HashMap<(&str, &str), LinePosition> = HashMap::new();
+        for log in logs {
+            let entry = last.entry((log.label.as_str(), log.path.as_str())).or_insert((log.file_id, log.offset));
+            // 로테이션 뒤 새 파일의 라인이면 새 파일 기준
+            if entry.0 != log.file_id || entry.1 < log.offset {
+                *entry = (log.file_id, log.offset);
+            }
+        }
+
+        Self(
+            last.into_iter()
+                .map(|((label, path), line)| ((label.to_string(), path.to_string()), line))
+                .collect(),
+        )
+    }
+
+    /// 배치를 넘긴(Sink 채널, 보류 큐) 뒤 기록, 드레인 제한 시간을 넘겨 Forwarder 를 중단해도 여기까지는 보관됨
+    pub fn record(self) {
+        let mut batched = batched();
+        for (key, (file_id, offset)) in self.0 {
+            let entry = batched.entry(key).or_insert((file_id, offset));
+            if entry.0 != file_id || entry.1 < offset {
+                *entry = (file_id, offset);
+            }
+        }
+    }
+}
+
+/// Forwarder 를 중단해 버린 라인이 있을 때의 오프셋, Collector 위치 대신 마지막으로 배치에 실은 라인부터 다시 읽음
+///
+/// 그 라인은 한 번 더 보내지만 서버는 경로/위치/file_id 로 중복을 가릴 수 있음
+pub fn batched_offsets(offsets: Vec<CollectorOffset>) -> Vec<CollectorOffset> {
+    let batched = batched();
+
+    offsets
+        .into_iter()
+        .map(|offset| {
+            let key = (offset.label.clone(), offset.path.to_string_lossy().into_owned());
+            match batched.get(&key) {
+                Some(&(file_id, position)) if file_id != offset.file_id || position < offset.position => CollectorOffset {
+                    file_id,
+                    position,
+                    ..offset
+                },
+                _ => offset,
+            }
+        })
+        .collect()
+}
+
+/// 재실행 요청 (이후 종료 절차에서 핸드오버 기록)
+pub fn request() {
+    REQUESTED.store(true, Ordering::SeqCst);
+}
+
+pub fn is_requested() -> bool {
+    REQUESTED.load(Ordering::SeqCst)
+}
+
+/// SIGUSR2 수신 시 재실행 요청 후 `shutdown` 취소
+#[cfg(unix)]
+pub fn spawn_signal_handler(shutdown: CancellationToken) {
+    use tokio::signal::unix::{signal, SignalKind};
+    use tracing::error;
+
+    let mut sigusr2 = match signal(SignalKind::user_defined2()) {
+        Ok(s) => s,
+        Err(e) => {
+            error!(error = %e, "{}", msg::SIGUSR2_HANDLER_FAILED);
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            received = sigusr2.recv() => {
+                if received.is_some() {
+                    info!("{}", msg::SIGUSR2_RECEIVED);
+                    request();
+                    shutdown.cancel();
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_signal_handler(_shutdown: CancellationToken) {}
+
+/// 현재 실행 파일 경로
+pub fn current_exe() -> Result<PathBuf> {
+    let exe = std::env::current_exe().context(msg::CURRENT_EXE_FAILED)?;
+    // 업그레이드로 실행 파일이 교체되면 Linux 는 경로 뒤에 " (deleted)" 를 붙임
+    Ok(match exe.to_str().and_then(|s| s.strip_suffix(" (deleted)")) {
+        Some(stripped) => PathBuf::from(stripped),
+        None => exe,
+    })
+}
+
+/// 현재 실행 파일 경로의 바이너리를 같은 인자로 exec (성공 시 반환하지 않음)
+#[cfg(unix)]
+pub fn exec() -> Result<()> {
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+
+    let exe = current_exe()?;
+    info!(exe = %exe.display(), "{}", msg::RESTARTING_WITH_NEW_BINARY);
+
+    let error = Command::new(&exe).args(std::env::args_os().skip(1)).exec();
+    Err(error).context(msg::RESTART_FAILED)
+}
+
+#[cfg(not(unix))]
+pub fn exec() -> Result<()> {
+    anyhow::bail!(msg::RESTART_UNIX_ONLY)
+}
@@ -0,0 +1,243 @@
+//! HTTP/JSON 대체 전송 (`http_fallback`)
+//!
+//! gRPC/HTTP2 를 막는 네트워크에서도 전송할 수 있도록, gRPC 전송이 `after_failures` 번 연속 실패하면 (backoff 재시도 포함)
+//! 배치를 gzip 압축한 JSON 으로 `url` 에 POST. `retry_grpc_after` 초가 지나면 다시 gRPC 부터 시도.
+//! 모든 레인이 하나를 공유하므로 한 레인에서 전환되면 다른 레인도 같이 전환
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use chrono::DateTime;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use prost_types::Timestamp;
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::auth::token_manager::TokenManager;
+use crate::error_code::ErrorCode;
+use crate::governor;
+use crate::messages as msg;
+use crate::proto::log::{Log, LogBatch, MetricKind};
+use crate::settings::HttpFallbackSettings;
+use crate::streamer::TRACE_ID_HEADER;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct HttpFallback {
+    client: reqwest::Client,
+    settings: HttpFallbackSettings,
+    token_manager: Arc<RwLock<TokenManager>>,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    grpc_failures: u32,
+    /// 이 시각까지 gRPC 대신 HTTP 로 전송
+    engaged_until: Option<Instant>,
+}
+
+impl HttpFallback {
+    pub fn new(settings: HttpFallbackSettings, token_manager: Arc<RwLock<TokenManager>>) -> Result<Self> {
+        if settings.url.trim().is_empty() {
+            bail!(msg::HTTP_FALLBACK_URL_REQUIRED);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context(msg::HTTP_FALLBACK_CLIENT_FAILED)?;
+
+        Ok(Self {
+            client,
+            settings,
+            token_manager,
+            state: Mutex::new(State::default()),
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// HTTP 로 전송할 차례인지, 전환 기간이 끝나면 gRPC 를 다시 시도
+    pub fn is_engaged(&self) -> bool {
+        let mut state = self.state();
+        match state.engaged_until {
+            Some(until) if Instant::now() < until => true,
+            Some(_) => {
+                info!("{}", msg::HTTP_FALLBACK_RETRYING_GRPC);
+                state.engaged_until = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn record_grpc_success(&self) {
+        self.state().grpc_failures = 0;
+    }
+
+    /// gRPC 실패 기록, 연속 실패가 `after_failures` 에 닿으면 전환하고 true
+    pub fn record_grpc_failure(&self) -> bool {
+        let mut state = self.state();
+        state.grpc_failures += 1;
+        if state.grpc_failures < self.settings.after_failures.max(1) {
+            return false;
+        }
+
+        warn!(
+            failures = state.grpc_failures,
+            url = %self.settings.url,
+            retry_grpc_after = self.settings.retry_grpc_after,
+            "{}",
+            msg::HTTP_FALLBACK_ENGAGED
+        );
+        state.grpc_failures = 0;
+        state.engaged_until = Some(Instant::now() + Duration::from_secs(self.settings.retry_grpc_after));
+        true
+    }
+
+    /// 배치를 JSON 으로 POST, 인증 만료 시 토큰 갱신 후 한 번 더 시도. 반환값은 압축 후 본문 크기
+    pub async fn send(&self, batch: &LogBatch) -> Result<usize> {
+        let body = encode(batch)?;
+        let len = body.len();
+
+        let mut status = self.post(batch, body.clone()).await?;
+        if status == StatusCode::UNAUTHORIZED {
+            warn!(batch_id = %batch.batch_id, code = %ErrorCode::TokenExpired, "{}", msg::TOKEN_EXPIRED_RETRY);
+            self.token_manager.write().await.refresh().await?;
+            status = self.post(batch, body).await?;
+        }
+
+        if !status.is_success() {
+            bail!("{}: {}", msg::HTTP_FALLBACK_REJECTED, status);
+        }
+        Ok(len)
+    }
+
+    async fn post(&self, batch: &LogBatch, body: Vec<u8>) -> Result<StatusCode> {
+        let token = self.token_manager.read().await.get_shared_token();
+        let token = token.read().map(|t| t.clone()).unwrap_or_default();
+
+        let response = self
+            .client
+            .post(&self.settings.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_ENCODING, "gzip")
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(TRACE_ID_HEADER, batch.trace_id.as_str())
+            .body(body)
+            .send()
+            .await
+            .context(msg::HTTP_FALLBACK_SEND_FAILED)?;
+
+        Ok(response.status())
+    }
+}
+
+#[derive(Serialize)]
+struct JsonBatch<'a> {
+    batch_id: &'a str,
+    trace_id: &'a str,
+    send_at: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    tags: &'a HashMap<String, String>,
+    logs: Vec<JsonLog<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    metrics: Vec<JsonMetric<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonLog<'a> {
+    label: &'a str,
+    line: Cow<'a, str>,
+    timestamp: Option<String>,
+    path: &'a str,
+    offset: u64,
+    file_id: u64,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    fields: &'a HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct JsonMetric<'a> {
+    name: &'a str,
+    value: f64,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    labels: &'a HashMap<String, String>,
+    timestamp: Option<String>,
+}
+
+/// `LogBatch` 를 proto 필드 이름 그대로의 JSON 으로 만들어 gzip 압축 (`kind: http` 추가 Sink 도 사용)
+pub(crate) fn encode(batch: &LogBatch) -> Result<Vec<u8>> {
+    let json = JsonBatch {
+        batch_id: &batch.batch_id,
+        trace_id: &batch.trace_id,
+        send_at: rfc3339(batch.send_at.as_ref()),
+        tags: &batch.tags,
+        logs: batch.logs.iter().map(json_log).collect(),
+        metrics: batch
+            .metrics
+            .iter()
+            .map(|metric| JsonMetric {
+                name: &metric.name,
+                value: metric.value,
+                kind: match metric.kind() {
+                    MetricKind::Gauge => "gauge",
+                    MetricKind::Counter => "counter",
+                    MetricKind::Unspecified => "unspecified",
+                },
+                labels: &metric.labels,
+                timestamp: rfc3339(metric.timestamp.as_ref()),
+            })
+            .collect(),
+    };
+
+    // 호스트 자원 압박 중에는 가장 빠른 단계로 압축
+    let level = if governor::is_throttled() {
+        Compression::fast()
+    } else {
+        Compression::default()
+    };
+    let mut encoder = GzEncoder::new(Vec::new(), level);
+    serde_json::to_writer(&mut encoder, &json)?;
+    Ok(encoder.finish()?)
+}
+
+/// 로그 한 건당 JSON 한 줄 (`kind: file` 추가 Sink)
+pub(crate) fn encode_lines(batch: &LogBatch) -> Result<Vec<u8>> {
+    let mut lines = Vec::new();
+    for log in &batch.logs {
+        serde_json::to_writer(&mut lines, &json_log(log))?;
+        lines.push(b'\n');
+    }
+    Ok(lines)
+}
+
+fn json_log(log: &Log) -> JsonLog<'_> {
+    JsonLog {
+        label: &log.label,
+        line: String::from_utf8_lossy(&log.line),
+        timestamp: rfc3339(log.timestamp.as_ref()),
+        path: &log.path,
+        offset: log.offset,
+        file_id: log.file_id,
+        fields: &log.fields,
+    }
+}
+
+fn rfc3339(timestamp: Option<&Timestamp>) -> Option<String> {
+    timestamp
+        .and_then(|t| DateTime::from_timestamp(t.seconds, t.nanos.max(0) as u32))
+        .map(|t| t.to_rfc3339())
+}
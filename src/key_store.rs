@@ -0,0 +1,154 @@
+//! 설정 파일의 project_key 보관 (`project_key_storage`)
+//!
+//! project_key 는 사실상 수명이 긴 자격 증명이라 `config/agent.yaml` 에 평문으로 남기지 않는 방법을 제공.
+//! `encrypted` 는 호스트 고유 식별자(Linux machine-id, Windows MachineGuid, macOS IOPlatformUUID)에서 만든 키로
+//! 암호화해 저장하므로 다른 호스트로 복사한 설정 파일로는 풀 수 없음.
+//! `env` 는 파일에 쓰지 않고 실행할 때마다 `PROJECT_KEY` 환경 변수에서 읽음
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::messages as msg;
+use crate::settings::ProjectKeyStorage;
+
+/// 암호화한 값의 접두어, 형식이 바뀌면 버전을 올림
+static ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+static ENV_PROJECT_KEY: &str = "PROJECT_KEY";
+
+/// 키 유도에 쓰는 고정 문맥 (같은 machine-id 를 쓰는 다른 용도와 키가 겹치지 않도록)
+static KEY_CONTEXT: &[u8] = b"rlog-agent project_key v1";
+
+/// 호스트 고유 식별자 파일 (systemd, dbus)
+#[cfg(not(any(windows, target_os = "macos")))]
+static MACHINE_ID_PATHS: &[&str] = &["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+const NONCE_LEN: usize = 12;
+
+/// 설정 파일에 쓸 값
+pub fn seal(project_key: &str, storage: ProjectKeyStorage) -> Result<String> {
+    match storage {
+        ProjectKeyStorage::Plain => Ok(project_key.to_string()),
+        ProjectKeyStorage::Encrypted if project_key.is_empty() => Ok(String::new()),
+        ProjectKeyStorage::Encrypted => encrypt(project_key),
+        ProjectKeyStorage::Env => Ok(String::new()),
+    }
+}
+
+/// 설정 파일의 값에서 실제 project_key
+pub fn open(stored: &str, storage: ProjectKeyStorage) -> Result<String> {
+    if let Some(encoded) = stored.strip_prefix(ENCRYPTED_PREFIX) {
+        return decrypt(encoded);
+    }
+
+    if storage == ProjectKeyStorage::Env && stored.is_empty() {
+        return std::env::var(ENV_PROJECT_KEY).map_err(|_| anyhow!("{}: {}", msg::ENV_MISSING, ENV_PROJECT_KEY));
+    }
+
+    Ok(stored.to_string())
+}
+
+/// 파일의 값이 `storage` 와 맞지 않아 다시 써야 하는지 (평문을 암호화로 바꾸는 등)
+pub fn needs_reseal(stored: &str, storage: ProjectKeyStorage) -> bool {
+    let encrypted = stored.starts_with(ENCRYPTED_PREFIX);
+
+    match storage {
+        ProjectKeyStorage::Plain => encrypted,
+        ProjectKeyStorage::Encrypted => !stored.is_empty() && !encrypted,
+        ProjectKeyStorage::Env => !stored.is_empty(),
+    }
+}
+
+fn encrypt(project_key: &str) -> Result<String> {
+    let cipher = cipher()?;
+    let nonce_bytes = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce_bytes, project_key.as_bytes())
+        .map_err(|_| anyhow!(msg::PROJECT_KEY_ENCRYPT_FAILED))?;
+
+    let mut sealed = nonce_bytes.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(sealed)))
+}
+
+fn decrypt(encoded: &str) -> Result<String> {
+    let sealed = STANDARD.decode(encoded).context(msg::PROJECT_KEY_DECRYPT_FAILED)?;
+    if sealed.len() <= NONCE_LEN {
+        bail!(msg::PROJECT_KEY_DECRYPT_FAILED);
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    // machine-id 가 다르면(다른 호스트로 복사한 설정) 인증 태그가 맞지 않음
+    let plaintext = cipher()?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!(msg::PROJECT_KEY_DECRYPT_FAILED))?;
+
+    String::from_utf8(plaintext).context(msg::PROJECT_KEY_DECRYPT_FAILED)
+}
+
+/// machine-id 로 유도한 키의 암호기
+fn cipher() -> Result<ChaCha20Poly1305> {
+    let machine_id = machine_id()?;
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(machine_id.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(KEY_CONTEXT);
+    let key = mac.finalize().into_bytes();
+
+    ChaCha20Poly1305::new_from_slice(&key).map_err(|_| anyhow!(msg::PROJECT_KEY_ENCRYPT_FAILED))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn machine_id() -> Result<String> {
+    MACHINE_ID_PATHS
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+        .context(msg::MACHINE_ID_UNAVAILABLE)
+}
+
+/// 레지스트리 `HKLM\SOFTWARE\Microsoft\Cryptography` 의 MachineGuid (설치 시 생성)
+#[cfg(windows)]
+fn machine_id() -> Result<String> {
+    let output = command_output("reg", &["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])?;
+
+    // `    MachineGuid    REG_SZ    xxxxxxxx-xxxx-...`
+    output
+        .lines()
+        .find(|line| line.trim_start().starts_with("MachineGuid"))
+        .and_then(|line| line.split_whitespace().nth(2))
+        .map(str::to_string)
+        .context(msg::MACHINE_ID_UNAVAILABLE)
+}
+
+/// IOKit 플랫폼 장치의 IOPlatformUUID (하드웨어 UUID)
+#[cfg(target_os = "macos")]
+fn machine_id() -> Result<String> {
+    let output = command_output("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])?;
+
+    // `  "IOPlatformUUID" = "XXXXXXXX-XXXX-..."`
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("\"IOPlatformUUID\" = "))
+        .map(|id| id.trim_matches('"').to_string())
+        .filter(|id| !id.is_empty())
+        .context(msg::MACHINE_ID_UNAVAILABLE)
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn command_output(program: &str, args: &[&str]) -> Result<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .context(msg::MACHINE_ID_UNAVAILABLE)?;
+    if !output.status.success() {
+        bail!(msg::MACHINE_ID_UNAVAILABLE);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,82 @@
+//! rlog-agent: 분산 시스템을 위한 경량 로그 수집 에이전트
+//!
+//! 파일을 감시하는 [`collector::Collector`], 이벤트를 배치로 묶는 [`forwarder::Forwarder`],
+//! 서버로 전송하는 [`streamer::Streamer`] 를 [`Agent`] 가 조립하여 실행
+
+pub mod admin;
+pub mod agent;
+pub mod anomaly;
+pub mod anonymize;
+pub mod auth;
+pub mod bench;
+pub mod batch_id;
+pub mod batch_pool;
+pub mod batch_codec;
+pub mod capabilities;
+pub mod chaos;
+pub mod clock;
+pub mod collector;
+pub mod collector_manager;
+pub mod control;
+pub mod crash;
+pub mod crash_loop;
+pub mod dedup;
+pub mod disk_guard;
+pub mod durability;
+pub mod doctor;
+pub mod dry_run;
+pub mod egress;
+pub mod endpoint;
+pub mod ephemeral;
+pub mod error_code;
+pub mod forwarder;
+pub mod gelf;
+pub mod governor;
+pub mod health;
+pub mod hot_restart;
+pub mod http_fallback;
+pub mod key_store;
+pub mod line_join;
+pub mod loadgen;
+pub mod log_level;
+pub mod log_metrics;
+pub mod log_throttle;
+pub mod memory;
+pub mod messages;
+pub mod models;
+pub mod multiline;
+pub mod oslog;
+pub mod oversize;
+pub mod parser;
+pub mod proto;
+pub mod redis_stream;
+pub mod replay;
+pub mod retry;
+pub mod routing;
+pub mod sampling;
+pub mod schedule;
+pub mod self_log;
+pub mod settings;
+pub mod sink_health;
+pub mod sinks;
+pub mod snmp_trap;
+pub mod spiffe;
+pub mod spool;
+pub mod sql_poll;
+pub mod state_meta;
+pub mod state_store;
+pub mod status;
+pub mod streamer;
+pub mod supervisor;
+pub mod systemd;
+pub mod tap;
+pub mod telemetry;
+#[cfg(feature = "test-server")]
+pub mod test_server;
+pub mod updater;
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+pub mod uring;
+pub mod watch_health;
+pub mod win_path;
+
+pub use agent::{Agent, AgentBuilder, Sink};
@@ -0,0 +1,119 @@
+//! 이어쓰기 표시로 나뉜 줄 합치기 (`join_on`)
+//!
+//! 형식별 여러 줄 레코드([`crate::multiline`]) 앞 단계에서 도구가 감싼 물리적인 줄을 논리적인 한 줄로 합침.
+//! `trailing_backslash` 는 `\` 로 끝난 줄에 다음 줄을 이어 붙이고(`\` 는 제거),
+//! `indent` 는 공백/탭으로 시작하는 줄을 앞 줄에 공백 하나로 이어 붙임
+
+use std::collections::VecDeque;
+
+use bytes::{Bytes, BytesMut};
+use tokio::time::Instant;
+
+use crate::multiline::{IDLE_TIMEOUT, Record};
+use crate::settings::JoinOn;
+
+/// 이어지는 줄이 끝없이 오는 파일에서 줄이 무한히 커지지 않도록 하는 상한
+const MAX_JOINED_BYTES: usize = 1024 * 1024;
+
+struct Pending {
+    content: BytesMut,
+    offset: u64,
+    updated: Instant,
+}
+
+pub struct LineJoin {
+    mode: JoinOn,
+    pending: Option<Pending>,
+    /// 합쳐져 [`LineJoin::pop`] 으로 꺼내기를 기다리는 줄
+    done: VecDeque<Record>,
+}
+
+impl LineJoin {
+    pub fn new(mode: JoinOn) -> Self {
+        Self {
+            mode,
+            pending: None,
+            done: VecDeque::new(),
+        }
+    }
+
+    /// 한 줄 추가, 합쳐진 줄은 [`LineJoin::pop`] 으로 꺼냄
+    ///
+    /// `line` 은 개행과 끝 공백을 뺀 UTF-8 라인
+    pub fn push(&mut self, line: Bytes, offset: u64) {
+        let full = self
+            .pending
+            .as_ref()
+            .is_some_and(|p| p.content.len() + line.len() >= MAX_JOINED_BYTES);
+        if full && let Some(record) = self.take() {
+            self.done.push_back(record);
+        }
+
+        match self.mode {
+            JoinOn::TrailingBackslash => self.push_backslash(line, offset),
+            JoinOn::Indent => self.push_indent(line, offset),
+        }
+    }
+
+    /// 앞 줄이 `\` 로 끝났으면 이어 붙이고, 이 줄도 `\` 로 끝나면 다음 줄을 기다림
+    fn push_backslash(&mut self, line: Bytes, offset: u64) {
+        // 대부분의 줄은 이어지지 않으므로 복사 없이 그대로
+        if self.pending.is_none() && !line.ends_with(b"\\") {
+            self.done.push_back(Record { content: line, offset });
+            return;
+        }
+
+        let pending = self.pending.get_or_insert_with(|| Pending {
+            content: BytesMut::new(),
+            offset,
+            updated: Instant::now(),
+        });
+        pending.content.extend_from_slice(&line);
+        pending.updated = Instant::now();
+
+        if pending.content.ends_with(b"\\") {
+            pending.content.truncate(pending.content.len() - 1);
+        } else if let Some(record) = self.take() {
+            self.done.push_back(record);
+        }
+    }
+
+    /// 들여쓴 줄은 앞 줄에 이어 붙이고, 아니면 앞 줄을 완성하고 다음 들여쓴 줄을 기다림
+    fn push_indent(&mut self, line: Bytes, offset: u64) {
+        let indented = line.first().is_some_and(|b| *b == b' ' || *b == b'\t');
+
+        if indented && let Some(pending) = self.pending.as_mut() {
+            pending.content.extend_from_slice(b" ");
+            pending.content.extend_from_slice(line.trim_ascii_start());
+            pending.updated = Instant::now();
+            return;
+        }
+
+        if let Some(record) = self.take() {
+            self.done.push_back(record);
+        }
+        self.pending = Some(Pending {
+            content: BytesMut::from(&line[..]),
+            offset,
+            updated: Instant::now(),
+        });
+    }
+
+    /// 합쳐진 줄을 순서대로 꺼냄
+    pub fn pop(&mut self) -> Option<Record> {
+        self.done.pop_front()
+    }
+
+    /// 합치고 있던 줄을 완성으로 보고 꺼냄 (대기 시간 초과, 종료, 파일 교체 시)
+    pub fn take(&mut self) -> Option<Record> {
+        self.pending.take().map(|pending| Record {
+            content: pending.content.freeze(),
+            offset: pending.offset,
+        })
+    }
+
+    /// 합치고 있는 줄을 완성으로 볼 시각, 없으면 None
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|p| p.updated + IDLE_TIMEOUT)
+    }
+}
@@ -0,0 +1,267 @@
+//! 합성 부하 생성 (`loadgen` 하위 명령)
+//!
+//! 정해진 속도로 합성 라인을 임시 파일에 쓰고 실제 Collector → Forwarder → Streamer 로 서버에 전송.
+//! 운영 배포 전 서버 용량 산정과 에이전트 처리 한계 확인용. 주기적으로 생성량과 서버가 받은 양(egress)을 출력하며,
+//! 전송이 생성을 따라가지 못하면 그 차이가 임시 파일과 메모리/디스크 큐에 쌓임
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::Utc;
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::agent::Agent;
+use crate::egress;
+use crate::messages as msg;
+use crate::settings::{Settings, SourceSettings};
+use crate::status;
+
+/// Collector 가 임시 파일을 열 때까지 기다리는 최대 시간
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 라인을 쓰는 간격, 간격마다 밀린 만큼 한 번에 써서 평균 속도를 맞춤
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 진행 상황 출력 간격
+const REPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 임시 파일을 새로 시작하는 크기, Collector 가 끝까지 읽은 뒤에만 바꿈
+const ROTATE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 압축률이 실제 로그와 비슷하도록 라인 본문을 채우는 단어
+static WORDS: [&str; 24] = [
+    "GET", "POST", "/api/v1/orders", "/api/v1/users", "/healthz", "status=200", "status=404", "status=500",
+    "latency_ms=12", "latency_ms=87", "user_id=1042", "user_id=77", "region=ap-northeast-2", "cache=hit",
+    "cache=miss", "retry=0", "retry=1", "request", "completed", "started", "upstream", "timeout", "db.query",
+    "payload",
+];
+
+/// 초당 생성 라인 수, `50k/s`, `1.5m/s`, `2000` 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadRate(pub u64);
+
+impl FromStr for LoadRate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let value = trimmed.strip_suffix("/s").unwrap_or(trimmed);
+        let (number, unit) = match value.char_indices().last() {
+            Some((i, 'k' | 'K')) => (&value[..i], 1_000.0),
+            Some((i, 'm' | 'M')) => (&value[..i], 1_000_000.0),
+            _ => (value, 1.0),
+        };
+
+        match number.parse::<f64>() {
+            Ok(rate) if rate.is_finite() && rate * unit >= 1.0 => Ok(Self((rate * unit) as u64)),
+            _ => Err(anyhow!("{}: {}", msg::LOADGEN_RATE_INVALID, s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadgenOptions {
+    pub rate: LoadRate,
+    /// 개행 제외 라인 길이
+    pub line_bytes: usize,
+    /// 생성 시간, None 이면 중지 신호까지
+    pub duration: Option<Duration>,
+    /// 합성 이벤트의 수집 대상 라벨
+    pub label: String,
+}
+
+#[derive(Debug)]
+pub struct LoadgenReport {
+    pub generated: u64,
+    /// 서버가 받은 이벤트 (필터/샘플링 이후)
+    pub sent: u64,
+    pub elapsed: Duration,
+}
+
+impl LoadgenReport {
+    pub fn print(&self) {
+        let secs = self.elapsed.as_secs_f64().max(f64::EPSILON);
+
+        println!("[loadgen] {}: {}", msg::LOADGEN_GENERATED, self.generated);
+        println!("[loadgen] {}: {}", msg::LOADGEN_SENT, self.sent);
+        println!("[loadgen] {}: {:.3}s", msg::LOADGEN_ELAPSED, secs);
+        println!(
+            "[loadgen] {}: {:.0} lines/s ({:.0} lines/s {})",
+            msg::LOADGEN_THROUGHPUT,
+            self.sent as f64 / secs,
+            self.generated as f64 / secs,
+            msg::LOADGEN_GENERATED
+        );
+    }
+}
+
+/// 설정의 서버로 합성 부하를 보냄, 설정의 수집 대상 대신 합성 수집 대상 하나만 실행
+pub async fn run(settings: Settings, options: LoadgenOptions, shutdown: CancellationToken) -> Result<LoadgenReport> {
+    let dir = std::env::temp_dir().join(format!("rlog-agent-loadgen-{}", std::process::id()));
+    fs::create_dir_all(&dir).await.context(msg::LOADGEN_SETUP_FAILED)?;
+
+    let result = run_in(&dir, settings, options, shutdown).await;
+    let _ = fs::remove_dir_all(&dir).await;
+    result
+}
+
+async fn run_in(dir: &Path, settings: Settings, options: LoadgenOptions, shutdown: CancellationToken) -> Result<LoadgenReport> {
+    let path = dir.join("loadgen.log");
+    File::create(&path).await.context(msg::LOADGEN_SETUP_FAILED)?;
+
+    let settings = Settings {
+        sources: vec![SourceSettings::new(options.label.clone(), path.to_string_lossy())],
+        ..settings
+    };
+    let agent = Agent::builder().settings(settings).build()?;
+    let agent_shutdown = shutdown.child_token();
+    let agent_handle = tokio::spawn(agent.run(agent_shutdown.clone()));
+
+    // Collector 가 빈 파일의 끝을 기록한 뒤 써야 모든 라인이 수집됨
+    let deadline = Instant::now() + READY_TIMEOUT;
+    while position(&options.label).is_none() {
+        if agent_handle.is_finished() || Instant::now() > deadline {
+            agent_shutdown.cancel();
+            agent_handle.await??;
+            bail!(msg::LOADGEN_SETUP_FAILED);
+        }
+        time::sleep(Duration::from_millis(10)).await;
+    }
+
+    info!(rate = options.rate.0, line_bytes = options.line_bytes, label = %options.label, "{}", msg::LOADGEN_STARTED);
+    let started = Instant::now();
+    let generated = generate(&path, &options, &agent_handle, &shutdown).await?;
+
+    // 남은 배치는 드레인 제한 시간(`drain_timeout`)까지 보내고 못 보낸 배치는 디스크 큐로
+    agent_shutdown.cancel();
+    agent_handle.await??;
+
+    Ok(LoadgenReport {
+        generated,
+        sent: sent(&options.label),
+        elapsed: started.elapsed(),
+    })
+}
+
+/// 중지 신호, 생성 시간 종료, 에이전트 종료 중 먼저 오는 때까지 라인 생성, 생성한 라인 수 반환
+async fn generate(
+    path: &Path,
+    options: &LoadgenOptions,
+    agent: &JoinHandle<Result<()>>,
+    shutdown: &CancellationToken,
+) -> Result<u64> {
+    let started = Instant::now();
+    let deadline = options.duration.map(|duration| started + duration);
+    let mut tick = time::interval(TICK_INTERVAL);
+    let mut report = time::interval_at(started + REPORT_INTERVAL, REPORT_INTERVAL);
+
+    let mut writer = open_append(path).await?;
+    let mut file_bytes = 0u64;
+    let mut line = Vec::with_capacity(options.line_bytes + 1);
+    let mut rng = u64::from(std::process::id()) | 1;
+
+    let mut generated = 0u64;
+    // 직전 출력 시점의 생성/전송 누적값
+    let mut last = (started, 0u64, 0u64);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tick.tick() => {
+                if agent.is_finished() || deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    break;
+                }
+
+                let due = (started.elapsed().as_secs_f64() * options.rate.0 as f64) as u64;
+                while generated < due {
+                    fill_line(&mut line, generated, options.line_bytes, &mut rng);
+                    writer.write_all(&line).await.context(msg::LOADGEN_WRITE_FAILED)?;
+                    file_bytes += line.len() as u64;
+                    generated += 1;
+                }
+                writer.flush().await.context(msg::LOADGEN_WRITE_FAILED)?;
+
+                if file_bytes >= ROTATE_BYTES && position(&options.label) >= Some(file_bytes) {
+                    writer = rotate(path).await?;
+                    file_bytes = 0;
+                }
+            }
+            _ = report.tick() => {
+                let now = Instant::now();
+                let sent = sent(&options.label);
+                let secs = now.duration_since(last.0).as_secs_f64().max(f64::EPSILON);
+                println!(
+                    "[loadgen] generated={} ({:.0}/s) sent={} ({:.0}/s) backlog={}",
+                    generated,
+                    (generated - last.1) as f64 / secs,
+                    sent,
+                    sent.saturating_sub(last.2) as f64 / secs,
+                    generated.saturating_sub(sent)
+                );
+                last = (now, generated, sent);
+            }
+        }
+    }
+
+    writer.flush().await.context(msg::LOADGEN_WRITE_FAILED)?;
+    Ok(generated)
+}
+
+/// 순번과 흔한 로그 단어로 `line_bytes` 를 채운 라인 (개행 포함)
+fn fill_line(line: &mut Vec<u8>, seq: u64, line_bytes: usize, rng: &mut u64) {
+    line.clear();
+    let _ = write!(line, "{} INFO loadgen seq={}", Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"), seq);
+
+    while line.len() < line_bytes {
+        // xorshift64
+        *rng ^= *rng << 13;
+        *rng ^= *rng >> 7;
+        *rng ^= *rng << 17;
+        line.push(b' ');
+        line.extend_from_slice(WORDS[(*rng % WORDS.len() as u64) as usize].as_bytes());
+    }
+
+    line.truncate(line_bytes);
+    line.push(b'\n');
+}
+
+/// 다 읽은 임시 파일을 옮기고 새 파일 시작 (Collector 는 로테이션으로 처리)
+async fn rotate(path: &Path) -> Result<BufWriter<File>> {
+    let mut rotated = PathBuf::from(path);
+    rotated.set_extension("log.1");
+    fs::rename(path, &rotated).await.context(msg::LOADGEN_WRITE_FAILED)?;
+    File::create(path).await.context(msg::LOADGEN_WRITE_FAILED)?;
+    open_append(path).await
+}
+
+async fn open_append(path: &Path) -> Result<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .append(true)
+        .open(path)
+        .await
+        .context(msg::LOADGEN_SETUP_FAILED)?;
+    Ok(BufWriter::with_capacity(256 * 1024, file))
+}
+
+/// 합성 수집 대상의 읽은 위치, Collector 시작 전이면 None
+fn position(label: &str) -> Option<u64> {
+    status::registry()
+        .snapshot()
+        .sources
+        .iter()
+        .find(|source| source.label == label)
+        .map(|source| source.position)
+}
+
+/// 서버가 받은 합성 이벤트 수
+fn sent(label: &str) -> u64 {
+    egress::snapshot().get(label).map_or(0, |egress| egress.events)
+}
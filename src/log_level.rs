@@ -0,0 +1,147 @@
+//! 실행 중 에이전트 자체 로그 필터 변경 (관리 API `log_level`, 제어 채널 `SetLogLevel`, 시그널)
+//!
+//! 시그널 전환은 SIGUSR1 을 사용, SIGUSR2 는 Hot Restart 재실행 신호라 쓰지 않음
+
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{anyhow, Context, Result};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::messages as msg;
+use crate::settings::LogFormat;
+
+static DEFAULT_FILTER: &str = "info";
+static DEBUG_FILTER: &str = "debug";
+
+static CONTROLLER: OnceLock<LogLevelController> = OnceLock::new();
+
+/// 실행 중 tracing 필터를 교체하는 컨트롤러
+pub struct LogLevelController {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// 시작 시 필터 (RUST_LOG 또는 기본값)
+    base: String,
+    current: Mutex<String>,
+}
+
+/// tracing 초기화, 이후 `controller()` 로 필터 변경 가능
+///
+/// OTLP 내보내기를 사용하려면 Tokio 런타임 컨텍스트 안에서 호출
+pub fn init(format: LogFormat) {
+    let base = std::env::var(EnvFilter::DEFAULT_ENV).unwrap_or_else(|_| DEFAULT_FILTER.to_string());
+    let filter = EnvFilter::try_new(&base).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let (text, json) = match format {
+        LogFormat::Text => (Some(fmt::layer()), None),
+        // 이벤트 필드를 최상위로 펼쳐 source, batch_id 등으로 바로 조회 가능하게 함
+        LogFormat::Json => (None, Some(fmt::layer().json().flatten_event(true))),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text)
+        .with(json)
+        .with(crate::log_throttle::layer())
+        .with(crate::crash::recent_errors_layer())
+        .with(crate::error_code::layer())
+        .with(crate::self_log::layer())
+        .with(crate::telemetry::layer())
+        .init();
+
+    let _ = CONTROLLER.set(LogLevelController {
+        handle,
+        current: Mutex::new(base.clone()),
+        base,
+    });
+}
+
+pub fn controller() -> Option<&'static LogLevelController> {
+    CONTROLLER.get()
+}
+
+impl LogLevelController {
+    /// 필터 지시자 교체 (예: "debug", "info,rlog_agent=trace")
+    pub fn set(&self, directives: &str) -> Result<()> {
+        let filter = EnvFilter::try_new(directives)
+            .with_context(|| format!("{}: {}", msg::INVALID_LOG_FILTER, directives))?;
+
+        self.handle
+            .reload(filter)
+            .map_err(|e| anyhow!("{}: {}", msg::LOG_FILTER_RELOAD_FAILED, e))?;
+
+        *self.current.lock().map_err(|_| anyhow!(msg::LOG_FILTER_LOCK_FAILED))? =
+            directives.to_string();
+
+        info!(filter = %directives, "{}", msg::LOG_LEVEL_CHANGED);
+        Ok(())
+    }
+
+    /// debug 와 시작 시 필터 사이 전환, 전환 후 debug 여부 반환
+    ///
+    /// 시작 시 필터가 이미 debug 면 바꿀 필터가 없으므로 그대로 두고 알림
+    pub fn toggle_debug(&self) -> Result<bool> {
+        if self.base == DEBUG_FILTER && self.current() == DEBUG_FILTER {
+            info!("{}", msg::LOG_LEVEL_ALREADY_DEBUG);
+            return Ok(true);
+        }
+
+        let enable_debug = self.current() != DEBUG_FILTER;
+        let next = if enable_debug { DEBUG_FILTER } else { self.base.as_str() };
+
+        self.set(next)?;
+        Ok(enable_debug)
+    }
+
+    /// 시작 시 필터
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn current(&self) -> String {
+        self.current
+            .lock()
+            .map(|c| c.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// SIGUSR1 수신 시 debug 로그 전환
+#[cfg(unix)]
+pub fn spawn_signal_toggle(shutdown: CancellationToken) {
+    use tokio::signal::unix::{signal, SignalKind};
+    use tracing::error;
+
+    let mut sigusr1 = match signal(SignalKind::user_defined1()) {
+        Ok(s) => s,
+        Err(e) => {
+            error!(error = %e, "{}", msg::SIGUSR1_HANDLER_FAILED);
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                received = sigusr1.recv() => {
+                    if received.is_none() {
+                        break;
+                    }
+
+                    if let Some(controller) = controller()
+                        && let Err(e) = controller.toggle_debug()
+                    {
+                        error!(error = %e, "{}", msg::LOG_LEVEL_TOGGLE_FAILED);
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+pub fn spawn_signal_toggle(_shutdown: CancellationToken) {}
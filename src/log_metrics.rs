@@ -0,0 +1,67 @@
+//! 로그 기반 메트릭
+//!
+//! 규칙에 맞는 라인 수를 카운터로 집계하고, 값이 바뀐 카운터만 배치마다 [`MetricEvent`] 로 내보냄.
+//! 여러 Forwarder 레인이 같은 카운터를 공유하므로 값은 원자적으로 갱신
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::models::{LogEvent, MetricEvent};
+use crate::settings::LogMetricRule;
+
+pub struct LogMetrics {
+    counters: Vec<Counter>,
+}
+
+struct Counter {
+    rule: LogMetricRule,
+    count: AtomicU64,
+    changed: AtomicBool,
+}
+
+impl LogMetrics {
+    pub fn new(rules: Vec<LogMetricRule>) -> Self {
+        let counters = rules
+            .into_iter()
+            .map(|rule| Counter {
+                rule,
+                count: AtomicU64::new(0),
+                changed: AtomicBool::new(false),
+            })
+            .collect();
+
+        Self { counters }
+    }
+
+    pub fn observe(&self, event: &LogEvent) {
+        for counter in &self.counters {
+            if counter.rule.matches(event) {
+                counter.count.fetch_add(1, Ordering::Relaxed);
+                counter.changed.store(true, Ordering::Release);
+            }
+        }
+    }
+
+    /// 마지막 호출 이후 값이 바뀐 카운터 (누적값), 여러 레인 중 먼저 호출한 쪽이 내보냄
+    pub fn collect(&self) -> Vec<MetricEvent> {
+        self.counters
+            .iter()
+            .filter(|counter| counter.changed.swap(false, Ordering::AcqRel))
+            .map(|counter| {
+                let count = counter.count.load(Ordering::Relaxed);
+
+                let metric = MetricEvent::counter(&counter.rule.name, count as f64);
+                match &counter.rule.source {
+                    Some(source) => metric.label("source", source),
+                    None => metric,
+                }
+            })
+            .collect()
+    }
+}
+
+impl LogMetricRule {
+    fn matches(&self, event: &LogEvent) -> bool {
+        self.source.as_ref().is_none_or(|source| *source == event.label)
+            && event.line().contains(&self.contains)
+    }
+}
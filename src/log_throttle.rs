@@ -0,0 +1,152 @@
+//! 반복 경고/오류 로그 억제
+//!
+//! 서버 장애 중에는 배치마다 같은 오류가 찍혀 에이전트 자체 로그가 넘침.
+//! 같은 위치에서 같은 문구/오류로 발생한 WARN 이상 이벤트는 구간 안에서 첫 건만 출력하고,
+//! 나머지는 개수만 세었다가 구간마다 "N회 반복" 요약 한 줄로 출력
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tracing::callsite::Identifier;
+use tracing::field::{Field, Visit};
+use tracing::{warn, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::messages as msg;
+
+/// 억제 구간, 구간마다 억제된 건수를 요약해 출력
+const SUPPRESS_WINDOW: Duration = Duration::from_secs(60);
+
+static SUPPRESSED: LazyLock<Mutex<HashMap<Key, Entry>>> = LazyLock::new(Default::default);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    callsite: Identifier,
+    message: String,
+    error: String,
+}
+
+struct Entry {
+    window_start: Instant,
+    suppressed: u64,
+    level: Level,
+    target: &'static str,
+}
+
+/// 반복 이벤트를 걸러내는 tracing 레이어, 요약 출력은 `spawn_summary` 가 담당
+pub fn layer() -> ThrottleLayer {
+    ThrottleLayer
+}
+
+pub struct ThrottleLayer;
+
+impl<S: Subscriber> Layer<S> for ThrottleLayer {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        let metadata = event.metadata();
+
+        // 요약 이벤트 자체는 억제하지 않음
+        if *metadata.level() > Level::WARN || metadata.target() == module_path!() {
+            return true;
+        }
+
+        let mut visitor = KeyVisitor::default();
+        event.record(&mut visitor);
+
+        let key = Key {
+            callsite: metadata.callsite(),
+            message: visitor.message,
+            error: visitor.error,
+        };
+
+        let now = Instant::now();
+        let mut suppressed = lock();
+
+        match suppressed.get_mut(&key) {
+            Some(entry) if now.duration_since(entry.window_start) < SUPPRESS_WINDOW => {
+                entry.suppressed += 1;
+                false
+            }
+            _ => {
+                suppressed.insert(
+                    key,
+                    Entry {
+                        window_start: now,
+                        suppressed: 0,
+                        level: *metadata.level(),
+                        target: metadata.target(),
+                    },
+                );
+                true
+            }
+        }
+    }
+}
+
+/// 구간마다 억제된 이벤트를 요약 출력 (Tokio 런타임 컨텍스트 안에서 호출)
+pub fn spawn_summary() {
+    tokio::spawn(async {
+        let mut ticker = tokio::time::interval(SUPPRESS_WINDOW);
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            summarize();
+        }
+    });
+}
+
+fn summarize() {
+    let now = Instant::now();
+    let mut summaries = Vec::new();
+
+    {
+        let mut suppressed = lock();
+        suppressed.retain(|key, entry| {
+            if entry.suppressed > 0 {
+                summaries.push((key.clone(), entry.level, entry.target, entry.suppressed));
+                // 계속 반복 중이면 다음 구간도 억제
+                entry.window_start = now;
+                entry.suppressed = 0;
+                return true;
+            }
+
+            now.duration_since(entry.window_start) < SUPPRESS_WINDOW
+        });
+    }
+
+    // 잠금을 푼 뒤 출력 (출력 중 다시 event_enabled 를 거치므로)
+    for (key, level, target, repeated) in summaries {
+        warn!(
+            repeated = repeated,
+            original_level = %level,
+            original_target = target,
+            original = %key.message,
+            error = %key.error,
+            "{}",
+            msg::LOG_REPEATED
+        );
+    }
+}
+
+fn lock() -> MutexGuard<'static, HashMap<Key, Entry>> {
+    SUPPRESSED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 같은 이벤트 판단에 쓰는 필드만 수집 (batch_id 등 배치마다 다른 필드는 제외)
+#[derive(Default)]
+struct KeyVisitor {
+    message: String,
+    error: String,
+}
+
+impl Visit for KeyVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{:?}", value),
+            "error" => self.error = format!("{:?}", value),
+            _ => {}
+        }
+    }
+}
@@ -1,245 +1,626 @@
-mod auth;
-mod collector;
-mod forwarder;
-mod health;
-mod models;
-mod proto;
-mod settings;
-mod streamer;
-
-use std::sync::Arc;
-
-use crate::auth::client::AuthClient;
-use crate::auth::interceptor::AuthInterceptor;
-use crate::auth::token_manager::TokenManager;
-use crate::collector::Collector;
-use crate::forwarder::Forwarder;
-use crate::health::HealthReporter;
-use crate::models::LogEvent;
-use crate::proto::log::LogBatch;
-use crate::settings::{Settings, SourceSettings};
-use crate::streamer::Streamer;
-use anyhow::{anyhow, bail, Result};
+mod cli;
+mod service;
+
+use rlog_agent::auth::client::AuthClient;
+use rlog_agent::auth::token_manager::TokenManager;
+use rlog_agent::bench::{self, BenchOptions};
+use rlog_agent::loadgen::{self, LoadgenOptions};
+use rlog_agent::replay::{self, ReplayOptions};
+use rlog_agent::messages as msg;
+use rlog_agent::settings::{self, AuthMode, AuthSettings, Settings};
+use rlog_agent::{state_meta, state_store, status, tap};
+use rlog_agent::{admin, chaos, crash, crash_loop, doctor, endpoint, ephemeral, hot_restart, log_level, log_throttle, spiffe, systemd, telemetry, Agent, Sink};
+use crate::cli::{Cli, Command, Overrides};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::Parser;
+use std::time::Duration;
 use tokio::signal;
-use tokio::sync::mpsc;
-use tokio::sync::mpsc::{Receiver, Sender};
-use tokio::sync::RwLock;
-use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
-use tonic::transport::Channel;
 use tracing::{error, info, warn};
-use tracing_subscriber::EnvFilter;
 
 static ENV_SERVER_ADDR: &str = "SERVER_ADDR";
 static ENV_PROJECT_KEY: &str = "PROJECT_KEY";
+static ENV_SPIFFE_ENDPOINT_SOCKET: &str = "SPIFFE_ENDPOINT_SOCKET";
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    ephemeral::configure(cli.ephemeral);
+    if let Some(profile) = cli.profile.clone() {
+        settings::select_profile(profile);
+    }
+    let runtime = tokio::runtime::Runtime::new()?;
+
+    {
+        // OTLP 내보내기는 런타임 컨텍스트 안에서 초기화해야 함
+        let _guard = runtime.enter();
+        log_level::init(Settings::peek_log_format());
+        log_throttle::spawn_summary();
+    }
+    crash::install_panic_hook();
+    chaos::configure(cli.chaos.as_deref())?;
+
+    let result = match cli.command {
+        // 서비스 디스패처는 자체 런타임을 생성하므로 Tokio 런타임 밖에서 실행
+        Some(Command::Service { action }) => service::handle(action, cli.profile.as_deref(), &cli.overrides),
+        Some(Command::SystemdUnit {
+            exec_path,
+            state_dir,
+            user,
+            watchdog_sec,
+            self_update,
+        }) => {
+            let exec_path = match exec_path {
+                Some(path) => path,
+                None => std::env::current_exe()?,
+            };
+            let args = cli.overrides.to_args(cli.profile.as_deref());
+
+            print!(
+                "{}",
+                systemd::render_unit(&systemd::UnitOptions {
+                    exec_path: &exec_path,
+                    args: &args,
+                    state_dir: &state_dir,
+                    user: user.as_deref(),
+                    watchdog_sec,
+                    self_update,
+                })
+            );
+            Ok(())
+        }
+        Some(Command::Doctor) => runtime.block_on(run_doctor(&cli.overrides)),
+        Some(Command::Replay { file, label, speed }) => {
+            runtime.block_on(run_replay(ReplayOptions { file, label, speed }, cli.dry_run, &cli.overrides))
+        }
+        #[cfg(feature = "test-server")]
+        Some(Command::TestServer { listen }) => runtime.block_on(run_test_server(listen)),
+        Some(Command::Loadgen {
+            rate,
+            line_bytes,
+            duration,
+            label,
+        }) => {
+            let options = LoadgenOptions {
+                rate,
+                line_bytes,
+                duration: duration.map(Duration::from_secs),
+                label,
+            };
+            runtime.block_on(run_loadgen(options, &cli.overrides))
+        }
+        Some(Command::Status { json }) => runtime.block_on(print_status(json)),
+        Some(Command::Tap { label, count, json }) => runtime.block_on(print_tap(label, count, json)),
+        None => runtime.block_on(async_main(cli)),
+    };
 
-    info!("Agent 시작 중..");
-    let (settings, token_manager, channel) = load_settings_and_auth().await?;
+    telemetry::shutdown();
+    result
+}
 
+async fn async_main(cli: Cli) -> Result<()> {
     let shutdown = CancellationToken::new();
-    let token_manager = Arc::new(RwLock::new(token_manager));
-    let interceptor = {
-        let tm = token_manager.read().await;
-        AuthInterceptor::new(tm.get_shared_token())
-    };
+    tokio::spawn(wait_for_shutdown_signal(shutdown.clone()));
+
+    // 실행 중인 에이전트와 관리 소켓/핸드오버가 겹치지 않도록 측정만 수행
+    if cli.bench_mode {
+        return run_bench(&cli, shutdown).await;
+    }
+
+    log_level::spawn_signal_toggle(shutdown.child_token());
+    hot_restart::spawn_signal_handler(shutdown.clone());
+    admin::spawn(shutdown.child_token());
+
+    if cli.dry_run {
+        run_dry_run(shutdown, &cli.overrides).await?;
+    } else {
+        run_agent(shutdown, &cli.overrides).await?;
+    }
+
+    // 드레인과 핸드오버 기록이 끝난 뒤 새 바이너리로 교체
+    if hot_restart::is_requested() {
+        telemetry::shutdown();
+        hot_restart::exec()?;
+    }
 
-    let (collector_tx, collector_rx) = mpsc::channel::<LogEvent>(100);
-    let (streamer_tx, streamer_rx) = mpsc::channel::<LogBatch>(1000);
-
-    let collector_handles = start_collectors(
-        collector_tx,
-        settings.sources,
-        shutdown.child_token(),
-    )
-    .await?;
-
-    let forwarder_handle = start_forwarder(
-        collector_rx,
-        streamer_tx,
-        settings.batch_size,
-        settings.flush_interval,
-    )
-    .await?;
-
-    let streamer_handle = start_streamer(
-        streamer_rx,
-        channel.clone(),
-        Arc::clone(&token_manager),
-        interceptor.clone(),
-    )
-    .await?;
-
-    let health_handle = start_health_reporter(
-        channel,
-        Arc::clone(&token_manager),
-        interceptor.clone(),
-        shutdown.child_token(),
-    )
-    .await?;
+    Ok(())
+}
+
+async fn wait_for_shutdown_signal(shutdown: CancellationToken) {
+    if let Err(e) = shutdown_signal().await {
+        error!(error = %e, "{}", msg::SHUTDOWN_SIGNAL_WAIT_FAILED);
+        return;
+    }
+
+    shutdown.cancel();
+}
+
+/// Ctrl+C, (Unix) SIGTERM/SIGQUIT 중 하나를 수신할 때까지 대기
+#[cfg(unix)]
+async fn shutdown_signal() -> Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigterm = signal(SignalKind::terminate())?;
+    let mut sigquit = signal(SignalKind::quit())?;
 
     tokio::select! {
-        _ = forwarder_handle => {
-            error!("Forwarder 종료");
-        }
-        _ = streamer_handle => {
-            error!("Streamer 종료");
+        result = signal::ctrl_c() => {
+            result?;
+            info!(signal = "SIGINT", "{}", msg::SIGNAL_RECEIVED);
         }
-        _ = health_handle => {
-            error!("HealthReporter 종료");
+        _ = sigterm.recv() => {
+            info!(signal = "SIGTERM", "{}", msg::SIGNAL_RECEIVED);
         }
-        _ = signal::ctrl_c() => {
-            info!("Ctrl+C 감지..");
+        _ = sigquit.recv() => {
+            info!(signal = "SIGQUIT", "{}", msg::SIGNAL_RECEIVED);
         }
     }
 
-    shutdown.cancel();
+    Ok(())
+}
 
-    for ch in collector_handles {
-        if let Err(e) = ch.await {
-            error!("Collector 태스크 종료 오류: {:?}", e);
-        }
+#[cfg(not(unix))]
+async fn shutdown_signal() -> Result<()> {
+    signal::ctrl_c().await?;
+    info!(signal = "CTRL_C", "{}", msg::SIGNAL_RECEIVED);
+    Ok(())
+}
+
+/// 에이전트 실행, `shutdown` 취소 시 정상 종료 절차 수행
+pub async fn run_agent(shutdown: CancellationToken, overrides: &Overrides) -> Result<()> {
+    info!("{}", msg::AGENT_STARTING);
+    state_meta::migrate()?;
+    crash_loop::on_start(&shutdown).await;
+    if shutdown.is_cancelled() {
+        crash_loop::on_clean_exit();
+        return Ok(());
+    }
+
+    let mut settings = load_or_register_settings(overrides).await?;
+    // 등록 직후의 토큰은 기본 보관소(`state/`)에 있고, 등록으로 받은 설정도 기본 보관소를 씀
+    state_store::configure(&settings.state_store)?;
+    overrides.apply(&mut settings);
+
+    let reload_overrides = overrides.clone();
+    admin::configure_reload(move || reload_settings(&reload_overrides));
+
+    if settings.startup_check {
+        info!("{}", msg::STARTUP_CHECK_RUNNING);
+        doctor::log_report(&doctor::run(&settings).await);
     }
 
-    info!("Agent 정상 종료");
+    Agent::builder().settings(settings).build()?.run(shutdown).await?;
+
+    // 오류로 끝난 실행만 재시작 반복으로 셈
+    crash_loop::on_clean_exit();
     Ok(())
 }
 
-async fn start_collectors(
-    tx: Sender<LogEvent>,
-    source_settings: Vec<SourceSettings>,
-    shutdown: CancellationToken,
-) -> Result<Vec<JoinHandle<()>>> {
-    let mut handles = Vec::new();
+/// 서버 연결 없이 Collector → Forwarder 까지만 실행하고 배치 요약을 출력
+async fn run_dry_run(shutdown: CancellationToken, overrides: &Overrides) -> Result<()> {
+    info!("{}", msg::DRY_RUN_STARTING);
+    let mut settings = Settings::load_settings().context(msg::DRY_RUN_CONFIG_REQUIRED)?;
+    overrides.apply(&mut settings);
 
-    for source in source_settings {
-        let mut collector = Collector::new(tx.clone(), source).await?;
-        let child_shutdown = shutdown.child_token();
+    Agent::builder()
+        .settings(settings)
+        .sink(Sink::Stdout)
+        .build()?
+        .run(shutdown)
+        .await?;
 
-        handles.push(tokio::spawn(async move {
-            collector.start(child_shutdown).await;
-        }));
-    }
+    info!("{}", msg::DRY_RUN_FINISHED);
+    Ok(())
+}
+
+/// 서버 대신 버리기만 하는 Sink 로 파이프라인을 실행해 처리량 출력
+async fn run_bench(cli: &Cli, shutdown: CancellationToken) -> Result<()> {
+    info!("{}", msg::BENCH_MODE_STARTING);
 
-    Ok(handles)
+    let options = BenchOptions {
+        lines: cli.bench_lines,
+        line_bytes: cli.bench_line_bytes,
+    };
+    bench::run(options, shutdown).await?.print();
+    Ok(())
 }
 
-async fn load_settings_and_auth() -> Result<(Settings, TokenManager, Channel)> {
-    match Settings::load_settings() {
-        Ok(settings) => {
-            // 설정 파일 있음 -> 저장된 토큰으로 인증
-            let channel = Channel::from_shared(settings.server_addr.clone())?
-                .connect()
-                .await?;
+/// 기존 파일을 파이프라인 전체로 재생, 설정은 파싱 규칙과 전송 대상으로 사용
+async fn run_replay(options: ReplayOptions, dry_run: bool, overrides: &Overrides) -> Result<()> {
+    info!("{}", msg::REPLAY_STARTING);
+    let shutdown = CancellationToken::new();
+    tokio::spawn(wait_for_shutdown_signal(shutdown.clone()));
 
-            let auth_client = AuthClient::new(channel.clone());
-            let token_manager = TokenManager::load(auth_client, settings.project_key.clone()).await?;
-            info!("설정 및 토큰 로드 완료");
+    let mut settings = match Settings::load_settings() {
+        Ok(settings) => settings,
+        Err(_) if dry_run => Settings::default(),
+        Err(e) => return Err(e.context(msg::REPLAY_CONFIG_REQUIRED)),
+    };
+    state_store::configure(&settings.state_store)?;
+    overrides.apply(&mut settings);
+
+    let sink = if dry_run {
+        Sink::Stdout
+    } else {
+        Sink::Grpc {
+            server_addr: settings.server_addr.clone(),
+            project_key: settings.project_key.clone(),
+        }
+    };
+    replay::run(settings, options, sink, shutdown).await?.print();
+    Ok(())
+}
+
+/// 설정의 서버로 합성 부하를 보내고 생성/전송량 출력
+async fn run_loadgen(options: LoadgenOptions, overrides: &Overrides) -> Result<()> {
+    info!("{}", msg::LOADGEN_STARTING);
+    let shutdown = CancellationToken::new();
+    tokio::spawn(wait_for_shutdown_signal(shutdown.clone()));
+
+    let mut settings = Settings::load_settings().context(msg::LOADGEN_CONFIG_REQUIRED)?;
+    state_store::configure(&settings.state_store)?;
+    overrides.apply(&mut settings);
+
+    loadgen::run(settings, options, shutdown).await?.print();
+    Ok(())
+}
+
+/// 중지 신호까지 로컬 테스트 서버 실행, 에이전트의 `server_addr` 를 이 주소로 지정해 설정 확인
+#[cfg(feature = "test-server")]
+async fn run_test_server(listen: std::net::SocketAddr) -> Result<()> {
+    let server = rlog_agent::test_server::TestServer::bind(listen).await?;
+    println!("{}", server.url());
+
+    shutdown_signal().await?;
+    server.shutdown().await;
+    Ok(())
+}
+
+/// 자가 진단 실행 후 결과 출력, 실패 항목이 있으면 비정상 종료 코드 반환
+async fn run_doctor(overrides: &Overrides) -> Result<()> {
+    let mut settings = Settings::load_settings().context(msg::DOCTOR_CONFIG_REQUIRED)?;
+    state_store::configure(&settings.state_store)?;
+    overrides.apply(&mut settings);
+
+    doctor::print_report(&doctor::run(&settings).await)
+}
 
-            Ok((settings, token_manager, channel))
+/// 관리 소켓으로 실행 중인 에이전트 상태를 조회해 출력
+async fn print_status(json: bool) -> Result<()> {
+    let value = admin::query("status").await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    let status: status::Snapshot = serde_json::from_value(value)?;
+
+    println!("{}: {} (pid {})", msg::STATUS_VERSION, status.version, status.pid);
+    println!("{}: {}s", msg::STATUS_UPTIME, status.uptime_secs);
+    println!("{}: {:?}", msg::STATUS_CONNECTION, status.connection);
+    println!();
+
+    println!("{}:", msg::STATUS_SOURCES);
+    for source in &status.sources {
+        let lag = source
+            .lag_bytes
+            .map_or_else(|| "-".to_string(), |lag| format!("{} bytes", lag));
+        let window = match source.window_open {
+            Some(false) => format!(" ({})", msg::STATUS_WINDOW_CLOSED),
+            _ => String::new(),
+        };
+        println!(
+            "  {:<16} offset={} lag={} ({}){}{}",
+            source.label,
+            source.position,
+            lag,
+            source.path.display(),
+            if source.paused { " paused" } else { "" },
+            window
+        );
+    }
+    println!();
+
+    println!("{}:", msg::STATUS_QUEUES);
+    for (name, depth) in &status.queues {
+        println!("  {:<16} {}", name, depth);
+    }
+    println!("  {:<16} {}", "spool", status.spool_batches);
+    if status.expired_events > 0 {
+        println!("  {:<16} {}", "expired", status.expired_events);
+    }
+    for (queue, stats) in &status.spools {
+        println!(
+            "  {:<16} bytes={} evicted={} rejected={}",
+            queue, stats.bytes, stats.evicted_batches, stats.rejected_batches
+        );
+    }
+
+    let backlog = &status.backlog;
+    if let Some(age) = backlog.oldest_event_age_secs {
+        println!("  {:<16} {}s", "oldest", age);
+    }
+    if backlog.retry.consecutive_failures > 0 {
+        println!(
+            "  {:<16} failures={} since={} backoff_until={} budget_exhausted={}",
+            "retry",
+            backlog.retry.consecutive_failures,
+            backlog.retry.failing_since.map_or_else(|| "-".to_string(), |at| at.to_rfc3339()),
+            backlog.retry.backoff_until.map_or_else(|| "-".to_string(), |at| at.to_rfc3339()),
+            backlog.retry.budget_exhausted
+        );
+    }
+    println!();
+
+    let free = status.disk.free_bytes.map_or_else(|| "-".to_string(), |free| free.to_string());
+    if status.disk.low {
+        println!("{}: {} bytes < {} ({})", msg::STATUS_DISK, free, status.disk.min_free_bytes, msg::DISK_SPACE_LOW);
+    } else {
+        println!("{}: {} bytes", msg::STATUS_DISK, free);
+    }
+
+    if status.crash_loop.is_looping() {
+        println!(
+            "{}: restarts={} process={} subsystems={}",
+            msg::STATUS_CRASH_LOOP,
+            status.crash_loop.recent_restarts,
+            status.crash_loop.process,
+            status.crash_loop.subsystems.join(",")
+        );
+    }
+
+    if let Some(skew) = status.clock.skew_secs {
+        if status.clock.correcting {
+            println!("{}: {:+}s {}", msg::STATUS_CLOCK_SKEW, skew, msg::STATUS_CLOCK_CORRECTING);
+        } else {
+            println!("{}: {:+}s", msg::STATUS_CLOCK_SKEW, skew);
         }
-        Err(_) => {
-            // 설정 파일 없음 -> 신규 등록
-            warn!("설정파일 로드 실패, 에이전트 등록 수행");
-            let (server_addr, project_key) = get_env()?;
+    }
+
+    let watchers: Vec<_> = status
+        .watchers
+        .iter()
+        .filter(|(_, watcher)| watcher.stale_incidents > 0 || watcher.polling)
+        .collect();
+    if !watchers.is_empty() {
+        println!("{}:", msg::STATUS_WATCHERS);
+        for (label, watcher) in watchers {
+            println!(
+                "  {:<16} stale={} recreated={} polling={}",
+                label, watcher.stale_incidents, watcher.recreated, watcher.polling
+            );
+        }
+        println!();
+    }
 
-            let channel = Channel::from_shared(server_addr.clone())?
-                .connect()
-                .await?;
+    if !status.invalid_utf8_skipped.is_empty() {
+        println!("{}:", msg::STATUS_INVALID_UTF8_SKIPPED);
+        for (label, skipped) in &status.invalid_utf8_skipped {
+            println!("  {:<16} {}", label, skipped);
+        }
+        println!();
+    }
 
-            let mut auth_client = AuthClient::new(channel.clone());
-            let response = auth_client.register(&project_key, None).await?;
+    if !status.error_codes.is_empty() {
+        println!("{}:", msg::STATUS_ERROR_CODES);
+        for (code, stats) in &status.error_codes {
+            println!(
+                "  {:<16} count={} last={} {}",
+                code,
+                stats.count,
+                stats.last_at.to_rfc3339(),
+                stats.last_message
+            );
+        }
+        println!();
+    }
 
-            if !response.success {
-                bail!("에이전트 등록 실패");
-            }
+    if !status.egress.is_empty() {
+        println!("{}:", msg::STATUS_EGRESS);
+        for (label, egress) in &status.egress {
+            println!("  {:<16} events={} bytes={}", label, egress.events, egress.bytes);
+        }
+        println!();
+    }
+
+    if !status.sinks.is_empty() {
+        println!("{}:", msg::STATUS_SINKS);
+        for (name, sink) in &status.sinks {
+            let last_success = sink.last_success.map_or_else(|| "-".to_string(), |at| at.to_rfc3339());
+            println!(
+                "  {:<16} healthy={} failures={} queued={} last_success={}",
+                name, sink.healthy, sink.consecutive_failures, sink.queue_depth, last_success
+            );
+        }
+        println!();
+    }
+
+    let limit = if status.memory_limit_bytes == 0 {
+        msg::STATUS_UNLIMITED.to_string()
+    } else {
+        format!("{} bytes", status.memory_limit_bytes)
+    };
+    println!("{}: {} bytes / {}", msg::STATUS_MEMORY, status.memory_used_bytes, limit);
 
-            let settings = Settings::from_response(
-                response.clone(),
-                server_addr.clone(),
-                project_key.clone(),
-            )?;
-            settings.save_settings()?;
+    match status.token_expires_at {
+        Some(expires_at) => println!("{}: {}", msg::STATUS_TOKEN_EXPIRES, expires_at.to_rfc3339()),
+        None => println!("{}: -", msg::STATUS_TOKEN_EXPIRES),
+    }
+    let token = &status.token;
+    println!(
+        "{}: issued={} refreshes={} failures={} last_register={}",
+        msg::STATUS_TOKEN,
+        token.issued_at.map_or_else(|| "-".to_string(), |at| at.to_rfc3339()),
+        token.refresh_successes,
+        token.refresh_failures,
+        token.last_register.map_or_else(|| "-".to_string(), |at| at.to_rfc3339())
+    );
+
+    match &status.last_send {
+        Some(last) => println!(
+            "{}: {} (count={}, batch_id={})",
+            msg::STATUS_LAST_SEND,
+            last.at.to_rfc3339(),
+            last.count,
+            last.batch_id
+        ),
+        None => println!("{}: -", msg::STATUS_LAST_SEND),
+    }
 
-            let token_manager = TokenManager::new(
-                auth_client,
-                response.access_token,
-                response.refresh_token,
-                response.agent_uuid,
-                project_key,
-            )?;
+    if !status.subsystems.is_empty() {
+        println!();
+        println!("{}:", msg::STATUS_SUBSYSTEMS);
+        for (name, subsystem) in &status.subsystems {
+            let error = subsystem
+                .last_error
+                .as_deref()
+                .map_or_else(String::new, |e| format!(" last_error={}", e));
+            println!(
+                "  {:<16} {:?} restarts={}{}",
+                name, subsystem.state, subsystem.restarts, error
+            );
+        }
+    }
 
-            info!("에이전트 등록 및 설정 저장 완료");
-            Ok((settings, token_manager, channel))
+    if !status.recent_errors.is_empty() {
+        println!();
+        println!("{}:", msg::STATUS_RECENT_ERRORS);
+        for error in &status.recent_errors {
+            println!("  {}", error);
         }
     }
+
+    Ok(())
 }
 
-async fn start_forwarder(
-    rx: Receiver<LogEvent>,
-    tx: Sender<LogBatch>,
-    batch_size: usize,
-    flush_interval: u64,
-) -> Result<JoinHandle<()>> {
-    let forwarder = Forwarder::new(rx, tx, batch_size, flush_interval);
-    let handle = tokio::spawn(async move {
-        forwarder.start().await;
-    });
+/// 관리 소켓으로 실행 중인 에이전트의 이벤트를 받아 출력
+async fn print_tap(label: Option<String>, count: usize, json: bool) -> Result<()> {
+    let mut command = format!("tap count={}", count);
+    if let Some(label) = label {
+        command.push_str(&format!(" label={}", label));
+    }
 
-    Ok(handle)
-}
+    admin::stream(&command, |value| {
+        if json {
+            println!("{}", value);
+            return Ok(());
+        }
 
-async fn start_streamer(
-    rx: Receiver<LogBatch>,
-    channel: Channel,
-    token_manager: Arc<RwLock<TokenManager>>,
-    interceptor: AuthInterceptor,
-) -> Result<JoinHandle<()>> {
-    let streamer = Streamer::new(rx, channel, interceptor, token_manager);
+        let event: tap::TapEvent = serde_json::from_value(value)?;
+        println!("{} [{}] {}", event.timestamp.to_rfc3339(), event.label, event.content);
+        Ok(())
+    })
+    .await
+}
 
-    let handle = tokio::spawn(async move {
-        streamer.start().await;
-    });
+/// 설정 파일 로드, 없으면 환경 변수로 신규 등록 후 설정과 토큰 저장
+/// 설정 파일, 상태 없는 실행은 `RLOG_CONFIG` 에서 읽은 설정 (없으면 None, 등록 필요)
+fn load_local_settings() -> Result<Option<Settings>> {
+    if ephemeral::is_enabled() {
+        ephemeral::settings()
+    } else {
+        Settings::load_existing()
+    }
+}
 
-    Ok(handle)
+/// 관리 API `reload_sources` 용, 시작 때와 같은 경로로 읽고 CLI 덮어쓰기 적용 (등록으로 받은 설정은 다시 읽을 곳이 없음)
+fn reload_settings(overrides: &Overrides) -> Result<Settings> {
+    let mut settings = load_local_settings()?.context(msg::RELOAD_CONFIG_MISSING)?;
+    overrides.apply(&mut settings);
+    Ok(settings)
 }
 
-async fn start_health_reporter(
-    channel: Channel,
-    token_manager: Arc<RwLock<TokenManager>>,
-    interceptor: AuthInterceptor,
-    shutdown: CancellationToken,
-) -> Result<JoinHandle<()>> {
-    let reporter = HealthReporter::new(channel, interceptor, token_manager);
+async fn load_or_register_settings(overrides: &Overrides) -> Result<Settings> {
+    // 상태 없는 실행은 설정 파일 대신 `RLOG_CONFIG`, 없으면 매번 등록해 서버의 설정을 받음
+    if let Some(settings) = load_local_settings()? {
+        if ephemeral::is_enabled() {
+            info!("{}", msg::EPHEMERAL_MODE);
+        }
+        return Ok(settings);
+    }
+    if ephemeral::is_enabled() {
+        info!("{}", msg::EPHEMERAL_REGISTERING);
+    } else {
+        // 설정 파일 없음 -> 신규 등록
+        warn!("{}", msg::CONFIG_MISSING_REGISTERING);
+    }
 
-    let handle = tokio::spawn(async move {
-        reporter.start(shutdown).await;
-    });
+    // 등록 응답으로 만든 설정에는 프로필이 없으므로 프로필을 고른 채로 등록하지 않음
+    if let Some(profile) = settings::selected_profile() {
+        bail!("{}: {} ({})", msg::PROFILE_NOT_FOUND, profile, msg::PROFILE_REQUIRES_CONFIG);
+    }
 
-    Ok(handle)
-}
+    let (server_addr, project_key) = get_env(overrides)?;
+
+    // PROJECT_KEY 없이 SPIFFE_ENDPOINT_SOCKET 만 있으면 SVID 로 등록
+    let auth = match project_key {
+        Some(_) => AuthSettings::default(),
+        None => AuthSettings {
+            mode: AuthMode::Spiffe,
+            ..AuthSettings::default()
+        },
+    };
+    if auth.mode == AuthMode::Spiffe {
+        spiffe::fetch(&spiffe::socket(&auth)).await?;
+    }
+    let project_key = project_key.unwrap_or_default();
 
-fn get_env() -> Result<(String, String)> {
-    let server_addr = std::env::var(ENV_SERVER_ADDR)
-        .map_err(|_| anyhow!("SERVER_ADDR 환경 변수를 찾을 수 없음"))?;
+    let channel = endpoint::connect(&server_addr, None).await?;
 
-    let project_key = std::env::var(ENV_PROJECT_KEY)
-        .map_err(|_| anyhow!("PROJECT_KEY 환경 변수를 찾을 수 없음"))?;
+    let mut auth_client = AuthClient::new(channel);
+    let response = auth_client.register(&project_key, ephemeral::agent_uuid().as_deref()).await?;
+
+    if !response.success {
+        bail!(msg::AGENT_REGISTER_FAILED);
+    }
+
+    let mut settings = Settings::from_response(
+        response.clone(),
+        server_addr,
+        project_key.clone(),
+    )?;
+    settings.auth = auth;
+    if !ephemeral::is_enabled() {
+        settings.save_settings()?;
+    }
+
+    // 발급된 토큰, agent_uuid 를 기본 보관소 state/ 에 저장 (상태 없는 실행은 메모리)
+    TokenManager::new(
+        auth_client,
+        response.access_token,
+        response.refresh_token,
+        response.agent_uuid,
+        project_key,
+    )?;
+
+    if !ephemeral::is_enabled() {
+        info!("{}", msg::AGENT_REGISTERED_AND_SAVED);
+    }
+    Ok(settings)
+}
+
+/// 등록할 서버 주소와 project_key, 명령줄 값(`--server-addr`, `--project-key`)이 환경 변수보다 우선
+///
+/// project_key 는 SPIFFE 로 등록할 때(`SPIFFE_ENDPOINT_SOCKET` 만 지정) None
+fn get_env(overrides: &Overrides) -> Result<(String, Option<String>)> {
+    let server_addr = match &overrides.server_addr {
+        Some(server_addr) => server_addr.clone(),
+        None => std::env::var(ENV_SERVER_ADDR).map_err(|_| anyhow!("{}: {}", msg::ENV_MISSING, ENV_SERVER_ADDR))?,
+    };
 
     if server_addr.trim().is_empty() {
-        bail!("SERVER_ADDR 환경 변수가 비어 있음")
+        bail!("{}: {}", msg::ENV_EMPTY, ENV_SERVER_ADDR)
     }
 
+    let project_key = match overrides.project_key.clone().map_or_else(|| std::env::var(ENV_PROJECT_KEY), Ok) {
+        Ok(project_key) => project_key,
+        Err(_) if std::env::var_os(ENV_SPIFFE_ENDPOINT_SOCKET).is_some() => return Ok((server_addr, None)),
+        Err(_) => bail!("{}: {}", msg::ENV_MISSING, ENV_PROJECT_KEY),
+    };
+
     if project_key.trim().is_empty() {
-        bail!("PROJECT_KEY 환경 변수가 비어 있음")
+        bail!("{}: {}", msg::ENV_EMPTY, ENV_PROJECT_KEY)
     }
 
-    Ok((server_addr, project_key))
+    Ok((server_addr, Some(project_key)))
 }
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use prost::Message;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::proto::log::LogBatch;
+
+/// LogEvent 구조체, 타임스탬프 등 본문 외 고정 비용 추정치
+const EVENT_OVERHEAD_BYTES: usize = 64;
+
+/// 채널, 대기 중인 배치, 재전송 대기열에 머무는 로그 데이터의 전역 메모리 예산
+///
+/// Collector 가 이벤트를 보낼 때 더하고, 배치가 전송(또는 스풀 저장)되어 메모리에서 빠질 때 뺀다.
+/// 한도를 넘으면 Collector 는 읽기를 멈추고 대기하므로 초과분은 원본 파일에 남는다.
+#[derive(Clone, Default)]
+pub struct MemoryBudget {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// 0 이면 무제한
+    limit: usize,
+    used: AtomicUsize,
+    exceeded: AtomicBool,
+    released: Notify,
+}
+
+impl MemoryBudget {
+    pub fn new(max_memory_mb: u64) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                limit: (max_memory_mb as usize).saturating_mul(1024 * 1024),
+                ..Default::default()
+            }),
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.inner.limit
+    }
+
+    pub fn used(&self) -> usize {
+        self.inner.used.load(Ordering::Relaxed)
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.inner.limit > 0 && self.used() >= self.inner.limit
+    }
+
+    pub fn add(&self, bytes: usize) {
+        self.inner.used.fetch_add(bytes, Ordering::Relaxed);
+
+        if self.is_exceeded() && !self.inner.exceeded.swap(true, Ordering::Relaxed) {
+            warn!(
+                used = self.used(),
+                limit = self.inner.limit,
+                "{}",
+                msg::MEMORY_BUDGET_EXCEEDED
+            );
+        }
+    }
+
+    pub fn release(&self, bytes: usize) {
+        let _ = self
+            .inner
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                Some(used.saturating_sub(bytes))
+            });
+
+        if !self.is_exceeded() && self.inner.exceeded.swap(false, Ordering::Relaxed) {
+            info!(used = self.used(), "{}", msg::MEMORY_BUDGET_RECOVERED);
+        }
+
+        self.inner.released.notify_waiters();
+    }
+
+    /// 예산에 여유가 생기거나 `shutdown` 이 취소될 때까지 대기
+    pub async fn wait_for_capacity(&self, shutdown: &CancellationToken) {
+        loop {
+            let released = self.inner.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            if !self.is_exceeded() {
+                return;
+            }
+
+            tokio::select! {
+                _ = released => {}
+                _ = shutdown.cancelled() => return,
+            }
+        }
+    }
+}
+
+pub fn event_bytes(event: &LogEvent) -> usize {
+    let fields: usize = event.fields.iter().map(|(k, v)| k.len() + v.len()).sum();
+    event.label.len() + event.content.len() + fields + EVENT_OVERHEAD_BYTES
+}
+
+pub fn batch_bytes(batch: &LogBatch) -> usize {
+    batch.encoded_len()
+}
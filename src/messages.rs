@@ -0,0 +1,740 @@
+//! 운영 메시지 카탈로그
+//!
+//! 로그/오류/CLI 출력 문구를 한곳에 모아 언어별로 제공. 기본은 영어이며
+//! `RLOG_LANG=ko` (또는 `ko_KR.UTF-8` 등 `ko` 로 시작하는 값)로 한국어 선택.
+//! 라벨, 경로, batch_id 등 값은 문구에 넣지 않고 구조화 필드로 남겨 언어와 무관하게 검색 가능
+
+use std::fmt;
+use std::sync::LazyLock;
+
+static ENV_LANG: &str = "RLOG_LANG";
+
+static LANG: LazyLock<Lang> = LazyLock::new(|| {
+    std::env::var(ENV_LANG)
+        .map(|value| Lang::parse(&value))
+        .unwrap_or_default()
+});
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Lang {
+    #[default]
+    En,
+    Ko,
+}
+
+impl Lang {
+    fn parse(value: &str) -> Self {
+        if value.trim().to_ascii_lowercase().starts_with("ko") {
+            Lang::Ko
+        } else {
+            Lang::En
+        }
+    }
+}
+
+/// 현재 출력 언어
+pub fn lang() -> Lang {
+    *LANG
+}
+
+/// 언어별 문구 한 건
+#[derive(Debug, Clone, Copy)]
+pub struct Msg {
+    en: &'static str,
+    ko: &'static str,
+}
+
+impl Msg {
+    const fn new(en: &'static str, ko: &'static str) -> Self {
+        Self { en, ko }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match lang() {
+            Lang::En => self.en,
+            Lang::Ko => self.ko,
+        }
+    }
+}
+
+impl fmt::Display for Msg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text())
+    }
+}
+
+// 공통
+pub const STATE_DIR_CREATE_FAILED: Msg = Msg::new("Failed to create state directory", "상태 디렉터리 생성 실패");
+pub const TOKEN_EXPIRED_RETRY: Msg = Msg::new("Token expired, refreshing", "토큰 만료, 재발급 시도");
+pub const BATCH_SPOOL_FAILED: Msg = Msg::new("Failed to spool batch", "배치 스풀 저장 실패");
+
+// supervisor
+pub const SUBSYSTEM_FAILED_DURING_SHUTDOWN: Msg = Msg::new("Subsystem exited abnormally during shutdown", "종료 중 서브시스템 비정상 종료");
+pub const RESTART_LIMIT_EXCEEDED: Msg = Msg::new("Restart limit exceeded", "재시작 한도 초과");
+pub const SUBSYSTEM_UNRECOVERABLE: Msg = Msg::new("Subsystem unrecoverable", "서브시스템 복구 불가");
+pub const SUBSYSTEM_RESTART_SCHEDULED: Msg = Msg::new("Subsystem exited abnormally, restart scheduled", "서브시스템 비정상 종료, 재시작 예정");
+pub const SUBSYSTEM_RESTARTING: Msg = Msg::new("Restarting subsystem", "서브시스템 재시작");
+
+// systemd
+pub const SYSTEMD_NOTIFY_FAILED: Msg = Msg::new("Failed to send systemd notification", "systemd 알림 전송 실패");
+pub const SYSTEMD_WATCHDOG_ENABLED: Msg = Msg::new("systemd watchdog enabled", "systemd watchdog 활성화");
+
+// forwarder
+pub const COLLECTORS_DONE_FLUSHING: Msg = Msg::new("All collectors stopped, flushing remaining data..", "모든 Collector 종료, 잔여 데이터 전송 중..");
+pub const FORWARDER_STOPPED: Msg = Msg::new("Forwarder stopped..", "Forwarder 종료..");
+pub const BATCH_CREATED: Msg = Msg::new("Batch created", "배치 생성");
+pub const BATCH_FORWARD_FAILED: Msg = Msg::new("Failed to forward batch", "배치 전송 실패");
+pub const EVENT_TRUNCATED: Msg = Msg::new("Truncated oversized event", "크기 상한을 넘는 이벤트 자름");
+
+// spool
+pub const SPOOL_DIR_CREATE_FAILED: Msg = Msg::new("Failed to create spool directory", "스풀 디렉터리 생성 실패");
+pub const SPOOL_WRITE_FAILED: Msg = Msg::new("Failed to write spool file", "스풀 파일 쓰기 실패");
+pub const SPOOL_RENAME_FAILED: Msg = Msg::new("Failed to move spool file", "스풀 파일 이동 실패");
+pub const SPOOL_DIR_READ_FAILED: Msg = Msg::new("Failed to read spool directory", "스풀 디렉터리 읽기 실패");
+pub const SPOOL_READ_FAILED: Msg = Msg::new("Failed to read spool file", "스풀 파일 읽기 실패");
+pub const SPOOL_EVENTS_EXPIRED: Msg = Msg::new("Dropped spooled events older than max_event_age", "max_event_age 보다 오래된 스풀 이벤트 버림");
+pub const SPOOL_CORRUPT_REMOVED: Msg = Msg::new("Removed corrupt spool file", "손상된 스풀 파일 삭제");
+pub const SPOOL_REMOVE_FAILED: Msg = Msg::new("Failed to remove spool file", "스풀 파일 삭제 실패");
+pub const SPOOL_FULL: Msg = Msg::new("Disk queue reached max_spool_mb, batch not stored", "디스크 큐가 max_spool_mb 에 도달해 배치 저장 안 함");
+pub const SPOOL_EVICTED: Msg = Msg::new("Disk queue reached max_spool_mb, removed oldest batches", "디스크 큐가 max_spool_mb 에 도달해 오래된 배치 삭제");
+pub const SPOOL_FULL_COLLECTION_PAUSED: Msg = Msg::new("Disk queue full, pausing collection until the server is reachable", "디스크 큐 가득 참, 서버 연결 시까지 수집 중단");
+
+// health
+pub const HEALTH_REPORTER_STARTED: Msg = Msg::new("HealthReporter started", "HealthReporter 시작");
+pub const HEALTH_REPORTER_STOPPED: Msg = Msg::new("HealthReporter stopped", "HealthReporter 종료");
+pub const HEARTBEAT_FAILED: Msg = Msg::new("Failed to send heartbeat", "Heartbeat 전송 실패");
+pub const HEARTBEAT_SENT: Msg = Msg::new("Heartbeat sent", "Heartbeat 전송 완료");
+pub const HEARTBEAT_SENT_AFTER_RETRY: Msg = Msg::new("Heartbeat sent after retry", "재시도 후 Heartbeat 전송 완료");
+pub const METRIC_DROPPED: Msg = Msg::new("Metric channel full, dropping measurement", "메트릭 채널이 가득 차 측정값 버림");
+
+// streamer
+pub const STREAMER_STARTED: Msg = Msg::new("Streamer started", "Streamer 시작");
+pub const STREAMER_STOPPED: Msg = Msg::new("Streamer stopped", "Streamer 종료");
+pub const LOG_SEND_FAILED: Msg = Msg::new("Failed to send logs, stored in disk queue", "로그 전송 실패, 디스크 큐에 저장");
+pub const LOG_SENT: Msg = Msg::new("Logs sent", "로그 전송 완료");
+pub const LOG_SENT_AFTER_RETRY: Msg = Msg::new("Logs sent after retry", "재시도 후 로그 전송 완료");
+pub const SPOOL_LIST_FAILED: Msg = Msg::new("Failed to list spool", "스풀 목록 조회 실패");
+pub const SPOOL_RESEND_STARTED: Msg = Msg::new("Resending spooled batches", "스풀된 배치 재전송 시작");
+pub const SPOOL_RESEND_FAILED: Msg = Msg::new("Failed to resend spooled batch, will retry on next run", "스풀 배치 재전송 실패, 다음 실행 시 재시도");
+pub const SPOOL_RESEND_REJECTED: Msg = Msg::new("Server rejected spooled batch, moving to dead-letter", "서버가 스풀 배치를 거부, dead-letter 로 이동");
+pub const LOGS_REJECTED: Msg = Msg::new("Server rejected some logs in batch", "서버가 배치 중 일부 로그 거부");
+pub const LOG_REJECTED: Msg = Msg::new("Log rejected by server", "서버가 로그 거부");
+pub const PARTIAL_RESEND_FAILED: Msg = Msg::new("Failed to resend rejected logs, spooling", "거부된 로그 재전송 실패, 스풀 저장");
+pub const DEAD_LETTERED: Msg = Msg::new("Moved rejected logs to dead-letter", "거부된 로그를 dead-letter 에 보관");
+pub const DEAD_LETTER_FAILED: Msg = Msg::new("Failed to write dead-letter", "dead-letter 저장 실패");
+pub const DRAIN_TIMEOUT_SPOOLED: Msg = Msg::new("Drain timeout exceeded, spooling unsent batches", "드레인 제한 시간 초과, 미전송 배치 스풀 저장");
+pub const SERVICE_NOT_READY: Msg = Msg::new("Service was not ready", "서비스 준비 실패");
+
+// auth
+pub const TOKEN_READ_FAILED: Msg = Msg::new("Failed to read token (RwLock poisoned)", "토큰 읽기 실패 (RwLock poisoned)");
+pub const TOKEN_MISSING: Msg = Msg::new("No access token", "토큰이 없습니다");
+pub const TOKEN_MALFORMED: Msg = Msg::new("Malformed access token", "토큰 형식이 잘못되었습니다.");
+pub const AGENT_REGISTERED: Msg = Msg::new("Agent registered", "Agent 등록 완료");
+pub const TOKEN_REFRESHED: Msg = Msg::new("Token refreshed", "토큰 갱신 완료");
+pub const AUTHENTICATED_WITH_SAVED_TOKEN: Msg = Msg::new("Authenticated with saved token", "저장된 토큰으로 인증 완료");
+pub const TOKEN_REFRESH_FAILED_REREGISTER: Msg = Msg::new("Token refresh failed, re-registering", "토큰 갱신 실패, 재등록 시도");
+pub const SAVED_TOKEN_MISSING: Msg = Msg::new("No saved token", "저장된 토큰 없음");
+pub const TOKEN_REFRESH_REQUEST_FAILED: Msg = Msg::new("Token refresh request failed", "토큰 갱신 요청 실패");
+pub const TOKEN_REFRESH_REJECTED: Msg = Msg::new("Token refresh rejected", "토큰 갱신 거부됨");
+pub const REGISTER_ERROR: Msg = Msg::new("Error during registration", "등록 중 오류");
+pub const REGISTER_FAILED: Msg = Msg::new("Registration failed", "등록 실패");
+pub const REGISTERED: Msg = Msg::new("Registration complete", "등록 완료");
+pub const ACCESS_TOKEN_WRITE_FAILED: Msg = Msg::new("Failed to write access_token (RwLock poisoned)", "access_token 쓰기 실패 (RwLock poisoned)");
+pub const SAVED_VALUE_EMPTY: Msg = Msg::new("Saved value is empty", "저장된 값이 비어 있음");
+
+// dry_run
+pub const DRY_RUN_SINK_STARTED: Msg = Msg::new("DryRunSink started", "DryRunSink 시작");
+pub const DRY_RUN_SINK_STOPPED: Msg = Msg::new("DryRunSink stopped", "DryRunSink 종료");
+pub const DRY_RUN_TOTAL: Msg = Msg::new("Total", "합계");
+
+// memory
+pub const MEMORY_BUDGET_EXCEEDED: Msg = Msg::new("Memory budget exceeded, pausing collector reads", "메모리 예산 초과, Collector 읽기 일시 중지");
+pub const MEMORY_BUDGET_RECOVERED: Msg = Msg::new("Memory budget recovered, resuming collector reads", "메모리 예산 회복, Collector 읽기 재개");
+
+// log_level
+pub const INVALID_LOG_FILTER: Msg = Msg::new("Invalid log filter", "잘못된 로그 필터");
+pub const LOG_FILTER_RELOAD_FAILED: Msg = Msg::new("Failed to replace log filter", "로그 필터 교체 실패");
+pub const LOG_FILTER_LOCK_FAILED: Msg = Msg::new("Failed to lock log filter state", "로그 필터 상태 잠금 실패");
+pub const LOG_LEVEL_CHANGED: Msg = Msg::new("Log level changed", "로그 레벨 변경");
+pub const SIGUSR1_HANDLER_FAILED: Msg = Msg::new("Failed to register SIGUSR1 handler", "SIGUSR1 핸들러 등록 실패");
+pub const LOG_LEVEL_TOGGLE_FAILED: Msg = Msg::new("Failed to toggle log level", "로그 레벨 전환 실패");
+pub const LOG_LEVEL_ALREADY_DEBUG: Msg = Msg::new(
+    "Startup log filter is already debug, nothing to toggle",
+    "시작 시 로그 필터가 이미 debug 라 전환하지 않음",
+);
+
+// crash
+pub const CRASH_REPORT_SAVE_FAILED: Msg = Msg::new("Failed to save crash report", "크래시 보고서 저장 실패");
+pub const UNKNOWN_PANIC: Msg = Msg::new("Unknown panic", "알 수 없는 panic");
+pub const CRASH_DIR_CREATE_FAILED: Msg = Msg::new("Failed to create crash report directory", "크래시 보고서 디렉터리 생성 실패");
+pub const CRASH_REPORT_WRITE_FAILED: Msg = Msg::new("Failed to write crash report", "크래시 보고서 쓰기 실패");
+pub const CRASH_REPORT_RENAME_FAILED: Msg = Msg::new("Failed to move crash report", "크래시 보고서 이동 실패");
+pub const CRASH_REPORT_CORRUPT_REMOVED: Msg = Msg::new("Removed corrupt crash report", "손상된 크래시 보고서 삭제");
+pub const CRASH_REPORT_SENT: Msg = Msg::new("Sent crash report from previous run", "이전 크래시 보고서 전송 완료");
+pub const CRASH_REPORT_SEND_FAILED: Msg = Msg::new("Failed to send crash report", "크래시 보고서 전송 실패");
+
+// telemetry
+pub const OTLP_INIT_FAILED: Msg = Msg::new("Failed to initialize OTLP trace exporter", "OTLP 트레이스 내보내기 초기화 실패");
+pub const OTLP_SHUTDOWN_FAILED: Msg = Msg::new("Failed to shut down OTLP trace exporter", "OTLP 트레이스 내보내기 종료 실패");
+
+// collector
+pub const FILE_OPEN_FAILED: Msg = Msg::new("Failed to open file", "파일 열기 실패");
+pub const FILE_REOPEN_FAILED: Msg = Msg::new("Failed to reopen file", "파일 재열기 실패");
+pub const FILE_SEEK_FAILED: Msg = Msg::new("Failed to seek file", "파일 포인터 이동 실패");
+pub const FILE_METADATA_FAILED: Msg = Msg::new("Failed to read file metadata", "파일 메타데이터 읽기 실패");
+pub const FILE_READ_FAILED: Msg = Msg::new("Error while reading file", "파일 읽기 중 오류");
+pub const FILE_REPLACED_DURING_RESTART: Msg = Msg::new("File replaced during restart, collecting from the beginning", "재실행 중 파일 교체 감지, 처음부터 수집");
+pub const WATCH_SETUP_FAILED: Msg = Msg::new("Failed to set up file watch", "파일 감지 설정 중 오류");
+pub const WATCH_STARTED: Msg = Msg::new("Watching file", "파일 감지 시작");
+pub const COLLECTOR_STOPPED: Msg = Msg::new("Collector stopped..", "Collector 종료..");
+pub const LINE_READ_FAILED: Msg = Msg::new("Failed to read line", "라인 읽기 실패");
+pub const ROTATION_DETECTED: Msg = Msg::new("Rotation detected", "Rotation 감지");
+pub const TRUNCATION_DETECTED: Msg = Msg::new("Truncation detected", "Truncation 감지");
+pub const FILE_DELETED: Msg = Msg::new("File deleted, forwarded remaining lines and stopped collecting", "파일 삭제 감지, 남은 라인 전달 후 수집 종료");
+pub const EVENT_CHANNEL_CLOSED: Msg = Msg::new("Event channel closed", "메세지 채널 닫힘");
+pub const LINE_DELIMITER_INVALID: Msg = Msg::new("line_delimiter must not be empty", "line_delimiter 는 비어 있을 수 없음");
+pub const TIMESTAMP_FORMAT_INVALID: Msg = Msg::new("Invalid timestamp_format", "잘못된 timestamp_format");
+pub const RECORD_FLUSH_FAILED: Msg = Msg::new("Failed to forward multiline record", "여러 줄 레코드 전달 실패");
+
+// settings
+pub const CONFIG_READ_FAILED: Msg = Msg::new("Failed to read config file", "설정 파일 읽기 실패");
+pub const CONFIG_PARSE_FAILED: Msg = Msg::new("Failed to parse config file", "설정 파일 파싱 실패");
+pub const CONFIG_VERSION_TOO_NEW: Msg = Msg::new("Config file version is newer than this agent supports, upgrade the agent", "설정 파일 버전이 에이전트가 지원하는 버전보다 높음, 에이전트를 업그레이드 하세요");
+pub const CONFIG_VERSION_INVALID: Msg = Msg::new("Invalid version field in config file", "설정 파일의 version 필드가 올바르지 않음");
+pub const CONFIG_NOT_MAPPING: Msg = Msg::new("Config file top level is not a mapping", "설정 파일 최상위가 매핑이 아님");
+pub const CONFIG_MIGRATION_FAILED: Msg = Msg::new("Config migration failed", "설정 마이그레이션 실패");
+pub const CONFIG_MIGRATED: Msg = Msg::new("Config file migrated", "설정 파일 마이그레이션 완료");
+pub const CONFIG_BACKUP_FAILED: Msg = Msg::new("Failed to back up config file", "설정 파일 백업 실패");
+pub const CONFIG_MIGRATED_SAVE_FAILED: Msg = Msg::new("Failed to save migrated config", "마이그레이션된 설정 저장 실패");
+pub const CONFIG_DESERIALIZE_FAILED: Msg = Msg::new("Failed to deserialize config file", "설정 파일 역직렬화 실패");
+pub const CONFIG_LOADED: Msg = Msg::new("Config loaded", "설정 로드 완료");
+pub const CONFIG_UNKNOWN_FIELD: Msg = Msg::new("Ignoring unknown config field", "알 수 없는 설정 필드 무시됨");
+pub const CONFIG_RESEAL_BACKED_UP: Msg = Msg::new("Rewrote config without comments, original kept in backup (may hold the previous project_key, delete after checking)", "설정 파일을 주석 없이 다시 씀, 원본은 백업에 남음 (이전 project_key 가 들어 있을 수 있으니 확인 후 삭제)");
+
+// hot_restart
+pub const HANDOVER_SPOOL_MISSING: Msg = Msg::new("Some spool files from before the restart are missing", "재실행 전 스풀 파일 일부 누락");
+pub const HANDOVER_SPOOL_RESUMING: Msg = Msg::new("Resuming resend of spool files from before the restart", "재실행 전 스풀 파일 이어서 재전송");
+pub const HANDOVER_SERIALIZE_FAILED: Msg = Msg::new("Failed to serialize handover", "핸드오버 직렬화 실패");
+pub const HANDOVER_WRITE_FAILED: Msg = Msg::new("Failed to write handover file", "핸드오버 파일 쓰기 실패");
+pub const HANDOVER_RENAME_FAILED: Msg = Msg::new("Failed to move handover file", "핸드오버 파일 이동 실패");
+pub const HANDOVER_LOADED: Msg = Msg::new("Loaded restart handover", "재실행 핸드오버 로드");
+pub const HANDOVER_PARSE_FAILED: Msg = Msg::new("Failed to parse handover file, ignoring", "핸드오버 파일 파싱 실패, 무시");
+pub const SIGUSR2_HANDLER_FAILED: Msg = Msg::new("Failed to register SIGUSR2 handler", "SIGUSR2 핸들러 등록 실패");
+pub const SIGUSR2_RECEIVED: Msg = Msg::new("SIGUSR2 received, preparing restart..", "SIGUSR2 감지, 재실행 준비..");
+pub const CURRENT_EXE_FAILED: Msg = Msg::new("Failed to resolve executable path", "실행 파일 경로 확인 실패");
+pub const RESTARTING_WITH_NEW_BINARY: Msg = Msg::new("Re-executing new binary", "새 바이너리로 재실행");
+pub const RESTART_FAILED: Msg = Msg::new("Re-exec failed", "재실행 실패");
+pub const RESTART_UNIX_ONLY: Msg = Msg::new("Hot restart is only supported on Unix", "재실행은 Unix 에서만 지원");
+
+// service
+pub const SERVICE_WINDOWS_ONLY: Msg = Msg::new("The service command is only supported on Windows", "service 명령은 Windows 에서만 지원됨");
+pub const SERVICE_DESCRIPTION: Msg = Msg::new("Lightweight log collection agent for distributed systems", "분산 시스템을 위한 경량 로그 수집 에이전트");
+pub const SERVICE_DISPATCHER_FAILED: Msg = Msg::new("Failed to start service dispatcher", "서비스 디스패처 시작 실패");
+pub const SERVICE_MANAGER_CONNECT_FAILED: Msg = Msg::new("Failed to connect to service manager", "서비스 매니저 연결 실패");
+pub const SERVICE_INSTALL_FAILED: Msg = Msg::new("Failed to install service", "서비스 등록 실패");
+pub const SERVICE_INSTALLED: Msg = Msg::new("Service installed", "서비스 등록 완료");
+pub const SERVICE_OPEN_FAILED: Msg = Msg::new("Failed to open service", "서비스 열기 실패");
+pub const SERVICE_STOP_FAILED: Msg = Msg::new("Failed to stop service", "서비스 중지 실패");
+pub const SERVICE_STOP_TIMEOUT: Msg = Msg::new("Timed out waiting for the service to stop", "서비스 중지 대기 시간 초과");
+pub const SERVICE_UNINSTALL_FAILED: Msg = Msg::new("Failed to uninstall service", "서비스 제거 실패");
+pub const SERVICE_UNINSTALLED: Msg = Msg::new("Service uninstalled", "서비스 제거 완료");
+pub const SERVICE_START_FAILED: Msg = Msg::new("Failed to start service", "서비스 시작 실패");
+pub const SERVICE_START_REQUESTED: Msg = Msg::new("Service start requested", "서비스 시작 요청 완료");
+pub const SERVICE_STOP_REQUESTED: Msg = Msg::new("Service stop requested", "서비스 중지 요청 완료");
+pub const SERVICE_RUN_FAILED: Msg = Msg::new("Service run failed", "서비스 실행 실패");
+pub const SERVICE_STOP_RECEIVED: Msg = Msg::new("Service stop request received", "서비스 중지 요청 수신");
+pub const SERVICE_HANDLER_FAILED: Msg = Msg::new("Failed to register service control handler", "서비스 컨트롤 핸들러 등록 실패");
+
+// admin
+pub const ADMIN_INVALID_COUNT: Msg = Msg::new("Invalid count", "잘못된 count");
+pub const ADMIN_UNKNOWN_TAP_ARG: Msg = Msg::new("Unknown tap argument", "알 수 없는 tap 인자");
+pub const ADMIN_EMPTY_COMMAND: Msg = Msg::new("Empty command", "빈 명령");
+pub const ADMIN_UNKNOWN_COMMAND: Msg = Msg::new("Unknown command", "알 수 없는 명령");
+pub const ADMIN_SOCKET_START_FAILED: Msg = Msg::new("Failed to start admin socket, admin API disabled", "관리 소켓 시작 실패, 관리 API 비활성화");
+pub const ADMIN_SOCKET_STARTED: Msg = Msg::new("Admin socket started", "관리 소켓 시작");
+pub const ADMIN_SOCKET_DISABLED_EPHEMERAL: Msg = Msg::new("Admin socket disabled in ephemeral mode", "상태 없는 실행이라 관리 소켓 사용 안 함");
+pub const ADMIN_ACCEPT_FAILED: Msg = Msg::new("Failed to accept admin socket connection", "관리 소켓 연결 수락 실패");
+pub const ADMIN_STALE_SOCKET_REMOVE_FAILED: Msg = Msg::new("Failed to remove stale socket file", "기존 소켓 파일 삭제 실패");
+pub const ADMIN_BIND_FAILED: Msg = Msg::new("Failed to bind socket", "소켓 바인드 실패");
+pub const ADMIN_PERMISSIONS_FAILED: Msg = Msg::new("Failed to set socket permissions", "소켓 권한 설정 실패");
+pub const ADMIN_COMMAND_RECEIVED: Msg = Msg::new("Admin command received", "관리 명령 수신");
+pub const TAP_LAGGED: Msg = Msg::new("Tap subscriber lagging, events skipped", "tap 구독자 지연, 이벤트 건너뜀");
+pub const ADMIN_CONNECT_FAILED: Msg = Msg::new("Failed to connect to admin socket, is the agent running?", "관리 소켓 연결 실패, 에이전트 실행 여부 확인");
+pub const ADMIN_RESPONSE_PARSE_FAILED: Msg = Msg::new("Failed to parse admin socket response", "관리 소켓 응답 파싱 실패");
+pub const ADMIN_COMMAND_FAILED: Msg = Msg::new("Admin command failed", "관리 명령 실패");
+pub const UNIX_ONLY_ADMIN: Msg = Msg::new("Admin socket is only supported on Unix", "관리 소켓은 Unix 에서만 지원");
+pub const ADMIN_NO_RESPONSE: Msg = Msg::new("No response from admin socket", "관리 소켓 응답 없음");
+
+// agent
+pub const AGENT_ALREADY_RUNNING: Msg = Msg::new(
+    "Only one Agent can run per process; drop the previous Agent first",
+    "한 프로세스에서는 Agent 를 하나만 실행할 수 있음, 이전 Agent 가 끝난 뒤 생성",
+);
+pub const GRPC_SETTINGS_REQUIRED: Msg = Msg::new("gRPC delivery requires server_addr and project_key", "gRPC 전송에는 server_addr, project_key 가 필요함");
+pub const SHUTDOWN_SIGNAL_RECEIVED: Msg = Msg::new("Shutdown signal received", "종료 신호 수신");
+pub const SHUTDOWN_SIGNAL_WHILE_DISCONNECTED: Msg = Msg::new("Shutdown signal received (server not connected)", "종료 신호 수신 (서버 미연결 상태)");
+pub const DRAIN_TIMEOUT_FORCED: Msg = Msg::new("Drain timeout exceeded, forcing shutdown", "드레인 제한 시간 초과, 강제 종료");
+pub const SPOOL_REMAINING_TIMEOUT: Msg = Msg::new("Timed out spooling remaining batches", "잔여 배치 스풀 저장 시간 초과");
+pub const AGENT_STOPPED: Msg = Msg::new("Agent stopped", "Agent 정상 종료");
+pub const CONNECTION_RECOVERED: Msg = Msg::new("Server connection recovered, resuming disk queue delivery", "서버 연결 복구, 디스크 큐 전송 재개");
+pub const CONNECT_RETRY_FAILED: Msg = Msg::new("Server connection retry failed", "서버 연결 재시도 실패");
+pub const CONNECT_FAILED_DEGRADED: Msg = Msg::new("Server connection failed, spooling to disk queue and retrying in background", "서버 연결 실패, 디스크 큐에 저장하며 백그라운드에서 재시도");
+pub const DISCONNECTED_SHUTDOWN_SPOOLED: Msg = Msg::new("Shutting down without server connection, spooling remaining batches", "서버 미연결 상태로 종료, 잔여 배치 스풀 저장");
+pub const SINK_CHANNEL_CLOSED: Msg = Msg::new("Sink channel closed, stopping batch relay", "Sink 채널 닫힘, 배치 전달 중단");
+pub const SAVED_TOKEN_AUTH_FAILED: Msg = Msg::new("Authentication with saved token failed, registering anew", "저장된 토큰으로 인증 실패, 신규 등록 수행");
+pub const TOKEN_LOADED: Msg = Msg::new("Token loaded", "토큰 로드 완료");
+pub const COLLECTOR_TASK_FAILED: Msg = Msg::new("Collector task terminated with error", "Collector 태스크 종료 오류");
+pub const HANDOVER_SAVED: Msg = Msg::new("Restart handover saved", "재실행 핸드오버 저장 완료");
+pub const HANDOVER_SKIPPED_EPHEMERAL: Msg = Msg::new("Ephemeral mode: restarting without handover, sources are read again", "상태 없는 실행: 핸드오버 없이 재실행, 수집 대상을 다시 읽음");
+pub const HANDOVER_SAVE_FAILED: Msg = Msg::new("Failed to save restart handover", "재실행 핸드오버 저장 실패");
+pub const SUPERVISOR_EXITED_UNEXPECTEDLY: Msg = Msg::new("Exited unexpectedly", "예기치 않게 종료");
+pub const SUPERVISOR_TASK_FAILED: Msg = Msg::new("Supervisor task error", "감독 태스크 오류");
+
+// doctor
+pub const DOCTOR_STATE_DIR: Msg = Msg::new("State directory writable", "상태 디렉터리 쓰기");
+pub const DOCTOR_SOURCE: Msg = Msg::new("Source file permissions", "수집 파일 권한");
+pub const DOCTOR_DNS: Msg = Msg::new("DNS lookup", "DNS 조회");
+pub const DOCTOR_TCP: Msg = Msg::new("TCP connect", "TCP 연결");
+pub const DOCTOR_GRPC: Msg = Msg::new("TLS/gRPC connect", "TLS/gRPC 연결");
+pub const DOCTOR_AUTH: Msg = Msg::new("Authentication", "인증");
+pub const DOCTOR_CLOCK_SKEW: Msg = Msg::new("Clock skew", "시계 오차");
+pub const DOCTOR_PREVIOUS_FAILED: Msg = Msg::new("Previous step failed", "이전 단계 실패");
+pub const DOCTOR_TLS_OK: Msg = Msg::new("TLS handshake and HTTP/2 connection succeeded", "TLS 핸드셰이크 및 HTTP/2 연결 성공");
+pub const DOCTOR_PLAINTEXT_OK: Msg = Msg::new("HTTP/2 connection succeeded (plaintext, no TLS)", "HTTP/2 연결 성공 (평문, TLS 미사용)");
+pub const DOCTOR_UNIX_SOCKET: Msg = Msg::new("Unix domain socket address, no name resolution", "Unix 도메인 소켓 주소, 이름 해석 없음");
+pub const DOCTOR_AUTH_OK: Msg = Msg::new("Authenticated with saved token", "저장된 토큰으로 인증 성공");
+pub const DOCTOR_AUTH_SESSION: Msg = Msg::new("Using running agent session", "실행 중인 에이전트 세션 사용");
+pub const DOCTOR_DIR_CREATE_FAILED: Msg = Msg::new("Failed to create directory", "디렉터리 생성 실패");
+pub const DOCTOR_FILE_WRITE_FAILED: Msg = Msg::new("Failed to write file", "파일 쓰기 실패");
+pub const DOCTOR_FILE_REMOVE_FAILED: Msg = Msg::new("Failed to remove file", "파일 삭제 실패");
+pub const DOCTOR_WRITABLE: Msg = Msg::new("Writable", "쓰기 가능");
+pub const DOCTOR_READABLE: Msg = Msg::new("Readable", "읽기 가능");
+pub const DOCTOR_UNREADABLE: Msg = Msg::new("Not readable", "읽기 불가");
+pub const DOCTOR_DIR_UNREADABLE: Msg = Msg::new("Directory not readable", "디렉터리 읽기 불가");
+pub const DOCTOR_OSLOG_AVAILABLE: Msg = Msg::new("Unified log available", "통합 로그 사용 가능");
+pub const DOCTOR_GELF_LISTEN: Msg = Msg::new("GELF listen address", "GELF 수신 주소");
+pub const DOCTOR_REDIS_REACHABLE: Msg = Msg::new("Redis reachable", "Redis 연결 가능");
+pub const DOCTOR_SQL_REACHABLE: Msg = Msg::new("Database reachable", "데이터베이스 연결 가능");
+pub const DOCTOR_SNMP_LISTEN: Msg = Msg::new("SNMP trap listen address", "SNMP 트랩 수신 주소");
+pub const DOCTOR_INVALID_SERVER_ADDR: Msg = Msg::new("Invalid server_addr", "잘못된 server_addr");
+pub const DOCTOR_NO_HOST: Msg = Msg::new("No host in server_addr", "server_addr 에 호스트 없음");
+pub const DOCTOR_DNS_TIMEOUT: Msg = Msg::new("DNS lookup timed out", "DNS 조회 시간 초과");
+pub const DOCTOR_DNS_FAILED: Msg = Msg::new("Lookup failed", "조회 실패");
+pub const DOCTOR_NO_ADDRESS: Msg = Msg::new("No address to connect to", "연결할 주소 없음");
+pub const DOCTOR_CONNECTED: Msg = Msg::new("Connected", "연결 성공");
+pub const DOCTOR_CONNECT_TIMEOUT: Msg = Msg::new("connection timed out", "연결 시간 초과");
+pub const DOCTOR_GRPC_FAILED: Msg = Msg::new("Failed to connect gRPC channel", "gRPC 채널 연결 실패");
+pub const DOCTOR_NO_DATE_HEADER: Msg = Msg::new("No date header in server response, cannot check", "서버 응답에 date 헤더 없음, 확인 불가");
+pub const DOCTOR_SKEW_VS_SERVER: Msg = Msg::new("Offset from server", "서버 대비");
+pub const DOCTOR_FAILED_COUNT: Msg = Msg::new("Checks failed", "실패한 항목 수");
+pub const DOCTOR_ALL_PASSED: Msg = Msg::new("All checks passed", "모든 항목 통과");
+pub const DOCTOR_RESULT: Msg = Msg::new("Self-check result", "자가 진단 결과");
+
+// main
+pub const SHUTDOWN_SIGNAL_WAIT_FAILED: Msg = Msg::new("Failed to wait for shutdown signal", "종료 시그널 대기 실패");
+pub const SIGNAL_RECEIVED: Msg = Msg::new("Signal received..", "시그널 감지..");
+pub const AGENT_STARTING: Msg = Msg::new("Starting agent..", "Agent 시작 중..");
+pub const STARTUP_CHECK_RUNNING: Msg = Msg::new("Running startup self-check..", "시작 시 자가 진단 실행 중..");
+pub const DRY_RUN_STARTING: Msg = Msg::new("Starting in dry-run mode..", "Dry-run 모드로 시작 중..");
+pub const DRY_RUN_CONFIG_REQUIRED: Msg = Msg::new("dry-run requires a config file", "dry-run 실행에는 설정 파일이 필요함");
+pub const DRY_RUN_FINISHED: Msg = Msg::new("Dry-run finished", "Dry-run 종료");
+pub const DOCTOR_CONFIG_REQUIRED: Msg = Msg::new("Self-check requires a config file", "자가 진단에는 설정 파일이 필요함");
+pub const CONFIG_MISSING_REGISTERING: Msg = Msg::new("Config file not found, registering agent", "설정 파일 없음, 에이전트 등록 수행");
+pub const AGENT_REGISTER_FAILED: Msg = Msg::new("Agent registration failed", "에이전트 등록 실패");
+pub const AGENT_REGISTERED_AND_SAVED: Msg = Msg::new("Agent registered and config saved", "에이전트 등록 및 설정 저장 완료");
+pub const ENV_MISSING: Msg = Msg::new("Environment variable not set", "환경 변수를 찾을 수 없음");
+pub const ENV_EMPTY: Msg = Msg::new("Environment variable is empty", "환경 변수가 비어 있음");
+
+// status 출력
+pub const STATUS_VERSION: Msg = Msg::new("Version", "버전");
+pub const STATUS_UPTIME: Msg = Msg::new("Uptime", "가동 시간");
+pub const STATUS_CONNECTION: Msg = Msg::new("Connection", "연결 상태");
+pub const STATUS_SOURCES: Msg = Msg::new("Sources", "수집 대상");
+pub const STATUS_QUEUES: Msg = Msg::new("Queues", "큐");
+pub const STATUS_EGRESS: Msg = Msg::new("Egress", "전송량");
+pub const STATUS_UNLIMITED: Msg = Msg::new("unlimited", "무제한");
+pub const STATUS_MEMORY: Msg = Msg::new("Memory", "메모리");
+pub const STATUS_TOKEN_EXPIRES: Msg = Msg::new("Token expires", "토큰 만료");
+pub const STATUS_LAST_SEND: Msg = Msg::new("Last send", "마지막 전송");
+pub const STATUS_SUBSYSTEMS: Msg = Msg::new("Subsystems", "서브시스템");
+pub const STATUS_RECENT_ERRORS: Msg = Msg::new("Recent warnings/errors", "최근 경고/오류");
+
+// log_throttle
+pub const LOG_REPEATED: Msg = Msg::new("Previous message repeated", "이전 메시지 반복");
+
+// capabilities
+pub const CAPABILITIES_NEGOTIATED: Msg = Msg::new(
+    "Negotiated protocol capabilities with server",
+    "서버와 프로토콜 기능 협상 완료",
+);
+pub const CAPABILITIES_LEGACY_SERVER: Msg = Msg::new(
+    "Server did not report capabilities, using legacy protocol",
+    "서버가 기능 정보를 보내지 않아 이전 프로토콜로 동작",
+);
+pub const BATCH_SPLIT: Msg = Msg::new(
+    "Batch exceeds server message size limit, splitting",
+    "배치가 서버 메시지 크기 제한을 넘어 분할 전송",
+);
+pub const LOG_TOO_LARGE: Msg = Msg::new(
+    "Single log exceeds server message size limit",
+    "로그 한 건이 서버 메시지 크기 제한을 넘음",
+);
+
+// control
+pub const CONTROL_CONNECTED: Msg = Msg::new("Control channel connected", "명령 채널 연결됨");
+pub const CONTROL_UNSUPPORTED: Msg = Msg::new(
+    "Server does not support control channel, continuing without it",
+    "서버가 명령 채널을 지원하지 않아 명령 채널 없이 동작",
+);
+pub const CONTROL_STREAM_CLOSED: Msg = Msg::new("Control channel closed by server", "서버가 명령 채널을 닫음");
+pub const CONTROL_DISCONNECTED: Msg = Msg::new("Control channel disconnected, reconnecting", "명령 채널 연결 끊김, 재연결 시도");
+pub const CONTROL_STOPPED: Msg = Msg::new("Control channel stopped", "명령 채널 종료");
+pub const CONTROL_COMMAND_RECEIVED: Msg = Msg::new("Received server command", "서버 명령 수신");
+pub const CONTROL_COMMAND_FAILED: Msg = Msg::new("Server command failed", "서버 명령 실행 실패");
+pub const CONTROL_UNKNOWN_COMMAND: Msg = Msg::new("Unsupported server command", "지원하지 않는 서버 명령");
+pub const CONTROL_RELOAD_SCHEDULED: Msg = Msg::new("Restarting to reload configuration", "설정을 다시 읽기 위해 재실행 예정");
+pub const CONTROL_RELOADING: Msg = Msg::new("Reloading configuration by server command", "서버 명령으로 설정 재적용");
+pub const CONTROL_UNKNOWN_SOURCE: Msg = Msg::new("Unknown source", "알 수 없는 수집 대상");
+pub const CONTROL_SOURCE_PAUSED: Msg = Msg::new("Source paused", "수집 일시 중지");
+pub const CONTROL_SOURCE_RESUMED: Msg = Msg::new("Source resumed", "수집 재개");
+pub const CONTROL_FLUSH_REQUESTED: Msg = Msg::new("Flush requested", "즉시 플러시 요청");
+pub const CONTROL_SELF_TEST_PASSED: Msg = Msg::new("Self-test passed", "자가 진단 통과");
+pub const CONTROL_SELF_TEST_FAILED: Msg = Msg::new("Self-test failed checks", "자가 진단 실패 항목 수");
+pub const LOG_LEVEL_UNAVAILABLE: Msg = Msg::new("Log level control is not initialized", "로그 레벨 제어가 초기화되지 않음");
+
+// bench
+pub const BENCH_MODE_STARTING: Msg = Msg::new("Starting in bench mode..", "벤치마크 모드로 시작 중..");
+pub const BENCH_STARTED: Msg = Msg::new("Writing benchmark lines", "벤치마크 라인 기록 시작");
+pub const BENCH_SETUP_FAILED: Msg = Msg::new("Failed to prepare benchmark source", "벤치마크 수집 대상 준비 실패");
+pub const BENCH_WRITE_FAILED: Msg = Msg::new("Failed to write benchmark lines", "벤치마크 라인 기록 실패");
+pub const BENCH_INCOMPLETE: Msg = Msg::new("Benchmark stopped before all lines were collected", "모든 라인을 수집하기 전에 벤치마크 중단");
+pub const BENCH_LINES: Msg = Msg::new("Lines", "라인 수");
+pub const BENCH_BATCHES: Msg = Msg::new("Batches", "배치 수");
+pub const BENCH_ELAPSED: Msg = Msg::new("Elapsed", "소요 시간");
+pub const BENCH_THROUGHPUT: Msg = Msg::new("Throughput", "처리량");
+
+// uring
+pub const URING_STARTED: Msg = Msg::new("io_uring read backend started", "io_uring 읽기 백엔드 시작");
+pub const URING_UNAVAILABLE: Msg = Msg::new("io_uring is unavailable, using standard file reads", "io_uring 을 사용할 수 없어 기본 파일 읽기 사용");
+pub const URING_THREAD_EXITED: Msg = Msg::new("io_uring runtime thread exited", "io_uring 런타임 스레드 종료됨");
+pub const READ_BACKEND_UNSUPPORTED: Msg = Msg::new("read_backend io_uring requires a Linux build with the io-uring feature, using standard file reads", "read_backend io_uring 은 io-uring 기능을 켠 Linux 빌드에서만 지원, 기본 파일 읽기 사용");
+
+// oslog
+pub const OSLOG_STARTED: Msg = Msg::new("Streaming unified log", "통합 로그 수집 시작");
+pub const OSLOG_UNAVAILABLE: Msg = Msg::new("Unified log is only available on macOS (log command not found)", "통합 로그는 macOS 에서만 사용 가능 (log 명령 없음)");
+pub const OSLOG_SPAWN_FAILED: Msg = Msg::new("Failed to run log stream", "log stream 실행 실패");
+pub const OSLOG_EXITED: Msg = Msg::new("log stream exited, restarting shortly", "log stream 종료, 잠시 후 다시 실행");
+
+// gelf
+pub const GELF_STARTED: Msg = Msg::new("Listening for GELF messages", "GELF 메시지 수신 시작");
+pub const GELF_BIND_FAILED: Msg = Msg::new("Failed to bind GELF listen address", "GELF 수신 주소 바인드 실패");
+pub const GELF_INVALID_LISTEN: Msg = Msg::new("Invalid GELF listen address", "잘못된 GELF 수신 주소");
+pub const GELF_RECEIVE_FAILED: Msg = Msg::new("Failed to receive GELF message", "GELF 메시지 수신 실패");
+pub const GELF_INVALID_MESSAGE: Msg = Msg::new("Dropped invalid GELF message", "잘못된 GELF 메시지 버림");
+pub const GELF_DECOMPRESS_FAILED: Msg = Msg::new("Failed to decompress GELF message", "GELF 메시지 압축 해제 실패");
+pub const GELF_INVALID_JSON: Msg = Msg::new("GELF message is not valid JSON", "GELF 메시지가 올바른 JSON 아님");
+pub const GELF_CHUNKS_EXPIRED: Msg = Msg::new("Dropped incomplete chunked GELF messages", "완성되지 않은 GELF 청크 메시지 버림");
+
+// redis
+pub const REDIS_STARTED: Msg = Msg::new("Reading Redis streams", "Redis 스트림 수집 시작");
+pub const REDIS_NOT_CONFIGURED: Msg = Msg::new("redis_stream source requires redis.streams", "redis_stream 수집 대상에 redis.streams 설정 필요");
+pub const REDIS_CONNECT_FAILED: Msg = Msg::new("Failed to connect to Redis", "Redis 연결 실패");
+pub const REDIS_AUTH_FAILED: Msg = Msg::new("Redis authentication failed", "Redis 인증 실패");
+pub const REDIS_DISCONNECTED: Msg = Msg::new("Redis connection lost, reconnecting shortly", "Redis 연결 끊김, 잠시 후 다시 연결");
+pub const REDIS_CONNECTION_CLOSED: Msg = Msg::new("Connection closed by Redis", "Redis 가 연결을 닫음");
+pub const REDIS_PROTOCOL_ERROR: Msg = Msg::new("Unexpected Redis reply", "예상하지 못한 Redis 응답");
+pub const REDIS_GROUP_CREATED: Msg = Msg::new("Created Redis consumer group", "Redis 컨슈머 그룹 생성");
+pub const REDIS_GROUP_CREATE_FAILED: Msg = Msg::new("Failed to create Redis consumer group", "Redis 컨슈머 그룹 생성 실패");
+pub const REDIS_ACK_FAILED: Msg = Msg::new("Failed to acknowledge Redis stream entries", "Redis 스트림 항목 ACK 실패");
+pub const REDIS_STATE_SAVE_FAILED: Msg = Msg::new("Failed to save Redis stream position", "Redis 스트림 위치 저장 실패");
+
+// sql
+pub const SQL_STARTED: Msg = Msg::new("Polling SQL query", "SQL 쿼리 폴링 시작");
+pub const SQL_NOT_CONFIGURED: Msg = Msg::new("sql source requires sql.url, sql.query and sql.cursor_column", "sql 수집 대상에 sql.url, sql.query, sql.cursor_column 설정 필요");
+pub const SQL_UNSUPPORTED_URL: Msg = Msg::new("Unsupported database URL (postgres:// or mysql://)", "지원하지 않는 데이터베이스 URL (postgres:// 또는 mysql://)");
+pub const SQL_INVALID_URL: Msg = Msg::new("Invalid database URL", "잘못된 데이터베이스 URL");
+pub const SQL_CONNECT_FAILED: Msg = Msg::new("Failed to connect to database", "데이터베이스 연결 실패");
+pub const SQL_QUERY_FAILED: Msg = Msg::new("SQL query failed, retrying at next poll", "SQL 쿼리 실패, 다음 주기에 다시 실행");
+pub const SQL_CURSOR_MISSING: Msg = Msg::new("Query result has no usable cursor column", "쿼리 결과에 사용할 수 있는 커서 컬럼 없음");
+pub const SQL_STATE_SAVE_FAILED: Msg = Msg::new("Failed to save SQL cursor", "SQL 커서 저장 실패");
+
+// snmp_trap
+pub const SNMP_STARTED: Msg = Msg::new("Listening for SNMP traps", "SNMP 트랩 수신 시작");
+pub const SNMP_BIND_FAILED: Msg = Msg::new("Failed to bind SNMP trap listen address (ports below 1024 need CAP_NET_BIND_SERVICE)", "SNMP 트랩 수신 주소 바인드 실패 (1024 미만 포트는 CAP_NET_BIND_SERVICE 필요)");
+pub const SNMP_INVALID_LISTEN: Msg = Msg::new("Invalid SNMP trap listen address", "잘못된 SNMP 트랩 수신 주소");
+pub const SNMP_NOT_CONFIGURED: Msg = Msg::new("snmp_trap source requires snmp.communities or snmp.users", "snmp_trap 수집 대상에 snmp.communities 또는 snmp.users 설정 필요");
+pub const SNMP_INVALID_USER: Msg = Msg::new("SNMPv3 user needs both auth_protocol and an auth_password of at least 8 characters", "SNMPv3 사용자에 auth_protocol 과 8자 이상 auth_password 가 함께 필요");
+pub const SNMP_RECEIVE_FAILED: Msg = Msg::new("Failed to receive SNMP trap", "SNMP 트랩 수신 실패");
+pub const SNMP_RESPONSE_FAILED: Msg = Msg::new("Failed to acknowledge SNMP inform", "SNMP 인폼 응답 실패");
+pub const SNMP_INVALID_MESSAGE: Msg = Msg::new("Dropped SNMP message", "SNMP 메시지 버림");
+pub const SNMP_UNSUPPORTED_VERSION: Msg = Msg::new("Unsupported SNMP version (v2c and v3 only)", "지원하지 않는 SNMP 버전 (v2c, v3 만 지원)");
+pub const SNMP_UNSUPPORTED_PDU: Msg = Msg::new("Not a trap or inform PDU", "트랩이나 인폼 PDU 아님");
+pub const SNMP_UNSUPPORTED_SECURITY_MODEL: Msg = Msg::new("Unsupported SNMPv3 security model (USM only)", "지원하지 않는 SNMPv3 보안 모델 (USM 만 지원)");
+pub const SNMP_UNKNOWN_COMMUNITY: Msg = Msg::new("Unknown SNMP community", "알 수 없는 SNMP 커뮤니티");
+pub const SNMP_UNKNOWN_USER: Msg = Msg::new("Unknown SNMPv3 user", "알 수 없는 SNMPv3 사용자");
+pub const SNMP_PRIV_UNSUPPORTED: Msg = Msg::new("Encrypted SNMPv3 messages are not supported", "암호화된 SNMPv3 메시지는 지원하지 않음");
+pub const SNMP_SECURITY_LEVEL_MISMATCH: Msg = Msg::new("SNMPv3 security level does not match user", "SNMPv3 보안 수준이 사용자 설정과 다름");
+pub const SNMP_AUTH_FAILED: Msg = Msg::new("SNMPv3 authentication failed", "SNMPv3 인증 실패");
+pub const SNMP_TRUNCATED: Msg = Msg::new("Truncated BER data", "BER 데이터가 잘림");
+pub const SNMP_UNEXPECTED_TAG: Msg = Msg::new("Unexpected BER tag", "예상하지 않은 BER 태그");
+pub const SNMP_INVALID_INTEGER: Msg = Msg::new("Invalid BER integer", "잘못된 BER 정수");
+pub const SNMP_INVALID_OID: Msg = Msg::new("Invalid OID", "잘못된 OID");
+
+// routing
+pub const ROUTE_UNKNOWN_SINK: Msg = Msg::new("Unknown route sink", "알 수 없는 라우팅 Sink");
+pub const ROUTE_AVAILABLE_SINKS: Msg = Msg::new("available", "사용 가능");
+pub const ROUTE_INVALID_CONDITION: Msg = Msg::new("Invalid route condition", "잘못된 라우팅 조건식");
+pub const ROUTE_UNEXPECTED_CHAR: Msg = Msg::new("Unexpected character", "예상하지 않은 문자");
+pub const ROUTE_UNEXPECTED_TOKEN: Msg = Msg::new("Unexpected token", "예상하지 않은 토큰");
+pub const ROUTE_UNEXPECTED_END: Msg = Msg::new("Condition ended unexpectedly", "조건식이 중간에 끝남");
+pub const ROUTE_UNTERMINATED_STRING: Msg = Msg::new("Unterminated string literal", "닫히지 않은 문자열");
+pub const ROUTE_UNCLOSED_PAREN: Msg = Msg::new("Missing closing parenthesis", "닫는 괄호 없음");
+pub const ROUTE_EXPECTED_OPERATOR: Msg = Msg::new("Expected a comparison operator", "비교 연산자 필요");
+pub const ROUTE_INVALID_NUMBER: Msg = Msg::new("Invalid number", "잘못된 숫자");
+
+// sinks
+pub const SINK_NAME_REQUIRED: Msg = Msg::new("sinks[].name is required", "sinks[].name 설정 필요");
+pub const SINK_NAME_RESERVED: Msg = Msg::new("Sink name is reserved", "예약된 Sink 이름");
+pub const SINK_NAME_DUPLICATE: Msg = Msg::new("Duplicate sink name", "중복된 Sink 이름");
+pub const SINK_PATH_REQUIRED: Msg = Msg::new("kind: file sink requires path", "kind: file Sink 에는 path 설정 필요");
+pub const SINK_URL_REQUIRED: Msg = Msg::new("kind: http sink requires url", "kind: http Sink 에는 url 설정 필요");
+pub const SINK_HTTP_CLIENT_FAILED: Msg = Msg::new("Failed to create sink HTTP client", "Sink HTTP 클라이언트 생성 실패");
+pub const SINK_HTTP_SEND_FAILED: Msg = Msg::new("Failed to send batch to sink", "Sink 로 배치 전송 실패");
+pub const SINK_HTTP_REJECTED: Msg = Msg::new("Sink rejected batch", "Sink 가 배치 거부");
+pub const SINK_FILE_OPEN_FAILED: Msg = Msg::new("Failed to open sink file", "Sink 파일 열기 실패");
+pub const NAMED_SINK_STARTED: Msg = Msg::new("Sink started", "Sink 시작");
+pub const NAMED_SINK_STOPPED: Msg = Msg::new("Sink stopped", "Sink 종료");
+pub const NAMED_SINK_RETRYING: Msg = Msg::new("Failed to write batch to sink, retrying", "Sink 에 배치 쓰기 실패, 재시도");
+pub const NAMED_SINK_DROPPED: Msg = Msg::new("Failed to write batch to sink, dropping", "Sink 에 배치 쓰기 실패, 버림");
+pub const NAMED_SINK_CLOSED: Msg = Msg::new("Sink channel closed, dropping routed event", "Sink 채널이 닫혀 라우팅된 이벤트 버림");
+
+// anonymize
+pub const ANONYMIZE_SALT_REQUIRED: Msg = Msg::new("anonymize.salt is required for hash fields", "hash 필드에는 anonymize.salt 설정 필요");
+
+// sampling
+pub const SAMPLING_INCREASED: Msg = Msg::new("Pipeline under pressure, sampling low-severity events", "파이프라인 역압, 낮은 레벨 이벤트 샘플링 강화");
+pub const SAMPLING_RELAXED: Msg = Msg::new("Pipeline pressure eased, relaxing sampling", "파이프라인 역압 완화, 샘플링 완화");
+
+// anomaly
+pub const VOLUME_ANOMALY_DETECTED: Msg = Msg::new("Log volume anomaly detected", "로그 양 이상 감지");
+pub const VOLUME_ANOMALY_RECOVERED: Msg = Msg::new("Log volume back to normal", "로그 양 정상 복귀");
+
+// endpoint
+pub const UNIX_SOCKET_PATH_REQUIRED: Msg = Msg::new("unix:// server address requires a socket path", "unix:// 서버 주소에는 소켓 경로 필요");
+pub const UNIX_SOCKET_UNSUPPORTED: Msg = Msg::new("Unix domain sockets are not supported on this platform", "이 플랫폼은 Unix 도메인 소켓 미지원");
+pub const TLS_CONFIG_FAILED: Msg = Msg::new("Failed to configure TLS", "TLS 설정 실패");
+pub const TLS_INVALID_AUTHORITY: Msg = Msg::new("Invalid tls.authority", "잘못된 tls.authority");
+
+// retry
+pub const RETRY_BUDGET_EXHAUSTED: Msg = Msg::new("Retry budget for this minute exhausted, giving up", "이번 분의 재시도 예산 소진, 재시도 중단");
+pub const RPC_RETRYING: Msg = Msg::new("Request failed with a transient error, retrying", "일시적 오류로 요청 실패, 재시도");
+pub const LOG_SEND_RETRYING: Msg = Msg::new("Log send failed with a transient error, retrying", "일시적 오류로 로그 전송 실패, 재시도");
+
+// sink_health
+pub const SINK_UNHEALTHY: Msg = Msg::new("Sink failing repeatedly, applying backpressure", "Sink 연속 실패, 역압 적용");
+pub const SINK_RECOVERED: Msg = Msg::new("Sink recovered", "Sink 복구");
+pub const FORWARDER_PAUSED_SINK_UNHEALTHY: Msg = Msg::new("Sink unhealthy, pausing batch production until queued batches drain", "Sink 비정상, 대기 배치가 빠질 때까지 배치 생성 중단");
+pub const FORWARDER_RESUMED: Msg = Msg::new("Resuming batch production", "배치 생성 재개");
+pub const STATUS_SINKS: Msg = Msg::new("Sinks", "Sink");
+
+// batch_id
+pub const BATCH_SEQUENCE_SAVE_FAILED: Msg = Msg::new("Failed to save batch sequence, ids may repeat after restart", "배치 순번 저장 실패, 재실행 후 식별자가 겹칠 수 있음");
+
+// http_fallback
+pub const HTTP_FALLBACK_URL_REQUIRED: Msg = Msg::new("http_fallback.url is required", "http_fallback.url 설정 필요");
+pub const HTTP_FALLBACK_CLIENT_FAILED: Msg = Msg::new("Failed to create HTTP fallback client", "HTTP 대체 전송 클라이언트 생성 실패");
+pub const HTTP_FALLBACK_ENGAGED: Msg = Msg::new("gRPC delivery keeps failing, switching to HTTP fallback", "gRPC 전송 연속 실패, HTTP 대체 전송으로 전환");
+pub const HTTP_FALLBACK_RETRYING_GRPC: Msg = Msg::new("Retrying gRPC delivery after HTTP fallback period", "HTTP 대체 전송 기간 종료, gRPC 전송 재시도");
+pub const HTTP_FALLBACK_SEND_FAILED: Msg = Msg::new("HTTP fallback request failed", "HTTP 대체 전송 요청 실패");
+pub const HTTP_FALLBACK_REJECTED: Msg = Msg::new("HTTP fallback endpoint rejected batch", "HTTP 대체 전송 주소가 배치 거부");
+pub const LOG_SENT_HTTP_FALLBACK: Msg = Msg::new("Logs sent via HTTP fallback", "HTTP 대체 전송으로 로그 전송 완료");
+
+// spiffe
+pub const SPIFFE_CONNECT_FAILED: Msg = Msg::new("Failed to connect to SPIFFE Workload API", "SPIFFE Workload API 연결 실패");
+pub const SPIFFE_FETCH_FAILED: Msg = Msg::new("Failed to fetch X.509 SVID", "X.509 SVID 조회 실패");
+pub const SPIFFE_STREAM_CLOSED: Msg = Msg::new("SPIFFE Workload API stream closed", "SPIFFE Workload API 스트림 종료");
+pub const SPIFFE_NO_SVID: Msg = Msg::new("Workload API returned no SVID for this workload", "Workload API 가 이 워크로드의 SVID 를 주지 않음");
+pub const SPIFFE_INVALID_DER: Msg = Msg::new("Invalid DER certificate data in SVID", "SVID 의 DER 인증서 데이터가 잘못됨");
+pub const SPIFFE_SVID_UPDATED: Msg = Msg::new("X.509 SVID updated", "X.509 SVID 갱신");
+pub const SPIFFE_WATCH_FAILED: Msg = Msg::new("SVID watch interrupted, reconnecting", "SVID 감시 중단, 재연결");
+pub const CLIENT_IDENTITY_REQUIRES_HTTPS: Msg = Msg::new("Client certificate authentication requires an https:// server", "클라이언트 인증서 인증은 https:// 서버 필요");
+pub const CLIENT_IDENTITY_NOT_READY: Msg = Msg::new("Client certificate (SVID) not received yet", "클라이언트 인증서(SVID) 를 아직 받지 못함");
+
+// oauth2
+pub const OAUTH2_SETTINGS_REQUIRED: Msg = Msg::new("auth.oauth2 requires token_url and client_id", "auth.oauth2 에 token_url, client_id 설정 필요");
+pub const OAUTH2_CLIENT_FAILED: Msg = Msg::new("Failed to create OAuth2 HTTP client", "OAuth2 HTTP 클라이언트 생성 실패");
+pub const OAUTH2_REQUEST_FAILED: Msg = Msg::new("OAuth2 token request failed", "OAuth2 토큰 요청 실패");
+pub const OAUTH2_TOKEN_REJECTED: Msg = Msg::new("OAuth2 token endpoint rejected request", "OAuth2 토큰 엔드포인트가 요청 거부");
+pub const OAUTH2_INVALID_RESPONSE: Msg = Msg::new("Invalid OAuth2 token response", "OAuth2 토큰 응답이 잘못됨");
+pub const OAUTH2_TOKEN_ISSUED: Msg = Msg::new("OAuth2 access token issued", "OAuth2 액세스 토큰 발급");
+pub const OAUTH2_REFRESH_FAILED: Msg = Msg::new("OAuth2 token refresh failed, retrying", "OAuth2 토큰 갱신 실패, 재시도");
+
+// token lifecycle
+pub const TOKEN_EXPIRING_SOON: Msg = Msg::new("Access token expires soon", "액세스 토큰 만료 임박");
+pub const STATUS_TOKEN: Msg = Msg::new("Token", "토큰");
+
+// governor
+pub const GOVERNOR_THROTTLING: Msg = Msg::new("Host under resource pressure, throttling reads and compression", "호스트 자원 압박, 읽기 속도와 압축 감속");
+pub const GOVERNOR_RELAXED: Msg = Msg::new("Host resource pressure eased, throttling lifted", "호스트 자원 압박 해소, 감속 해제");
+
+// disk_guard
+pub const DISK_SPACE_LOW: Msg = Msg::new("Free disk space below min_free_disk_mb", "디스크 여유 공간이 min_free_disk_mb 미만");
+pub const DISK_SPACE_LOW_REDUCED_MODE: Msg = Msg::new("Free disk space low, stopped writing spool and checkpoints (in-memory only, overflow dropped)", "디스크 여유 공간 부족, 디스크 큐와 체크포인트 쓰기 중지 (메모리에서만 처리, 넘치면 버림)");
+pub const DISK_SPACE_RECOVERED: Msg = Msg::new("Free disk space recovered, resumed writing state", "디스크 여유 공간 회복, 상태 쓰기 재개");
+pub const STATUS_DISK: Msg = Msg::new("Disk free", "디스크 여유");
+
+// crash_loop
+pub const CRASH_LOOP_DETECTED: Msg = Msg::new("Agent is restarting repeatedly, delaying start", "에이전트 재시작 반복 감지, 시작 지연");
+pub const SUBSYSTEM_CRASH_LOOP: Msg = Msg::new("Subsystem is restarting repeatedly", "서브시스템 재시작 반복 감지");
+pub const SUBSYSTEM_CRASH_LOOP_CLEARED: Msg = Msg::new("Subsystem stable again", "서브시스템 재시작 반복 해소");
+pub const RESTARTS_SAVE_FAILED: Msg = Msg::new("Failed to save restart history", "재시작 기록 저장 실패");
+pub const STATUS_CRASH_LOOP: Msg = Msg::new("Crash loop", "재시작 반복");
+
+// optional source
+pub const OPTIONAL_SOURCE_MISSING: Msg = Msg::new("Optional source file not found, waiting for it in the background", "선택 수집 대상 파일 없음, 생길 때까지 백그라운드에서 확인");
+pub const OPTIONAL_SOURCE_APPEARED: Msg = Msg::new("Optional source file appeared, collecting from the beginning", "선택 수집 대상 파일 생성 감지, 처음부터 수집");
+
+// cli overrides
+pub const CLI_OVERRIDES_APPLIED: Msg = Msg::new("Command-line overrides applied (config file unchanged)", "명령줄 지정 값으로 설정 덮어씀 (설정 파일은 그대로)");
+
+// key_store
+pub const PROJECT_KEY_ENCRYPT_FAILED: Msg = Msg::new("Failed to encrypt project_key", "project_key 암호화 실패");
+pub const PROJECT_KEY_DECRYPT_FAILED: Msg = Msg::new("Failed to decrypt project_key (config copied from another host or host id changed?), put the plain project_key back in the config", "project_key 복호화 실패 (다른 호스트에서 복사했거나 호스트 식별자가 바뀐 설정?), 설정 파일에 평문 project_key 를 다시 넣어야 함");
+pub const MACHINE_ID_UNAVAILABLE: Msg = Msg::new("No host id (machine-id, MachineGuid, IOPlatformUUID) on this host, use project_key_storage: env instead", "이 호스트의 식별자(machine-id, MachineGuid, IOPlatformUUID)를 읽을 수 없음, project_key_storage: env 사용 필요");
+pub const PROJECT_KEY_LOAD_FAILED: Msg = Msg::new("Failed to load project_key", "project_key 읽기 실패");
+pub const PROJECT_KEY_RESEAL_FAILED: Msg = Msg::new("Failed to rewrite config for project_key_storage", "project_key_storage 에 맞게 설정 파일 다시 쓰기 실패");
+pub const PROJECT_KEY_RESEALED: Msg = Msg::new("Rewrote project_key in config for project_key_storage", "project_key_storage 에 맞게 설정 파일의 project_key 다시 씀");
+
+// profiles
+pub const PROFILE_NOT_FOUND: Msg = Msg::new("Config profile not found", "설정 프로필 없음");
+pub const PROFILE_REQUIRES_CONFIG: Msg = Msg::new("no config file to select it from, register without --profile first", "고를 설정 파일이 없음, 먼저 --profile 없이 등록 필요");
+pub const PROFILE_APPLIED: Msg = Msg::new("Config profile applied", "설정 프로필 적용");
+
+// collector_manager
+pub const COLLECTOR_MANAGER_NOT_RUNNING: Msg = Msg::new("Collectors are not running", "Collector 관리자가 실행 중이 아님");
+pub const COLLECTOR_MANAGER_REQUEST_FAILED: Msg = Msg::new("Failed to change running sources", "실행 중 수집 대상 변경 실패");
+pub const SOURCE_ALREADY_RUNNING: Msg = Msg::new("Source with this label is already running", "같은 라벨의 수집 대상이 이미 실행 중");
+pub const SOURCE_ADDED: Msg = Msg::new("Source added", "수집 대상 추가");
+pub const SOURCE_REMOVED: Msg = Msg::new("Source removed", "수집 대상 제거");
+pub const SOURCES_SYNCED: Msg = Msg::new("Sources synced with config", "설정에 맞춰 수집 대상 갱신");
+pub const RELOAD_CONFIG_MISSING: Msg = Msg::new(
+    "No config file or RLOG_CONFIG to reload (settings came from registration)",
+    "다시 읽을 설정 파일이나 RLOG_CONFIG 가 없음 (등록으로 받은 설정)",
+);
+pub const ADMIN_INVALID_SOURCE: Msg = Msg::new("Invalid source JSON", "잘못된 수집 대상 JSON");
+
+// updater
+pub const UPDATE_DOWNLOADING: Msg = Msg::new("New agent version available, downloading", "새 에이전트 버전 확인, 다운로드 중");
+pub const UPDATE_INSTALLED: Msg = Msg::new("Verified and installed new agent binary", "서명 검증 후 새 에이전트 바이너리 설치");
+pub const UPDATE_RESTARTING: Msg = Msg::new("Restarting into the updated binary", "업데이트한 바이너리로 재실행 예정");
+pub const UPDATE_ALREADY_CURRENT: Msg = Msg::new("Agent is already at this version or newer", "이미 같거나 더 새 버전");
+pub const UPDATE_FAILED: Msg = Msg::new("Self-update failed", "자체 업데이트 실패");
+pub const UPDATE_NOT_CONFIGURED: Msg = Msg::new("Self-update is not configured (update.public_key)", "자체 업데이트 설정 없음 (update.public_key)");
+pub const UPDATE_MANIFEST_FAILED: Msg = Msg::new("Failed to fetch update manifest", "업데이트 매니페스트 조회 실패");
+pub const UPDATE_NO_ARTIFACT: Msg = Msg::new("Update manifest has no binary for this target", "업데이트 매니페스트에 이 대상의 바이너리 없음");
+pub const UPDATE_DOWNLOAD_FAILED: Msg = Msg::new("Failed to download agent binary", "에이전트 바이너리 다운로드 실패");
+pub const UPDATE_BINARY_TOO_LARGE: Msg = Msg::new("Agent binary exceeds the download size limit", "에이전트 바이너리가 다운로드 크기 상한 초과");
+pub const UPDATE_INVALID_PUBLIC_KEY: Msg = Msg::new("Invalid update public key (base64 Ed25519 expected)", "잘못된 업데이트 공개키 (base64 Ed25519 필요)");
+pub const UPDATE_SIGNATURE_INVALID: Msg = Msg::new("Agent binary signature verification failed", "에이전트 바이너리 서명 검증 실패");
+pub const UPDATE_INSTALL_FAILED: Msg = Msg::new("Failed to replace agent binary", "에이전트 바이너리 교체 실패");
+pub const UPDATE_BACKUP_FAILED: Msg = Msg::new("Failed to keep previous agent binary", "이전 에이전트 바이너리 보관 실패");
+
+// admin spool
+pub const ADMIN_SPOOL_TARGET_REQUIRED: Msg = Msg::new("Specify a queue (spool, dead_letter) or batch file names", "큐(spool, dead_letter) 또는 배치 파일 이름 필요");
+pub const ADMIN_UNKNOWN_SPOOL_ACTION: Msg = Msg::new("Unknown spool action (list, resend, purge)", "알 수 없는 spool 명령 (list, resend, purge)");
+pub const SPOOL_MOVE_FAILED: Msg = Msg::new("Failed to move spooled batch", "디스크 큐 배치 이동 실패");
+
+// state_meta
+pub const STATE_MIGRATING: Msg = Msg::new("Migrating state directory format", "상태 디렉터리 형식 변환 중");
+pub const STATE_MIGRATION_FAILED: Msg = Msg::new("State migration failed", "상태 형식 변환 실패");
+pub const STATE_SCHEMA_TOO_NEW: Msg = Msg::new("State directory was written by a newer agent, refusing to start", "더 새 에이전트가 만든 상태 디렉터리, 시작하지 않음");
+pub const STATE_META_READ_FAILED: Msg = Msg::new("Failed to read state/meta.json", "state/meta.json 읽기 실패");
+pub const STATE_META_WRITE_FAILED: Msg = Msg::new("Failed to write state/meta.json", "state/meta.json 쓰기 실패");
+pub const STATE_TMP_REMOVE_FAILED: Msg = Msg::new("Failed to remove leftover temporary state file", "남은 임시 상태 파일 삭제 실패");
+
+// clock
+pub const CLOCK_SKEW_DETECTED: Msg = Msg::new("Local clock differs from server beyond clock_skew.threshold_secs", "로컬 시계가 서버와 clock_skew.threshold_secs 이상 차이남");
+pub const CLOCK_SKEW_RESOLVED: Msg = Msg::new("Local clock back within threshold of server", "로컬 시계 오차가 기준 안으로 돌아옴");
+pub const STATUS_CLOCK_SKEW: Msg = Msg::new("Clock skew vs server", "서버 대비 시계 오차");
+pub const STATUS_CLOCK_CORRECTING: Msg = Msg::new("(correcting timestamps)", "(타임스탬프 보정 중)");
+
+// watch_health
+pub const WATCHER_RECREATED: Msg = Msg::new("File watcher stopped delivering events, recreated it", "파일 감시자가 이벤트를 전달하지 않아 다시 생성");
+pub const WATCHER_POLLING: Msg = Msg::new("File watcher unreliable, falling back to polling", "파일 감시자를 믿을 수 없어 폴링으로 전환");
+pub const WATCH_POLLING_NETWORK: Msg = Msg::new("Polling file instead of watching (poll setting or network share)", "감시 대신 폴링 (poll 설정 또는 네트워크 공유)");
+pub const STATUS_WATCHERS: Msg = Msg::new("File watchers", "파일 감시자");
+
+// ephemeral
+pub const EPHEMERAL_MODE: Msg = Msg::new("Ephemeral mode: configuration from RLOG_CONFIG, nothing persisted", "상태 없는 실행: RLOG_CONFIG 로 설정, 디스크에 쓰지 않음");
+pub const EPHEMERAL_REGISTERING: Msg = Msg::new("Ephemeral mode: registering to fetch configuration, nothing persisted", "상태 없는 실행: 등록해 설정을 받음, 디스크에 쓰지 않음");
+pub const EPHEMERAL_NOT_PERSISTED: Msg = Msg::new("Not persisted in ephemeral mode", "상태 없는 실행이라 저장하지 않음");
+
+// state_store
+pub const STATE_STORE_READ_FAILED: Msg = Msg::new("Failed to read from state store", "상태 보관소 읽기 실패");
+pub const STATE_STORE_WRITE_FAILED: Msg = Msg::new("Failed to write to state store", "상태 보관소 쓰기 실패");
+pub const STATE_STORE_OPEN_FAILED: Msg = Msg::new("Failed to open SQLite state store", "SQLite 상태 보관소 열기 실패");
+pub const STATE_STORE_SQLITE_UNAVAILABLE: Msg = Msg::new("state_store.backend sqlite requires building with the sqlite-state feature", "state_store.backend sqlite 는 sqlite-state 기능으로 빌드해야 함");
+
+// invalid_utf8
+pub const INVALID_UTF8_SKIPPED: Msg = Msg::new("Dropped record with invalid UTF-8 (invalid_utf8: skip)", "UTF-8 이 아닌 레코드를 버림 (invalid_utf8: skip)");
+pub const STATUS_INVALID_UTF8_SKIPPED: Msg = Msg::new("Invalid UTF-8 records dropped", "UTF-8 이 아니라 버린 레코드");
+
+// schedule
+pub const SCHEDULE_WINDOW_INVALID: Msg = Msg::new("Invalid schedule window (expected [days] HH:MM-HH:MM)", "잘못된 schedule 구간 ([요일] HH:MM-HH:MM 형식)");
+pub const SCHEDULE_WINDOW_OPENED: Msg = Msg::new("Schedule window opened", "schedule 구간 열림");
+pub const SCHEDULE_WINDOW_CLOSED: Msg = Msg::new("Schedule window closed", "schedule 구간 닫힘");
+pub const SCHEDULE_DEFERRED_RELEASED: Msg = Msg::new("Moved deferred batches to the spool for sending", "보류한 배치를 전송하려고 디스크 큐로 옮김");
+pub const SCHEDULE_RELEASE_FAILED: Msg = Msg::new("Failed to move deferred batches to the spool", "보류한 배치를 디스크 큐로 옮기지 못함");
+pub const SCHEDULE_DEFER_FAILED: Msg = Msg::new("Could not store deferred batch, sending it now", "보류할 배치를 저장하지 못해 바로 전송");
+pub const STATUS_WINDOW_CLOSED: Msg = Msg::new("outside schedule", "구간 밖");
+
+// replay
+pub const REPLAY_STARTING: Msg = Msg::new("Starting replay..", "재생 시작 중..");
+pub const REPLAY_CONFIG_REQUIRED: Msg = Msg::new("replay requires a config file unless --dry-run is given", "replay 는 --dry-run 이 아니면 설정 파일이 필요함");
+pub const REPLAY_SPEED_INVALID: Msg = Msg::new("Invalid replay speed (expected max or a factor like 10x)", "잘못된 재생 속도 (max 또는 10x 같은 배속)");
+pub const REPLAY_SETUP_FAILED: Msg = Msg::new("Failed to prepare replay source", "재생 수집 대상 준비 실패");
+pub const REPLAY_FILE_OPEN_FAILED: Msg = Msg::new("Failed to open replay file", "재생할 파일 열기 실패");
+pub const REPLAY_READ_FAILED: Msg = Msg::new("Failed to read replay file", "재생할 파일 읽기 실패");
+pub const REPLAY_WRITE_FAILED: Msg = Msg::new("Failed to write replay lines", "재생 라인 기록 실패");
+pub const REPLAY_NO_TIMESTAMP_FORMAT: Msg = Msg::new("Source has no timestamp_format, replaying at max speed", "수집 대상에 timestamp_format 이 없어 최대 속도로 재생");
+pub const REPLAY_STARTED: Msg = Msg::new("Replaying file through the pipeline", "파일을 파이프라인으로 재생 시작");
+pub const REPLAY_FINISHED: Msg = Msg::new("Replay finished", "재생 종료");
+pub const REPLAY_LINES: Msg = Msg::new("Lines", "라인 수");
+pub const REPLAY_BYTES: Msg = Msg::new("Bytes", "바이트");
+pub const REPLAY_ELAPSED: Msg = Msg::new("Elapsed", "소요 시간");
+
+// loadgen
+pub const LOADGEN_STARTING: Msg = Msg::new("Starting load generator..", "부하 생성 시작 중..");
+pub const LOADGEN_CONFIG_REQUIRED: Msg = Msg::new("loadgen requires a config file with the server to send to", "loadgen 은 전송할 서버가 있는 설정 파일이 필요함");
+pub const LOADGEN_RATE_INVALID: Msg = Msg::new("Invalid load rate (expected lines per second like 50k/s)", "잘못된 부하 속도 (50k/s 같은 초당 라인 수)");
+pub const LOADGEN_SETUP_FAILED: Msg = Msg::new("Failed to prepare load generator source", "부하 생성 수집 대상 준비 실패");
+pub const LOADGEN_WRITE_FAILED: Msg = Msg::new("Failed to write synthetic lines", "합성 라인 기록 실패");
+pub const LOADGEN_STARTED: Msg = Msg::new("Generating synthetic load", "합성 부하 생성 시작");
+pub const LOADGEN_GENERATED: Msg = Msg::new("Generated", "생성");
+pub const LOADGEN_SENT: Msg = Msg::new("Sent", "전송");
+pub const LOADGEN_ELAPSED: Msg = Msg::new("Elapsed", "소요 시간");
+pub const LOADGEN_THROUGHPUT: Msg = Msg::new("Throughput", "처리량");
+
+// test_server
+pub const TEST_SERVER_STARTED: Msg = Msg::new("Test server listening", "테스트 서버 수신 시작");
+pub const TEST_SERVER_BIND_FAILED: Msg = Msg::new("Failed to bind test server address", "테스트 서버 주소 바인드 실패");
+pub const TEST_SERVER_FAILED: Msg = Msg::new("Test server stopped with error", "테스트 서버가 오류로 멈춤");
+pub const TEST_SERVER_BATCH_RECEIVED: Msg = Msg::new("Test server received batch", "테스트 서버가 배치를 받음");
+pub const TEST_SERVER_TOKEN_REJECTED: Msg = Msg::new("Token not issued by test server or expired", "테스트 서버가 발급하지 않았거나 만료된 토큰");
+pub const TEST_SERVER_INJECTED_FAILURE: Msg = Msg::new("Injected test server failure", "테스트 서버가 흉내 낸 실패");
+
+// chaos
+pub const CHAOS_ENABLED: Msg = Msg::new("Fault injection enabled, do not use in production", "장애 주입 활성화, 운영 환경에서는 쓰지 말 것");
+pub const CHAOS_SPEC_INVALID: Msg = Msg::new("Invalid --chaos entry (expected key=value)", "잘못된 --chaos 항목 (key=value 형식)");
+pub const CHAOS_INJECTED: Msg = Msg::new("Injected fault", "주입한 장애");
+
+// error_code
+pub const STATUS_ERROR_CODES: Msg = Msg::new("Error codes", "오류 코드");
+
+// self_log
+pub const SELF_LOG_STARTED: Msg = Msg::new("Shipping agent logs", "에이전트 자체 로그 수집 시작");
+pub const SELF_LOG_ALREADY_RUNNING: Msg = Msg::new("Only one self source can run", "자체 로그 수집 대상은 하나만 실행 가능");
+pub const DOCTOR_SELF_LOG_LEVEL: Msg = Msg::new("Agent logs at or above", "보낼 에이전트 로그 레벨");
+
+// durability
+pub const DURABILITY_SYNC_FAILED: Msg = Msg::new("Failed to fsync file or directory on shutdown", "종료 시 파일/디렉터리 fsync 실패");
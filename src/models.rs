@@ -1,8 +1,71 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 
+use crate::parser::Fields;
+
+/// Collector 가 수집한 로그 한 줄
 #[derive(Debug)]
 pub struct LogEvent {
     pub label: String,
-    pub content: String,
+    /// 개행 제외 라인, Collector 읽기 버퍼를 공유하는 조각 (항상 UTF-8)
+    pub content: Bytes,
+    pub timestamp: DateTime<Utc>,
+    /// 수집 파일 경로 (같은 Collector 의 이벤트끼리 공유)
+    pub path: Arc<str>,
+    /// 라인 시작 바이트 위치
+    pub offset: u64,
+    /// 읽은 시점의 파일 식별자 (로테이션 구분)
+    pub file_id: u64,
+    /// 수집 대상 형식 파서가 뽑은 구조화 필드, 원문 형식이면 비어 있음
+    pub fields: Fields,
+}
+
+impl LogEvent {
+    pub fn line(&self) -> &str {
+        std::str::from_utf8(&self.content).unwrap_or_default()
+    }
+}
+
+/// 프로세서(헬스, 로그 기반 카운터 등)가 만든 수치 이벤트, Forwarder 가 로그와 함께 배치로 묶음
+#[derive(Debug, Clone)]
+pub struct MetricEvent {
+    pub name: String,
+    pub value: f64,
+    pub kind: MetricKind,
+    pub labels: BTreeMap<String, String>,
     pub timestamp: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Gauge,
+    Counter,
+}
+
+impl MetricEvent {
+    pub fn gauge(name: impl Into<String>, value: f64) -> Self {
+        Self::new(name, value, MetricKind::Gauge)
+    }
+
+    pub fn counter(name: impl Into<String>, value: f64) -> Self {
+        Self::new(name, value, MetricKind::Counter)
+    }
+
+    fn new(name: impl Into<String>, value: f64, kind: MetricKind) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            kind,
+            labels: BTreeMap::new(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+}
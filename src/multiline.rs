@@ -0,0 +1,127 @@
+//! 여러 줄 레코드 묶기
+//!
+//! 형식(`format`)이 정한 첫 줄이 나올 때까지 이어지는 줄을 하나의 레코드로 모음.
+//! 레코드는 형식이 정한 마지막 줄이 오거나, 다음 첫 줄이 오거나, 일정 시간 새 줄이 없을 때 완성
+
+use std::collections::VecDeque;
+
+use bytes::{Bytes, BytesMut};
+use tokio::time::{Duration, Instant};
+
+use crate::parser;
+use crate::settings::SourceFormat;
+
+/// 마지막 줄 이후 이 시간 동안 이어지는 줄이 없으면 레코드 완성
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 첫 줄 없이 끝없이 이어지는 파일에서 레코드가 무한히 커지지 않도록 하는 상한
+const MAX_RECORD_LINES: usize = 1000;
+const MAX_RECORD_BYTES: usize = 1024 * 1024;
+
+/// 완성된 레코드 (줄 사이는 `\n`), `offset` 은 첫 줄 시작 위치
+#[derive(Debug)]
+pub struct Record {
+    pub content: Bytes,
+    pub offset: u64,
+}
+
+struct Pending {
+    content: BytesMut,
+    offset: u64,
+    lines: usize,
+    updated: Instant,
+}
+
+impl Pending {
+    fn append(&mut self, line: &[u8]) {
+        self.content.extend_from_slice(b"\n");
+        self.content.extend_from_slice(line);
+        self.lines += 1;
+        self.updated = Instant::now();
+    }
+}
+
+pub struct Multiline {
+    format: SourceFormat,
+    pending: Option<Pending>,
+    /// 완성되어 [`Multiline::pop`] 으로 꺼내기를 기다리는 레코드
+    done: VecDeque<Record>,
+}
+
+impl Multiline {
+    pub fn new(format: SourceFormat) -> Self {
+        Self {
+            format,
+            pending: None,
+            done: VecDeque::new(),
+        }
+    }
+
+    /// 한 줄 추가, 완성된 레코드는 [`Multiline::pop`] 으로 꺼냄
+    ///
+    /// `line` 은 개행을 뺀 UTF-8 라인
+    pub fn push(&mut self, line: Bytes, offset: u64) {
+        // 빈 줄은 새 레코드를 시작하지 않음 (쿼리 중간의 빈 줄은 유지, 레코드 끝의 빈 줄은 완성 시 제거)
+        if line.trim_ascii().is_empty() {
+            if let Some(pending) = self.pending.as_mut() {
+                pending.append(&line);
+            }
+            return;
+        }
+
+        let text = std::str::from_utf8(&line).unwrap_or_default();
+        let pending_text = self
+            .pending
+            .as_ref()
+            .map(|p| std::str::from_utf8(&p.content).unwrap_or_default())
+            .unwrap_or_default();
+
+        let full = self.pending.as_ref().is_some_and(|p| {
+            p.lines >= MAX_RECORD_LINES || p.content.len() + line.len() >= MAX_RECORD_BYTES
+        });
+
+        if self.pending.is_none() || full || parser::starts_record(self.format, text, pending_text) {
+            if let Some(record) = self.take() {
+                self.done.push_back(record);
+            }
+            self.pending = Some(Pending {
+                content: BytesMut::from(&line[..]),
+                offset,
+                lines: 1,
+                updated: Instant::now(),
+            });
+        } else if let Some(pending) = self.pending.as_mut() {
+            pending.append(&line);
+        }
+
+        let ends = self.pending.as_ref().is_some_and(|p| {
+            parser::ends_record(self.format, text, std::str::from_utf8(&p.content).unwrap_or_default())
+        });
+        if ends && let Some(record) = self.take() {
+            self.done.push_back(record);
+        }
+    }
+
+    /// 완성된 레코드를 순서대로 꺼냄
+    pub fn pop(&mut self) -> Option<Record> {
+        self.done.pop_front()
+    }
+
+    /// 묶고 있던 레코드를 완성으로 보고 꺼냄 (대기 시간 초과, 종료, 파일 교체 시)
+    pub fn take(&mut self) -> Option<Record> {
+        self.pending.take().map(|mut pending| {
+            let len = pending.content.trim_ascii_end().len();
+            pending.content.truncate(len);
+
+            Record {
+                content: pending.content.freeze(),
+                offset: pending.offset,
+            }
+        })
+    }
+
+    /// 묶고 있는 레코드를 완성으로 볼 시각, 없으면 None
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|p| p.updated + IDLE_TIMEOUT)
+    }
+}
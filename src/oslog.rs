@@ -0,0 +1,210 @@
+//! macOS 통합 로그 수집 (`kind: oslog`)
+//!
+//! `log stream --style ndjson` 을 실행해 한 줄에 하나씩 나오는 JSON 항목을 [`LogEvent`] 로 변환.
+//! 서브시스템/카테고리/프로세스는 구조화 필드로 싣고, 명령이 종료되면 잠시 후 다시 실행
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc::Sender;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::{self, Fields};
+use crate::settings::{SourceFormat, SourceSettings};
+use crate::status;
+
+pub const LOG_COMMAND: &str = "/usr/bin/log";
+
+/// 이벤트와 상태 조회에 표시할 경로
+const OSLOG_PATH: &str = "oslog";
+
+/// `log stream` 이 종료된 뒤 다시 실행하기까지 대기 시간
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// `log stream --style ndjson` 항목 (사용하는 필드만)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    #[serde(default)]
+    event_type: String,
+    #[serde(default)]
+    timestamp: String,
+    #[serde(default)]
+    event_message: String,
+    #[serde(default)]
+    message_type: String,
+    #[serde(default)]
+    subsystem: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    process_image_path: String,
+    #[serde(default, rename = "processID")]
+    process_id: u64,
+    /// 부팅 후 단조 증가하는 시각, 이벤트 위치(`offset`)로 사용
+    #[serde(default)]
+    mach_timestamp: u64,
+}
+
+pub struct OsLogCollector {
+    tx: Sender<LogEvent>,
+    label: String,
+    predicate: Option<String>,
+    format: SourceFormat,
+    path: Arc<str>,
+    /// 지금까지 전달한 항목 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+}
+
+impl OsLogCollector {
+    pub fn new(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        if !Path::new(LOG_COMMAND).exists() {
+            bail!("{}: {}", msg::OSLOG_UNAVAILABLE, source.label);
+        }
+
+        status::registry().update_source(&source.label, Path::new(OSLOG_PATH), 0);
+
+        Ok(Self {
+            tx,
+            label: source.label,
+            predicate: source.predicate,
+            format: source.format,
+            path: Arc::from(OSLOG_PATH),
+            forwarded: 0,
+            budget,
+        })
+    }
+
+    /// `shutdown` 취소 시까지 `log stream` 출력을 수집
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, "{}", msg::OSLOG_STARTED);
+
+        loop {
+            match self.stream(&shutdown).await {
+                Ok(()) if shutdown.is_cancelled() => break,
+                Ok(()) => warn!(source = %self.label, "{}", msg::OSLOG_EXITED),
+                Err(e) => warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::OSLOG_EXITED),
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = time::sleep(RESTART_DELAY) => {}
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    /// `log stream` 한 번 실행, 종료되거나 `shutdown` 될 때까지 항목 전달
+    async fn stream(&mut self, shutdown: &CancellationToken) -> Result<()> {
+        let mut command = Command::new(LOG_COMMAND);
+        command.args(["stream", "--style", "ndjson"]);
+        if let Some(predicate) = &self.predicate {
+            command.args(["--predicate", predicate]);
+        }
+
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .context(msg::OSLOG_SPAWN_FAILED)?;
+        let stdout = child.stdout.take().context(msg::OSLOG_SPAWN_FAILED)?;
+        let mut lines = BufReader::new(stdout).lines();
+
+        loop {
+            let line = tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                line = lines.next_line() => line.context(msg::LINE_READ_FAILED)?,
+            };
+            let Some(line) = line else {
+                return Ok(());
+            };
+
+            // 첫 줄의 안내 문구(`Filtering the log data ...`) 등 JSON 이 아닌 줄은 건너뜀
+            let Ok(entry) = serde_json::from_str::<Entry>(&line) else {
+                continue;
+            };
+            if entry.event_type != "logEvent" {
+                continue;
+            }
+            // 파일과 달리 다시 읽을 수 없으므로 일시 중지 중 항목은 버림
+            if collector::is_paused(&self.label) {
+                continue;
+            }
+
+            self.budget.wait_for_capacity(shutdown).await;
+            self.send_event(entry).await?;
+        }
+    }
+
+    async fn send_event(&mut self, entry: Entry) -> Result<()> {
+        if entry.event_message.trim().is_empty() {
+            return Ok(());
+        }
+
+        let mut fields = entry_fields(&entry);
+        fields.extend(parser::parse(self.format, &entry.event_message));
+
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(entry.event_message),
+            timestamp: parse_timestamp(&entry.timestamp).unwrap_or_else(Utc::now),
+            path: Arc::clone(&self.path),
+            offset: entry.mach_timestamp,
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        self.forwarded += 1;
+        status::registry().update_source(&self.label, Path::new(OSLOG_PATH), self.forwarded);
+
+        Ok(())
+    }
+}
+
+fn entry_fields(entry: &Entry) -> Fields {
+    let process = entry
+        .process_image_path
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+
+    [
+        ("subsystem", entry.subsystem.as_str()),
+        ("category", entry.category.as_str()),
+        ("process", process),
+        ("message_type", entry.message_type.as_str()),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_empty())
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .chain((entry.process_id != 0).then(|| ("pid".to_string(), entry.process_id.to_string())))
+    .collect()
+}
+
+/// `2026-01-01 09:00:00.123456+0900`
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S%.f%z")
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
@@ -0,0 +1,40 @@
+//! 크기 상한을 넘는 이벤트 자르기 (`max_event_bytes`)
+//!
+//! 파싱/익명화가 끝난 이벤트가 상한을 넘으면 gRPC 메시지 크기 제한으로 배치 전체가 실패하지 않도록
+//! 큰 필드부터 버려 필드를 상한의 절반 이하로 줄이고, 라인은 남은 크기에 맞춰 자름.
+//! 자른 이벤트에는 `truncated=true` 와 원래 크기 `original_bytes` 필드를 붙임
+
+use bytes::Bytes;
+
+use crate::models::LogEvent;
+
+/// `truncated`, `original_bytes` 필드 몫으로 남겨 두는 크기
+const ANNOTATION_BYTES: usize = 64;
+
+/// 상한을 넘으면 잘라서 true, 자른 뒤 크기는 `max_bytes` 이하
+pub fn truncate(event: &mut LogEvent, max_bytes: usize) -> bool {
+    let mut fields_bytes: usize = event.fields.iter().map(|(k, v)| k.len() + v.len()).sum();
+    let original = event.content.len() + fields_bytes;
+    if original <= max_bytes {
+        return false;
+    }
+
+    let limit = max_bytes.saturating_sub(ANNOTATION_BYTES);
+    while fields_bytes > limit / 2
+        && let Some(largest) = (0..event.fields.len()).max_by_key(|&i| event.fields[i].0.len() + event.fields[i].1.len())
+    {
+        let (key, value) = event.fields.remove(largest);
+        fields_bytes -= key.len() + value.len();
+    }
+
+    let line_limit = limit - fields_bytes;
+    if event.content.len() > line_limit {
+        // content 는 항상 UTF-8, 글자 중간에서 자르지 않음
+        let end = event.line().floor_char_boundary(line_limit);
+        event.content = Bytes::copy_from_slice(&event.content[..end]);
+    }
+
+    event.fields.push(("truncated".to_string(), "true".to_string()));
+    event.fields.push(("original_bytes".to_string(), original.to_string()));
+    true
+}
@@ -0,0 +1,145 @@
+//! Linux auditd 로그 (`/var/log/audit/audit.log`)
+//!
+//! ```text
+//! type=SYSCALL msg=audit(1767225600.123:4242): arch=c000003e syscall=257 success=yes exe="/usr/bin/cat" key="secrets"
+//! type=CWD msg=audit(1767225600.123:4242): cwd="/root"
+//! type=PATH msg=audit(1767225600.123:4242): item=0 name="/etc/shadow" inode=1234 nametype=NORMAL
+//! type=EOE msg=audit(1767225600.123:4242):
+//! ```
+//!
+//! 같은 audit ID(`시각:일련번호`)의 레코드를 한 이벤트로 묶음.
+//! 첫 레코드의 `key=value` 는 그대로, 이후 레코드는 타입 이름(`cwd.cwd`, `path.name`, 같은 타입이 반복되면 `path1.name`)을 붙여 필드로 추출
+
+use super::{Fields, logfmt};
+
+const ID_PREFIX: &str = "msg=audit(";
+
+pub fn starts_record(line: &str, pending: &str) -> bool {
+    match (audit_id(line), audit_id(pending)) {
+        (Some(id), Some(pending_id)) => id != pending_id,
+        _ => true,
+    }
+}
+
+/// 여러 레코드 이벤트는 `type=EOE` 로 끝남 (단일 레코드 이벤트는 다음 ID 나 대기 시간 초과로 완성)
+pub fn ends_record(line: &str) -> bool {
+    record_type(line) == Some("EOE")
+}
+
+pub fn parse(record: &str) -> Fields {
+    let Some(id) = audit_id(record) else {
+        return Fields::new();
+    };
+
+    let mut fields = Fields::new();
+    if let Some((time, serial)) = id.split_once(':') {
+        fields.push(("audit_time".to_string(), time.to_string()));
+        fields.push(("audit_serial".to_string(), serial.to_string()));
+    }
+
+    let mut types: Vec<&str> = Vec::new();
+    for line in record.lines() {
+        let Some(record_type) = record_type(line) else {
+            continue;
+        };
+        // 여러 레코드 이벤트의 끝 표시, 내용 없음
+        if record_type == "EOE" {
+            continue;
+        }
+
+        let prefix = if types.is_empty() {
+            None
+        } else {
+            let repeated = types.iter().filter(|t| **t == record_type).count();
+            let name = record_type.to_ascii_lowercase();
+            Some(if repeated == 0 { name } else { format!("{}{}", name, repeated) })
+        };
+        types.push(record_type);
+
+        for (key, value) in payload_fields(line) {
+            let key = match &prefix {
+                Some(prefix) => format!("{}.{}", prefix, key),
+                None => key,
+            };
+            fields.push((key, value));
+        }
+    }
+
+    fields.push(("audit_types".to_string(), types.join(",")));
+    fields
+}
+
+/// `msg=audit(1767225600.123:4242):` 의 괄호 안
+fn audit_id(text: &str) -> Option<&str> {
+    let start = text.find(ID_PREFIX)? + ID_PREFIX.len();
+    let len = text[start..].find(')')?;
+    Some(&text[start..start + len])
+}
+
+/// `type=SYSCALL` (원격 수집 로그의 `node=host` 머리말은 건너뜀)
+fn record_type(line: &str) -> Option<&str> {
+    let start = line.find("type=")? + "type=".len();
+    line[start..].split_whitespace().next()
+}
+
+/// `msg=audit(...):` 뒤의 `key=value`, 사용자 공간 레코드의 `msg='op=... res=success'` 는 펼쳐서 추출
+fn payload_fields(line: &str) -> Fields {
+    let Some(id_end) = line.find("):") else {
+        return Fields::new();
+    };
+    // 보강(enriched) 형식은 원문과 해석된 값(`AUID="root"`) 사이를 0x1d 로 구분
+    let payload = line[id_end + 2..].replace('\x1d', " ");
+
+    let Some((before, rest)) = payload.split_once("msg='") else {
+        return logfmt::parse(&payload);
+    };
+    let (inner, after) = rest.split_once('\'').unwrap_or((rest, ""));
+
+    let mut fields = logfmt::parse(before);
+    fields.extend(logfmt::parse(inner));
+    fields.extend(logfmt::parse(after));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    const SYSCALL: &str = r#"type=SYSCALL msg=audit(1767225600.123:4242): syscall=257 success=yes exe="/usr/bin/cat""#;
+    const CWD: &str = r#"type=CWD msg=audit(1767225600.123:4242): cwd="/root""#;
+    const PATH0: &str = r#"type=PATH msg=audit(1767225600.123:4242): item=0 name="/etc/shadow""#;
+    const PATH1: &str = r#"type=PATH msg=audit(1767225600.123:4242): item=1 name="/lib64/ld.so""#;
+    const EOE: &str = "type=EOE msg=audit(1767225600.123:4242): ";
+
+    #[test]
+    fn groups_records_by_audit_id() {
+        assert!(!starts_record(CWD, SYSCALL));
+        assert!(starts_record("type=SYSCALL msg=audit(1767225601.000:4243): syscall=2", SYSCALL));
+        assert!(ends_record(EOE));
+        assert!(!ends_record(PATH0));
+    }
+
+    #[test]
+    fn prefixes_later_records_with_their_type() {
+        let record = [SYSCALL, CWD, PATH0, PATH1, EOE].join("\n");
+        let fields = parse(&record);
+
+        assert_eq!(field(&fields, "audit_time"), Some("1767225600.123"));
+        assert_eq!(field(&fields, "audit_serial"), Some("4242"));
+        assert_eq!(field(&fields, "exe"), Some("/usr/bin/cat"));
+        assert_eq!(field(&fields, "cwd.cwd"), Some("/root"));
+        assert_eq!(field(&fields, "path.name"), Some("/etc/shadow"));
+        assert_eq!(field(&fields, "path1.name"), Some("/lib64/ld.so"));
+        assert_eq!(field(&fields, "audit_types"), Some("SYSCALL,CWD,PATH,PATH"));
+    }
+
+    #[test]
+    fn expands_user_space_message() {
+        let fields = parse("type=USER_LOGIN msg=audit(1767225600.000:1): pid=1 msg='op=login acct=\"alice\" res=success'");
+
+        assert_eq!(field(&fields, "op"), Some("login"));
+        assert_eq!(field(&fields, "acct"), Some("alice"));
+        assert_eq!(field(&fields, "res"), Some("success"));
+    }
+}
@@ -0,0 +1,129 @@
+//! ArcSight CEF (Common Event Format)
+//!
+//! ```text
+//! Jan 01 00:00:00 fw01 CEF:0|Vendor|Firewall|1.0|100|Connection blocked|7|src=10.0.0.1 dst=10.0.0.2 dpt=443 msg=blocked by rule 12
+//! ```
+//!
+//! 앞의 syslog 머리말은 건너뛰고, 헤더 7개와 확장 `key=value` 를 필드로 추출.
+//! 확장 값에는 공백이 올 수 있어 다음 `key=` 직전까지가 값
+
+use super::{Fields, split_header};
+
+const MARKER: &str = "CEF:";
+
+const HEADER_KEYS: [&str; 7] = [
+    "cef_version",
+    "device_vendor",
+    "device_product",
+    "device_version",
+    "signature_id",
+    "name",
+    "severity",
+];
+
+pub fn parse(line: &str) -> Fields {
+    let Some(start) = line.find(MARKER) else {
+        return Fields::new();
+    };
+
+    let (header, extension) = split_header(&line[start + MARKER.len()..], HEADER_KEYS.len());
+    if header.len() < HEADER_KEYS.len() {
+        return Fields::new();
+    }
+
+    let mut fields: Fields = HEADER_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .zip(header)
+        .collect();
+    parse_extension(extension, &mut fields);
+
+    fields
+}
+
+/// `src=10.0.0.1 msg=blocked by rule 12`, 값 안의 `=` 는 `\=` 로 이스케이프됨
+fn parse_extension(extension: &str, fields: &mut Fields) {
+    let bytes = extension.as_bytes();
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+
+    // 이스케이프되지 않은 `=` 마다 바로 앞 단어가 키 (공백 없이 이어진 `=` 는 값의 일부)
+    let mut escaped = false;
+    for (i, &b) in bytes.iter().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        match b {
+            b'\\' => escaped = true,
+            b'=' => {
+                let key_start = extension[..i].rfind(' ').map_or(0, |p| p + 1);
+                let after_previous = pairs.last().is_none_or(|&(_, eq)| key_start > eq);
+                if key_start < i && after_previous {
+                    pairs.push((key_start, i));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (n, &(key_start, eq)) in pairs.iter().enumerate() {
+        let value_end = pairs
+            .get(n + 1)
+            .map_or(extension.len(), |&(next_key, _)| next_key);
+        let value = extension[eq + 1..value_end].trim_end();
+
+        fields.push((extension[key_start..eq].to_string(), unescape(value)));
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(escaped) => out.push(escaped),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn parses_header_after_syslog_prefix() {
+        let fields = parse("Jan 01 00:00:00 fw01 CEF:0|Vendor|Fire\\|wall|1.0|100|Connection blocked|7|src=10.0.0.1");
+
+        assert_eq!(field(&fields, "cef_version"), Some("0"));
+        assert_eq!(field(&fields, "device_product"), Some("Fire|wall"));
+        assert_eq!(field(&fields, "severity"), Some("7"));
+        assert_eq!(field(&fields, "src"), Some("10.0.0.1"));
+    }
+
+    #[test]
+    fn extension_values_keep_spaces_and_escapes() {
+        let fields = parse("CEF:0|V|P|1|1|N|3|msg=blocked by rule\\=12 dpt=443 request=/a b");
+
+        assert_eq!(field(&fields, "msg"), Some("blocked by rule=12"));
+        assert_eq!(field(&fields, "dpt"), Some("443"));
+        assert_eq!(field(&fields, "request"), Some("/a b"));
+    }
+
+    #[test]
+    fn incomplete_header_has_no_fields() {
+        assert!(parse("CEF:0|Vendor|Product").is_empty());
+        assert!(parse("not a cef line").is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+//! CRI 컨테이너 로그 (containerd/CRI-O, Kubernetes 노드의 `/var/log/pods/...`)
+//!
+//! ```text
+//! 2026-01-01T00:00:00.123456789Z stdout P first half of a long li
+//! 2026-01-01T00:00:00.123456789Z stdout F ne that was split
+//! 2026-01-01T00:00:01.000000000Z stderr F error: something failed
+//! ```
+//!
+//! `시각 스트림 플래그 메시지` 머리말을 벗기고, 플래그 `P`(partial) 로 나뉜 줄은 `F`(full) 까지 이어 붙여 원래 한 줄로 복원
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+
+use super::Frame;
+
+/// 직전 줄이 `P` 로 끝났으면 같은 레코드
+pub fn starts_record(pending: &str) -> bool {
+    let last = pending.rsplit('\n').next().unwrap_or_default();
+    !matches!(split(last), Some((_, _, "P", _)))
+}
+
+/// `F` 줄이면 나뉜 줄의 끝
+pub fn ends_record(line: &str) -> bool {
+    matches!(split(line), Some((_, _, "F", _)))
+}
+
+pub fn unframe(record: Bytes) -> Frame {
+    let Ok(text) = std::str::from_utf8(&record) else {
+        return Frame::raw(record);
+    };
+
+    let mut lines = text.split('\n');
+    let Some((timestamp, stream, _, first)) = lines.next().and_then(split) else {
+        return Frame::raw(record);
+    };
+
+    let mut content = BytesMut::with_capacity(record.len());
+    content.put_slice(first.as_bytes());
+    for line in lines {
+        match split(line) {
+            Some((_, _, _, message)) => content.put_slice(message.as_bytes()),
+            // 머리말이 없는 줄은 그대로 (형식이 깨진 파일)
+            None => content.put_slice(line.as_bytes()),
+        }
+    }
+
+    Frame {
+        content: content.freeze(),
+        timestamp: DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        fields: vec![("stream".to_string(), stream.to_string())],
+    }
+}
+
+/// (시각, 스트림, 플래그, 메시지), 메시지가 빈 줄이면 빈 문자열
+fn split(line: &str) -> Option<(&str, &str, &str, &str)> {
+    let mut parts = line.splitn(4, ' ');
+    let timestamp = parts.next()?;
+    let stream = parts.next()?;
+    let flag = parts.next()?;
+    let message = parts.next().unwrap_or_default();
+
+    if !matches!(stream, "stdout" | "stderr") || !matches!(flag, "P" | "F") {
+        return None;
+    }
+    Some((timestamp, stream, flag, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn joins_partial_lines() {
+        let first = "2026-01-01T00:00:00.123456789Z stdout P first half of a long li";
+        let last = "2026-01-01T00:00:00.123456789Z stdout F ne that was split";
+
+        assert!(!starts_record(first));
+        assert!(!ends_record(first));
+        assert!(ends_record(last));
+
+        let frame = unframe(Bytes::from(format!("{}\n{}", first, last)));
+        assert_eq!(frame.content, "first half of a long line that was split");
+        assert_eq!(field(&frame.fields, "stream"), Some("stdout"));
+        assert_eq!(
+            frame.timestamp.map(|t| t.timestamp_nanos_opt()),
+            Some(Some(1_767_225_600_123_456_789))
+        );
+    }
+
+    #[test]
+    fn keeps_lines_without_cri_prefix() {
+        let frame = unframe(Bytes::from_static(b"plain line"));
+
+        assert_eq!(frame.content, "plain line");
+        assert!(frame.timestamp.is_none());
+        assert!(starts_record("plain line"));
+    }
+}
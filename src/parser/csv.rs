@@ -0,0 +1,180 @@
+//! CSV (RFC 4180)
+//!
+//! ```text
+//! time,level,user,message
+//! 2026-01-01T00:00:00Z,INFO,alice,"login ok, 2fa"
+//! 2026-01-01T00:00:01Z,WARN,bob,"multi
+//! line ""quoted"" value"
+//! ```
+//!
+//! 큰따옴표로 감싼 값 안의 `,` 와 줄바꿈은 값의 일부 (`""` 는 `"`), 따옴표가 닫히지 않은 줄은 다음 줄과 한 레코드.
+//! 컬럼 이름은 설정(`columns`)이 있으면 그대로, 없으면 파일 첫 레코드(헤더)에서 읽음.
+//! 로테이션이나 truncate 뒤 새 파일의 첫 레코드도 헤더로 다시 읽어 컬럼이 바뀐 파일도 이어서 해석
+
+use super::Fields;
+
+/// 지금까지 묶인 레코드가 따옴표 안에서 끝나지 않았으면 새 레코드
+pub fn starts_record(pending: &str) -> bool {
+    !in_quotes(pending)
+}
+
+/// 따옴표가 모두 닫혔으면 레코드 끝
+pub fn ends_record(record: &str) -> bool {
+    !in_quotes(record)
+}
+
+/// 수집 대상별 컬럼 상태
+#[derive(Debug)]
+pub struct Header {
+    columns: Option<Vec<String>>,
+    /// 설정으로 정한 컬럼이면 파일의 헤더로 바꾸지 않음
+    configured: bool,
+}
+
+impl Header {
+    pub fn new(columns: Option<Vec<String>>) -> Self {
+        Self {
+            configured: columns.is_some(),
+            columns,
+        }
+    }
+
+    /// 헤더 레코드면 컬럼을 갱신하고 true (이벤트로 보내지 않음)
+    ///
+    /// 파일 처음(`offset` 0)의 레코드가 헤더, 컬럼을 설정했으면 설정과 같은 레코드만 헤더로 보고 건너뜀
+    pub fn update(&mut self, record: &str, offset: u64) -> bool {
+        let names: Vec<String> = split(record.trim_start_matches('\u{feff}'))
+            .into_iter()
+            .map(|name| name.trim().to_string())
+            .collect();
+
+        if self.configured {
+            return self.columns.as_ref() == Some(&names);
+        }
+        if offset != 0 {
+            return false;
+        }
+
+        self.columns = Some(names);
+        true
+    }
+
+    /// 컬럼 이름과 값의 쌍, 빈 값은 생략하고 헤더보다 많은 값은 `column{순번}` 이름으로
+    pub fn parse(&self, record: &str) -> Fields {
+        let Some(columns) = &self.columns else {
+            return Fields::new();
+        };
+
+        split(record)
+            .into_iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(i, value)| {
+                let name = columns
+                    .get(i)
+                    .filter(|name| !name.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| format!("column{}", i + 1));
+                (name, value)
+            })
+            .collect()
+    }
+}
+
+/// 레코드를 값으로 나눔, 닫히지 않은 따옴표는 레코드 끝까지 값
+fn split(record: &str) -> Vec<String> {
+    let record = record.strip_suffix('\r').unwrap_or(record);
+    let mut values = Vec::new();
+    let mut value = String::new();
+    let mut state = State::Start;
+
+    for c in record.chars() {
+        state = match (state, c) {
+            (State::Start, '"') => State::Quoted,
+            (State::Quoted, '"') => State::QuoteInQuoted,
+            (State::QuoteInQuoted, '"') => {
+                value.push('"');
+                State::Quoted
+            }
+            (State::Quoted, c) => {
+                value.push(c);
+                State::Quoted
+            }
+            (_, ',') => {
+                values.push(std::mem::take(&mut value));
+                State::Start
+            }
+            // 따옴표로 시작하지 않은 값의 `"` 나 닫는 따옴표 뒤의 문자는 그대로 (느슨하게 해석)
+            (_, c) => {
+                value.push(c);
+                State::Unquoted
+            }
+        };
+    }
+
+    values.push(value);
+    values
+}
+
+/// [`split`] 과 같은 규칙으로 끝까지 읽었을 때 따옴표 안인지
+fn in_quotes(record: &str) -> bool {
+    let mut state = State::Start;
+    for c in record.chars() {
+        state = match (state, c) {
+            (State::Start, '"') | (State::QuoteInQuoted, '"') => State::Quoted,
+            (State::Quoted, '"') => State::QuoteInQuoted,
+            (State::Quoted, _) => State::Quoted,
+            (_, ',') => State::Start,
+            _ => State::Unquoted,
+        };
+    }
+    state == State::Quoted
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// 값의 첫 문자 전
+    Start,
+    Unquoted,
+    Quoted,
+    /// 따옴표 안에서 `"` 를 만난 직후 (`""` 이스케이프거나 닫는 따옴표)
+    QuoteInQuoted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn reads_header_then_quoted_values() {
+        let mut header = Header::new(None);
+        assert!(header.update("\u{feff}time,level,user,message", 0));
+
+        let record = "2026-01-01T00:00:01Z,WARN,,\"multi\nline \"\"quoted\"\", value\",extra";
+        assert!(!header.update(record, 40));
+
+        let fields = header.parse(record);
+        assert_eq!(field(&fields, "level"), Some("WARN"));
+        assert_eq!(field(&fields, "user"), None);
+        assert_eq!(field(&fields, "message"), Some("multi\nline \"quoted\", value"));
+        assert_eq!(field(&fields, "column5"), Some("extra"));
+    }
+
+    #[test]
+    fn open_quote_continues_record() {
+        assert!(!ends_record("1,\"multi"));
+        assert!(!starts_record("1,\"multi"));
+        assert!(ends_record("1,\"multi\nline\""));
+        assert!(ends_record("1,say \"hi\""));
+    }
+
+    #[test]
+    fn configured_columns_skip_matching_header() {
+        let mut header = Header::new(Some(vec!["a".to_string(), "b".to_string()]));
+
+        assert!(header.update("a,b", 0));
+        assert!(!header.update("x,y", 0));
+        assert_eq!(field(&header.parse("1,2"), "b"), Some("2"));
+    }
+}
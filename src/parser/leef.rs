@@ -0,0 +1,96 @@
+//! IBM QRadar LEEF (Log Event Extended Format)
+//!
+//! ```text
+//! LEEF:1.0|Vendor|Firewall|1.0|Blocked|src=10.0.0.1<TAB>dst=10.0.0.2<TAB>usrName=alice
+//! LEEF:2.0|Vendor|Firewall|1.0|Blocked|^|src=10.0.0.1^dst=10.0.0.2
+//! ```
+//!
+//! 앞의 syslog 머리말은 건너뛰고, 헤더 5개와 속성 `key=value` 를 필드로 추출.
+//! 속성 구분자는 1.0 은 탭, 2.0 은 헤더의 구분자 필드 (`^`, `0x09` 등, 생략 시 탭)
+
+use super::{Fields, split_header};
+
+const MARKER: &str = "LEEF:";
+
+const HEADER_KEYS: [&str; 5] = [
+    "leef_version",
+    "device_vendor",
+    "device_product",
+    "device_version",
+    "event_id",
+];
+
+pub fn parse(line: &str) -> Fields {
+    let Some(start) = line.find(MARKER) else {
+        return Fields::new();
+    };
+
+    let (header, mut attributes) = split_header(&line[start + MARKER.len()..], HEADER_KEYS.len());
+    if header.len() < HEADER_KEYS.len() {
+        return Fields::new();
+    }
+
+    let mut delimiter = '\t';
+    if header[0].starts_with('2')
+        && let Some((spec, rest)) = attributes.split_once('|')
+        && let Some(parsed) = parse_delimiter(spec)
+    {
+        delimiter = parsed;
+        attributes = rest;
+    }
+
+    let mut fields: Fields = HEADER_KEYS
+        .iter()
+        .map(|key| key.to_string())
+        .zip(header)
+        .collect();
+
+    for attribute in attributes.split(delimiter) {
+        if let Some((key, value)) = attribute.split_once('=')
+            && !key.trim().is_empty()
+        {
+            fields.push((key.trim().to_string(), value.to_string()));
+        }
+    }
+
+    fields
+}
+
+/// 한 글자 또는 16진수 코드 (`0x09`, `x09`)
+fn parse_delimiter(spec: &str) -> Option<char> {
+    let mut chars = spec.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(c);
+    }
+
+    let hex = spec
+        .strip_prefix("0x")
+        .or_else(|| spec.strip_prefix("0X"))
+        .or_else(|| spec.strip_prefix('x'))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn leef1_attributes_are_tab_separated() {
+        let fields = parse("LEEF:1.0|Vendor|Firewall|1.0|Blocked|src=10.0.0.1\tdst=10.0.0.2\tusrName=alice");
+
+        assert_eq!(field(&fields, "event_id"), Some("Blocked"));
+        assert_eq!(field(&fields, "dst"), Some("10.0.0.2"));
+        assert_eq!(field(&fields, "usrName"), Some("alice"));
+    }
+
+    #[test]
+    fn leef2_uses_header_delimiter() {
+        let caret = parse("LEEF:2.0|Vendor|Firewall|1.0|Blocked|^|src=10.0.0.1^dst=10.0.0.2");
+        let hex = parse("LEEF:2.0|Vendor|Firewall|1.0|Blocked|0x7c|src=10.0.0.1|dst=10.0.0.2");
+
+        assert_eq!(field(&caret, "src"), Some("10.0.0.1"));
+        assert_eq!(field(&caret, "dst"), Some("10.0.0.2"));
+        assert_eq!(field(&hex, "dst"), Some("10.0.0.2"));
+    }
+}
@@ -0,0 +1,117 @@
+//! logfmt (`key=value` 나열, Go/Heroku 계열 애플리케이션)
+//!
+//! ```text
+//! time=2026-01-01T00:00:00Z level=info msg="request handled" path=/api/items status=200 cached
+//! ```
+//!
+//! 값에 공백이 있으면 큰따옴표로 감싸고 안에서는 `\"`, `\\`, `\n`, `\t`, `\r` 이스케이프 사용.
+//! 값 없이 키만 있으면 빈 값, `=` 가 하나도 없는 줄은 logfmt 가 아닌 것으로 보고 필드 없음
+
+use super::Fields;
+
+pub fn parse(line: &str) -> Fields {
+    let mut fields = Fields::new();
+    let mut has_pair = false;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        // 키: 공백, `=`, `"` 전까지
+        let mut end = start;
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() || c == '=' || c == '"' {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        let key = &line[start..end];
+
+        if key.is_empty() {
+            // 키 자리에 온 `=` 나 `"` 는 건너뜀
+            chars.next();
+            continue;
+        }
+
+        if chars.peek().is_some_and(|&(_, c)| c == '=') {
+            chars.next();
+            has_pair = true;
+
+            let value = if chars.peek().is_some_and(|&(_, c)| c == '"') {
+                chars.next();
+                quoted(&mut chars)
+            } else {
+                let mut value = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                value
+            };
+            fields.push((key.to_string(), value));
+        } else {
+            fields.push((key.to_string(), String::new()));
+        }
+    }
+
+    if !has_pair {
+        return Fields::new();
+    }
+    fields
+}
+
+/// 여는 따옴표 다음부터 닫는 따옴표까지 (닫히지 않았으면 줄 끝까지)
+fn quoted(chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>) -> String {
+    let mut value = String::new();
+
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, 'r')) => value.push('\r'),
+                Some((_, escaped)) => value.push(escaped),
+                None => value.push('\\'),
+            },
+            c => value.push(c),
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn parses_quoted_and_bare_values() {
+        let fields = parse(r#"level=info msg="request \"ok\"\tdone" path=/api cached"#);
+
+        assert_eq!(field(&fields, "level"), Some("info"));
+        assert_eq!(field(&fields, "msg"), Some("request \"ok\"\tdone"));
+        assert_eq!(field(&fields, "path"), Some("/api"));
+        assert_eq!(field(&fields, "cached"), Some(""));
+    }
+
+    #[test]
+    fn line_without_pairs_has_no_fields() {
+        assert!(parse("plain text log line").is_empty());
+    }
+
+    #[test]
+    fn unterminated_quote_runs_to_end_of_line() {
+        let fields = parse(r#"msg="never closed level=warn"#);
+
+        assert_eq!(field(&fields, "msg"), Some("never closed level=warn"));
+    }
+}
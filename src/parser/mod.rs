@@ -0,0 +1,180 @@
+//! 수집 대상 형식(`format`)별 레코드 경계와 구조화 필드 추출
+//!
+//! 여러 줄로 이어지는 형식은 Collector 가 [`starts_record`] 기준으로 라인을 한 레코드로 묶고,
+//! 완성된 레코드는 [`unframe`] 으로 형식의 틀(CRI 머리말 등)을 벗긴 뒤 [`parse`] 로 필드를 뽑아
+//! [`crate::models::LogEvent`] 에 실음. 파일 안의 헤더가 컬럼을 정하는 형식은 [`Header`] 로 해석하고,
+//! 형식이 시각을 주지 않으면 수집 대상의 [`timestamp::TimestampFormat`] 으로 레코드에서 시각을 찾음
+
+pub mod auditd;
+pub mod cef;
+pub mod cri;
+pub mod csv;
+pub mod leef;
+pub mod logfmt;
+pub mod mysql;
+pub mod postgres;
+pub mod timestamp;
+pub mod w3c;
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::settings::{SourceFormat, SourceSettings};
+
+/// 레코드에서 뽑은 (이름, 값) 목록, 원문 형식이면 비어 있음
+pub type Fields = Vec<(String, String)>;
+
+/// 틀을 벗긴 레코드
+#[derive(Debug)]
+pub struct Frame {
+    /// 전송할 본문
+    pub content: Bytes,
+    /// 레코드에 기록된 시각, 없으면 수집 시각 사용
+    pub timestamp: Option<DateTime<Utc>>,
+    /// 틀에서 얻은 필드 (CRI 의 `stream` 등)
+    pub fields: Fields,
+}
+
+impl Frame {
+    fn raw(content: Bytes) -> Self {
+        Self {
+            content,
+            timestamp: None,
+            fields: Fields::new(),
+        }
+    }
+}
+
+/// 여러 줄을 한 레코드로 묶는 형식인지
+pub fn is_multiline(format: SourceFormat) -> bool {
+    match format {
+        SourceFormat::Raw | SourceFormat::Logfmt | SourceFormat::Cef | SourceFormat::Leef | SourceFormat::W3c => false,
+        SourceFormat::MysqlSlow | SourceFormat::PostgresSlow | SourceFormat::Auditd | SourceFormat::Cri | SourceFormat::Csv => true,
+    }
+}
+
+/// `line` 이 새 레코드의 첫 줄인지, `pending` 은 지금까지 묶인 레코드 (없으면 빈 문자열)
+pub fn starts_record(format: SourceFormat, line: &str, pending: &str) -> bool {
+    match format {
+        SourceFormat::Raw | SourceFormat::Logfmt | SourceFormat::Cef | SourceFormat::Leef | SourceFormat::W3c => true,
+        SourceFormat::MysqlSlow => mysql::starts_record(line, pending),
+        SourceFormat::PostgresSlow => postgres::starts_record(line),
+        SourceFormat::Auditd => auditd::starts_record(line, pending),
+        SourceFormat::Cri => cri::starts_record(pending),
+        SourceFormat::Csv => csv::starts_record(pending),
+    }
+}
+
+/// `line` 이 레코드의 마지막 줄인지, 아니면 다음 첫 줄이나 대기 시간 초과까지 기다림
+///
+/// `record` 는 `line` 까지 묶인 레코드
+pub fn ends_record(format: SourceFormat, line: &str, record: &str) -> bool {
+    match format {
+        SourceFormat::Cri => cri::ends_record(line),
+        SourceFormat::Auditd => auditd::ends_record(line),
+        SourceFormat::Csv => csv::ends_record(record),
+        _ => false,
+    }
+}
+
+/// 형식의 틀을 벗긴 본문과 시각, 틀이 없는 형식은 그대로
+pub fn unframe(format: SourceFormat, record: Bytes) -> Frame {
+    match format {
+        SourceFormat::Cri => cri::unframe(record),
+        _ => Frame::raw(record),
+    }
+}
+
+/// 완성된 레코드에서 구조화 필드 추출, 형식에 맞지 않는 레코드는 빈 목록
+///
+/// W3C, CSV 는 파일의 헤더 상태가 필요해 Collector 가 [`Header`] 로 해석
+pub fn parse(format: SourceFormat, record: &str) -> Fields {
+    match format {
+        SourceFormat::Raw | SourceFormat::Cri | SourceFormat::W3c | SourceFormat::Csv => Fields::new(),
+        SourceFormat::Logfmt => logfmt::parse(record),
+        SourceFormat::Cef => cef::parse(record),
+        SourceFormat::Leef => leef::parse(record),
+        SourceFormat::MysqlSlow => mysql::parse(record),
+        SourceFormat::PostgresSlow => postgres::parse(record),
+        SourceFormat::Auditd => auditd::parse(record),
+    }
+}
+
+/// 파일 안의 헤더 레코드가 컬럼을 정하는 형식의 수집 대상별 상태 (W3C `#Fields:`, CSV 첫 레코드)
+#[derive(Debug)]
+pub enum Header {
+    W3c(w3c::Header),
+    Csv(csv::Header),
+}
+
+impl Header {
+    /// 헤더가 필요한 형식이면 상태 생성
+    pub fn new(source: &SourceSettings) -> Option<Self> {
+        match source.format {
+            // 규격상 UTC 라 전역 `timezone` 은 적용하지 않음
+            SourceFormat::W3c => Some(Self::W3c(w3c::Header::new(source.timezone.unwrap_or(Tz::UTC)))),
+            SourceFormat::Csv => Some(Self::Csv(csv::Header::new(source.columns.clone()))),
+            _ => None,
+        }
+    }
+
+    /// 헤더 레코드면 상태를 갱신하고 true (이벤트로 보내지 않음), `offset` 은 레코드의 파일 내 위치
+    pub fn update(&mut self, record: &str, offset: u64) -> bool {
+        match self {
+            Self::W3c(header) => header.directive(record),
+            Self::Csv(header) => header.update(record, offset),
+        }
+    }
+
+    /// 데이터 레코드의 필드와 레코드에 기록된 시각
+    pub fn parse(&self, record: &str) -> (Fields, Option<DateTime<Utc>>) {
+        match self {
+            Self::W3c(header) => header.parse(record),
+            Self::Csv(header) => (header.parse(record), None),
+        }
+    }
+}
+
+/// 초 단위 소수 문자열을 밀리초 문자열로 (프리셋 간 공통 `duration_ms` 필드)
+fn secs_to_ms(secs: &str) -> Option<String> {
+    let secs: f64 = secs.parse().ok()?;
+    Some(format!("{:.3}", secs * 1000.0))
+}
+
+/// `|` 로 구분된 헤더를 앞에서부터 `count` 개 나누고 나머지와 함께 반환 (`\|`, `\\` 이스케이프)
+///
+/// 헤더가 `count` 개보다 적으면 나머지는 빈 문자열
+fn split_header(s: &str, count: usize) -> (Vec<String>, &str) {
+    let mut header = Vec::with_capacity(count);
+    let mut field = String::new();
+    let mut chars = s.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('|' | '\\'))) => field.push(escaped),
+                Some((_, other)) => {
+                    field.push('\\');
+                    field.push(other);
+                }
+                None => field.push('\\'),
+            },
+            '|' => {
+                header.push(std::mem::take(&mut field));
+                if header.len() == count {
+                    return (header, &s[i + 1..]);
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    (header, "")
+}
+
+/// 테스트에서 필드 값 조회
+#[cfg(test)]
+pub(crate) fn field<'a>(fields: &'a Fields, key: &str) -> Option<&'a str> {
+    fields.iter().find(|(name, _)| name == key).map(|(_, value)| value.as_str())
+}
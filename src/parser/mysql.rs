@@ -0,0 +1,160 @@
+//! MySQL 슬로우 쿼리 로그 (`slow_query_log`)
+//!
+//! ```text
+//! # Time: 2026-01-01T00:00:00.123456Z
+//! # User@Host: app[app] @ localhost [127.0.0.1]  Id:     8
+//! # Query_time: 2.000123  Lock_time: 0.000010 Rows_sent: 1  Rows_examined: 1000
+//! use shop;
+//! SET timestamp=1767225600;
+//! SELECT *
+//!   FROM orders WHERE ...;
+//! ```
+//!
+//! `# Time:` (구버전은 생략 가능) 부터 다음 헤더 전까지가 한 레코드
+
+use super::{Fields, secs_to_ms};
+
+const TIME_HEADER: &str = "# Time:";
+const USER_HEADER: &str = "# User@Host:";
+
+pub fn starts_record(line: &str, pending: &str) -> bool {
+    if line.starts_with(TIME_HEADER) {
+        return true;
+    }
+
+    // `# Time:` 바로 다음 줄이면 같은 레코드
+    if line.starts_with(USER_HEADER) {
+        let time_header_only = pending.starts_with(TIME_HEADER) && !pending.contains('\n');
+        return !time_header_only;
+    }
+
+    // 서버 재시작 시 기록되는 파일 헤더 (`/usr/sbin/mysqld, Version: ... started with:`)
+    line.contains(", Version: ") && line.ends_with("started with:")
+}
+
+pub fn parse(record: &str) -> Fields {
+    if !record.starts_with(TIME_HEADER) && !record.starts_with(USER_HEADER) {
+        return Fields::new();
+    }
+
+    let mut fields = Fields::new();
+    let mut query = Vec::new();
+    let mut db = None;
+
+    for line in record.lines() {
+        if let Some(time) = line.strip_prefix(TIME_HEADER) {
+            fields.push(("time".to_string(), time.trim().to_string()));
+        } else if let Some(user_host) = line.strip_prefix(USER_HEADER) {
+            parse_user_host(user_host, &mut fields);
+        } else if let Some(header) = line.strip_prefix("# ") {
+            parse_pairs(header, &mut fields, &mut db);
+        } else if let Some(name) = strip_prefix_ignore_case(line, "use ") {
+            db = Some(name.trim_end_matches(';').trim().to_string());
+        } else if strip_prefix_ignore_case(line, "SET timestamp=").is_none() {
+            query.push(line);
+        }
+    }
+
+    if let Some(db) = db {
+        fields.push(("db".to_string(), db));
+    }
+    if !query.is_empty() {
+        fields.push(("query".to_string(), query.join("\n")));
+    }
+
+    fields
+}
+
+/// `app[app] @ localhost [127.0.0.1]  Id:     8`
+fn parse_user_host(value: &str, fields: &mut Fields) {
+    let (user_host, id) = match value.split_once("Id:") {
+        Some((user_host, id)) => (user_host, Some(id.trim())),
+        None => (value, None),
+    };
+
+    if let Some((user, host)) = user_host.split_once(" @ ") {
+        let user = user.split('[').next().unwrap_or_default().trim();
+        if !user.is_empty() {
+            fields.push(("user".to_string(), user.to_string()));
+        }
+
+        // 호스트 이름이 없으면 괄호 안의 IP
+        let (name, ip) = host.split_once('[').unwrap_or((host, ""));
+        let ip = ip.trim_end().trim_end_matches(']').trim();
+        let host = if name.trim().is_empty() { ip } else { name.trim() };
+        if !host.is_empty() {
+            fields.push(("host".to_string(), host.to_string()));
+        }
+    }
+
+    if let Some(id) = id.filter(|id| !id.is_empty()) {
+        fields.push(("thread_id".to_string(), id.to_string()));
+    }
+}
+
+/// `Query_time: 2.000123  Lock_time: 0.000010 Rows_sent: 1` 처럼 `이름: 값` 이 이어진 헤더
+fn parse_pairs(header: &str, fields: &mut Fields, db: &mut Option<String>) {
+    let mut tokens = header.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        let Some(key) = token.strip_suffix(':') else {
+            continue;
+        };
+        let Some(value) = tokens.next() else {
+            break;
+        };
+
+        let key = key.to_ascii_lowercase();
+        if key == "query_time"
+            && let Some(ms) = secs_to_ms(value)
+        {
+            fields.push(("duration_ms".to_string(), ms));
+        }
+        // Percona/MariaDB 확장 헤더의 스키마 (`use` 문이 있으면 그쪽 우선)
+        if key == "schema" && db.is_none() && !value.is_empty() {
+            *db = Some(value.to_string());
+            continue;
+        }
+        fields.push((key, value.to_string()));
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = line.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &line[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    const RECORD: &str = "# Time: 2026-01-01T00:00:00.123456Z
+# User@Host: app[app] @  [127.0.0.1]  Id:     8
+# Query_time: 2.000123  Lock_time: 0.000010 Rows_sent: 1  Rows_examined: 1000
+use shop;
+SET timestamp=1767225600;
+SELECT *
+  FROM orders;";
+
+    #[test]
+    fn user_host_follows_time_header() {
+        assert!(starts_record("# Time: 2026-01-01T00:00:00Z", "SELECT 1;"));
+        assert!(!starts_record("# User@Host: app[app] @ localhost []", "# Time: 2026-01-01T00:00:00Z"));
+        assert!(!starts_record("  FROM orders;", "# Time: 2026-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn parses_headers_and_query() {
+        let fields = parse(RECORD);
+
+        assert_eq!(field(&fields, "time"), Some("2026-01-01T00:00:00.123456Z"));
+        assert_eq!(field(&fields, "user"), Some("app"));
+        assert_eq!(field(&fields, "host"), Some("127.0.0.1"));
+        assert_eq!(field(&fields, "thread_id"), Some("8"));
+        assert_eq!(field(&fields, "duration_ms"), Some("2000.123"));
+        assert_eq!(field(&fields, "rows_examined"), Some("1000"));
+        assert_eq!(field(&fields, "db"), Some("shop"));
+        assert_eq!(field(&fields, "query"), Some("SELECT *\n  FROM orders;"));
+    }
+}
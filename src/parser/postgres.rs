@@ -0,0 +1,113 @@
+//! PostgreSQL 슬로우 쿼리 로그 (`log_min_duration_statement`, stderr 형식)
+//!
+//! ```text
+//! 2026-01-01 00:00:00.123 UTC [12345] app@shop LOG:  duration: 2001.234 ms  statement: SELECT *
+//! <TAB>  FROM orders WHERE ...;
+//! ```
+//!
+//! 쿼리가 여러 줄이면 이어지는 줄은 탭(`<TAB>`)으로 시작하므로, 공백으로 시작하지 않는 줄부터 새 레코드.
+//! 접두어(`log_line_prefix`)는 기본값 `%m [%p] ` 와 흔히 쓰는 `%q%u@%d ` 를 인식
+
+use super::Fields;
+
+pub fn starts_record(line: &str) -> bool {
+    !line.starts_with(['\t', ' '])
+}
+
+pub fn parse(record: &str) -> Fields {
+    let (first, rest) = record.split_once('\n').unwrap_or((record, ""));
+
+    // 접두어 뒤 `LOG:  ` 처럼 심각도와 콜론, 공백 두 칸
+    let Some((head, message)) = first.split_once(":  ") else {
+        return Fields::new();
+    };
+    let (prefix, severity) = head.rsplit_once(' ').unwrap_or(("", head));
+    if severity.is_empty() || !severity.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Fields::new();
+    }
+
+    let mut fields = Fields::new();
+    parse_prefix(prefix, &mut fields);
+    fields.push(("severity".to_string(), severity.to_string()));
+
+    let Some(duration) = message.strip_prefix("duration: ") else {
+        return fields;
+    };
+    let (ms, statement) = duration.split_once(" ms").unwrap_or((duration, ""));
+    fields.push(("duration_ms".to_string(), ms.trim().to_string()));
+
+    // `statement: ...`, `execute <name>: ...`, `parse <name>: ...` (log_duration 만 켜져 있으면 없음)
+    let statement = statement.trim_start();
+    if let Some((kind, text)) = statement.split_once(": ") {
+        if let Some(name) = kind.strip_prefix("execute ") {
+            fields.push(("statement_name".to_string(), name.to_string()));
+        }
+
+        let mut query = text.to_string();
+        for line in rest.lines() {
+            query.push('\n');
+            query.push_str(line.strip_prefix('\t').unwrap_or(line));
+        }
+        fields.push(("query".to_string(), query));
+    }
+
+    fields
+}
+
+/// `2026-01-01 00:00:00.123 UTC [12345] app@shop`
+fn parse_prefix(prefix: &str, fields: &mut Fields) {
+    let mut time = Vec::new();
+
+    for token in prefix.split_whitespace() {
+        if let Some(pid) = token.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            // `%c` 세션 ID 등 `[12345-1]` 형태는 앞부분이 PID
+            let pid = pid.split('-').next().unwrap_or(pid);
+            if pid.bytes().all(|b| b.is_ascii_digit()) && !pid.is_empty() {
+                fields.push(("pid".to_string(), pid.to_string()));
+            }
+        } else if let Some((user, db)) = token.split_once('@') {
+            if !user.is_empty() {
+                fields.push(("user".to_string(), user.to_string()));
+            }
+            if !db.is_empty() {
+                fields.push(("db".to_string(), db.to_string()));
+            }
+        } else if fields.is_empty() {
+            time.push(token);
+        }
+    }
+
+    if time.first().is_some_and(|t| t.starts_with(|c: char| c.is_ascii_digit())) {
+        fields.insert(0, ("time".to_string(), time.join(" ")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn parses_duration_and_multiline_statement() {
+        let record = "2026-01-01 00:00:00.123 UTC [12345] app@shop LOG:  duration: 2001.234 ms  statement: SELECT *\n\t  FROM orders;";
+        assert!(!starts_record("\t  FROM orders;"));
+
+        let fields = parse(record);
+        assert_eq!(field(&fields, "time"), Some("2026-01-01 00:00:00.123 UTC"));
+        assert_eq!(field(&fields, "pid"), Some("12345"));
+        assert_eq!(field(&fields, "user"), Some("app"));
+        assert_eq!(field(&fields, "db"), Some("shop"));
+        assert_eq!(field(&fields, "severity"), Some("LOG"));
+        assert_eq!(field(&fields, "duration_ms"), Some("2001.234"));
+        assert_eq!(field(&fields, "query"), Some("SELECT *\n  FROM orders;"));
+    }
+
+    #[test]
+    fn execute_records_statement_name() {
+        let fields = parse("2026-01-01 00:00:00 UTC [1-1] LOG:  duration: 5.0 ms  execute S_1: SELECT 1");
+
+        assert_eq!(field(&fields, "pid"), Some("1"));
+        assert_eq!(field(&fields, "statement_name"), Some("S_1"));
+        assert_eq!(field(&fields, "query"), Some("SELECT 1"));
+    }
+}
@@ -0,0 +1,86 @@
+//! 수집 대상별 `timestamp_format` 으로 레코드 안의 시각 추출
+//!
+//! ```text
+//! 10.0.0.1 - - [01/Jan/2026:09:00:00 +0900] "GET / HTTP/1.1" 200   (%d/%b/%Y:%H:%M:%S %z)
+//! 2026-01-01 09:00:00 ERROR connection refused                      (%Y-%m-%d %H:%M:%S, timezone: Asia/Seoul)
+//! ```
+//!
+//! 레코드 첫머리와 공백/괄호/따옴표 바로 뒤에서 차례로 형식을 맞춰 보고 처음 맞는 시각을 사용.
+//! 형식에 오프셋(`%z`)이 없으면 수집 대상의 `timezone`, 없으면 전역 `timezone` (둘 다 생략 시 UTC) 의 현지 시각으로 해석
+
+use std::sync::Mutex;
+
+use anyhow::{Result, bail};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::messages as msg;
+use crate::settings::SourceSettings;
+
+/// 시각을 찾는 레코드 앞부분 길이 (긴 레코드 전체를 훑지 않도록)
+const MAX_SCAN_BYTES: usize = 256;
+
+/// 전역 `timezone`, 수집 대상에 `timezone` 이 없을 때 사용
+static DEFAULT_TIMEZONE: Mutex<Tz> = Mutex::new(Tz::UTC);
+
+pub fn configure(timezone: Option<Tz>) {
+    *DEFAULT_TIMEZONE.lock().unwrap_or_else(|e| e.into_inner()) = timezone.unwrap_or(Tz::UTC);
+}
+
+/// 수집 대상의 오프셋 없는 시각을 해석할 시간대
+pub fn timezone_for(source: &SourceSettings) -> Tz {
+    source
+        .timezone
+        .unwrap_or_else(|| *DEFAULT_TIMEZONE.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// `timezone` 의 현지 시각을 UTC 로, 서머타임 전환으로 겹치는 시각은 이른 쪽 (건너뛴 시각이면 None)
+pub fn local_to_utc(naive: &NaiveDateTime, timezone: Tz) -> Option<DateTime<Utc>> {
+    timezone
+        .from_local_datetime(naive)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+#[derive(Debug)]
+pub struct TimestampFormat {
+    format: String,
+    timezone: Tz,
+}
+
+impl TimestampFormat {
+    /// `timestamp_format` 이 설정된 수집 대상이면 생성, 형식 문자열이 잘못되었으면 오류
+    pub fn new(source: &SourceSettings) -> Result<Option<Self>> {
+        let Some(format) = source.timestamp_format.clone() else {
+            return Ok(None);
+        };
+        if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+            bail!("{}: {} ({})", msg::TIMESTAMP_FORMAT_INVALID, format, source.label);
+        }
+
+        Ok(Some(Self {
+            format,
+            timezone: timezone_for(source),
+        }))
+    }
+
+    /// 레코드에서 처음으로 형식에 맞는 시각, 없으면 None (수집 시각 사용)
+    pub fn extract(&self, record: &str) -> Option<DateTime<Utc>> {
+        let scan = &record[..record.floor_char_boundary(MAX_SCAN_BYTES)];
+
+        std::iter::once(0)
+            .chain(scan.match_indices([' ', '[', '(', '"']).map(|(i, _)| i + 1))
+            .find_map(|start| self.parse(&record[start..]))
+    }
+
+    fn parse(&self, s: &str) -> Option<DateTime<Utc>> {
+        if let Ok((datetime, _)) = DateTime::parse_and_remainder(s, &self.format) {
+            return Some(datetime.with_timezone(&Utc));
+        }
+
+        // 오프셋 없는 형식
+        let (naive, _) = NaiveDateTime::parse_and_remainder(s, &self.format).ok()?;
+        local_to_utc(&naive, self.timezone)
+    }
+}
@@ -0,0 +1,135 @@
+//! W3C 확장 로그 (IIS 등)
+//!
+//! ```text
+//! #Software: Microsoft Internet Information Services 10.0
+//! #Version: 1.0
+//! #Date: 2026-01-01 00:00:00
+//! #Fields: date time s-ip cs-method cs-uri-stem cs-uri-query s-port cs-username c-ip cs(User-Agent) cs(Referer) sc-status sc-substatus sc-win32-status time-taken
+//! 2026-01-01 00:00:01 10.0.0.5 GET /index.html - 443 - 10.0.0.9 Mozilla/5.0 - 200 0 0 15
+//! ```
+//!
+//! 컬럼 목록은 `#Fields:` 지시문이 정하고, 파일 중간에 새 지시문이 오면(설정 변경, 서버 재시작) 그 뒤 줄부터 새 목록 적용.
+//! `date`/`time` 은 규격상 UTC, IIS 를 현지 시각으로 기록하게 바꾼 경우 수집 대상의 `timezone` 으로 해석.
+//! 컬럼 이름은 필드 이름으로 (`cs-uri-stem` → `cs_uri_stem`, `cs(User-Agent)` → `cs_user_agent`), 값이 `-` 인 컬럼은 생략
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use super::Fields;
+use super::timestamp;
+
+/// 지시문을 보기 전(파일 중간부터 읽기 시작한 경우)에 가정하는 IIS 기본 컬럼
+const IIS_DEFAULT_FIELDS: &str = "date time s-ip cs-method cs-uri-stem cs-uri-query s-port cs-username c-ip cs(User-Agent) cs(Referer) sc-status sc-substatus sc-win32-status time-taken";
+
+/// 수집 대상별로 유지하는 지시문 상태
+#[derive(Debug)]
+pub struct Header {
+    columns: Vec<String>,
+    /// `#Date:` 의 날짜, `date` 컬럼이 없을 때 `time` 과 합쳐 시각으로 사용
+    date: Option<NaiveDate>,
+    /// `#Fields:` 를 본 적 있는지, 없으면 컬럼 수가 기본 목록과 같은 줄만 해석
+    declared: bool,
+    /// `date`/`time` 을 해석할 시간대
+    timezone: Tz,
+}
+
+impl Header {
+    pub fn new(timezone: Tz) -> Self {
+        Self {
+            columns: IIS_DEFAULT_FIELDS.split(' ').map(column_name).collect(),
+            date: None,
+            declared: false,
+            timezone,
+        }
+    }
+
+    /// `#` 로 시작하는 지시문이면 상태를 갱신하고 true (이벤트로 보내지 않음)
+    pub fn directive(&mut self, line: &str) -> bool {
+        let Some(directive) = line.trim_start_matches('\u{feff}').strip_prefix('#') else {
+            return false;
+        };
+
+        if let Some(fields) = directive.strip_prefix("Fields:") {
+            self.columns = fields.split_whitespace().map(column_name).collect();
+            self.declared = true;
+        } else if let Some(date) = directive.strip_prefix("Date:") {
+            self.date = date
+                .split_whitespace()
+                .next()
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        }
+        true
+    }
+
+    /// 데이터 줄의 필드와 `date`/`time` 컬럼의 시각
+    pub fn parse(&self, line: &str) -> (Fields, Option<DateTime<Utc>>) {
+        let values: Vec<&str> = line.split_whitespace().collect();
+        if values.len() != self.columns.len() && !self.declared {
+            return (Fields::new(), None);
+        }
+
+        let mut date = self.date;
+        let mut time = None;
+        let mut fields = Fields::new();
+        for (column, value) in self.columns.iter().zip(values) {
+            match column.as_str() {
+                "date" => date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().or(date),
+                "time" => time = NaiveTime::parse_from_str(value, "%H:%M:%S%.f").ok(),
+                _ => {}
+            }
+            if value != "-" {
+                fields.push((column.clone(), value.to_string()));
+            }
+        }
+
+        let timestamp = date
+            .zip(time)
+            .and_then(|(date, time)| timestamp::local_to_utc(&NaiveDateTime::new(date, time), self.timezone));
+        (fields, timestamp)
+    }
+}
+
+/// `cs(User-Agent)` → `cs_user_agent`
+fn column_name(column: &str) -> String {
+    let name: String = column
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    name.trim_matches('_').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::field;
+
+    #[test]
+    fn fields_directive_sets_columns() {
+        let mut header = Header::new(Tz::UTC);
+        assert!(header.directive("#Fields: date time cs-method cs(User-Agent) sc-status"));
+        assert!(!header.directive("2026-01-01 00:00:01 GET Mozilla/5.0 200"));
+
+        let (fields, timestamp) = header.parse("2026-01-01 00:00:01 GET - 200");
+        assert_eq!(field(&fields, "cs_method"), Some("GET"));
+        assert_eq!(field(&fields, "cs_user_agent"), None);
+        assert_eq!(field(&fields, "sc_status"), Some("200"));
+        assert_eq!(timestamp.map(|t| t.to_rfc3339()).as_deref(), Some("2026-01-01T00:00:01+00:00"));
+    }
+
+    #[test]
+    fn date_directive_and_timezone_apply_to_time_column() {
+        let mut header = Header::new(chrono_tz::Asia::Seoul);
+        header.directive("#Date: 2026-01-01 00:00:00");
+        header.directive("#Fields: time c-ip");
+
+        let (_, timestamp) = header.parse("09:00:00 10.0.0.9");
+        assert_eq!(timestamp.map(|t| t.to_rfc3339()).as_deref(), Some("2026-01-01T00:00:00+00:00"));
+    }
+
+    #[test]
+    fn undeclared_header_only_accepts_default_column_count() {
+        let header = Header::new(Tz::UTC);
+
+        assert!(header.parse("too few columns").0.is_empty());
+    }
+}
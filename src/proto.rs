@@ -9,3 +9,12 @@ pub mod auth {
 pub mod health {
     tonic::include_proto!("health");
 }
+
+pub mod control {
+    tonic::include_proto!("control");
+}
+
+/// SPIFFE Workload API, 원본 proto 에 package 가 없어 `_` 로 생성됨
+pub mod workload {
+    tonic::include_proto!("_");
+}
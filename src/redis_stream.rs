@@ -0,0 +1,470 @@
+//! Redis 스트림 수집 (`kind: redis_stream`)
+//!
+//! 컨슈머 그룹으로 `XREADGROUP` 해 항목을 [`LogEvent`] 로 변환하고, 파이프라인에 넘긴 항목은 `XACK`.
+//! 재연결 시에는 이 컨슈머가 받고 ACK 하지 못한 항목(pending)부터 다시 읽음.
+//! 스트림별 마지막 전달 ID 는 상태 보관소의 `redis/<label>.yaml`(기본 `state/redis/`)에 기록해, 그룹이 사라졌을 때(영속화 없는 Redis 재시작 등)
+//! 그 다음 항목부터 그룹을 다시 만듦
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::Sender;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::disk_guard;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::{self, Fields};
+use crate::settings::{RedisStreamSettings, SourceFormat, SourceSettings};
+use crate::state_store;
+use crate::status;
+
+/// 수집 대상별 마지막 전달 ID 보관소 키 접두어 (`redis/<label>.yaml`)
+const STATE_PREFIX: &str = "redis";
+
+/// 연결이 끊긴 뒤 다시 연결하기까지 대기 시간
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 한 번의 `XREADGROUP` 이 새 항목을 기다리는 시간 (밀리초)
+const BLOCK_MS: &str = "5000";
+
+/// 한 번의 `XREADGROUP` 으로 스트림마다 읽는 최대 항목 수
+const READ_COUNT: &str = "100";
+
+/// 일시 중지 중 재개 여부를 확인하는 간격 (중지 동안 항목은 Redis 에 남음)
+const PAUSE_POLL: Duration = Duration::from_secs(1);
+
+/// 스트림별 마지막으로 파이프라인에 넘긴 항목 ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StreamState {
+    last_ids: BTreeMap<String, String>,
+}
+
+/// `XREADGROUP` 응답의 항목, 읽기 전에 삭제된 pending 항목은 `fields` 가 None
+struct Entry {
+    stream: String,
+    id: String,
+    fields: Option<Vec<(String, String)>>,
+}
+
+pub struct RedisStreamCollector {
+    tx: Sender<LogEvent>,
+    label: String,
+    settings: RedisStreamSettings,
+    consumer: String,
+    format: SourceFormat,
+    /// 스트림별 이벤트 경로 (`redis://host:port/stream`)
+    paths: HashMap<String, Arc<str>>,
+    state: StreamState,
+    state_key: String,
+    /// 지금까지 전달한 항목 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+}
+
+impl RedisStreamCollector {
+    pub fn new(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        let Some(settings) = source.redis.filter(|s| !s.streams.is_empty()) else {
+            bail!("{}: {}", msg::REDIS_NOT_CONFIGURED, source.label);
+        };
+
+        let consumer = settings
+            .consumer
+            .clone()
+            .unwrap_or_else(|| gethostname::gethostname().to_string_lossy().into_owned());
+        let paths = settings
+            .streams
+            .iter()
+            .map(|stream| (stream.clone(), Arc::from(format!("redis://{}/{}", settings.addr, stream))))
+            .collect();
+        let state_key = format!("{}/{}.yaml", STATE_PREFIX, source.label);
+        let state = load_state(&state_key);
+
+        status::registry().update_source(&source.label, Path::new(&status_path(&settings)), 0);
+
+        Ok(Self {
+            tx,
+            label: source.label,
+            settings,
+            consumer,
+            format: source.format,
+            paths,
+            state,
+            state_key,
+            forwarded: 0,
+            budget,
+        })
+    }
+
+    /// `shutdown` 취소 시까지 스트림 항목을 수집, 연결이 끊기면 잠시 후 다시 연결
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, addr = %self.settings.addr, "{}", msg::REDIS_STARTED);
+
+        loop {
+            match self.consume(&shutdown).await {
+                Ok(()) => break,
+                Err(e) => warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::REDIS_DISCONNECTED),
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = time::sleep(RESTART_DELAY) => {}
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    /// 한 번 연결해 `shutdown` 까지 읽음, 연결/프로토콜 오류면 Err
+    async fn consume(&mut self, shutdown: &CancellationToken) -> Result<()> {
+        let mut conn = Connection::open(&self.settings).await?;
+        for stream in self.settings.streams.clone() {
+            self.create_group(&mut conn, &stream).await?;
+        }
+
+        // 이전 연결에서 받고 ACK 하지 못한 항목부터, 다 읽으면 새 항목
+        let mut pending = true;
+        loop {
+            if collector::is_paused(&self.label) {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return Ok(()),
+                    _ = time::sleep(PAUSE_POLL) => continue,
+                }
+            }
+            self.budget.wait_for_capacity(shutdown).await;
+
+            let command = self.read_command(if pending { "0" } else { ">" });
+            let reply = tokio::select! {
+                _ = shutdown.cancelled() => return Ok(()),
+                reply = conn.command(&command) => reply?,
+            };
+
+            let entries = entries(reply)?;
+            if entries.is_empty() {
+                pending = false;
+                continue;
+            }
+            self.deliver(&mut conn, entries).await?;
+        }
+    }
+
+    /// 그룹이 없으면 마지막 전달 ID 다음(처음 실행이면 지금 이후)부터 읽도록 생성
+    async fn create_group(&self, conn: &mut Connection, stream: &str) -> Result<()> {
+        let start = self.state.last_ids.get(stream).map_or("$", String::as_str);
+        let command = ["XGROUP", "CREATE", stream, &self.settings.group, start, "MKSTREAM"];
+
+        match conn.command(&command).await {
+            Ok(_) => {
+                info!(source = %self.label, stream = stream, group = %self.settings.group, start = start, "{}", msg::REDIS_GROUP_CREATED);
+                Ok(())
+            }
+            // 이미 있는 그룹은 Redis 가 기억하는 위치부터
+            Err(e) if e.to_string().starts_with("BUSYGROUP") => Ok(()),
+            Err(e) => Err(e).context(msg::REDIS_GROUP_CREATE_FAILED),
+        }
+    }
+
+    fn read_command<'a>(&'a self, id: &'a str) -> Vec<&'a str> {
+        let mut command = vec![
+            "XREADGROUP",
+            "GROUP",
+            &self.settings.group,
+            &self.consumer,
+            "COUNT",
+            READ_COUNT,
+            "BLOCK",
+            BLOCK_MS,
+            "STREAMS",
+        ];
+        command.extend(self.settings.streams.iter().map(String::as_str));
+        command.extend(self.settings.streams.iter().map(|_| id));
+        command
+    }
+
+    /// 항목을 파이프라인에 넘긴 뒤 스트림별로 `XACK` 하고 마지막 ID 기록
+    async fn deliver(&mut self, conn: &mut Connection, entries: Vec<Entry>) -> Result<()> {
+        let mut acks: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+        for entry in entries {
+            if let Some(fields) = entry.fields {
+                self.send_event(&entry.stream, &entry.id, fields).await?;
+            }
+            acks.entry(entry.stream).or_default().push(entry.id);
+        }
+
+        for (stream, ids) in acks {
+            let mut command = vec!["XACK", stream.as_str(), self.settings.group.as_str()];
+            command.extend(ids.iter().map(String::as_str));
+            conn.command(&command).await.context(msg::REDIS_ACK_FAILED)?;
+
+            if let Some(last) = ids.last() {
+                self.state.last_ids.insert(stream, last.clone());
+            }
+        }
+
+        if let Err(e) = save_state(&self.state_key, &self.state) {
+            warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::REDIS_STATE_SAVE_FAILED);
+        }
+        Ok(())
+    }
+
+    async fn send_event(&mut self, stream: &str, id: &str, entry_fields: Vec<(String, String)>) -> Result<()> {
+        let content = match entry_fields.iter().find(|(key, _)| *key == self.settings.message_field) {
+            Some((_, message)) => message.clone(),
+            None => serde_json::to_string(&entry_fields.iter().cloned().collect::<BTreeMap<_, _>>())
+                .context(msg::REDIS_PROTOCOL_ERROR)?,
+        };
+        if content.trim().is_empty() {
+            return Ok(());
+        }
+
+        let millis = id_millis(id);
+        let mut fields: Fields = entry_fields
+            .into_iter()
+            .filter(|(key, _)| *key != self.settings.message_field)
+            .collect();
+        fields.push(("redis_stream".to_string(), stream.to_string()));
+        fields.push(("redis_id".to_string(), id.to_string()));
+        fields.extend(parser::parse(self.format, &content));
+
+        let path = self
+            .paths
+            .get(stream)
+            .cloned()
+            .unwrap_or_else(|| Arc::from(stream));
+
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(content),
+            timestamp: millis
+                .and_then(|ms| DateTime::from_timestamp_millis(ms as i64))
+                .unwrap_or_else(Utc::now),
+            path,
+            offset: millis.unwrap_or(self.forwarded),
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        self.forwarded += 1;
+        status::registry().update_source(&self.label, Path::new(&status_path(&self.settings)), self.forwarded);
+
+        Ok(())
+    }
+}
+
+/// 연결, 인증, `PING` 확인 (doctor)
+pub async fn ping(settings: &RedisStreamSettings) -> Result<()> {
+    let mut conn = Connection::open(settings).await?;
+    conn.command(&["PING"]).await?;
+    Ok(())
+}
+
+fn status_path(settings: &RedisStreamSettings) -> String {
+    format!("redis://{}", settings.addr)
+}
+
+/// `1767225600000-0` 의 밀리초 부분
+fn id_millis(id: &str) -> Option<u64> {
+    id.split_once('-').and_then(|(ms, _)| ms.parse().ok())
+}
+
+fn load_state(key: &str) -> StreamState {
+    state_store::store()
+        .load(key)
+        .ok()
+        .flatten()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(key: &str, state: &StreamState) -> Result<()> {
+    // 디스크 여유 공간이 부족하면 메모리의 위치만 유지
+    disk_guard::ensure_room()?;
+
+    let yaml = serde_yaml::to_string(state).context(msg::REDIS_STATE_SAVE_FAILED)?;
+    state_store::store().save(key, &yaml)
+}
+
+/// `XREADGROUP` 응답 (`[[stream, [[id, [field, value, ...]], ...]], ...]`, 시간 초과면 nil) 을 항목 목록으로
+fn entries(reply: Value) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    for stream in reply.into_array() {
+        let mut parts = stream.into_array().into_iter();
+        let (Some(name), Some(items)) = (parts.next(), parts.next()) else {
+            bail!(msg::REDIS_PROTOCOL_ERROR);
+        };
+        let name = name.into_string().context(msg::REDIS_PROTOCOL_ERROR)?;
+
+        for item in items.into_array() {
+            let mut parts = item.into_array().into_iter();
+            let id = parts
+                .next()
+                .and_then(Value::into_string)
+                .context(msg::REDIS_PROTOCOL_ERROR)?;
+            let fields = match parts.next() {
+                Some(Value::Array(Some(values))) => {
+                    let mut values = values.into_iter().filter_map(Value::into_string);
+                    let mut fields = Vec::new();
+                    while let (Some(key), Some(value)) = (values.next(), values.next()) {
+                        fields.push((key, value));
+                    }
+                    Some(fields)
+                }
+                _ => None,
+            };
+
+            entries.push(Entry {
+                stream: name.clone(),
+                id,
+                fields,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// RESP2 응답 값
+#[derive(Debug)]
+enum Value {
+    Status(String),
+    Error(String),
+    Integer(i64),
+    Bulk(Option<Bytes>),
+    Array(Option<Vec<Value>>),
+}
+
+impl Value {
+    fn into_array(self) -> Vec<Value> {
+        match self {
+            Value::Array(Some(values)) => values,
+            _ => Vec::new(),
+        }
+    }
+
+    fn into_string(self) -> Option<String> {
+        match self {
+            Value::Status(s) => Some(s),
+            Value::Bulk(Some(b)) => Some(String::from_utf8_lossy(&b).into_owned()),
+            Value::Integer(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// 최소한의 RESP2 클라이언트 (요청 하나에 응답 하나)
+struct Connection {
+    stream: TcpStream,
+    buf: BytesMut,
+}
+
+impl Connection {
+    async fn open(settings: &RedisStreamSettings) -> Result<Self> {
+        let stream = time::timeout(CONNECT_TIMEOUT, TcpStream::connect(&settings.addr))
+            .await
+            .with_context(|| format!("{}: {}", msg::REDIS_CONNECT_FAILED, settings.addr))?
+            .with_context(|| format!("{}: {}", msg::REDIS_CONNECT_FAILED, settings.addr))?;
+
+        let mut conn = Self {
+            stream,
+            buf: BytesMut::with_capacity(16 * 1024),
+        };
+
+        if let Some(password) = &settings.password {
+            let result = match &settings.username {
+                Some(username) => conn.command(&["AUTH", username, password]).await,
+                None => conn.command(&["AUTH", password]).await,
+            };
+            result.context(msg::REDIS_AUTH_FAILED)?;
+        }
+
+        Ok(conn)
+    }
+
+    /// 명령을 보내고 응답 하나를 읽음, 오류 응답은 Err
+    async fn command(&mut self, args: &[&str]) -> Result<Value> {
+        let mut request = format!("*{}\r\n", args.len()).into_bytes();
+        for arg in args {
+            request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+            request.extend_from_slice(arg.as_bytes());
+            request.extend_from_slice(b"\r\n");
+        }
+        self.stream.write_all(&request).await.context(msg::REDIS_CONNECTION_CLOSED)?;
+
+        loop {
+            if let Some((value, len)) = parse(&self.buf)? {
+                self.buf.advance(len);
+                return match value {
+                    Value::Error(e) => bail!("{}", e),
+                    value => Ok(value),
+                };
+            }
+
+            let read = self.stream.read_buf(&mut self.buf).await.context(msg::REDIS_CONNECTION_CLOSED)?;
+            if read == 0 {
+                bail!(msg::REDIS_CONNECTION_CLOSED);
+            }
+        }
+    }
+}
+
+/// 버퍼 앞의 응답 하나와 그 길이, 아직 다 받지 못했으면 None
+fn parse(buf: &[u8]) -> Result<Option<(Value, usize)>> {
+    let Some(end) = buf.windows(2).position(|w| w == b"\r\n") else {
+        return Ok(None);
+    };
+    let line = std::str::from_utf8(&buf[1..end]).context(msg::REDIS_PROTOCOL_ERROR)?;
+    let next = end + 2;
+
+    let value = match buf[0] {
+        b'+' => Value::Status(line.to_string()),
+        b'-' => Value::Error(line.to_string()),
+        b':' => Value::Integer(line.parse().context(msg::REDIS_PROTOCOL_ERROR)?),
+        b'$' => {
+            let Ok(len) = usize::try_from(line.parse::<i64>().context(msg::REDIS_PROTOCOL_ERROR)?) else {
+                return Ok(Some((Value::Bulk(None), next)));
+            };
+            if buf.len() < next + len + 2 {
+                return Ok(None);
+            }
+            return Ok(Some((Value::Bulk(Some(Bytes::copy_from_slice(&buf[next..next + len]))), next + len + 2)));
+        }
+        b'*' => {
+            let Ok(len) = usize::try_from(line.parse::<i64>().context(msg::REDIS_PROTOCOL_ERROR)?) else {
+                return Ok(Some((Value::Array(None), next)));
+            };
+            let mut values = Vec::with_capacity(len.min(1024));
+            let mut pos = next;
+            for _ in 0..len {
+                let Some((value, used)) = parse(&buf[pos..])? else {
+                    return Ok(None);
+                };
+                values.push(value);
+                pos += used;
+            }
+            return Ok(Some((Value::Array(Some(values)), pos)));
+        }
+        _ => bail!(msg::REDIS_PROTOCOL_ERROR),
+    };
+
+    Ok(Some((value, next)))
+}
@@ -0,0 +1,252 @@
+//! 기존 파일 재생 (`replay` 하위 명령)
+//!
+//! 파일을 임시 파일에 옮겨 쓰며 Collector → Forwarder → Sink 전체 파이프라인으로 보냄. 파서 설정 검증이나
+//! 서버에서 잃은 데이터를 다시 넣을 때 사용. 설정에 같은 라벨의 수집 대상이 있으면 `format`, `timestamp_format` 등을
+//! 그대로 쓰고, 배속(`10x`)을 주면 레코드 시각 간격을 배속으로 나눈 만큼 기다리며 씀 (`timestamp_format` 필요).
+//! 이벤트의 `path` 는 재생용 임시 파일
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::agent::{Agent, Sink};
+use crate::messages as msg;
+use crate::parser::timestamp::{self, TimestampFormat};
+use crate::settings::{Settings, SourceKind, SourceSettings};
+use crate::status;
+
+/// Collector 가 임시 파일을 열 때까지 기다리는 최대 시간
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Collector 진행 상황을 확인하는 간격
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 재생 속도
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    /// 기다리지 않고 바로 씀
+    Max,
+    /// 레코드 시각 간격을 이 값으로 나눈 만큼 기다림 (`1x` 는 원래 속도)
+    Factor(f64),
+}
+
+impl FromStr for ReplaySpeed {
+    type Err = anyhow::Error;
+
+    /// `max`, `10x`, `0.5x` (`x` 생략 가능)
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("max") {
+            return Ok(Self::Max);
+        }
+
+        match s.strip_suffix(['x', 'X']).unwrap_or(s).parse::<f64>() {
+            Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(Self::Factor(factor)),
+            _ => Err(anyhow!("{}: {}", msg::REPLAY_SPEED_INVALID, s)),
+        }
+    }
+}
+
+impl fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Max => f.write_str("max"),
+            Self::Factor(factor) => write!(f, "{}x", factor),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// 재생할 파일
+    pub file: PathBuf,
+    /// 이벤트에 붙일 수집 대상 라벨
+    pub label: String,
+    pub speed: ReplaySpeed,
+}
+
+#[derive(Debug)]
+pub struct ReplayReport {
+    pub lines: usize,
+    pub bytes: u64,
+    pub elapsed: Duration,
+}
+
+impl ReplayReport {
+    pub fn print(&self) {
+        println!("[replay] {}: {}", msg::REPLAY_LINES, self.lines);
+        println!("[replay] {}: {}", msg::REPLAY_BYTES, self.bytes);
+        println!("[replay] {}: {:.3}s", msg::REPLAY_ELAPSED, self.elapsed.as_secs_f64());
+    }
+}
+
+/// 파일을 끝까지 재생하고 Sink 드레인까지 기다림, `shutdown` 이 취소되면 그때까지 쓴 라인만 보냄
+pub async fn run(settings: Settings, options: ReplayOptions, sink: Sink, shutdown: CancellationToken) -> Result<ReplayReport> {
+    let dir = std::env::temp_dir().join(format!("rlog-agent-replay-{}", std::process::id()));
+    fs::create_dir_all(&dir).await.context(msg::REPLAY_SETUP_FAILED)?;
+
+    let result = run_in(&dir, settings, options, sink, shutdown).await;
+    let _ = fs::remove_dir_all(&dir).await;
+    result
+}
+
+async fn run_in(
+    dir: &Path,
+    settings: Settings,
+    options: ReplayOptions,
+    sink: Sink,
+    shutdown: CancellationToken,
+) -> Result<ReplayReport> {
+    let input = File::open(&options.file)
+        .await
+        .with_context(|| format!("{}: {}", msg::REPLAY_FILE_OPEN_FAILED, options.file.display()))?;
+
+    let name = options.file.file_name().map_or_else(|| "replay.log".into(), |name| name.to_os_string());
+    let path = dir.join(name);
+    File::create(&path).await.context(msg::REPLAY_SETUP_FAILED)?;
+
+    // 설정에 같은 라벨이 있으면 형식, 시각 추출 등 파싱 설정을 그대로 사용 (구간, 폴링은 적용하지 않음)
+    let mut source = settings
+        .sources
+        .iter()
+        .find(|source| source.label == options.label)
+        .cloned()
+        .unwrap_or_else(|| SourceSettings::new(options.label.clone(), ""));
+    source.kind = SourceKind::File;
+    source.path = path.to_string_lossy().into_owned();
+    source.optional = false;
+    source.poll = false;
+    source.schedule = None;
+
+    timestamp::configure(settings.timezone);
+    let timestamps = match options.speed {
+        ReplaySpeed::Max => None,
+        ReplaySpeed::Factor(_) => {
+            let timestamps = TimestampFormat::new(&source)?;
+            if timestamps.is_none() {
+                warn!(label = %options.label, "{}", msg::REPLAY_NO_TIMESTAMP_FORMAT);
+            }
+            timestamps
+        }
+    };
+
+    let settings = Settings {
+        sources: vec![source],
+        ..settings
+    };
+    let agent = Agent::builder().settings(settings).sink(sink).build()?;
+    let agent_shutdown = shutdown.child_token();
+    let agent_handle = tokio::spawn(agent.run(agent_shutdown.clone()));
+
+    // Collector 가 빈 파일의 끝을 기록한 뒤 써야 모든 라인이 수집됨
+    let deadline = Instant::now() + READY_TIMEOUT;
+    if !wait_for(&agent_handle, &shutdown, |position| position.is_some(), &options.label, Some(deadline)).await {
+        agent_shutdown.cancel();
+        agent_handle.await??;
+        bail!(msg::REPLAY_SETUP_FAILED);
+    }
+
+    info!(file = %options.file.display(), label = %options.label, speed = %options.speed, "{}", msg::REPLAY_STARTED);
+    let started = Instant::now();
+    let (lines, bytes) = copy_paced(input, &path, timestamps.as_ref(), options.speed, &shutdown).await?;
+
+    // 쓴 만큼 모두 읽은 뒤 종료해야 Forwarder 가 남은 이벤트를 플러시함
+    wait_for(&agent_handle, &shutdown, |position| position >= Some(bytes), &options.label, None).await;
+    agent_shutdown.cancel();
+    agent_handle.await??;
+
+    info!(lines, bytes, "{}", msg::REPLAY_FINISHED);
+    Ok(ReplayReport {
+        lines,
+        bytes,
+        elapsed: started.elapsed(),
+    })
+}
+
+/// 수집 위치가 조건을 만족할 때까지 대기, 에이전트가 먼저 끝나거나 취소되거나 제한 시간을 넘으면 false
+async fn wait_for(
+    agent: &JoinHandle<Result<()>>,
+    shutdown: &CancellationToken,
+    done: impl Fn(Option<u64>) -> bool,
+    label: &str,
+    deadline: Option<Instant>,
+) -> bool {
+    loop {
+        let position = status::registry()
+            .snapshot()
+            .sources
+            .iter()
+            .find(|source| source.label == label)
+            .map(|source| source.position);
+        if done(position) {
+            return true;
+        }
+
+        if agent.is_finished() || shutdown.is_cancelled() || deadline.is_some_and(|deadline| Instant::now() > deadline) {
+            return false;
+        }
+        time::sleep(PROGRESS_INTERVAL).await;
+    }
+}
+
+/// 원본을 라인 단위로 임시 파일 끝에 씀, 쓴 라인 수와 바이트 수 반환
+async fn copy_paced(
+    input: File,
+    path: &Path,
+    timestamps: Option<&TimestampFormat>,
+    speed: ReplaySpeed,
+    shutdown: &CancellationToken,
+) -> Result<(usize, u64)> {
+    let mut reader = BufReader::new(input);
+    let file = OpenOptions::new().append(true).open(path).await.context(msg::REPLAY_SETUP_FAILED)?;
+    let mut writer = BufWriter::with_capacity(256 * 1024, file);
+
+    let mut line = Vec::new();
+    let (mut lines, mut bytes) = (0usize, 0u64);
+    // 첫 레코드의 시각과 그때의 실제 시각, 이후 레코드는 시각 차이를 배속으로 나눈 만큼 뒤에 씀
+    let mut origin: Option<(DateTime<Utc>, Instant)> = None;
+
+    while !shutdown.is_cancelled() {
+        line.clear();
+        if reader.read_until(b'\n', &mut line).await.context(msg::REPLAY_READ_FAILED)? == 0 {
+            break;
+        }
+        // 개행 없는 마지막 라인은 Collector 가 다음 읽기를 기다리므로 닫아 줌
+        if !line.ends_with(b"\n") {
+            line.push(b'\n');
+        }
+
+        if let (ReplaySpeed::Factor(factor), Some(timestamps)) = (speed, timestamps)
+            && let Some(at) = timestamps.extract(&String::from_utf8_lossy(&line))
+        {
+            let (first, started) = *origin.get_or_insert((at, Instant::now()));
+            let target = started + (at - first).to_std().unwrap_or_default().div_f64(factor);
+
+            if target > Instant::now() {
+                // 기다리는 동안 앞서 쓴 라인이 수집되도록 먼저 내보냄
+                writer.flush().await.context(msg::REPLAY_WRITE_FAILED)?;
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = time::sleep_until(target) => {}
+                }
+            }
+        }
+
+        writer.write_all(&line).await.context(msg::REPLAY_WRITE_FAILED)?;
+        lines += 1;
+        bytes += line.len() as u64;
+    }
+
+    writer.flush().await.context(msg::REPLAY_WRITE_FAILED)?;
+    Ok((lines, bytes))
+}
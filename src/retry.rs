@@ -0,0 +1,198 @@
+//! 재시도 간격과 재시도 예산 (`retry`)
+//!
+//! 서버 재시작 뒤 여러 에이전트가 같은 간격으로 한꺼번에 재접속하지 않도록 지수 backoff 에 무작위 지연(jitter)을 섞고,
+//! 프로세스 전체(Streamer, 인증, 재연결)의 재시도 횟수를 분당 예산으로 제한
+
+use std::sync::{LazyLock, Mutex, MutexGuard, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::time;
+use tonic::{Code, Status};
+use tracing::warn;
+
+use crate::error_code::ErrorCode;
+use crate::messages as msg;
+
+use crate::settings::RetrySettings;
+
+const BUDGET_WINDOW: Duration = Duration::from_secs(60);
+
+static POLICY: LazyLock<RwLock<RetrySettings>> = LazyLock::new(Default::default);
+static BUDGET: LazyLock<Mutex<Budget>> = LazyLock::new(|| {
+    Mutex::new(Budget {
+        window_start: Instant::now(),
+        used: 0,
+    })
+});
+
+static SEND_STATE: LazyLock<Mutex<RetryState>> = LazyLock::new(Default::default);
+
+struct Budget {
+    window_start: Instant,
+    used: u32,
+}
+
+/// 배치 전송 재시도 상태, 관리 API `status` 와 heartbeat 로 노출
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RetryState {
+    /// 마지막 전송 성공 이후 일시적 오류로 실패한 횟수
+    pub consecutive_failures: u32,
+    /// 연속 실패가 시작된 시각
+    pub failing_since: Option<DateTime<Utc>>,
+    /// backoff 대기가 끝나는 시각, 대기 중이 아니면 None
+    pub backoff_until: Option<DateTime<Utc>>,
+    /// 이번 분의 재시도 예산을 다 써 실패한 배치를 재시도 없이 디스크 큐에 저장 중
+    pub budget_exhausted: bool,
+}
+
+/// 설정의 재시도 정책 적용, 이후 만드는 [`Backoff`] 와 예산에 반영
+pub fn configure(settings: &RetrySettings) {
+    *POLICY.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+}
+
+fn policy() -> RetrySettings {
+    POLICY.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn budget() -> MutexGuard<'static, Budget> {
+    BUDGET.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 재시도 한 번을 예산에서 차감, 이번 분의 예산을 다 썼으면 false
+pub fn try_acquire() -> bool {
+    let limit = policy().budget_per_minute;
+    if limit == 0 {
+        return true;
+    }
+
+    let mut budget = budget();
+    if budget.window_start.elapsed() >= BUDGET_WINDOW {
+        budget.window_start = Instant::now();
+        budget.used = 0;
+    }
+
+    if budget.used >= limit {
+        return false;
+    }
+    budget.used += 1;
+    true
+}
+
+/// 예산이 다시 채워질 때까지 남은 시간
+pub fn until_refill() -> Duration {
+    BUDGET_WINDOW.saturating_sub(budget().window_start.elapsed())
+}
+
+fn send_state() -> MutexGuard<'static, RetryState> {
+    SEND_STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 배치 전송이 일시적 오류로 실패해 `delay` 만큼 기다린 뒤 재시도
+pub fn record_send_backoff(delay: Duration) {
+    let now = Utc::now();
+    let mut state = send_state();
+    state.consecutive_failures += 1;
+    state.failing_since.get_or_insert(now);
+    state.backoff_until = chrono::Duration::from_std(delay).ok().map(|delay| now + delay);
+    state.budget_exhausted = false;
+}
+
+/// 재시도 예산이 없어 배치 전송 실패로 처리
+pub fn record_send_exhausted() {
+    let mut state = send_state();
+    state.consecutive_failures += 1;
+    state.failing_since.get_or_insert_with(Utc::now);
+    state.backoff_until = None;
+    state.budget_exhausted = true;
+}
+
+pub fn record_send_success() {
+    *send_state() = RetryState::default();
+}
+
+/// 현재 배치 전송 재시도 상태, 지난 backoff 와 다시 채워진 예산은 해제
+pub fn send_state_snapshot() -> RetryState {
+    let mut state = send_state().clone();
+    state.backoff_until = state.backoff_until.filter(|until| *until > Utc::now());
+    state.budget_exhausted &= !until_refill().is_zero();
+    state
+}
+
+/// 서버 재시작, 과부하 등 잠시 뒤 다시 보내면 성공할 수 있는 오류
+pub fn is_transient(status: &Status) -> bool {
+    matches!(
+        status.code(),
+        Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted | Code::Aborted
+    )
+}
+
+/// 서버가 배치 자체를 거부해 몇 번을 다시 보내도 실패할 오류
+pub fn is_permanent(status: &Status) -> bool {
+    matches!(
+        status.code(),
+        Code::InvalidArgument | Code::PermissionDenied | Code::FailedPrecondition | Code::OutOfRange
+    )
+}
+
+/// 일시적 오류면 backoff 후 다시 호출, 재시도 예산을 다 쓰면 마지막 오류 반환
+pub async fn with_backoff<T, F, Fut>(mut call: F) -> Result<T, Status>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Status>>,
+{
+    let mut backoff = Backoff::new();
+
+    loop {
+        let status = match call().await {
+            Err(status) if is_transient(&status) => status,
+            result => return result,
+        };
+
+        if !try_acquire() {
+            warn!(code = %ErrorCode::RetryBudgetExhausted, "{}", msg::RETRY_BUDGET_EXHAUSTED);
+            return Err(status);
+        }
+
+        let delay = backoff.next_delay();
+        warn!(backoff_ms = delay.as_millis() as u64, error = %status, "{}", msg::RPC_RETRYING);
+        time::sleep(delay).await;
+    }
+}
+
+/// 재시도 간격 계산, 성공하면 [`Backoff::reset`] 으로 처음 간격부터 다시 시작
+pub struct Backoff {
+    settings: RetrySettings,
+    next: Duration,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        let settings = policy();
+        let next = settings.base_delay();
+        Self { settings, next }
+    }
+
+    /// 이번 대기 시간, 다음 간격은 `multiplier` 배 (`max_delay_ms` 까지)
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = delay
+            .mul_f64(self.settings.multiplier.max(1.0))
+            .min(self.settings.max_delay());
+
+        // jitter 비율만큼 간격을 무작위로 줄여 에이전트끼리 재시도 시점을 흩뜨림
+        let jitter = self.settings.jitter.clamp(0.0, 1.0);
+        delay.mul_f64(1.0 - jitter * fastrand::f64())
+    }
+
+    pub fn reset(&mut self) {
+        self.next = self.settings.base_delay();
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,351 @@
+//! 라우팅 규칙의 조건식
+//!
+//! ```text
+//! label == "audit" && level >= WARN
+//! (path contains "/nginx/" || status >= 500) && !(line starts_with "GET /health")
+//! ```
+//!
+//! 식별자는 `label`, `line`(`message`), `path`, `level` 과 형식 파서가 뽑은 필드 이름.
+//! 대문자 레벨 이름(`TRACE` ~ `FATAL`)은 레벨 값, 양쪽이 숫자면 숫자로, 아니면 문자열로 비교.
+//! 없는 필드와의 비교는 항상 거짓
+
+use anyhow::{Result, bail};
+
+use crate::messages as msg;
+use crate::models::LogEvent;
+
+/// 레벨 이름, 순서가 심각도 순 (`FATAL` 이 가장 높음)
+const LEVELS: [&str; 6] = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR", "FATAL"];
+/// `WARN`, `ERROR` 의 순위
+pub const LEVEL_WARN: u8 = 3;
+pub const LEVEL_ERROR: u8 = 4;
+
+/// 레벨이 필드에 없을 때 라인 앞부분에서 레벨 단어를 찾는 토큰 수
+const LEVEL_SCAN_TOKENS: usize = 8;
+
+/// 해석된 조건식
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(Operand, Op, Operand),
+    /// 비교 없이 쓴 식별자, 값이 있고 비어 있지 않으면 참
+    Present(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Level(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+}
+
+/// 이벤트에서 읽은 값
+enum Value<'a> {
+    Str(&'a str),
+    Number(f64),
+    Level(u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+
+        let expr = parser.or()?;
+        if parser.pos < tokens.len() {
+            bail!("{}: {:?}", msg::ROUTE_UNEXPECTED_TOKEN, tokens[parser.pos]);
+        }
+        Ok(expr)
+    }
+
+    pub fn eval(&self, event: &LogEvent) -> bool {
+        match self {
+            Expr::And(left, right) => left.eval(event) && right.eval(event),
+            Expr::Or(left, right) => left.eval(event) || right.eval(event),
+            Expr::Not(inner) => !inner.eval(event),
+            Expr::Present(name) => lookup(event, name).is_some_and(|v| match v {
+                Value::Str(s) => !s.is_empty(),
+                _ => true,
+            }),
+            Expr::Compare(left, op, right) => {
+                // 한쪽이 레벨이면 다른 쪽도 레벨로 읽음
+                let as_level = is_level(left) || is_level(right);
+                match (resolve(event, left, as_level), resolve(event, right, as_level)) {
+                    (Some(left), Some(right)) => compare(&left, *op, &right),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+fn is_level(operand: &Operand) -> bool {
+    matches!(operand, Operand::Level(_)) || matches!(operand, Operand::Ident(name) if name == "level")
+}
+
+fn resolve<'a>(event: &'a LogEvent, operand: &'a Operand, as_level: bool) -> Option<Value<'a>> {
+    let value = match operand {
+        Operand::Ident(name) => lookup(event, name)?,
+        Operand::Str(s) => Value::Str(s),
+        Operand::Number(n) => Value::Number(*n),
+        Operand::Level(level) => Value::Level(*level),
+    };
+
+    match value {
+        Value::Str(s) if as_level => level_rank(s).map(Value::Level),
+        value => Some(value),
+    }
+}
+
+fn lookup<'a>(event: &'a LogEvent, name: &str) -> Option<Value<'a>> {
+    match name {
+        "label" => Some(Value::Str(&event.label)),
+        "line" | "message" => Some(Value::Str(event.line())),
+        "path" => Some(Value::Str(&event.path)),
+        "level" => event_level(event).map(Value::Level),
+        _ => event
+            .fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| Value::Str(value)),
+    }
+}
+
+/// `level`/`severity` 필드, 없으면 라인 앞부분의 레벨 단어 (`ERROR`, `[warn]` 등)의 순위
+pub fn event_level(event: &LogEvent) -> Option<u8> {
+    let field = event
+        .fields
+        .iter()
+        .find(|(key, _)| key == "level" || key == "severity")
+        .and_then(|(_, value)| level_rank(value));
+
+    field.or_else(|| {
+        event
+            .line()
+            .split_ascii_whitespace()
+            .take(LEVEL_SCAN_TOKENS)
+            .map(|token| token.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
+            .find_map(|token| LEVELS.iter().position(|level| *level == token))
+            .map(|rank| rank as u8)
+    })
+}
+
+/// 레벨 이름(대소문자 무시, 흔한 별칭 포함)이나 syslog 심각도 숫자(0 emerg ~ 7 debug)를 순위로
+fn level_rank(value: &str) -> Option<u8> {
+    let rank = match value.trim().to_ascii_uppercase().as_str() {
+        "TRACE" => 0,
+        "DEBUG" | "7" => 1,
+        "INFO" | "NOTICE" | "LOG" | "5" | "6" => 2,
+        "WARN" | "WARNING" | "4" => 3,
+        "ERROR" | "ERR" | "3" => 4,
+        "FATAL" | "CRITICAL" | "CRIT" | "ALERT" | "EMERG" | "PANIC" | "0" | "1" | "2" => 5,
+        _ => return None,
+    };
+    Some(rank)
+}
+
+fn compare(left: &Value, op: Op, right: &Value) -> bool {
+    let ordering = match (left, right) {
+        (Value::Level(l), Value::Level(r)) => l.partial_cmp(r),
+        (Value::Number(l), Value::Number(r)) => l.partial_cmp(r),
+        (Value::Str(s), Value::Number(n)) => s.trim().parse::<f64>().ok().and_then(|v| v.partial_cmp(n)),
+        (Value::Number(n), Value::Str(s)) => s.trim().parse::<f64>().ok().and_then(|v| n.partial_cmp(&v)),
+        (Value::Str(l), Value::Str(r)) => {
+            match op {
+                Op::Contains => return l.contains(r),
+                Op::StartsWith => return l.starts_with(r),
+                _ => {}
+            }
+            // 둘 다 숫자 문자열이면 숫자로 (`status >= "500"`)
+            match (l.trim().parse::<f64>(), r.trim().parse::<f64>()) {
+                (Ok(l), Ok(r)) if !matches!(op, Op::Eq | Op::Ne) => l.partial_cmp(&r),
+                _ => Some(l.cmp(r)),
+            }
+        }
+        _ => None,
+    };
+    let Some(ordering) = ordering else {
+        return false;
+    };
+
+    match op {
+        Op::Eq => ordering.is_eq(),
+        Op::Ne => ordering.is_ne(),
+        Op::Lt => ordering.is_lt(),
+        Op::Le => ordering.is_le(),
+        Op::Gt => ordering.is_gt(),
+        Op::Ge => ordering.is_ge(),
+        // 문자열이 아닌 값에는 포함 관계가 없음
+        Op::Contains | Op::StartsWith => false,
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '&' if chars.next_if(|(_, c)| *c == '&').is_some() => Token::And,
+            '|' if chars.next_if(|(_, c)| *c == '|').is_some() => Token::Or,
+            '=' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Op(Op::Eq),
+            '!' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Op(Op::Le),
+            '<' => Token::Op(Op::Lt),
+            '>' if chars.next_if(|(_, c)| *c == '=').is_some() => Token::Op(Op::Ge),
+            '>' => Token::Op(Op::Gt),
+            '"' => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, escaped)) => s.push(escaped),
+                            None => bail!(msg::ROUTE_UNTERMINATED_STRING),
+                        },
+                        Some((_, c)) => s.push(c),
+                        None => bail!(msg::ROUTE_UNTERMINATED_STRING),
+                    }
+                }
+                Token::Str(s)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                    end = i + c.len_utf8();
+                }
+                let number = &source[start..end];
+                Token::Number(number.parse().map_err(|_| anyhow::anyhow!("{}: {}", msg::ROUTE_INVALID_NUMBER, number))?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '.') {
+                    end = i + c.len_utf8();
+                }
+                match &source[start..end] {
+                    "contains" => Token::Op(Op::Contains),
+                    "starts_with" => Token::Op(Op::StartsWith),
+                    ident => Token::Ident(ident.to_string()),
+                }
+            }
+            c => bail!("{}: '{}' ({})", msg::ROUTE_UNEXPECTED_CHAR, c, start),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// 우선순위: `||` < `&&` < `!` < 비교
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.pos += 1;
+                Ok(Expr::Not(Box::new(self.unary()?)))
+            }
+            Some(Token::Open) => {
+                self.pos += 1;
+                let expr = self.or()?;
+                if self.next() != Some(&Token::Close) {
+                    bail!(msg::ROUTE_UNCLOSED_PAREN);
+                }
+                Ok(expr)
+            }
+            _ => self.comparison(),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let left = self.operand()?;
+
+        let Some(Token::Op(op)) = self.peek().cloned() else {
+            return match left {
+                Operand::Ident(name) => Ok(Expr::Present(name)),
+                _ => bail!(msg::ROUTE_EXPECTED_OPERATOR),
+            };
+        };
+        self.pos += 1;
+
+        Ok(Expr::Compare(left, op, self.operand()?))
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        let operand = match self.next() {
+            Some(Token::Ident(name)) => match LEVELS.iter().position(|level| level == name) {
+                Some(rank) => Operand::Level(rank as u8),
+                None => Operand::Ident(name.clone()),
+            },
+            Some(Token::Str(s)) => Operand::Str(s.clone()),
+            Some(Token::Number(n)) => Operand::Number(*n),
+            Some(token) => bail!("{}: {:?}", msg::ROUTE_UNEXPECTED_TOKEN, token),
+            None => bail!(msg::ROUTE_UNEXPECTED_END),
+        };
+        Ok(operand)
+    }
+}
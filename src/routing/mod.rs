@@ -0,0 +1,152 @@
+//! 이벤트별 전송 대상 선택 (`routes`)
+//!
+//! 규칙을 위에서부터 평가해 처음 맞는 규칙의 `sink` 로 보내고, `when` 이 없는 규칙은 항상 맞음 (else).
+//! `sink` 는 에이전트 Sink(`grpc`), `drop`, 또는 `sinks` 에 설정한 추가 전송 대상의 이름.
+//! 맞는 규칙이 없으면 에이전트 Sink 로 전송
+
+pub mod expr;
+
+use anyhow::{Context, Result, bail};
+
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::settings::{RouteRule, SinkSettings};
+use expr::Expr;
+
+/// 에이전트에 설정된 Sink (gRPC 서버, 임베드 시 호출자 채널/표준 출력)
+pub const SINK_DEFAULT: &str = "grpc";
+/// 전송하지 않고 버림
+pub const SINK_DROP: &str = "drop";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Sink,
+    Drop,
+    /// `sinks` 의 순서 번호
+    Named(usize),
+}
+
+struct CompiledRule {
+    when: Option<Expr>,
+    route: Route,
+}
+
+pub struct Router {
+    rules: Vec<CompiledRule>,
+}
+
+impl Router {
+    /// 규칙의 조건식 해석, 조건식이나 Sink 이름이 잘못되었으면 규칙 번호와 함께 오류
+    pub fn new(rules: &[RouteRule], sinks: &[SinkSettings]) -> Result<Self> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let route = match rule.sink.as_str() {
+                    SINK_DEFAULT => Route::Sink,
+                    SINK_DROP => Route::Drop,
+                    other => match sinks.iter().position(|sink| sink.name == other) {
+                        Some(index) => Route::Named(index),
+                        None => {
+                            let available: Vec<&str> = [SINK_DEFAULT, SINK_DROP]
+                                .into_iter()
+                                .chain(sinks.iter().map(|sink| sink.name.as_str()))
+                                .collect();
+                            bail!(
+                                "{}: {} (routes[{}], {}: {})",
+                                msg::ROUTE_UNKNOWN_SINK,
+                                other,
+                                i,
+                                msg::ROUTE_AVAILABLE_SINKS,
+                                available.join(", ")
+                            )
+                        }
+                    },
+                };
+                let when = rule
+                    .when
+                    .as_deref()
+                    .map(Expr::parse)
+                    .transpose()
+                    .with_context(|| format!("{} (routes[{}])", msg::ROUTE_INVALID_CONDITION, i))?;
+
+                Ok(CompiledRule { when, route })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// 처음 맞는 규칙의 대상, 없으면 에이전트 Sink
+    pub fn route(&self, event: &LogEvent) -> Route {
+        self.rules
+            .iter()
+            .find(|rule| rule.when.as_ref().is_none_or(|when| when.eval(event)))
+            .map_or(Route::Sink, |rule| rule.route)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::settings::SinkKind;
+
+    fn rule(when: Option<&str>, sink: &str) -> RouteRule {
+        RouteRule {
+            when: when.map(str::to_string),
+            sink: sink.to_string(),
+        }
+    }
+
+    fn sink(name: &str) -> SinkSettings {
+        SinkSettings {
+            name: name.to_string(),
+            kind: SinkKind::File,
+            path: format!("/tmp/{name}.jsonl"),
+            url: String::new(),
+            headers: Default::default(),
+        }
+    }
+
+    fn event(label: &str, line: &str) -> LogEvent {
+        LogEvent {
+            label: label.to_string(),
+            content: line.to_string().into(),
+            timestamp: Utc::now(),
+            path: Arc::from("/var/log/app.log"),
+            offset: 0,
+            file_id: 0,
+            fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn routes_to_named_sinks_in_order() {
+        let rules = [
+            rule(Some(r#"label == "audit" && level >= WARN"#), "grpc"),
+            rule(Some(r#"label == "audit""#), "archive"),
+            rule(Some(r#"line starts_with "GET /health""#), "drop"),
+            rule(None, "s3"),
+        ];
+        let router = Router::new(&rules, &[sink("s3"), sink("archive")]).unwrap();
+
+        assert_eq!(router.route(&event("audit", "ERROR denied")), Route::Sink);
+        assert_eq!(router.route(&event("audit", "INFO login")), Route::Named(1));
+        assert_eq!(router.route(&event("nginx", "GET /health 200")), Route::Drop);
+        assert_eq!(router.route(&event("nginx", "GET / 200")), Route::Named(0));
+    }
+
+    #[test]
+    fn unknown_sink_lists_configured_names() {
+        let e = Router::new(&[rule(None, "s4")], &[sink("s3")]).err().unwrap();
+        assert!(e.to_string().contains("grpc, drop, s3"), "{e}");
+    }
+}
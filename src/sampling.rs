@@ -0,0 +1,85 @@
+//! 역압 시 적응형 샘플링 (`adaptive_sampling`)
+//!
+//! 배치 채널이나 메모리 예산 사용률이 `high_water` 를 넘으면 WARN 미만(레벨을 모르는 라인 포함) 이벤트를
+//! N 건 중 1건만 남기도록 N 을 두 배씩 늘리고, `low_water` 아래로 내려가면 절반씩 되돌림.
+//! WARN 이상 이벤트는 항상 전송. 적용 중인 비율은 헬스 리포터가 heartbeat 와 게이지로 보고
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::routing::expr::{self, LEVEL_WARN};
+use crate::settings::AdaptiveSamplingSettings;
+
+/// 샘플링 비율을 바꾸는 최소 간격, 한 번에 급격히 바뀌지 않도록
+pub const ADJUST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 낮은 레벨 이벤트를 N 건 중 1건만 남김, 1 이면 모두 전송
+static KEEP_EVERY: AtomicU32 = AtomicU32::new(1);
+
+/// 현재 적용 중인 낮은 레벨 이벤트 전송 비율 (1.0 이면 샘플링 없음)
+pub fn rate() -> f64 {
+    1.0 / KEEP_EVERY.load(Ordering::Relaxed) as f64
+}
+
+/// 모든 Forwarder 레인이 공유하는 샘플링 조절기
+pub struct Sampler {
+    settings: AdaptiveSamplingSettings,
+    counter: AtomicU64,
+    last_adjust: Mutex<Instant>,
+}
+
+impl Sampler {
+    pub fn new(settings: AdaptiveSamplingSettings) -> Self {
+        Self {
+            settings,
+            counter: AtomicU64::new(0),
+            last_adjust: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// 보낼 이벤트면 true, WARN 이상은 항상 보냄
+    pub fn keep(&self, event: &LogEvent) -> bool {
+        let keep_every = KEEP_EVERY.load(Ordering::Relaxed) as u64;
+        if keep_every == 1 || expr::event_level(event).is_some_and(|level| level >= LEVEL_WARN) {
+            return true;
+        }
+
+        self.counter.fetch_add(1, Ordering::Relaxed).is_multiple_of(keep_every)
+    }
+
+    /// 사용률(0.0 ~ 1.0)에 맞춰 샘플링 강화/완화, 여러 레인이 호출해도 간격마다 한 번만 바꿈
+    pub fn adjust(&self, pressure: f64) {
+        {
+            let mut last = self.last_adjust.lock().unwrap_or_else(|e| e.into_inner());
+            if last.elapsed() < ADJUST_INTERVAL {
+                return;
+            }
+            *last = Instant::now();
+        }
+
+        let current = KEEP_EVERY.load(Ordering::Relaxed);
+        let next = if pressure >= self.settings.high_water {
+            (current * 2).min(self.settings.max_keep_every.max(1))
+        } else if pressure <= self.settings.low_water {
+            (current / 2).max(1)
+        } else {
+            current
+        };
+        if next == current {
+            return;
+        }
+
+        KEEP_EVERY.store(next, Ordering::Relaxed);
+        if next > current {
+            warn!(pressure = pressure, keep_every = next, "{}", msg::SAMPLING_INCREASED);
+        } else {
+            info!(pressure = pressure, keep_every = next, "{}", msg::SAMPLING_RELAXED);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! 수집 대상의 시간대별 수집/전송 구간 (`sources[].schedule`)
+//!
+//! 구간이 닫히면 `hold: collect` 는 읽기를 멈추고(파일이 버퍼, 닫힌 동안 여러 번 로테이션되면 중간 파일은 놓칠 수 있음),
+//! `hold: ship` 은 계속 읽되 Forwarder 가 배치를 `state/deferred/<라벨>` 에 모아 두었다가 구간이 열리면 디스크 큐로 옮겨 전송.
+//! 구간 경계에서만 바꾸므로 그 사이 `pause_source`/`resume_source` 는 다음 경계까지 유지됨.
+//! 보류 큐도 `max_spool_mb` 상한을 따르고, 상태 없는 실행에서는 보관하지 못해 바로 전송.
+//! 서버로 전송하는 실행에서만 판정하고 임베드 실행(`run_local`)에서는 항상 수집/전송
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard, RwLock};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::messages as msg;
+use crate::parser::timestamp;
+use crate::proto::log::LogBatch;
+use crate::settings::{ScheduleHold, SourceSettings};
+use crate::spool::{Spool, SPOOL_DIR};
+use crate::streamer;
+
+/// 구간 경계를 확인하는 간격
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 전송을 보류한 배치를 모아 두는 디렉터리, 수집 대상마다 하위 디렉터리
+static DEFERRED_DIR: &str = "state/deferred";
+
+static DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// 판정 작업 실행 중, 아니면 등록만 하고 멈추지 않음
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 라벨별 구간과 마지막 판정 (None 이면 아직 판정 전)
+static SCHEDULES: LazyLock<Mutex<Schedules>> = LazyLock::new(Default::default);
+
+type Schedules = BTreeMap<String, (Schedule, Option<bool>)>;
+
+/// 구간이 닫혀 전송을 보류 중인 라벨 (`hold: ship`)
+static DEFERRED: LazyLock<RwLock<HashSet<String>>> = LazyLock::new(Default::default);
+
+/// 수집 대상 하나의 구간
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    windows: Vec<Window>,
+    timezone: Tz,
+    hold: ScheduleHold,
+}
+
+/// `[요일] HH:MM-HH:MM`, 시각은 자정부터의 분
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    /// 월요일부터, 구간이 시작하는 요일
+    days: [bool; 7],
+    start: u32,
+    end: u32,
+}
+
+impl Schedule {
+    /// 설정의 `schedule`, 없으면 None
+    pub fn new(source: &SourceSettings) -> Result<Option<Self>> {
+        let Some(settings) = &source.schedule else {
+            return Ok(None);
+        };
+        if settings.windows.is_empty() {
+            bail!("{}: {}", msg::SCHEDULE_WINDOW_INVALID, source.label);
+        }
+
+        let windows = settings
+            .windows
+            .iter()
+            .map(|spec| Window::parse(spec).with_context(|| format!("{}: {}", msg::SCHEDULE_WINDOW_INVALID, spec)))
+            .collect::<Result<_>>()?;
+
+        Ok(Some(Self {
+            windows,
+            timezone: settings.timezone.unwrap_or_else(|| timestamp::timezone_for(source)),
+            hold: settings.hold,
+        }))
+    }
+
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.timezone);
+        let day = local.weekday().num_days_from_monday() as usize;
+        let minute = local.hour() * 60 + local.minute();
+        self.windows.iter().any(|window| window.contains(day, minute))
+    }
+}
+
+impl Window {
+    fn parse(spec: &str) -> Result<Self> {
+        let mut parts = spec.split_whitespace().rev();
+        let range = parts.next().context(msg::SCHEDULE_WINDOW_INVALID)?;
+        let days = match parts.next() {
+            Some(days) => parse_days(days)?,
+            None => [true; 7],
+        };
+        if parts.next().is_some() {
+            bail!(msg::SCHEDULE_WINDOW_INVALID);
+        }
+
+        let (start, end) = range.split_once('-').context(msg::SCHEDULE_WINDOW_INVALID)?;
+        Ok(Self {
+            days,
+            start: parse_time(start)?,
+            end: parse_time(end)?,
+        })
+    }
+
+    /// 시작 시각이 끝 시각보다 늦으면 자정을 넘고, 같으면 하루 전체
+    fn contains(&self, day: usize, minute: u32) -> bool {
+        if self.start < self.end {
+            return self.days[day] && (self.start..self.end).contains(&minute);
+        }
+        let previous = (day + 6) % 7;
+        (self.days[day] && minute >= self.start) || (self.days[previous] && minute < self.end)
+    }
+}
+
+/// `mon-fri`, `sat,sun`, `fri-mon` 처럼 요일 목록과 범위
+fn parse_days(spec: &str) -> Result<[bool; 7]> {
+    let day = |name: &str| {
+        DAYS.iter()
+            .position(|day| day.eq_ignore_ascii_case(name))
+            .with_context(|| format!("{}: {}", msg::SCHEDULE_WINDOW_INVALID, name))
+    };
+
+    let mut days = [false; 7];
+    for item in spec.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (day(from)?, day(to)?);
+                let mut current = from;
+                loop {
+                    days[current] = true;
+                    if current == to {
+                        break;
+                    }
+                    current = (current + 1) % 7;
+                }
+            }
+            None => days[day(item)?] = true,
+        }
+    }
+    Ok(days)
+}
+
+/// `HH:MM`, 끝 시각으로 `24:00` 허용
+fn parse_time(spec: &str) -> Result<u32> {
+    let (hour, minute) = spec.split_once(':').context(msg::SCHEDULE_WINDOW_INVALID)?;
+    let (hour, minute): (u32, u32) = (
+        hour.parse().context(msg::SCHEDULE_WINDOW_INVALID)?,
+        minute.parse().context(msg::SCHEDULE_WINDOW_INVALID)?,
+    );
+    if minute >= 60 || hour * 60 + minute > 24 * 60 {
+        bail!("{}: {}", msg::SCHEDULE_WINDOW_INVALID, spec);
+    }
+    Ok((hour * 60 + minute) % (24 * 60))
+}
+
+fn lock() -> MutexGuard<'static, Schedules> {
+    SCHEDULES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 수집 대상 시작 시 구간 등록 후 바로 판정, 설정이 바뀌었을 수 있으므로 이전 구간은 먼저 지움
+pub fn register(source: &SourceSettings) -> Result<()> {
+    let schedule = Schedule::new(source)?;
+    remove(&source.label);
+    let Some(schedule) = schedule else {
+        return Ok(());
+    };
+
+    lock().insert(source.label.clone(), (schedule, None));
+    if RUNNING.load(Ordering::Relaxed) {
+        evaluate(Utc::now());
+    }
+    Ok(())
+}
+
+/// 실행 중 제거한 수집 대상, 보류 중인 배치는 바로 전송
+pub fn remove(label: &str) {
+    lock().remove(label);
+    if DEFERRED.write().unwrap_or_else(|e| e.into_inner()).remove(label) {
+        release(label);
+    }
+}
+
+/// 구간 밖이라 전송을 보류 중
+pub fn is_deferred(label: &str) -> bool {
+    DEFERRED.read().unwrap_or_else(|e| e.into_inner()).contains(label)
+}
+
+/// 구간이 있는 라벨별 현재 열림 여부
+pub fn snapshot() -> BTreeMap<String, bool> {
+    lock()
+        .iter()
+        .filter_map(|(label, (_, open))| open.map(|open| (label.clone(), open)))
+        .collect()
+}
+
+/// 구간 경계마다 수집/전송을 멈추거나 재개
+pub fn spawn(shutdown: CancellationToken) {
+    RUNNING.store(true, Ordering::Relaxed);
+
+    tokio::spawn(async move {
+        let mut interval = time::interval(TICK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => evaluate(Utc::now()),
+            }
+        }
+        RUNNING.store(false, Ordering::Relaxed);
+    });
+}
+
+/// 판정이 바뀐 라벨만 적용
+fn evaluate(now: DateTime<Utc>) {
+    let changed: Vec<(String, ScheduleHold, bool)> = lock()
+        .iter_mut()
+        .filter_map(|(label, (schedule, last))| {
+            let open = schedule.is_open(now);
+            (*last != Some(open)).then(|| {
+                *last = Some(open);
+                (label.clone(), schedule.hold, open)
+            })
+        })
+        .collect();
+
+    for (label, hold, open) in changed {
+        if open {
+            info!(source = %label, hold = ?hold, "{}", msg::SCHEDULE_WINDOW_OPENED);
+        } else {
+            info!(source = %label, hold = ?hold, "{}", msg::SCHEDULE_WINDOW_CLOSED);
+        }
+
+        match hold {
+            ScheduleHold::Collect => collector::set_paused(&label, !open),
+            ScheduleHold::Ship => {
+                let mut deferred = DEFERRED.write().unwrap_or_else(|e| e.into_inner());
+                if open {
+                    deferred.remove(&label);
+                } else {
+                    deferred.insert(label.clone());
+                }
+                drop(deferred);
+
+                // 이전 실행에서 보류한 배치도 첫 판정에서 내보냄
+                if open {
+                    release(&label);
+                }
+            }
+        }
+    }
+}
+
+/// 보류한 배치를 수집 대상의 보류 큐에 저장, 실패하면 호출자가 바로 전송
+pub fn defer(label: &str, batch: &LogBatch) -> Result<()> {
+    Spool::open(Path::new(DEFERRED_DIR).join(label))?.push(batch)
+}
+
+/// 보류 큐의 배치를 디스크 큐로 옮기고 재전송 요청
+pub fn release(label: &str) {
+    let moved = (|| -> Result<usize> {
+        let deferred = Spool::open(Path::new(DEFERRED_DIR).join(label))?;
+        let paths = deferred.list()?;
+        if paths.is_empty() {
+            return Ok(0);
+        }
+
+        let spool = Spool::open(SPOOL_DIR)?;
+        for path in &paths {
+            deferred.move_to(path, &spool)?;
+        }
+        Ok(paths.len())
+    })();
+
+    match moved {
+        Ok(0) => {}
+        Ok(count) => {
+            info!(source = label, count, "{}", msg::SCHEDULE_DEFERRED_RELEASED);
+            streamer::request_resend();
+        }
+        Err(e) => warn!(source = label, error = %e, "{}", msg::SCHEDULE_RELEASE_FAILED),
+    }
+}
@@ -0,0 +1,224 @@
+//! 에이전트 자체 로그 수집 (`kind: self`)
+//!
+//! tracing 레이어가 로그 필터(`RUST_LOG`, SIGUSR1 전환)를 통과한 이벤트 중 `level` 이상을 넘기면
+//! 일반 수집 대상처럼 라벨을 붙여 파이프라인으로 보냄. 호스트에 접속하지 않고도 서버에서 에이전트 문제를 확인하기 위함.
+//! 문구를 본문으로, `level`, `target` 과 이벤트 필드(`source`, `code`, `error` 등)를 필드로 사용.
+//! 전송 실패 경고가 다시 전송되며 불어나지 않도록 채널이 차면 버리고, 버린 수는 다음 이벤트의 `dropped` 필드로 알림.
+//! 자체 로그 수집 대상은 하나만 실행 가능
+
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use anyhow::{bail, Context as _, Result};
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio_util::sync::CancellationToken;
+use tracing::field::{Field, Visit};
+use tracing::{info, Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::collector;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::Fields;
+use crate::settings::{SelfLogLevel, SourceSettings};
+use crate::status;
+
+/// 이벤트와 상태 조회에 표시할 경로
+const SELF_LOG_PATH: &str = "self";
+
+/// 레이어와 Collector 사이 채널 크기, 차면 버림
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// 실행 중인 자체 로그 수집 대상의 채널과 최소 레벨
+static CAPTURE: RwLock<Option<Capture>> = RwLock::new(None);
+
+/// 채널이 차서 버린 이벤트 수, 다음 이벤트에 실어 보내고 비움
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+struct Capture {
+    tx: Sender<Record>,
+    level: Level,
+}
+
+/// 레이어가 넘긴 이벤트 하나
+struct Record {
+    timestamp: DateTime<Utc>,
+    message: String,
+    fields: Fields,
+}
+
+/// 설정의 `level`, 생략 시 WARN
+pub fn threshold(source: &SourceSettings) -> Level {
+    match source.level.unwrap_or_default() {
+        SelfLogLevel::Error => Level::ERROR,
+        SelfLogLevel::Warn => Level::WARN,
+        SelfLogLevel::Info => Level::INFO,
+        SelfLogLevel::Debug => Level::DEBUG,
+    }
+}
+
+/// 자체 로그 수집 대상이 실행 중일 때 이벤트를 넘기는 tracing 레이어
+pub fn layer() -> SelfLogLayer {
+    SelfLogLayer
+}
+
+pub struct SelfLogLayer;
+
+impl<S: Subscriber> Layer<S> for SelfLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let capture = CAPTURE.read().unwrap_or_else(|e| e.into_inner());
+        let Some(capture) = capture.as_ref() else {
+            return;
+        };
+
+        let metadata = event.metadata();
+        if *metadata.level() > capture.level {
+            return;
+        }
+
+        let mut visitor = RecordVisitor {
+            message: String::new(),
+            fields: vec![
+                ("level".to_string(), metadata.level().to_string()),
+                ("target".to_string(), metadata.target().to_string()),
+            ],
+        };
+        event.record(&mut visitor);
+
+        let record = Record {
+            timestamp: Utc::now(),
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        if capture.tx.try_send(record).is_err() {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+struct RecordVisitor {
+    message: String,
+    fields: Fields,
+}
+
+impl Visit for RecordVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+}
+
+pub struct SelfLogCollector {
+    tx: Sender<LogEvent>,
+    rx: Receiver<Record>,
+    label: String,
+    level: Level,
+    path: Arc<str>,
+    /// 지금까지 전달한 이벤트 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+}
+
+impl SelfLogCollector {
+    /// 레이어에 채널 등록, 이미 자체 로그 수집 대상이 실행 중이면 오류
+    pub fn new(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        let level = threshold(&source);
+        let (records, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+        {
+            let mut capture = CAPTURE.write().unwrap_or_else(|e| e.into_inner());
+            if capture.is_some() {
+                bail!("{}: {}", msg::SELF_LOG_ALREADY_RUNNING, source.label);
+            }
+            *capture = Some(Capture { tx: records, level });
+        }
+        DROPPED.store(0, Ordering::Relaxed);
+
+        status::registry().update_source(&source.label, Path::new(SELF_LOG_PATH), 0);
+
+        Ok(Self {
+            tx,
+            rx,
+            label: source.label,
+            level,
+            path: Arc::from(SELF_LOG_PATH),
+            forwarded: 0,
+            budget,
+        })
+    }
+
+    /// `shutdown` 취소 시까지 넘겨받은 이벤트를 전달
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, level = %self.level, "{}", msg::SELF_LOG_STARTED);
+
+        loop {
+            let record = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                record = self.rx.recv() => record,
+            };
+            let Some(record) = record else {
+                break;
+            };
+            // 다시 읽을 수 없으므로 일시 중지 중 이벤트는 버림
+            if collector::is_paused(&self.label) {
+                continue;
+            }
+
+            self.budget.wait_for_capacity(&shutdown).await;
+            if self.send_event(record).await.is_err() {
+                break;
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    async fn send_event(&mut self, record: Record) -> Result<()> {
+        let mut fields = record.fields;
+        let dropped = DROPPED.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            fields.push(("dropped".to_string(), dropped.to_string()));
+        }
+
+        self.forwarded += 1;
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(record.message),
+            timestamp: record.timestamp,
+            path: Arc::clone(&self.path),
+            offset: self.forwarded,
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        status::registry().update_source(&self.label, Path::new(SELF_LOG_PATH), self.forwarded);
+        Ok(())
+    }
+}
+
+/// 중지되면 레이어가 더 넘기지 않도록 채널 해제
+impl Drop for SelfLogCollector {
+    fn drop(&mut self) {
+        CAPTURE.write().unwrap_or_else(|e| e.into_inner()).take();
+    }
+}
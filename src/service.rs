@@ -0,0 +1,212 @@
+#[cfg(not(windows))]
+use anyhow::Result;
+
+#[cfg(not(windows))]
+use crate::cli::{Overrides, ServiceAction};
+
+#[cfg(not(windows))]
+pub fn handle(_action: ServiceAction, _profile: Option<&str>, _overrides: &Overrides) -> Result<()> {
+    anyhow::bail!(rlog_agent::messages::SERVICE_WINDOWS_ONLY)
+}
+
+#[cfg(windows)]
+pub use windows_impl::handle;
+
+#[cfg(windows)]
+mod windows_impl {
+    use std::ffi::{OsStr, OsString};
+    use std::sync::OnceLock;
+    use std::time::{Duration, Instant};
+
+    use anyhow::{Context, Result, bail};
+    use tokio_util::sync::CancellationToken;
+    use tracing::{error, info};
+    use windows_service::service::{
+        Service, ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+
+    use crate::cli::{Overrides, ServiceAction};
+    use rlog_agent::messages as msg;
+
+    static SERVICE_NAME: &str = "rlog-agent";
+    static SERVICE_DISPLAY_NAME: &str = "rlog Agent";
+
+    /// 중지 완료 대기 상한, 에이전트 드레인(`drain_timeout` 기본 30초)보다 길게
+    const STOP_TIMEOUT: Duration = Duration::from_secs(90);
+    const STOP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// SCM 이 `service run` 으로 실행할 때 서비스 본체에 넘길 명령줄 지정 값 (프로필은 main 에서 선택됨)
+    static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    pub fn handle(action: ServiceAction, profile: Option<&str>, overrides: &Overrides) -> Result<()> {
+        match action {
+            ServiceAction::Install => install(overrides.to_args(profile)),
+            ServiceAction::Uninstall => uninstall(),
+            ServiceAction::Start => start(),
+            ServiceAction::Stop => stop(),
+            ServiceAction::Run => {
+                let _ = OVERRIDES.set(overrides.clone());
+                service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+                    .context(msg::SERVICE_DISPATCHER_FAILED)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// `args`(`--profile`, 명령줄 지정 값)는 `service run` 앞에 붙여 서비스 실행 인자로 등록
+    fn install(args: Vec<String>) -> Result<()> {
+        let manager = ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .context(msg::SERVICE_MANAGER_CONNECT_FAILED)?;
+
+        let service_info = ServiceInfo {
+            name: OsString::from(SERVICE_NAME),
+            display_name: OsString::from(SERVICE_DISPLAY_NAME),
+            service_type: ServiceType::OWN_PROCESS,
+            start_type: ServiceStartType::AutoStart,
+            error_control: ServiceErrorControl::Normal,
+            executable_path: std::env::current_exe()?,
+            launch_arguments: args
+                .into_iter()
+                .map(OsString::from)
+                .chain([OsString::from("service"), OsString::from("run")])
+                .collect(),
+            dependencies: vec![],
+            account_name: None,
+            account_password: None,
+        };
+
+        let service = manager
+            .create_service(&service_info, ServiceAccess::CHANGE_CONFIG)
+            .context(msg::SERVICE_INSTALL_FAILED)?;
+        service.set_description(msg::SERVICE_DESCRIPTION.text())?;
+
+        info!(service = SERVICE_NAME, "{}", msg::SERVICE_INSTALLED);
+        Ok(())
+    }
+
+    fn uninstall() -> Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .context(msg::SERVICE_MANAGER_CONNECT_FAILED)?;
+
+        let service = manager
+            .open_service(
+                SERVICE_NAME,
+                ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+            )
+            .context(msg::SERVICE_OPEN_FAILED)?;
+
+        if service.query_status()?.current_state != ServiceState::Stopped {
+            service.stop().context(msg::SERVICE_STOP_FAILED)?;
+            // 실행 중에 삭제하면 프로세스가 끝날 때까지 삭제 대기 상태로 남아 같은 이름으로 다시 등록할 수 없음
+            wait_stopped(&service)?;
+        }
+
+        service.delete().context(msg::SERVICE_UNINSTALL_FAILED)?;
+
+        info!(service = SERVICE_NAME, "{}", msg::SERVICE_UNINSTALLED);
+        Ok(())
+    }
+
+    fn wait_stopped(service: &Service) -> Result<()> {
+        let deadline = Instant::now() + STOP_TIMEOUT;
+
+        while service.query_status().context(msg::SERVICE_STOP_FAILED)?.current_state != ServiceState::Stopped {
+            if Instant::now() >= deadline {
+                bail!("{}: {}s", msg::SERVICE_STOP_TIMEOUT, STOP_TIMEOUT.as_secs());
+            }
+            std::thread::sleep(STOP_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    fn start() -> Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .context(msg::SERVICE_MANAGER_CONNECT_FAILED)?;
+
+        let service = manager
+            .open_service(SERVICE_NAME, ServiceAccess::START)
+            .context(msg::SERVICE_OPEN_FAILED)?;
+        service.start(&[] as &[&OsStr]).context(msg::SERVICE_START_FAILED)?;
+
+        info!(service = SERVICE_NAME, "{}", msg::SERVICE_START_REQUESTED);
+        Ok(())
+    }
+
+    fn stop() -> Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .context(msg::SERVICE_MANAGER_CONNECT_FAILED)?;
+
+        let service = manager
+            .open_service(SERVICE_NAME, ServiceAccess::STOP)
+            .context(msg::SERVICE_OPEN_FAILED)?;
+        service.stop().context(msg::SERVICE_STOP_FAILED)?;
+
+        info!(service = SERVICE_NAME, "{}", msg::SERVICE_STOP_REQUESTED);
+        Ok(())
+    }
+
+    fn service_main(_arguments: Vec<OsString>) {
+        if let Err(e) = run_service() {
+            error!(error = ?e, "{}", msg::SERVICE_RUN_FAILED);
+        }
+    }
+
+    fn run_service() -> Result<()> {
+        // SCM은 System32를 작업 디렉터리로 사용하므로 config/, state/ 상대 경로를 위해 실행 파일 위치로 이동
+        if let Some(dir) = std::env::current_exe()?.parent() {
+            std::env::set_current_dir(dir)?;
+        }
+
+        let shutdown = CancellationToken::new();
+        let handler_shutdown = shutdown.clone();
+
+        let event_handler = move |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                info!("{}", msg::SERVICE_STOP_RECEIVED);
+                handler_shutdown.cancel();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        };
+
+        let status_handle = service_control_handler::register(SERVICE_NAME, event_handler)
+            .context(msg::SERVICE_HANDLER_FAILED)?;
+
+        status_handle.set_service_status(service_status(
+            ServiceState::Running,
+            ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        ))?;
+
+        let runtime = tokio::runtime::Runtime::new()?;
+        let result = runtime.block_on(crate::run_agent(shutdown, OVERRIDES.get().unwrap_or(&Overrides::default())));
+
+        status_handle.set_service_status(service_status(
+            ServiceState::Stopped,
+            ServiceControlAccept::empty(),
+        ))?;
+
+        result
+    }
+
+    fn service_status(state: ServiceState, controls: ServiceControlAccept) -> ServiceStatus {
+        ServiceStatus {
+            service_type: ServiceType::OWN_PROCESS,
+            current_state: state,
+            controls_accepted: controls,
+            exit_code: ServiceExitCode::Win32(0),
+            checkpoint: 0,
+            wait_hint: Duration::default(),
+            process_id: None,
+        }
+    }
+}
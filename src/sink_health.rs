@@ -0,0 +1,123 @@
+//! 레인별 Sink 상태 (연속 실패, 대기 배치 수, 마지막 성공)
+//!
+//! Streamer 가 전송 결과를 기록하고 Forwarder 가 읽음. Sink 가 연속으로 실패하는 동안 대기 배치가 남아 있으면
+//! Forwarder 는 새 배치를 만들지 않고 수집 채널 읽기를 멈추며, 채널이 차면 Collector 도 읽기를 멈춤.
+//! 대기 배치가 모두 빠지면 한 배치씩 만들어 회복을 확인. 관리 API `status` 의 `sinks` 로 노출
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio::sync::mpsc::{Sender, WeakSender};
+use tracing::{info, warn};
+
+use crate::messages as msg;
+use crate::proto::log::LogBatch;
+
+/// 이 횟수만큼 연속으로 실패하면 Sink 를 비정상으로 판단
+pub const UNHEALTHY_AFTER_FAILURES: u32 = 3;
+
+static SINKS: LazyLock<Mutex<BTreeMap<&'static str, SinkHealth>>> = LazyLock::new(Default::default);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 레인의 Sink 상태, 같은 이름이면 Forwarder 와 Streamer 가 같은 상태를 공유
+pub fn lane(name: &'static str) -> SinkHealth {
+    lock(&SINKS)
+        .entry(name)
+        .or_insert_with(|| SinkHealth {
+            inner: Arc::new(Inner {
+                name,
+                consecutive_failures: AtomicU32::new(0),
+                last_success: Mutex::new(None),
+                queue: Mutex::new(None),
+                changed: Notify::new(),
+            }),
+        })
+        .clone()
+}
+
+/// 관리 API `status` 에 실을 레인별 Sink 상태
+pub fn snapshot() -> BTreeMap<String, SinkSnapshot> {
+    lock(&SINKS)
+        .iter()
+        .map(|(name, health)| (name.to_string(), health.snapshot()))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkSnapshot {
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    /// Sink 로 넘어가길 기다리는 배치 수
+    pub queue_depth: usize,
+    pub last_success: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct SinkHealth {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    name: &'static str,
+    consecutive_failures: AtomicU32,
+    last_success: Mutex<Option<DateTime<Utc>>>,
+    queue: Mutex<Option<WeakSender<LogBatch>>>,
+    changed: Notify,
+}
+
+impl SinkHealth {
+    pub fn record_success(&self) {
+        *lock(&self.inner.last_success) = Some(Utc::now());
+
+        let failures = self.inner.consecutive_failures.swap(0, Ordering::Relaxed);
+        if failures >= UNHEALTHY_AFTER_FAILURES {
+            info!(sink = self.inner.name, failures = failures, "{}", msg::SINK_RECOVERED);
+        }
+        self.inner.changed.notify_waiters();
+    }
+
+    pub fn record_failure(&self) {
+        let failures = self.inner.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures == UNHEALTHY_AFTER_FAILURES {
+            warn!(sink = self.inner.name, failures = failures, "{}", msg::SINK_UNHEALTHY);
+        }
+        self.inner.changed.notify_waiters();
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.inner.consecutive_failures.load(Ordering::Relaxed) < UNHEALTHY_AFTER_FAILURES
+    }
+
+    /// 대기 배치 수를 셀 배치 채널 (Forwarder 의 송신측)
+    pub fn watch_queue(&self, tx: &Sender<LogBatch>) {
+        *lock(&self.inner.queue) = Some(tx.downgrade());
+    }
+
+    pub fn queue_depth(&self) -> usize {
+        lock(&self.inner.queue)
+            .as_ref()
+            .and_then(WeakSender::upgrade)
+            .map_or(0, |tx| tx.max_capacity() - tx.capacity())
+    }
+
+    /// 다음 전송 결과 기록까지 대기
+    pub async fn changed(&self) {
+        self.inner.changed.notified().await;
+    }
+
+    fn snapshot(&self) -> SinkSnapshot {
+        SinkSnapshot {
+            healthy: self.is_healthy(),
+            consecutive_failures: self.inner.consecutive_failures.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth(),
+            last_success: *lock(&self.inner.last_success),
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! `routes` 가 이름으로 고르는 추가 전송 대상 (`sinks`)
+//!
+//! Forwarder 가 규칙에 맞는 이벤트를 대상별 채널로 넘기면 대상마다 태스크 하나가 `batch_size`/`flush_interval` 로
+//! 배치를 묶어 `file` 은 로그 한 건당 JSON 한 줄로 덧붙이고 `http` 는 gzip JSON 으로 POST.
+//! 에이전트 Sink 와 달리 디스크 큐가 없어 재시도 후에도 실패한 배치는 버림. 관리 API `status` 의 `sinks` 에 `sink:<이름>` 으로 표시
+
+use std::collections::HashSet;
+use std::mem;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio::time;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::batch_id;
+use crate::forwarder;
+use crate::http_fallback;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::proto::log::{Log, LogBatch};
+use crate::retry::Backoff;
+use crate::routing::{SINK_DEFAULT, SINK_DROP};
+use crate::settings::{Settings, SinkKind, SinkSettings};
+use crate::sink_health::{self, SinkHealth};
+
+const CHANNEL_CAPACITY: usize = 100;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// 배치 하나를 쓰거나 보내는 최대 시도 횟수
+const MAX_ATTEMPTS: u32 = 3;
+
+enum Writer {
+    File(PathBuf),
+    Http {
+        client: reqwest::Client,
+        url: String,
+        headers: Vec<(String, String)>,
+    },
+}
+
+/// 설정을 검사하고 대상마다 배치 태스크 시작, 채널은 `sinks` 순서 ([`crate::routing::Route::Named`] 의 번호)
+///
+/// 모든 송신측이 사라지면 남은 이벤트를 쓰고 끝나며, 반환한 핸들은 모든 대상이 끝날 때 완료
+pub fn start(settings: &Settings, budget: MemoryBudget) -> Result<(Vec<Sender<LogEvent>>, JoinHandle<()>)> {
+    validate(&settings.sinks)?;
+    let writers = settings.sinks.iter().map(writer).collect::<Result<Vec<_>>>()?;
+
+    let mut tasks = JoinSet::new();
+    let txs = settings
+        .sinks
+        .iter()
+        .zip(writers)
+        .map(|(sink, writer)| {
+            let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+            // 시작 시 대상 수만큼만 생성되므로 해제하지 않음
+            let name: &'static str = Box::leak(format!("sink:{}", sink.name).into_boxed_str());
+            tasks.spawn(
+                NamedSink {
+                    name,
+                    rx,
+                    writer,
+                    health: sink_health::lane(name),
+                    batch_size: settings.batch_size.max(1),
+                    flush_interval: Duration::from_secs(settings.flush_interval.max(1)),
+                    budget: budget.clone(),
+                }
+                .start(),
+            );
+            tx
+        })
+        .collect();
+
+    let handle = tokio::spawn(async move { while tasks.join_next().await.is_some() {} });
+    Ok((txs, handle))
+}
+
+/// 종류별 필수 값 확인 후 쓰기 대상 생성
+fn writer(sink: &SinkSettings) -> Result<Writer> {
+    Ok(match sink.kind {
+        SinkKind::File if sink.path.trim().is_empty() => bail!("{}: {}", msg::SINK_PATH_REQUIRED, sink.name),
+        SinkKind::File => Writer::File(PathBuf::from(&sink.path)),
+        SinkKind::Http if sink.url.trim().is_empty() => bail!("{}: {}", msg::SINK_URL_REQUIRED, sink.name),
+        SinkKind::Http => Writer::Http {
+            client: reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .context(msg::SINK_HTTP_CLIENT_FAILED)?,
+            url: sink.url.clone(),
+            headers: sink.headers.clone().into_iter().collect(),
+        },
+    })
+}
+
+/// `sinks` 이름 검사, 비었거나 예약된 이름(`grpc`, `drop`)이거나 중복이면 오류
+fn validate(sinks: &[SinkSettings]) -> Result<()> {
+    let mut names = HashSet::new();
+    for sink in sinks {
+        let name = sink.name.trim();
+        if name.is_empty() {
+            bail!(msg::SINK_NAME_REQUIRED);
+        }
+        if name == SINK_DEFAULT || name == SINK_DROP {
+            bail!("{}: {}", msg::SINK_NAME_RESERVED, name);
+        }
+        if !names.insert(name) {
+            bail!("{}: {}", msg::SINK_NAME_DUPLICATE, name);
+        }
+    }
+    Ok(())
+}
+
+struct NamedSink {
+    name: &'static str,
+    rx: Receiver<LogEvent>,
+    writer: Writer,
+    health: SinkHealth,
+    batch_size: usize,
+    flush_interval: Duration,
+    budget: MemoryBudget,
+}
+
+impl NamedSink {
+    async fn start(mut self) {
+        info!(sink = self.name, "{}", msg::NAMED_SINK_STARTED);
+
+        let mut logs: Vec<Log> = Vec::with_capacity(self.batch_size);
+        // logs 에 쌓인 이벤트의 예산 사용량, 쓰고 나면 해제
+        let mut pending_bytes = 0usize;
+        let mut interval = time::interval(self.flush_interval);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                event = self.rx.recv() => match event {
+                    Some(event) => {
+                        pending_bytes += memory::event_bytes(&event);
+                        logs.push(forwarder::event_to_log(event));
+                        if logs.len() >= self.batch_size {
+                            self.flush(&mut logs, &mut pending_bytes).await;
+                            interval.reset();
+                        }
+                    }
+                    None => {
+                        self.flush(&mut logs, &mut pending_bytes).await;
+                        break;
+                    }
+                },
+                _ = interval.tick() => self.flush(&mut logs, &mut pending_bytes).await,
+            }
+        }
+
+        info!(sink = self.name, "{}", msg::NAMED_SINK_STOPPED);
+    }
+
+    async fn flush(&mut self, logs: &mut Vec<Log>, pending_bytes: &mut usize) {
+        if logs.is_empty() {
+            return;
+        }
+
+        let batch = LogBatch {
+            batch_id: batch_id::next(),
+            send_at: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+            logs: mem::replace(logs, Vec::with_capacity(self.batch_size)),
+            trace_id: Uuid::new_v4().simple().to_string(),
+            metrics: Vec::new(),
+            tags: Default::default(),
+        };
+
+        let mut backoff = Backoff::new();
+        let mut attempt = 1;
+        loop {
+            match self.write(&batch).await {
+                Ok(()) => {
+                    self.health.record_success();
+                    break;
+                }
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    warn!(sink = self.name, batch_id = %batch.batch_id, attempt, error = %format!("{:#}", e), "{}", msg::NAMED_SINK_RETRYING);
+                    attempt += 1;
+                    time::sleep(backoff.next_delay()).await;
+                }
+                Err(e) => {
+                    self.health.record_failure();
+                    error!(
+                        sink = self.name,
+                        batch_id = %batch.batch_id,
+                        count = batch.logs.len(),
+                        error = %format!("{:#}", e),
+                        "{}",
+                        msg::NAMED_SINK_DROPPED
+                    );
+                    break;
+                }
+            }
+        }
+
+        self.budget.release(mem::take(pending_bytes));
+    }
+
+    async fn write(&self, batch: &LogBatch) -> Result<()> {
+        match &self.writer {
+            Writer::File(path) => {
+                let lines = http_fallback::encode_lines(batch)?;
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await
+                    .with_context(|| format!("{}: {}", msg::SINK_FILE_OPEN_FAILED, path.display()))?;
+                file.write_all(&lines).await?;
+                file.flush().await?;
+                Ok(())
+            }
+            Writer::Http { client, url, headers } => {
+                let mut request = client
+                    .post(url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(CONTENT_ENCODING, "gzip");
+                for (name, value) in headers {
+                    request = request.header(name, value);
+                }
+
+                let status = request
+                    .body(http_fallback::encode(batch)?)
+                    .send()
+                    .await
+                    .context(msg::SINK_HTTP_SEND_FAILED)?
+                    .status();
+                if !status.is_success() {
+                    bail!("{}: {}", msg::SINK_HTTP_REJECTED, status);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chrono::Utc;
+
+    use super::*;
+
+    fn event(line: &str) -> LogEvent {
+        LogEvent {
+            label: "audit".to_string(),
+            content: line.to_string().into(),
+            timestamp: Utc::now(),
+            path: Arc::from("/var/log/audit.log"),
+            offset: 0,
+            file_id: 0,
+            fields: Vec::new(),
+        }
+    }
+
+    fn settings(sinks: Vec<SinkSettings>) -> Settings {
+        Settings {
+            sinks,
+            ..Settings::default()
+        }
+    }
+
+    fn file_sink(name: &str, path: &str) -> SinkSettings {
+        SinkSettings {
+            name: name.to_string(),
+            kind: SinkKind::File,
+            path: path.to_string(),
+            url: String::new(),
+            headers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn rejects_reserved_duplicate_and_incomplete_sinks() {
+        let budget = MemoryBudget::new(0);
+        for sinks in [
+            vec![file_sink("grpc", "/tmp/a")],
+            vec![file_sink("a", "/tmp/a"), file_sink("a", "/tmp/b")],
+            vec![file_sink("a", "")],
+        ] {
+            assert!(start(&settings(sinks), budget.clone()).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn file_sink_writes_remaining_events_when_closed() {
+        let path = std::env::temp_dir().join(format!("rlog-agent-sink-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let budget = MemoryBudget::new(0);
+
+        let (txs, handle) = start(
+            &settings(vec![file_sink("archive", &path.to_string_lossy())]),
+            budget.clone(),
+        )
+        .unwrap();
+        for line in ["first", "second"] {
+            let event = event(line);
+            budget.add(memory::event_bytes(&event));
+            txs[0].send(event).await.unwrap();
+        }
+        drop(txs);
+        handle.await.unwrap();
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = written.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["line"], "first");
+        assert_eq!(lines[1]["label"], "audit");
+        assert_eq!(budget.used(), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,780 @@
+//! SNMP 트랩 수신 (`kind: snmp_trap`)
+//!
+//! v2c 트랩/인폼과 v3 트랩 데이터그램을 BER 로 풀어 [`LogEvent`] 로 변환, 인폼은 전달한 뒤 응답.
+//! v2c 는 `communities` 의 커뮤니티만, v3 는 `users` 의 USM 사용자만 받고 인증 사용자는 HMAC 을 검증.
+//! 암호화(authPriv)된 v3 메시지는 풀지 않고 버림.
+//! 본문은 트랩 OID 와 varbind 를 `oid=value` 로 이은 문자열이고, varbind 는 OID 를 키로 구조화 필드에도 실음
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, anyhow, bail};
+use bytes::Bytes;
+use chrono::Utc;
+use hmac::digest::{Digest, KeyInit};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc::Sender;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::{self, Fields};
+use crate::settings::{SnmpAuthProtocol, SnmpTrapSettings, SourceFormat, SourceSettings};
+use crate::status;
+
+/// `listen` 생략 시 수신 주소, 트랩은 원격 장비가 보내므로 모든 주소에서 받고
+/// 기본 포트 162 는 `CAP_NET_BIND_SERVICE` 가 필요해 권한 없이 바인드할 수 있는 1162 사용
+pub const DEFAULT_LISTEN: &str = "0.0.0.0:1162";
+
+/// UDP 데이터그램 최대 크기
+const MAX_DATAGRAM: usize = 65536;
+
+const VERSION_V2C: i64 = 1;
+const VERSION_V3: i64 = 3;
+
+/// msgSecurityModel 의 USM
+const SECURITY_MODEL_USM: i64 = 3;
+
+/// msgFlags 비트
+const FLAG_AUTH: u8 = 0x01;
+const FLAG_PRIV: u8 = 0x02;
+
+/// RFC 3414 의 암호 확장 길이 (암호를 이 길이까지 반복해 해시)
+const PASSWORD_EXPANSION: usize = 1024 * 1024;
+
+/// RFC 3414 의 최소 암호 길이
+const MIN_PASSWORD_LEN: usize = 8;
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_NULL: u8 = 0x05;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_IP_ADDRESS: u8 = 0x40;
+const TAG_COUNTER32: u8 = 0x41;
+const TAG_GAUGE32: u8 = 0x42;
+const TAG_TIMETICKS: u8 = 0x43;
+const TAG_COUNTER64: u8 = 0x46;
+const TAG_NO_SUCH_OBJECT: u8 = 0x80;
+const TAG_NO_SUCH_INSTANCE: u8 = 0x81;
+const TAG_END_OF_MIB_VIEW: u8 = 0x82;
+
+const PDU_RESPONSE: u8 = 0xa2;
+const PDU_INFORM: u8 = 0xa6;
+const PDU_TRAP_V2: u8 = 0xa7;
+
+/// 트랩 첫 두 varbind, 본문 대신 `uptime`, `trap_oid` 필드로 실음
+const SYS_UPTIME_OID: &str = "1.3.6.1.2.1.1.3.0";
+const SNMP_TRAP_OID: &str = "1.3.6.1.6.3.1.1.4.1.0";
+
+/// 암호에서 만든 키(Ku)까지 준비된 v3 사용자, 엔진별 키는 받을 때마다 지역화
+struct User {
+    name: String,
+    auth: Option<(SnmpAuthProtocol, Vec<u8>)>,
+}
+
+/// 풀어낸 트랩 PDU, 인폼 응답에 쓰도록 request-id 와 varbind 목록은 원래 바이트를 보관
+struct Pdu<'a> {
+    kind: u8,
+    request_id: &'a [u8],
+    raw_varbinds: &'a [u8],
+    varbinds: Vec<(String, String)>,
+}
+
+/// 인증을 통과한 트랩, `security` 는 v2c 커뮤니티나 v3 사용자 이름
+struct Trap<'a> {
+    version: &'static str,
+    security: String,
+    pdu: Pdu<'a>,
+}
+
+pub struct SnmpTrapCollector {
+    tx: Sender<LogEvent>,
+    label: String,
+    socket: UdpSocket,
+    communities: Vec<String>,
+    users: Vec<User>,
+    format: SourceFormat,
+    path: Arc<str>,
+    /// 지금까지 전달한 트랩 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+}
+
+impl SnmpTrapCollector {
+    pub async fn bind(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        let settings = source.snmp.unwrap_or_default();
+        validate(&settings)?;
+
+        let listen = source.listen.as_deref().unwrap_or(DEFAULT_LISTEN);
+        let socket = UdpSocket::bind(listen)
+            .await
+            .with_context(|| format!("{}: {}", msg::SNMP_BIND_FAILED, listen))?;
+
+        let users = settings
+            .users
+            .into_iter()
+            .map(|user| User {
+                auth: user
+                    .auth_protocol
+                    .zip(user.auth_password)
+                    .map(|(protocol, password)| (protocol, password_key(protocol, password.as_bytes()))),
+                name: user.name,
+            })
+            .collect();
+
+        let path = format!("udp://{}", listen);
+        status::registry().update_source(&source.label, Path::new(&path), 0);
+
+        Ok(Self {
+            tx,
+            label: source.label,
+            socket,
+            communities: settings.communities,
+            users,
+            format: source.format,
+            path: Arc::from(path),
+            forwarded: 0,
+            budget,
+        })
+    }
+
+    /// `shutdown` 취소 시까지 트랩을 받아 전달
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, path = %self.path, "{}", msg::SNMP_STARTED);
+
+        let mut buf = vec![0u8; MAX_DATAGRAM];
+
+        loop {
+            let received = tokio::select! {
+                _ = shutdown.cancelled() => break,
+                received = self.socket.recv_from(&mut buf) => received,
+            };
+
+            let (len, peer) = match received {
+                Ok(received) => received,
+                Err(e) => {
+                    warn!(source = %self.label, error = %e, "{}", msg::SNMP_RECEIVE_FAILED);
+                    continue;
+                }
+            };
+            // 파일과 달리 다시 받을 수 없으므로 일시 중지 중 트랩은 버림, 인폼은 응답하지 않아 보낸 쪽이 재전송
+            if collector::is_paused(&self.label) {
+                continue;
+            }
+
+            let datagram = &buf[..len];
+            let trap = match self.decode(datagram) {
+                Ok(trap) => trap,
+                Err(e) => {
+                    warn!(source = %self.label, peer = %peer, error = %format!("{:#}", e), "{}", msg::SNMP_INVALID_MESSAGE);
+                    continue;
+                }
+            };
+            let response = (trap.pdu.kind == PDU_INFORM).then(|| inform_response(&trap));
+
+            self.budget.wait_for_capacity(&shutdown).await;
+            if let Err(e) = self.send_event(trap, peer).await {
+                warn!(source = %self.label, error = %e, "{}", msg::SNMP_RECEIVE_FAILED);
+                break;
+            }
+
+            if let Some(response) = response
+                && let Err(e) = self.socket.send_to(&response, peer).await
+            {
+                warn!(source = %self.label, peer = %peer, error = %e, "{}", msg::SNMP_RESPONSE_FAILED);
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    fn decode<'a>(&self, datagram: &'a [u8]) -> Result<Trap<'a>> {
+        let mut message = Reader::new(Reader::new(datagram).expect(TAG_SEQUENCE)?);
+
+        match message.integer()? {
+            VERSION_V2C => self.decode_v2c(message),
+            VERSION_V3 => self.decode_v3(datagram, message),
+            version => bail!("{}: {}", msg::SNMP_UNSUPPORTED_VERSION, version),
+        }
+    }
+
+    fn decode_v2c<'a>(&self, mut message: Reader<'a>) -> Result<Trap<'a>> {
+        let community = message.expect(TAG_OCTET_STRING)?;
+        if !self.communities.iter().any(|c| c.as_bytes() == community) {
+            bail!(msg::SNMP_UNKNOWN_COMMUNITY);
+        }
+
+        let (kind, pdu) = message.next()?;
+        if kind != PDU_TRAP_V2 && kind != PDU_INFORM {
+            bail!("{}: 0x{:02x}", msg::SNMP_UNSUPPORTED_PDU, kind);
+        }
+
+        Ok(Trap {
+            version: "v2c",
+            security: String::from_utf8_lossy(community).into_owned(),
+            pdu: decode_pdu(kind, pdu)?,
+        })
+    }
+
+    /// USM 보안 파라미터로 사용자를 찾고 보안 수준에 맞게 인증, 인폼은 엔진 탐색이 필요해 트랩만 받음
+    fn decode_v3<'a>(&self, datagram: &'a [u8], mut message: Reader<'a>) -> Result<Trap<'a>> {
+        let mut global = Reader::new(message.expect(TAG_SEQUENCE)?);
+        let _msg_id = global.integer()?;
+        let _max_size = global.integer()?;
+        let flags = global.expect(TAG_OCTET_STRING)?.first().copied().unwrap_or(0);
+        if global.integer()? != SECURITY_MODEL_USM {
+            bail!(msg::SNMP_UNSUPPORTED_SECURITY_MODEL);
+        }
+
+        let mut usm = Reader::new(Reader::new(message.expect(TAG_OCTET_STRING)?).expect(TAG_SEQUENCE)?);
+        let engine_id = usm.expect(TAG_OCTET_STRING)?;
+        let _engine_boots = usm.integer()?;
+        let _engine_time = usm.integer()?;
+        let user_name = usm.expect(TAG_OCTET_STRING)?;
+        let auth_params = usm.expect(TAG_OCTET_STRING)?;
+
+        let user = self
+            .users
+            .iter()
+            .find(|u| u.name.as_bytes() == user_name)
+            .ok_or_else(|| anyhow!("{}: {}", msg::SNMP_UNKNOWN_USER, String::from_utf8_lossy(user_name)))?;
+        if flags & FLAG_PRIV != 0 {
+            bail!(msg::SNMP_PRIV_UNSUPPORTED);
+        }
+        match (&user.auth, flags & FLAG_AUTH != 0) {
+            (Some((protocol, key)), true) => authenticate(*protocol, key, engine_id, datagram, auth_params)?,
+            (None, false) => {}
+            _ => bail!(msg::SNMP_SECURITY_LEVEL_MISMATCH),
+        }
+
+        let mut scoped = Reader::new(message.expect(TAG_SEQUENCE)?);
+        let _context_engine_id = scoped.expect(TAG_OCTET_STRING)?;
+        let _context_name = scoped.expect(TAG_OCTET_STRING)?;
+        let (kind, pdu) = scoped.next()?;
+        if kind != PDU_TRAP_V2 {
+            bail!("{}: 0x{:02x}", msg::SNMP_UNSUPPORTED_PDU, kind);
+        }
+
+        Ok(Trap {
+            version: "v3",
+            security: user.name.clone(),
+            pdu: decode_pdu(kind, pdu)?,
+        })
+    }
+
+    async fn send_event(&mut self, trap: Trap<'_>, peer: SocketAddr) -> Result<()> {
+        let mut fields = Fields::new();
+        fields.push(("snmp_version".to_string(), trap.version.to_string()));
+        let security_key = if trap.version == "v3" { "user" } else { "community" };
+        fields.push((security_key.to_string(), trap.security));
+        fields.push(("agent".to_string(), peer.ip().to_string()));
+
+        let mut trap_oid = None;
+        let mut pairs = Vec::new();
+        for (oid, value) in trap.pdu.varbinds {
+            match oid.as_str() {
+                SYS_UPTIME_OID => fields.push(("uptime".to_string(), value)),
+                SNMP_TRAP_OID => trap_oid = Some(value),
+                _ => {
+                    pairs.push(format!("{}={}", oid, value));
+                    fields.push((oid, value));
+                }
+            }
+        }
+
+        let content = match trap_oid {
+            Some(trap_oid) => {
+                pairs.insert(0, trap_oid.clone());
+                fields.push(("trap_oid".to_string(), trap_oid));
+                pairs.join(" ")
+            }
+            None => pairs.join(" "),
+        };
+        fields.extend(parser::parse(self.format, &content));
+
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(content),
+            timestamp: Utc::now(),
+            path: Arc::clone(&self.path),
+            offset: self.forwarded,
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        self.forwarded += 1;
+        status::registry().update_source(&self.label, Path::new(self.path.as_ref()), self.forwarded);
+
+        Ok(())
+    }
+}
+
+/// 커뮤니티와 사용자 설정 확인, 인증 프로토콜을 쓰는 사용자는 8자 이상 암호 필요
+pub fn validate(settings: &SnmpTrapSettings) -> Result<()> {
+    if settings.communities.is_empty() && settings.users.is_empty() {
+        bail!(msg::SNMP_NOT_CONFIGURED);
+    }
+
+    for user in &settings.users {
+        match (user.auth_protocol, user.auth_password.as_deref()) {
+            (Some(_), Some(password)) if password.len() >= MIN_PASSWORD_LEN => {}
+            (None, None) => {}
+            _ => bail!("{}: {}", msg::SNMP_INVALID_USER, user.name),
+        }
+    }
+
+    Ok(())
+}
+
+/// 트랩 PDU: request-id, error-status, error-index, varbind 목록
+fn decode_pdu(kind: u8, pdu: &[u8]) -> Result<Pdu<'_>> {
+    let mut pdu = Reader::new(pdu);
+    let request_id = pdu.expect(TAG_INTEGER)?;
+    let _error_status = pdu.integer()?;
+    let _error_index = pdu.integer()?;
+    let raw_varbinds = pdu.expect(TAG_SEQUENCE)?;
+
+    let mut varbinds = Vec::new();
+    let mut list = Reader::new(raw_varbinds);
+    while !list.is_empty() {
+        let mut varbind = Reader::new(list.expect(TAG_SEQUENCE)?);
+        let oid = decode_oid(varbind.expect(TAG_OID)?)?;
+        let (tag, value) = varbind.next()?;
+        varbinds.push((oid, decode_value(tag, value)?));
+    }
+
+    Ok(Pdu {
+        kind,
+        request_id,
+        raw_varbinds,
+        varbinds,
+    })
+}
+
+/// v2c 인폼에 받은 request-id 와 varbind 를 그대로 담은 Response PDU
+fn inform_response(trap: &Trap<'_>) -> Vec<u8> {
+    let mut pdu = Vec::new();
+    encode_tlv(TAG_INTEGER, trap.pdu.request_id, &mut pdu);
+    encode_tlv(TAG_INTEGER, &[0], &mut pdu);
+    encode_tlv(TAG_INTEGER, &[0], &mut pdu);
+    encode_tlv(TAG_SEQUENCE, trap.pdu.raw_varbinds, &mut pdu);
+
+    let mut message = Vec::new();
+    encode_tlv(TAG_INTEGER, &[VERSION_V2C as u8], &mut message);
+    encode_tlv(TAG_OCTET_STRING, trap.security.as_bytes(), &mut message);
+    encode_tlv(PDU_RESPONSE, &pdu, &mut message);
+
+    let mut out = Vec::new();
+    encode_tlv(TAG_SEQUENCE, &message, &mut out);
+    out
+}
+
+fn encode_tlv(tag: u8, content: &[u8], out: &mut Vec<u8>) {
+    out.push(tag);
+
+    let len = content.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+
+    out.extend_from_slice(content);
+}
+
+/// 인증 파라미터 자리를 0 으로 채운 메시지 전체의 HMAC 을 엔진별로 지역화한 키로 검증 (RFC 3414, RFC 7860)
+fn authenticate(protocol: SnmpAuthProtocol, key: &[u8], engine_id: &[u8], datagram: &[u8], auth_params: &[u8]) -> Result<()> {
+    if auth_params.len() != mac_len(protocol) {
+        bail!(msg::SNMP_AUTH_FAILED);
+    }
+
+    // auth_params 는 datagram 을 자른 조각이므로 주소 차이가 메시지 안의 위치
+    let start = auth_params.as_ptr() as usize - datagram.as_ptr() as usize;
+    let mut zeroed = datagram.to_vec();
+    zeroed[start..start + auth_params.len()].fill(0);
+
+    let verified = match protocol {
+        SnmpAuthProtocol::Md5 => verify_mac::<Hmac<Md5>>(&localize_key::<Md5>(key, engine_id), &zeroed, auth_params),
+        SnmpAuthProtocol::Sha => verify_mac::<Hmac<Sha1>>(&localize_key::<Sha1>(key, engine_id), &zeroed, auth_params),
+        SnmpAuthProtocol::Sha256 => verify_mac::<Hmac<Sha256>>(&localize_key::<Sha256>(key, engine_id), &zeroed, auth_params),
+        SnmpAuthProtocol::Sha512 => verify_mac::<Hmac<Sha512>>(&localize_key::<Sha512>(key, engine_id), &zeroed, auth_params),
+    };
+    if !verified {
+        bail!(msg::SNMP_AUTH_FAILED);
+    }
+
+    Ok(())
+}
+
+/// 메시지에 실리는 잘린 HMAC 길이
+fn mac_len(protocol: SnmpAuthProtocol) -> usize {
+    match protocol {
+        SnmpAuthProtocol::Md5 | SnmpAuthProtocol::Sha => 12,
+        SnmpAuthProtocol::Sha256 => 24,
+        SnmpAuthProtocol::Sha512 => 48,
+    }
+}
+
+/// 암호를 1MB 까지 반복해 해시한 키 (Ku), 시작 시 한 번만 계산
+fn password_key(protocol: SnmpAuthProtocol, password: &[u8]) -> Vec<u8> {
+    match protocol {
+        SnmpAuthProtocol::Md5 => expand_password::<Md5>(password),
+        SnmpAuthProtocol::Sha => expand_password::<Sha1>(password),
+        SnmpAuthProtocol::Sha256 => expand_password::<Sha256>(password),
+        SnmpAuthProtocol::Sha512 => expand_password::<Sha512>(password),
+    }
+}
+
+fn expand_password<D: Digest>(password: &[u8]) -> Vec<u8> {
+    let expanded: Vec<u8> = password.iter().copied().cycle().take(PASSWORD_EXPANSION).collect();
+    D::digest(&expanded).to_vec()
+}
+
+/// 엔진 ID 로 지역화한 키: H(Ku || engineID || Ku)
+fn localize_key<D: Digest>(key: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    D::new().chain_update(key).chain_update(engine_id).chain_update(key).finalize().to_vec()
+}
+
+fn verify_mac<M: Mac + KeyInit>(key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let Ok(mut mac) = <M as Mac>::new_from_slice(key) else {
+        return false;
+    };
+    mac.update(data);
+    mac.verify_truncated_left(tag).is_ok()
+}
+
+/// BER TLV 를 차례로 읽는 커서
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// 다음 TLV 의 태그와 내용
+    fn next(&mut self) -> Result<(u8, &'a [u8])> {
+        let buf = self.buf;
+        let [tag, first, rest @ ..] = buf else {
+            bail!(msg::SNMP_TRUNCATED);
+        };
+
+        let (len, rest) = if first & 0x80 == 0 {
+            (*first as usize, rest)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || rest.len() < count {
+                bail!(msg::SNMP_TRUNCATED);
+            }
+            let len = rest[..count].iter().fold(0usize, |len, b| len << 8 | *b as usize);
+            (len, &rest[count..])
+        };
+        if rest.len() < len {
+            bail!(msg::SNMP_TRUNCATED);
+        }
+
+        let (content, rest) = rest.split_at(len);
+        self.buf = rest;
+        Ok((*tag, content))
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<&'a [u8]> {
+        let (tag, content) = self.next()?;
+        if tag != expected {
+            bail!("{}: 0x{:02x}", msg::SNMP_UNEXPECTED_TAG, tag);
+        }
+        Ok(content)
+    }
+
+    fn integer(&mut self) -> Result<i64> {
+        decode_integer(self.expect(TAG_INTEGER)?)
+    }
+}
+
+fn decode_integer(content: &[u8]) -> Result<i64> {
+    if content.is_empty() || content.len() > 8 {
+        bail!(msg::SNMP_INVALID_INTEGER);
+    }
+
+    let sign = if content[0] & 0x80 != 0 { -1 } else { 0 };
+    Ok(content.iter().fold(sign, |value, b| value << 8 | *b as i64))
+}
+
+/// Counter/Gauge/TimeTicks, 최상위 비트를 위한 0 바이트가 붙을 수 있음
+fn decode_unsigned(content: &[u8]) -> Result<u64> {
+    if content.is_empty() || content.len() > 9 || (content.len() == 9 && content[0] != 0) {
+        bail!(msg::SNMP_INVALID_INTEGER);
+    }
+
+    Ok(content.iter().fold(0u64, |value, b| value << 8 | *b as u64))
+}
+
+fn decode_oid(content: &[u8]) -> Result<String> {
+    let mut ids = Vec::new();
+    let mut id = 0u64;
+    for b in content {
+        if id > u64::MAX >> 7 {
+            bail!(msg::SNMP_INVALID_OID);
+        }
+        id = id << 7 | (b & 0x7f) as u64;
+        if b & 0x80 == 0 {
+            ids.push(id);
+            id = 0;
+        }
+    }
+    if ids.is_empty() || content.last().is_some_and(|b| b & 0x80 != 0) {
+        bail!(msg::SNMP_INVALID_OID);
+    }
+
+    // 첫 하위 식별자에 앞 두 자리가 X * 40 + Y 로 합쳐져 있음
+    let (first, second) = match ids[0] {
+        id @ 0..40 => (0, id),
+        id @ 40..80 => (1, id - 40),
+        id => (2, id - 80),
+    };
+    let mut oid = format!("{}.{}", first, second);
+    for id in &ids[1..] {
+        oid.push('.');
+        oid.push_str(&id.to_string());
+    }
+
+    Ok(oid)
+}
+
+/// varbind 값을 문자열로, 출력할 수 없는 OCTET STRING 은 `aa:bb` 형태 16진수
+fn decode_value(tag: u8, content: &[u8]) -> Result<String> {
+    let value = match tag {
+        TAG_INTEGER => decode_integer(content)?.to_string(),
+        TAG_OCTET_STRING => match std::str::from_utf8(content) {
+            Ok(s) if !s.chars().any(|c| c.is_control() && !c.is_whitespace()) => s.to_string(),
+            _ => hex(content),
+        },
+        TAG_NULL => String::new(),
+        TAG_OID => decode_oid(content)?,
+        TAG_IP_ADDRESS if content.len() == 4 => {
+            format!("{}.{}.{}.{}", content[0], content[1], content[2], content[3])
+        }
+        TAG_COUNTER32 | TAG_GAUGE32 | TAG_TIMETICKS | TAG_COUNTER64 => decode_unsigned(content)?.to_string(),
+        TAG_NO_SUCH_OBJECT => "noSuchObject".to_string(),
+        TAG_NO_SUCH_INSTANCE => "noSuchInstance".to_string(),
+        TAG_END_OF_MIB_VIEW => "endOfMibView".to_string(),
+        _ => hex(content),
+    };
+
+    Ok(value)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::settings::SnmpUser;
+
+    /// sysUpTime.0, snmpTrapOID.0, linkDown, ifIndex.2
+    const UPTIME: &[u8] = &[0x2b, 6, 1, 2, 1, 1, 3, 0];
+    const TRAP_OID: &[u8] = &[0x2b, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+    const LINK_DOWN: &[u8] = &[0x2b, 6, 1, 6, 3, 1, 1, 5, 3];
+    const IF_INDEX: &[u8] = &[0x2b, 6, 1, 2, 1, 2, 2, 1, 1, 2];
+
+    fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode_tlv(tag, content, &mut out);
+        out
+    }
+
+    fn varbind(oid: &[u8], tag: u8, value: &[u8]) -> Vec<u8> {
+        tlv(TAG_SEQUENCE, &[tlv(TAG_OID, oid), tlv(tag, value)].concat())
+    }
+
+    fn pdu(kind: u8) -> Vec<u8> {
+        let varbinds = [
+            varbind(UPTIME, TAG_TIMETICKS, &[0x00, 0x98, 0x96, 0x80]),
+            varbind(TRAP_OID, TAG_OID, LINK_DOWN),
+            varbind(IF_INDEX, TAG_INTEGER, &[2]),
+        ]
+        .concat();
+        let body = [
+            tlv(TAG_INTEGER, &[0x12, 0x34]),
+            tlv(TAG_INTEGER, &[0]),
+            tlv(TAG_INTEGER, &[0]),
+            tlv(TAG_SEQUENCE, &varbinds),
+        ];
+        tlv(kind, &body.concat())
+    }
+
+    fn v2c(community: &str, kind: u8) -> Vec<u8> {
+        let message = [
+            tlv(TAG_INTEGER, &[VERSION_V2C as u8]),
+            tlv(TAG_OCTET_STRING, community.as_bytes()),
+            pdu(kind),
+        ];
+        tlv(TAG_SEQUENCE, &message.concat())
+    }
+
+    /// authNoPriv v3 트랩, 인증 파라미터 자리를 HMAC-MD5-96 으로 채움
+    fn v3_md5(user: &str, password: &str, engine_id: &[u8]) -> Vec<u8> {
+        let placeholder = [0xaa; 12];
+        let global = [
+            tlv(TAG_INTEGER, &[1]),
+            tlv(TAG_INTEGER, &[0x05, 0xdc]),
+            tlv(TAG_OCTET_STRING, &[FLAG_AUTH]),
+            tlv(TAG_INTEGER, &[SECURITY_MODEL_USM as u8]),
+        ];
+        let usm = [
+            tlv(TAG_OCTET_STRING, engine_id),
+            tlv(TAG_INTEGER, &[1]),
+            tlv(TAG_INTEGER, &[100]),
+            tlv(TAG_OCTET_STRING, user.as_bytes()),
+            tlv(TAG_OCTET_STRING, &placeholder),
+            tlv(TAG_OCTET_STRING, &[]),
+        ];
+        let scoped = [tlv(TAG_OCTET_STRING, engine_id), tlv(TAG_OCTET_STRING, &[]), pdu(PDU_TRAP_V2)];
+        let message = [
+            tlv(TAG_INTEGER, &[VERSION_V3 as u8]),
+            tlv(TAG_SEQUENCE, &global.concat()),
+            tlv(TAG_OCTET_STRING, &tlv(TAG_SEQUENCE, &usm.concat())),
+            tlv(TAG_SEQUENCE, &scoped.concat()),
+        ];
+        let mut datagram = tlv(TAG_SEQUENCE, &message.concat());
+
+        let start = datagram.windows(12).position(|w| w == placeholder).unwrap();
+        datagram[start..start + 12].fill(0);
+        let key = localize_key::<Md5>(&password_key(SnmpAuthProtocol::Md5, password.as_bytes()), engine_id);
+        let mut mac = <Hmac<Md5> as Mac>::new_from_slice(&key).unwrap();
+        mac.update(&datagram);
+        datagram[start..start + 12].copy_from_slice(&mac.finalize().into_bytes()[..12]);
+        datagram
+    }
+
+    async fn collector(settings: SnmpTrapSettings) -> SnmpTrapCollector {
+        let mut source = SourceSettings::new("traps", "");
+        source.listen = Some("127.0.0.1:0".to_string());
+        source.snmp = Some(settings);
+
+        let (tx, _rx) = mpsc::channel(1);
+        SnmpTrapCollector::bind(tx, source, MemoryBudget::new(0)).await.unwrap()
+    }
+
+    #[test]
+    fn reader_handles_long_form_length_and_truncation() {
+        let mut long = vec![TAG_OCTET_STRING, 0x81, 0x80];
+        long.extend([b'x'; 0x80]);
+        assert_eq!(Reader::new(&long).expect(TAG_OCTET_STRING).unwrap().len(), 0x80);
+        assert_eq!(tlv(TAG_OCTET_STRING, &[b'x'; 0x80]), long);
+
+        assert!(Reader::new(&[TAG_OCTET_STRING, 0x05, 1, 2]).next().is_err());
+        assert!(Reader::new(&[TAG_OCTET_STRING, 0x85, 0, 0, 0, 0, 1]).next().is_err());
+        assert!(Reader::new(&[TAG_INTEGER, 1, 1]).expect(TAG_OCTET_STRING).is_err());
+    }
+
+    #[test]
+    fn decodes_integers_and_oids() {
+        assert_eq!(decode_integer(&[0xff]).unwrap(), -1);
+        assert_eq!(decode_integer(&[0x00, 0x80]).unwrap(), 128);
+        assert_eq!(decode_unsigned(&[0x00, 0xff, 0xff, 0xff, 0xff]).unwrap(), u32::MAX as u64);
+        assert!(decode_integer(&[]).is_err());
+
+        assert_eq!(decode_oid(TRAP_OID).unwrap(), "1.3.6.1.6.3.1.1.4.1.0");
+        assert_eq!(decode_oid(&[0x2b, 6, 1, 4, 1, 0x8f, 0x65]).unwrap(), "1.3.6.1.4.1.2021");
+        assert!(decode_oid(&[0x2b, 0x8f]).is_err());
+    }
+
+    #[test]
+    fn decodes_values_by_tag() {
+        assert_eq!(decode_value(TAG_IP_ADDRESS, &[10, 0, 0, 1]).unwrap(), "10.0.0.1");
+        assert_eq!(decode_value(TAG_OCTET_STRING, b"eth0").unwrap(), "eth0");
+        assert_eq!(decode_value(TAG_OCTET_STRING, &[0x00, 0x1b]).unwrap(), "00:1b");
+        assert_eq!(decode_value(TAG_NO_SUCH_OBJECT, &[]).unwrap(), "noSuchObject");
+    }
+
+    #[test]
+    fn localizes_keys_as_rfc3414() {
+        // RFC 3414 A.3.1 의 maplesyrup 예
+        let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+        let key = localize_key::<Md5>(&password_key(SnmpAuthProtocol::Md5, b"maplesyrup"), &engine_id);
+
+        assert_eq!(hex(&key), "52:6f:5e:ed:9f:cc:e2:6f:89:64:c2:93:07:87:d8:2b");
+    }
+
+    #[tokio::test]
+    async fn decodes_v2c_trap_and_answers_inform() {
+        let collector = collector(SnmpTrapSettings::default()).await;
+
+        let datagram = v2c("public", PDU_TRAP_V2);
+        let trap = collector.decode(&datagram).unwrap();
+        assert_eq!(trap.version, "v2c");
+        assert_eq!(trap.security, "public");
+        assert_eq!(
+            trap.pdu.varbinds,
+            vec![
+                (SYS_UPTIME_OID.to_string(), "10000000".to_string()),
+                (SNMP_TRAP_OID.to_string(), "1.3.6.1.6.3.1.1.5.3".to_string()),
+                ("1.3.6.1.2.1.2.2.1.1.2".to_string(), "2".to_string()),
+            ]
+        );
+
+        let inform = v2c("public", PDU_INFORM);
+        let response = inform_response(&collector.decode(&inform).unwrap());
+        let mut message = Reader::new(Reader::new(&response).expect(TAG_SEQUENCE).unwrap());
+        assert_eq!(message.integer().unwrap(), VERSION_V2C);
+        assert_eq!(message.expect(TAG_OCTET_STRING).unwrap(), b"public");
+        let (kind, pdu) = message.next().unwrap();
+        assert_eq!(kind, PDU_RESPONSE);
+        assert_eq!(Reader::new(pdu).expect(TAG_INTEGER).unwrap(), &[0x12, 0x34]);
+
+        assert!(collector.decode(&v2c("private", PDU_TRAP_V2)).is_err());
+    }
+
+    #[tokio::test]
+    async fn verifies_v3_authentication() {
+        let user = SnmpUser {
+            name: "monitor".to_string(),
+            auth_protocol: Some(SnmpAuthProtocol::Md5),
+            auth_password: Some("changeme-please".to_string()),
+        };
+        let collector = collector(SnmpTrapSettings {
+            communities: Vec::new(),
+            users: vec![user],
+        })
+        .await;
+        let engine_id = b"\x80\x00\x1f\x88\x04test";
+
+        let datagram = v3_md5("monitor", "changeme-please", engine_id);
+        let trap = collector.decode(&datagram).unwrap();
+        assert_eq!(trap.version, "v3");
+        assert_eq!(trap.security, "monitor");
+
+        assert!(collector.decode(&v3_md5("monitor", "wrong-password", engine_id)).is_err());
+        assert!(collector.decode(&v3_md5("intruder", "changeme-please", engine_id)).is_err());
+
+        let mut tampered = datagram.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(collector.decode(&tampered).is_err());
+    }
+}
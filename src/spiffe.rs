@@ -0,0 +1,182 @@
+//! SPIFFE 워크로드 인증 (`auth.mode: spiffe`)
+//!
+//! 로컬 SPIFFE Workload API(SPIRE 에이전트 등) 소켓에서 X.509 SVID 를 받아 서버 연결의 mTLS 클라이언트 인증서로 사용.
+//! 서버는 인증서의 SPIFFE ID 로 에이전트를 식별하므로 `project_key` 를 배포할 필요가 없음.
+//! SVID 는 수명이 짧아 Workload API 스트림으로 갱신본을 계속 받아 교체하며, 새 연결부터 적용
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use tokio::time;
+use tonic::Streaming;
+use tonic::metadata::MetadataValue;
+use tonic::transport::{Certificate, Identity};
+use tracing::{info, warn};
+
+use crate::endpoint::{self, ClientIdentity};
+use crate::messages as msg;
+use crate::proto::workload::spiffe_workload_api_client::SpiffeWorkloadApiClient;
+use crate::proto::workload::{X509svidRequest, X509svidResponse};
+use crate::retry::Backoff;
+use crate::settings::AuthSettings;
+
+static ENV_SPIFFE_ENDPOINT_SOCKET: &str = "SPIFFE_ENDPOINT_SOCKET";
+
+/// SPIRE 에이전트 기본 소켓
+static DEFAULT_SOCKET: &str = "unix:///run/spire/sockets/agent.sock";
+
+/// Workload API 가 요구하는 요청 헤더 (브라우저 등 다른 클라이언트의 요청과 구분)
+static WORKLOAD_HEADER: &str = "workload.spiffe.io";
+
+/// 스트림이 끊긴 뒤 다시 연결하기 전 최소 대기
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// 설정의 소켓, 없으면 `SPIFFE_ENDPOINT_SOCKET`, 그것도 없으면 SPIRE 기본 경로
+pub fn socket(settings: &AuthSettings) -> String {
+    settings
+        .spiffe_socket
+        .clone()
+        .or_else(|| std::env::var(ENV_SPIFFE_ENDPOINT_SOCKET).ok())
+        .filter(|socket| !socket.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SOCKET.to_string())
+}
+
+/// SVID 를 한 번 받아 적용 (등록, 자가 진단용)
+pub async fn fetch(socket: &str) -> Result<String> {
+    endpoint::require_client_identity();
+    let mut stream = open(socket).await?;
+    let response = next(&mut stream).await?;
+    apply(&response)
+}
+
+/// 서버 채널이 SVID 를 기다리도록 한 뒤 백그라운드에서 Workload API 스트림을 계속 받아 교체
+pub fn spawn_watch(socket: String) {
+    endpoint::require_client_identity();
+    tokio::spawn(watch(socket));
+}
+
+/// 끊기면 backoff 후 다시 연결
+async fn watch(socket: String) {
+    let mut backoff = Backoff::new();
+
+    loop {
+        if let Err(e) = watch_once(&socket, &mut backoff).await {
+            let delay = backoff.next_delay().max(RECONNECT_DELAY);
+            warn!(
+                socket = %socket,
+                backoff_ms = delay.as_millis() as u64,
+                error = %format!("{:#}", e),
+                "{}",
+                msg::SPIFFE_WATCH_FAILED
+            );
+            time::sleep(delay).await;
+        }
+    }
+}
+
+async fn watch_once(socket: &str, backoff: &mut Backoff) -> Result<()> {
+    let mut stream = open(socket).await?;
+    loop {
+        let response = next(&mut stream).await?;
+        apply(&response)?;
+        backoff.reset();
+    }
+}
+
+async fn open(socket: &str) -> Result<Streaming<X509svidResponse>> {
+    let channel = endpoint::connect_local(socket)
+        .await
+        .with_context(|| format!("{}: {}", msg::SPIFFE_CONNECT_FAILED, socket))?;
+
+    let mut request = tonic::Request::new(X509svidRequest {});
+    request
+        .metadata_mut()
+        .insert(WORKLOAD_HEADER, MetadataValue::from_static("true"));
+
+    Ok(SpiffeWorkloadApiClient::new(channel)
+        .fetch_x509svid(request)
+        .await
+        .context(msg::SPIFFE_FETCH_FAILED)?
+        .into_inner())
+}
+
+async fn next(stream: &mut Streaming<X509svidResponse>) -> Result<X509svidResponse> {
+    match stream.message().await.context(msg::SPIFFE_FETCH_FAILED)? {
+        Some(response) => Ok(response),
+        None => bail!(msg::SPIFFE_STREAM_CLOSED),
+    }
+}
+
+/// 첫 번째 SVID 를 클라이언트 인증서로 적용, 적용한 SPIFFE ID 반환
+fn apply(response: &X509svidResponse) -> Result<String> {
+    let Some(svid) = response.svids.first() else {
+        bail!(msg::SPIFFE_NO_SVID);
+    };
+
+    let chain = to_pem("CERTIFICATE", &svid.x509_svid)?;
+    let key = pem_block("PRIVATE KEY", &svid.x509_svid_key);
+    let bundle = to_pem("CERTIFICATE", &svid.bundle)?;
+
+    endpoint::set_client_identity(ClientIdentity {
+        identity: Identity::from_pem(chain, key),
+        trust_bundle: Certificate::from_pem(bundle),
+    });
+    info!(spiffe_id = %svid.spiffe_id, "{}", msg::SPIFFE_SVID_UPDATED);
+    Ok(svid.spiffe_id.clone())
+}
+
+/// 이어 붙인 DER 인증서들을 PEM 으로
+fn to_pem(label: &str, der: &[u8]) -> Result<String> {
+    let mut pem = String::new();
+    let mut rest = der;
+
+    while !rest.is_empty() {
+        let len = der_len(rest).context(msg::SPIFFE_INVALID_DER)?;
+        let (cert, tail) = rest.split_at(len);
+        pem.push_str(&pem_block(label, cert));
+        rest = tail;
+    }
+
+    if pem.is_empty() {
+        bail!(msg::SPIFFE_INVALID_DER);
+    }
+    Ok(pem)
+}
+
+/// DER SEQUENCE 하나의 전체 길이 (태그, 길이 바이트 포함)
+fn der_len(der: &[u8]) -> Option<usize> {
+    const SEQUENCE: u8 = 0x30;
+
+    if *der.first()? != SEQUENCE {
+        return None;
+    }
+
+    let first = *der.get(1)? as usize;
+    let (header, content) = if first < 0x80 {
+        (2, first)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > size_of::<usize>() {
+            return None;
+        }
+        let bytes = der.get(2..2 + count)?;
+        (2 + count, bytes.iter().fold(0usize, |len, b| (len << 8) | *b as usize))
+    };
+
+    let total = header.checked_add(content)?;
+    (total <= der.len()).then_some(total)
+}
+
+fn pem_block(label: &str, der: &[u8]) -> String {
+    let encoded = STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64) {
+        // base64 출력은 ASCII
+        pem.push_str(std::str::from_utf8(line).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::disk_guard;
+use crate::durability;
+use crate::ephemeral;
+use crate::messages as msg;
+use crate::proto::log::LogBatch;
+use crate::settings::SpoolEviction;
+
+pub static SPOOL_DIR: &str = "state/spool";
+/// 서버가 재시도 불가로 거부했거나 재전송 한도를 넘긴 로그 (자동 재전송하지 않음)
+pub static DEAD_LETTER_DIR: &str = "state/dead_letter";
+static SPOOL_EXTENSION: &str = "pb";
+
+/// 디스크 큐마다 적용하는 용량 상한, 0 이면 무제한
+static LIMIT: LazyLock<Mutex<(u64, SpoolEviction)>> = LazyLock::new(|| Mutex::new((0, SpoolEviction::DropOldest)));
+/// 디스크 큐별 크기와 버린/거부한 배치 수, 디렉터리 이름별
+static STATS: LazyLock<Mutex<BTreeMap<String, SpoolStats>>> = LazyLock::new(Default::default);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 설정의 `max_spool_mb`, `spool_eviction` 적용, 모든 디스크 큐(스풀, dead-letter)에 각각 적용
+pub fn configure(max_spool_mb: u64, eviction: SpoolEviction) {
+    *lock(&LIMIT) = (max_spool_mb.saturating_mul(1024 * 1024), eviction);
+}
+
+/// 디스크 큐별 통계, 관리 API `status` 와 헬스 리포터 메트릭으로 노출
+pub fn stats() -> BTreeMap<String, SpoolStats> {
+    lock(&STATS).clone()
+}
+
+/// 관리 API `spool list` 에 보여줄 디스크 큐의 배치 하나
+#[derive(Debug, Clone, Serialize)]
+pub struct SpoolEntry {
+    /// 디스크 큐 (디렉터리 이름)
+    pub queue: String,
+    pub file: String,
+    pub batch_id: String,
+    /// 저장 후 지난 시간 (초)
+    pub age_secs: i64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SpoolStats {
+    pub bytes: u64,
+    /// `drop_oldest` 로 지운 오래된 배치
+    pub evicted_batches: u64,
+    pub evicted_bytes: u64,
+    /// `stop_collecting` 에서 상한에 닿아 저장하지 못한 배치
+    pub rejected_batches: u64,
+}
+
+/// 전송하지 못한 LogBatch 를 디스크에 보관하는 큐
+///
+/// 배치 하나당 파일 하나 (`{저장시각 ms}-{batch_id}.pb`), 파일명 순서가 곧 전송 순서.
+/// 용량 상한에 닿으면 `drop_oldest` 는 오래된 파일부터 지우고, `stop_collecting` 은 새 배치를 받지 않음
+#[derive(Clone)]
+pub struct Spool {
+    dir: PathBuf,
+    /// 통계 키 (디렉터리 이름)
+    name: String,
+}
+
+impl Spool {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        let name = dir
+            .file_name()
+            .map_or_else(|| dir.display().to_string(), |name| name.to_string_lossy().into_owned());
+        let spool = Self { dir, name };
+
+        // 상태 없는 실행은 디렉터리를 만들지 않음 (항상 빈 큐)
+        if ephemeral::is_enabled() {
+            return Ok(spool);
+        }
+
+        fs::create_dir_all(&spool.dir).context(msg::SPOOL_DIR_CREATE_FAILED)?;
+        let bytes = spool.sized_files()?.iter().map(|(_, len)| len).sum();
+        spool.update_stats(|stats| stats.bytes = bytes);
+
+        Ok(spool)
+    }
+
+    /// `stop_collecting` 에서 상한에 닿아 더 저장할 수 없음
+    pub fn is_full(&self) -> bool {
+        let (limit, eviction) = *lock(&LIMIT);
+        limit > 0 && eviction == SpoolEviction::StopCollecting && self.bytes() >= limit
+    }
+
+    pub fn push(&self, batch: &LogBatch) -> Result<()> {
+        if ephemeral::is_enabled() {
+            self.update_stats(|stats| stats.rejected_batches += 1);
+            bail!(msg::EPHEMERAL_NOT_PERSISTED);
+        }
+
+        // 디스크 여유 공간이 부족하면 메모리에서만 처리 (보관하지 못한 배치는 버림)
+        if let Err(e) = disk_guard::ensure_room() {
+            self.update_stats(|stats| stats.rejected_batches += 1);
+            return Err(e);
+        }
+
+        let encoded = batch.encode_to_vec();
+        self.make_room(encoded.len() as u64)?;
+
+        let name = format!(
+            "{:013}-{}.{}",
+            Utc::now().timestamp_millis(),
+            batch.batch_id,
+            SPOOL_EXTENSION
+        );
+        let path = self.dir.join(name);
+        let tmp = path.with_extension("tmp");
+
+        fs::write(&tmp, &encoded).context(msg::SPOOL_WRITE_FAILED)?;
+        durability::persist(&tmp, &path).context(msg::SPOOL_RENAME_FAILED)?;
+        self.update_stats(|stats| stats.bytes += encoded.len() as u64);
+
+        Ok(())
+    }
+
+    /// 상한을 넘지 않도록 정책에 따라 오래된 파일을 지우거나 저장 거부
+    fn make_room(&self, incoming: u64) -> Result<()> {
+        let (limit, eviction) = *lock(&LIMIT);
+        if limit == 0 || self.bytes() + incoming <= limit {
+            return Ok(());
+        }
+
+        match eviction {
+            // 상한 아래에서는 받으므로 쓰는 쪽마다 배치 하나만큼 넘을 수 있음
+            SpoolEviction::StopCollecting if self.bytes() < limit => Ok(()),
+            SpoolEviction::StopCollecting => {
+                self.update_stats(|stats| stats.rejected_batches += 1);
+                bail!("{}: {}", msg::SPOOL_FULL, self.dir.display())
+            }
+            SpoolEviction::DropOldest => {
+                let mut size = self.bytes();
+                let (mut evicted, mut evicted_bytes) = (0u64, 0u64);
+
+                for (path, len) in self.sized_files()? {
+                    if size + incoming <= limit {
+                        break;
+                    }
+                    if fs::remove_file(&path).is_ok() {
+                        size = size.saturating_sub(len);
+                        evicted += 1;
+                        evicted_bytes += len;
+                    }
+                }
+
+                self.update_stats(|stats| {
+                    stats.bytes = size;
+                    stats.evicted_batches += evicted;
+                    stats.evicted_bytes += evicted_bytes;
+                });
+                warn!(
+                    dir = %self.dir.display(),
+                    evicted = evicted,
+                    evicted_bytes = evicted_bytes,
+                    limit = limit,
+                    "{}",
+                    msg::SPOOL_EVICTED
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// 통계 키이자 관리 API 의 큐 이름 (`spool`, `dead_letter`)
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 저장 순서대로 배치 목록, 저장 시각과 batch_id 는 파일 이름에서 읽음
+    pub fn entries(&self) -> Result<Vec<SpoolEntry>> {
+        let now = Utc::now().timestamp_millis();
+
+        Ok(self
+            .sized_files()?
+            .into_iter()
+            .filter_map(|(path, bytes)| {
+                let file = path.file_name()?.to_string_lossy().into_owned();
+                let stem = file.strip_suffix(&format!(".{}", SPOOL_EXTENSION))?;
+                let (saved_at, batch_id) = stem.split_once('-')?;
+
+                Some(SpoolEntry {
+                    queue: self.name.clone(),
+                    batch_id: batch_id.to_string(),
+                    age_secs: (now - saved_at.parse::<i64>().ok()?) / 1000,
+                    bytes,
+                    file,
+                })
+            })
+            .collect())
+    }
+
+    /// 가장 먼저 저장한 배치의 가장 이른 로그 시각, 읽지 못하면 저장 시각 (손상된 파일도 지우지 않음)
+    pub fn oldest_event_at(&self) -> Option<DateTime<Utc>> {
+        let path = self.list().ok()?.into_iter().next()?;
+
+        let saved_at = path
+            .file_stem()
+            .and_then(|stem| stem.to_str()?.split_once('-')?.0.parse::<i64>().ok())
+            .and_then(DateTime::from_timestamp_millis);
+        let logged_at = fs::read(&path)
+            .ok()
+            .and_then(|bytes| LogBatch::decode(bytes.as_slice()).ok())
+            .and_then(|batch| batch.logs.iter().filter_map(|log| log.timestamp.as_ref()).map(|t| t.seconds).min())
+            .and_then(|secs| DateTime::from_timestamp(secs, 0));
+
+        saved_at.into_iter().chain(logged_at).min()
+    }
+
+    /// 큐에 있는 배치 파일 경로, 이름이 목록에 없으면 None (큐 밖의 경로를 가리키지 않도록)
+    pub fn find(&self, file: &str) -> Option<PathBuf> {
+        self.list()
+            .ok()?
+            .into_iter()
+            .find(|path| path.file_name().is_some_and(|name| name == file))
+    }
+
+    /// 배치 파일을 다른 디스크 큐로 옮김 (dead-letter 를 스풀로 되돌려 재전송 등), 파일 이름(전송 순서)은 유지
+    pub fn move_to(&self, path: &Path, target: &Spool) -> Result<()> {
+        let name = path.file_name().context(msg::SPOOL_MOVE_FAILED)?;
+        let len = fs::metadata(path).map_or(0, |m| m.len());
+
+        durability::persist(path, &target.dir.join(name)).context(msg::SPOOL_MOVE_FAILED)?;
+        self.update_stats(|stats| stats.bytes = stats.bytes.saturating_sub(len));
+        target.update_stats(|stats| stats.bytes += len);
+        Ok(())
+    }
+
+    fn bytes(&self) -> u64 {
+        lock(&STATS).get(&self.name).map_or(0, |stats| stats.bytes)
+    }
+
+    fn update_stats(&self, update: impl FnOnce(&mut SpoolStats)) {
+        update(lock(&STATS).entry(self.name.clone()).or_default());
+    }
+
+    /// 저장 순서대로 스풀 파일 경로와 크기
+    fn sized_files(&self) -> Result<Vec<(PathBuf, u64)>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter_map(|path| fs::metadata(&path).ok().map(|m| (path, m.len())))
+            .collect())
+    }
+
+    /// 저장 순서대로 스풀 파일 경로 반환
+    pub fn list(&self) -> Result<Vec<PathBuf>> {
+        if ephemeral::is_enabled() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .context(msg::SPOOL_DIR_READ_FAILED)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == SPOOL_EXTENSION))
+            .collect();
+
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// 스풀 파일 읽기, 손상된 파일은 삭제 후 None
+    pub fn read(&self, path: &Path) -> Option<LogBatch> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "{}", msg::SPOOL_READ_FAILED);
+                return None;
+            }
+        };
+
+        match LogBatch::decode(bytes.as_slice()) {
+            Ok(batch) => Some(batch),
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "{}", msg::SPOOL_CORRUPT_REMOVED);
+                self.remove(path);
+                None
+            }
+        }
+    }
+
+    pub fn remove(&self, path: &Path) {
+        let len = fs::metadata(path).map_or(0, |m| m.len());
+
+        match fs::remove_file(path) {
+            Ok(()) => self.update_stats(|stats| stats.bytes = stats.bytes.saturating_sub(len)),
+            Err(e) => warn!(path = %path.display(), error = %e, "{}", msg::SPOOL_REMOVE_FAILED),
+        }
+    }
+}
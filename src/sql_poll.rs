@@ -0,0 +1,437 @@
+//! SQL 폴링 수집 (`kind: sql`)
+//!
+//! 설정한 쿼리를 주기적으로 실행해 행마다 [`LogEvent`] 하나를 만듦. 감사(audit) 테이블처럼 파일로 남지 않는 로그용.
+//! 쿼리는 커서 값을 바인드 파라미터 하나로 받고(`WHERE id > $1 ORDER BY id LIMIT 500`), 받은 행의 커서 컬럼 최댓값을
+//! 상태 보관소의 `sql/<label>.yaml`(기본 `state/sql/`)에 기록해 다음 실행과 재시작 후에 이어서 읽음.
+//! 새 행이 있으면 바로 다시, 없으면 `poll_interval` 뒤에 실행
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
+use sqlx::{Column, Row};
+use tokio::sync::mpsc::Sender;
+use tokio::time;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::collector;
+use crate::disk_guard;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::models::LogEvent;
+use crate::parser::{self, Fields, timestamp};
+use crate::settings::{SourceFormat, SourceSettings, SqlSourceSettings};
+use crate::state_store;
+use crate::status;
+
+/// 수집 대상별 커서 보관소 키 접두어 (`sql/<label>.yaml`)
+const STATE_PREFIX: &str = "sql";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 커서 컬럼 값, 종류에 맞는 타입으로 바인드
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+enum Cursor {
+    Int(i64),
+    Time(DateTime<Utc>),
+    Text(String),
+}
+
+impl Cursor {
+    /// `start_cursor` 설정 값, 정수나 RFC 3339 시각이 아니면 문자열
+    fn parse(value: &str) -> Self {
+        if let Ok(n) = value.parse() {
+            return Cursor::Int(n);
+        }
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(time) => Cursor::Time(time.with_timezone(&Utc)),
+            Err(_) => Cursor::Text(value.to_string()),
+        }
+    }
+
+    fn from_cell(cell: &Cell) -> Option<Self> {
+        match cell {
+            Cell::Int(n) => Some(Cursor::Int(*n)),
+            Cell::Time(time) => Some(Cursor::Time(*time)),
+            // 커서는 컬럼 값 그대로 다시 바인드하므로 시간대를 적용하지 않음
+            Cell::LocalTime(time) => Some(Cursor::Time(time.and_utc())),
+            Cell::Text(text) => Some(Cursor::Text(text.clone())),
+            Cell::Float(_) | Cell::Bool(_) | Cell::Json(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CursorState {
+    cursor: Option<Cursor>,
+}
+
+/// 행의 컬럼 값, 지원하지 않는 타입(NUMERIC, 바이너리 등)과 NULL 은 생략 (쿼리에서 문자열로 CAST 해서 사용)
+#[derive(Debug, Clone)]
+enum Cell {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+    Time(DateTime<Utc>),
+    /// 시간대 없는 시각(`TIMESTAMP`, `DATETIME`), 수집 대상 `timezone` 의 현지 시각으로 해석
+    LocalTime(NaiveDateTime),
+    /// JSON 컬럼, 본문 JSON 에는 문자열이 아닌 값으로 포함
+    Json(Value),
+}
+
+impl Cell {
+    /// 시각 컬럼의 UTC 시각, 서머타임으로 건너뛴 현지 시각은 UTC 로 간주
+    fn time(&self, timezone: Tz) -> Option<DateTime<Utc>> {
+        match self {
+            Cell::Time(time) => Some(*time),
+            Cell::LocalTime(time) => Some(timestamp::local_to_utc(time, timezone).unwrap_or_else(|| time.and_utc())),
+            _ => None,
+        }
+    }
+
+    fn to_field(&self, timezone: Tz) -> String {
+        match self {
+            Cell::Int(n) => n.to_string(),
+            Cell::Float(f) => f.to_string(),
+            Cell::Bool(b) => b.to_string(),
+            Cell::Text(text) => text.clone(),
+            Cell::Time(_) | Cell::LocalTime(_) => self.time(timezone).map(|time| time.to_rfc3339()).unwrap_or_default(),
+            Cell::Json(value) => value.to_string(),
+        }
+    }
+
+    fn to_json(&self, timezone: Tz) -> Value {
+        match self {
+            Cell::Int(n) => Value::from(*n),
+            Cell::Float(f) => Value::from(*f),
+            Cell::Bool(b) => Value::from(*b),
+            Cell::Json(value) => value.clone(),
+            _ => Value::from(self.to_field(timezone)),
+        }
+    }
+}
+
+/// (컬럼 이름, 값) 목록, 쿼리의 컬럼 순서
+type Columns = Vec<(String, Cell)>;
+
+enum Pool {
+    Postgres(PgPool),
+    MySql(MySqlPool),
+}
+
+impl Pool {
+    /// URL 스킴으로 드라이버 선택, 실제 연결은 첫 쿼리 때 (데이터베이스가 내려가 있어도 시작은 됨)
+    fn connect(url: &str) -> Result<Self> {
+        let scheme = url.split_once("://").map(|(scheme, _)| scheme).unwrap_or_default();
+
+        match scheme {
+            "postgres" | "postgresql" => Ok(Pool::Postgres(
+                PgPoolOptions::new()
+                    .max_connections(1)
+                    .acquire_timeout(CONNECT_TIMEOUT)
+                    .connect_lazy(url)
+                    .context(msg::SQL_INVALID_URL)?,
+            )),
+            "mysql" | "mariadb" => Ok(Pool::MySql(
+                MySqlPoolOptions::new()
+                    .max_connections(1)
+                    .acquire_timeout(CONNECT_TIMEOUT)
+                    .connect_lazy(url)
+                    .context(msg::SQL_INVALID_URL)?,
+            )),
+            _ => bail!("{}: {}", msg::SQL_UNSUPPORTED_URL, redact(url)),
+        }
+    }
+
+    async fn fetch(&self, query: &str, cursor: &Cursor) -> Result<Vec<Columns>> {
+        match self {
+            Pool::Postgres(pool) => {
+                let query = sqlx::query(query);
+                let query = match cursor {
+                    Cursor::Int(n) => query.bind(*n),
+                    Cursor::Time(time) => query.bind(*time),
+                    Cursor::Text(text) => query.bind(text.clone()),
+                };
+                let rows = query.fetch_all(pool).await.context(msg::SQL_QUERY_FAILED)?;
+                Ok(rows.iter().map(|row| columns(row, pg_cell)).collect())
+            }
+            Pool::MySql(pool) => {
+                let query = sqlx::query(query);
+                let query = match cursor {
+                    Cursor::Int(n) => query.bind(*n),
+                    Cursor::Time(time) => query.bind(*time),
+                    Cursor::Text(text) => query.bind(text.clone()),
+                };
+                let rows = query.fetch_all(pool).await.context(msg::SQL_QUERY_FAILED)?;
+                Ok(rows.iter().map(|row| columns(row, mysql_cell)).collect())
+            }
+        }
+    }
+
+    async fn ping(&self) -> Result<()> {
+        match self {
+            Pool::Postgres(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+            Pool::MySql(pool) => sqlx::query("SELECT 1").execute(pool).await.map(|_| ()),
+        }
+        .context(msg::SQL_CONNECT_FAILED)
+    }
+}
+
+pub struct SqlCollector {
+    tx: Sender<LogEvent>,
+    label: String,
+    settings: SqlSourceSettings,
+    pool: Pool,
+    format: SourceFormat,
+    /// 시간대 없는 시각 컬럼을 해석할 시간대
+    timezone: Tz,
+    path: Arc<str>,
+    cursor: Cursor,
+    state_key: String,
+    /// 지금까지 전달한 행 수 (상태 조회의 위치)
+    forwarded: u64,
+    budget: MemoryBudget,
+}
+
+impl SqlCollector {
+    pub fn new(tx: Sender<LogEvent>, source: SourceSettings, budget: MemoryBudget) -> Result<Self> {
+        let timezone = timestamp::timezone_for(&source);
+        let Some(settings) = source.sql else {
+            bail!("{}: {}", msg::SQL_NOT_CONFIGURED, source.label);
+        };
+
+        let pool = Pool::connect(&settings.url)?;
+        let state_key = format!("{}/{}.yaml", STATE_PREFIX, source.label);
+        let cursor = load_state(&state_key)
+            .cursor
+            .or_else(|| settings.start_cursor.as_deref().map(Cursor::parse))
+            .unwrap_or(Cursor::Int(0));
+        let path: Arc<str> = Arc::from(redact(&settings.url));
+
+        status::registry().update_source(&source.label, Path::new(path.as_ref()), 0);
+
+        Ok(Self {
+            tx,
+            label: source.label,
+            settings,
+            pool,
+            format: source.format,
+            timezone,
+            path,
+            cursor,
+            state_key,
+            forwarded: 0,
+            budget,
+        })
+    }
+
+    /// `shutdown` 취소 시까지 쿼리를 반복 실행
+    pub async fn start(&mut self, shutdown: CancellationToken) {
+        info!(source = %self.label, path = %self.path, cursor = ?self.cursor, "{}", msg::SQL_STARTED);
+        let interval = Duration::from_secs(self.settings.poll_interval.max(1));
+
+        while !shutdown.is_cancelled() {
+            // 일시 중지 중에는 실행하지 않음 (행은 테이블에 남음)
+            if !collector::is_paused(&self.label) {
+                self.budget.wait_for_capacity(&shutdown).await;
+                match self.poll(&shutdown).await {
+                    // 밀린 행이 더 있을 수 있으므로 바로 다시 실행
+                    Ok(rows) if rows > 0 => continue,
+                    Ok(_) => {}
+                    Err(e) => warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::SQL_QUERY_FAILED),
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = time::sleep(interval) => {}
+            }
+        }
+
+        info!(source = %self.label, "{}", msg::COLLECTOR_STOPPED);
+    }
+
+    /// 쿼리 한 번 실행해 행을 전달하고 커서 기록, 전달한 행 수 반환
+    async fn poll(&mut self, shutdown: &CancellationToken) -> Result<usize> {
+        let rows = tokio::select! {
+            _ = shutdown.cancelled() => return Ok(0),
+            rows = self.pool.fetch(&self.settings.query, &self.cursor) => rows?,
+        };
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        // 커서가 없는 행만 오면 같은 행을 계속 다시 읽게 되므로 전달하지 않음
+        let Some(next) = rows.iter().filter_map(|row| self.row_cursor(row)).max() else {
+            bail!("{}: {}", msg::SQL_CURSOR_MISSING, self.settings.cursor_column);
+        };
+
+        let count = rows.len();
+        for row in rows {
+            self.send_event(row).await?;
+        }
+
+        self.cursor = next.max(self.cursor.clone());
+        if let Err(e) = save_state(&self.state_key, &self.cursor) {
+            warn!(source = %self.label, error = %format!("{:#}", e), "{}", msg::SQL_STATE_SAVE_FAILED);
+        }
+
+        Ok(count)
+    }
+
+    fn row_cursor(&self, row: &Columns) -> Option<Cursor> {
+        row.iter()
+            .find(|(name, _)| *name == self.settings.cursor_column)
+            .and_then(|(_, cell)| Cursor::from_cell(cell))
+    }
+
+    async fn send_event(&mut self, row: Columns) -> Result<()> {
+        let cursor = self.row_cursor(&row);
+        let time = row
+            .iter()
+            .find(|(name, _)| *name == self.settings.cursor_column)
+            .and_then(|(_, cell)| cell.time(self.timezone));
+        let message_column = self.settings.message_column.as_deref();
+
+        let content = match row.iter().find(|(name, _)| Some(name.as_str()) == message_column) {
+            Some((_, cell)) => cell.to_field(self.timezone),
+            None => {
+                let object: Map<String, Value> = row.iter().map(|(name, cell)| (name.clone(), cell.to_json(self.timezone))).collect();
+                Value::Object(object).to_string()
+            }
+        };
+
+        let mut fields: Fields = row
+            .iter()
+            .filter(|(name, _)| Some(name.as_str()) != message_column)
+            .map(|(name, cell)| (name.clone(), cell.to_field(self.timezone)))
+            .collect();
+        fields.extend(parser::parse(self.format, &content));
+
+        let event = LogEvent {
+            label: self.label.clone(),
+            content: Bytes::from(content),
+            timestamp: time.unwrap_or_else(Utc::now),
+            path: Arc::clone(&self.path),
+            offset: match cursor {
+                Some(Cursor::Int(n)) => n as u64,
+                _ => self.forwarded,
+            },
+            file_id: 0,
+            fields,
+        };
+
+        let bytes = memory::event_bytes(&event);
+        self.tx.send(event).await.context(msg::EVENT_CHANNEL_CLOSED)?;
+        self.budget.add(bytes);
+
+        self.forwarded += 1;
+        status::registry().update_source(&self.label, Path::new(self.path.as_ref()), self.forwarded);
+
+        Ok(())
+    }
+}
+
+/// 연결과 `SELECT 1` 확인 (doctor)
+pub async fn ping(settings: &SqlSourceSettings) -> Result<()> {
+    Pool::connect(&settings.url)?.ping().await
+}
+
+/// 비밀번호가 들어 있는 사용자 정보를 뺀 `scheme://host/db` (이벤트 경로와 로그용)
+pub fn redact(url: &str) -> String {
+    match (url.split_once("://"), url.rfind('@')) {
+        (Some((scheme, _)), Some(at)) => format!("{}://{}", scheme, &url[at + 1..]),
+        _ => url.to_string(),
+    }
+}
+
+fn load_state(key: &str) -> CursorState {
+    state_store::store()
+        .load(key)
+        .ok()
+        .flatten()
+        .and_then(|content| serde_yaml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(key: &str, cursor: &Cursor) -> Result<()> {
+    // 디스크 여유 공간이 부족하면 메모리의 위치만 유지
+    disk_guard::ensure_room()?;
+
+    let state = CursorState {
+        cursor: Some(cursor.clone()),
+    };
+    let yaml = serde_yaml::to_string(&state).context(msg::SQL_STATE_SAVE_FAILED)?;
+    state_store::store().save(key, &yaml)
+}
+
+fn columns<R: Row>(row: &R, cell: fn(&R, usize) -> Option<Cell>) -> Columns {
+    row.columns()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, column)| cell(row, i).map(|value| (column.name().to_string(), value)))
+        .collect()
+}
+
+/// 호환되는 첫 타입으로 값을 읽음, NULL 이면 첫 시도에서 None
+macro_rules! decode_cell {
+    ($row:expr, $i:expr; $($ty:ty => $map:expr),* $(,)?) => {{
+        $(
+            if let Ok(value) = $row.try_get::<Option<$ty>, _>($i) {
+                return value.map($map);
+            }
+        )*
+        None
+    }};
+}
+
+fn pg_cell(row: &PgRow, i: usize) -> Option<Cell> {
+    decode_cell!(row, i;
+        i64 => Cell::Int,
+        i32 => |n| Cell::Int(n.into()),
+        i16 => |n| Cell::Int(n.into()),
+        f64 => Cell::Float,
+        f32 => |f| Cell::Float(f.into()),
+        bool => Cell::Bool,
+        String => Cell::Text,
+        DateTime<Utc> => Cell::Time,
+        NaiveDateTime => Cell::LocalTime,
+        NaiveDate => |d| Cell::Text(d.to_string()),
+        NaiveTime => |t| Cell::Text(t.to_string()),
+        uuid::Uuid => |u| Cell::Text(u.to_string()),
+        Value => Cell::Json,
+    )
+}
+
+fn mysql_cell(row: &MySqlRow, i: usize) -> Option<Cell> {
+    decode_cell!(row, i;
+        i64 => Cell::Int,
+        i32 => |n| Cell::Int(n.into()),
+        i16 => |n| Cell::Int(n.into()),
+        i8 => |n| Cell::Int(n.into()),
+        u64 => |n| i64::try_from(n).map_or_else(|_| Cell::Text(n.to_string()), Cell::Int),
+        u32 => |n| Cell::Int(n.into()),
+        u16 => |n| Cell::Int(n.into()),
+        u8 => |n| Cell::Int(n.into()),
+        f64 => Cell::Float,
+        f32 => |f| Cell::Float(f.into()),
+        bool => Cell::Bool,
+        String => Cell::Text,
+        DateTime<Utc> => Cell::Time,
+        NaiveDateTime => Cell::LocalTime,
+        NaiveDate => |d| Cell::Text(d.to_string()),
+        NaiveTime => |t| Cell::Text(t.to_string()),
+        Value => Cell::Json,
+    )
+}
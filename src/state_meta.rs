@@ -0,0 +1,136 @@
+//! 상태 디렉터리 형식 버전과 마이그레이션 (`state/meta.json`)
+//!
+//! 토큰, 오프셋, 스풀 등 `state/` 아래 파일 형식을 바꿀 때는 `SCHEMA_VERSION` 을 올리고 `MIGRATIONS` 에 변환을 추가.
+//! 시작 시 기록된 버전부터 차례로 적용하고 단계마다 버전을 기록하므로, 재등록이나 수집 위치 유실 없이 새 형식으로 넘어감.
+//! 이 에이전트보다 새 형식의 상태는 건드리지 않고 시작을 멈춤 (이전 버전으로 되돌렸을 때 상태 손상 방지)
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::durability;
+use crate::ephemeral;
+use crate::messages as msg;
+
+static STATE_DIR: &str = "state";
+pub static META_PATH: &str = "state/meta.json";
+
+/// 이 에이전트가 읽고 쓰는 상태 형식
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// 상태 형식 기록, `meta.json` 이 없는 기존 상태는 버전 0
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateMeta {
+    pub schema_version: u32,
+    /// 마지막으로 기록한 에이전트 버전
+    pub agent_version: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// `to - 1` 형식의 상태를 `to` 형식으로 변환
+struct Migration {
+    to: u32,
+    description: &'static str,
+    apply: fn(&Path) -> Result<()>,
+}
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    description: "remove temporary files left by interrupted writes",
+    apply: remove_stray_tmp,
+}];
+
+/// 시작 시 상태 형식 확인 후 필요한 마이그레이션 적용
+pub fn migrate() -> Result<()> {
+    // 상태를 남기지 않으므로 변환할 것도 없음
+    if ephemeral::is_enabled() {
+        return Ok(());
+    }
+
+    let dir = Path::new(STATE_DIR);
+
+    let current = match load()? {
+        Some(meta) => meta.schema_version,
+        // 처음 실행이면 변환할 상태가 없음
+        None if is_empty(dir) => return save(SCHEMA_VERSION),
+        None => 0,
+    };
+
+    if current > SCHEMA_VERSION {
+        bail!("{}: state={} agent={}", msg::STATE_SCHEMA_TOO_NEW, current, SCHEMA_VERSION);
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.to > current) {
+        info!(
+            from = migration.to - 1,
+            to = migration.to,
+            migration = migration.description,
+            "{}",
+            msg::STATE_MIGRATING
+        );
+        (migration.apply)(dir).with_context(|| format!("{}: {}", msg::STATE_MIGRATION_FAILED, migration.to))?;
+        save(migration.to)?;
+    }
+
+    // 형식이 같아도 마지막 에이전트 버전은 갱신
+    if current == SCHEMA_VERSION {
+        save(SCHEMA_VERSION)?;
+    }
+    Ok(())
+}
+
+/// 기록된 상태 형식, 없으면 None
+pub fn load() -> Result<Option<StateMeta>> {
+    let content = match fs::read_to_string(META_PATH) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context(msg::STATE_META_READ_FAILED),
+    };
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .context(msg::STATE_META_READ_FAILED)
+}
+
+fn save(schema_version: u32) -> Result<()> {
+    let path = Path::new(META_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+    }
+
+    let meta = StateMeta {
+        schema_version,
+        agent_version: env!("CARGO_PKG_VERSION").to_string(),
+        updated_at: Utc::now(),
+    };
+    let json = serde_json::to_string_pretty(&meta).context(msg::STATE_META_WRITE_FAILED)?;
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json).context(msg::STATE_META_WRITE_FAILED)?;
+    durability::persist(&tmp, path).context(msg::STATE_META_WRITE_FAILED)?;
+    Ok(())
+}
+
+fn is_empty(dir: &Path) -> bool {
+    !fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_some())
+}
+
+/// v1: 임시 파일(`*.tmp`)에 쓴 뒤 rename 하는 저장이 중간에 끊겨 남은 파일 삭제 (스풀 용량만 차지하고 전송되지 않음)
+fn remove_stray_tmp(dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(dir)?.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+
+        if path.is_dir() {
+            remove_stray_tmp(&path)?;
+        } else if path.extension().is_some_and(|ext| ext == "tmp")
+            && let Err(e) = fs::remove_file(&path)
+        {
+            warn!(path = %path.display(), error = %e, "{}", msg::STATE_TMP_REMOVE_FAILED);
+        }
+    }
+    Ok(())
+}
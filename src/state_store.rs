@@ -0,0 +1,217 @@
+//! 토큰과 수집 위치 보관소 (`state_store`)
+//!
+//! refresh_token, agent_uuid, 배치 순번, Redis/SQL 수집 커서를 키(`token`, `sql/<라벨>.yaml` 등)로 읽고 씀.
+//! `file` 은 디렉터리 아래 키 이름의 파일(기본 `state/`, 기존 배치 그대로), `memory` 는 프로세스 메모리(재시작 시 사라짐),
+//! `sqlite` 는 파일 하나의 테이블(`sqlite-state` 기능)로, 읽기 전용 루트나 여러 프로세스가 공유하는 위치에 둘 수 있음.
+//! 디스크 큐, 크래시 보고서, 재실행 핸드오버는 파일이라 계속 `state/` 를 씀
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+
+use anyhow::{Context, Result};
+#[cfg(not(feature = "sqlite-state"))]
+use anyhow::bail;
+
+use crate::chaos;
+use crate::durability;
+use crate::ephemeral;
+use crate::messages as msg;
+use crate::settings::{StateBackend, StateStoreSettings};
+
+static DEFAULT_DIR: &str = "state";
+#[cfg(feature = "sqlite-state")]
+static DEFAULT_SQLITE_PATH: &str = "state/state.db";
+
+/// 설정 전(등록 중 등)에도 쓰는 보관소, None 이면 기본값
+static STORE: RwLock<Option<Arc<dyn StateStore>>> = RwLock::new(None);
+
+/// 상태 없는 실행과 `memory` 가 함께 쓰는 보관소 (설정 전에 저장한 토큰을 이어받음)
+static MEMORY: LazyLock<Arc<MemoryStore>> = LazyLock::new(|| Arc::new(MemoryStore::default()));
+
+/// 키/값 보관소, 값은 짧은 문자열(토큰, YAML)
+pub trait StateStore: Send + Sync {
+    /// 저장된 값, 없으면 None
+    fn load(&self, key: &str) -> Result<Option<String>>;
+    fn save(&self, key: &str, value: &str) -> Result<()>;
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// 설정의 `state_store` 로 보관소 선택, 상태 없는 실행이면 항상 메모리
+pub fn configure(settings: &StateStoreSettings) -> Result<()> {
+    let backend = if ephemeral::is_enabled() { StateBackend::Memory } else { settings.backend };
+
+    let store: Arc<dyn StateStore> = match backend {
+        StateBackend::Memory => MEMORY.clone(),
+        StateBackend::File => Arc::new(FileStore::new(settings.path.as_deref().unwrap_or(DEFAULT_DIR))),
+        #[cfg(feature = "sqlite-state")]
+        StateBackend::Sqlite => Arc::new(SqliteStore::open(settings.path.as_deref().unwrap_or(DEFAULT_SQLITE_PATH))?),
+        #[cfg(not(feature = "sqlite-state"))]
+        StateBackend::Sqlite => bail!(msg::STATE_STORE_SQLITE_UNAVAILABLE),
+    };
+
+    *STORE.write().unwrap_or_else(|e| e.into_inner()) = Some(store);
+    Ok(())
+}
+
+/// 현재 보관소, 설정 전이면 `state/` 파일 (상태 없는 실행이면 메모리)
+pub fn store() -> Arc<dyn StateStore> {
+    if let Some(store) = STORE.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Arc::clone(store);
+    }
+
+    if ephemeral::is_enabled() {
+        MEMORY.clone()
+    } else {
+        Arc::new(FileStore::new(DEFAULT_DIR))
+    }
+}
+
+/// 디렉터리 아래 키 이름의 파일, 임시 파일에 쓴 뒤 rename
+pub struct FileStore {
+    root: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+impl StateStore for FileStore {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        match fs::read_to_string(self.path(key)) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("{}: {}", msg::STATE_STORE_READ_FAILED, key)),
+        }
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<()> {
+        chaos::disk_full()?;
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+        }
+
+        write_private(&path, value).with_context(|| format!("{}: {}", msg::STATE_STORE_WRITE_FAILED, key))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("{}: {}", msg::STATE_STORE_WRITE_FAILED, key))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 토큰이 들어 있으므로 소유자만 읽기/쓰기 (Unix 0600)
+fn write_private(path: &Path, value: &str) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, value)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+    }
+
+    durability::persist(&tmp, path)
+}
+
+/// 프로세스 메모리, 재시작하면 사라짐
+#[derive(Default)]
+pub struct MemoryStore {
+    values: Mutex<BTreeMap<String, String>>,
+}
+
+impl StateStore for MemoryStore {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.values.lock().unwrap_or_else(|e| e.into_inner()).get(key).cloned())
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<()> {
+        self.values
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.values.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+        Ok(())
+    }
+}
+
+/// SQLite 파일의 `state` 테이블, 잠금은 SQLite 가 처리하므로 여러 프로세스가 같은 파일을 써도 됨 (키는 겹치지 않게)
+#[cfg(feature = "sqlite-state")]
+pub struct SqliteStore {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite-state")]
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).context(msg::STATE_DIR_CREATE_FAILED)?;
+        }
+
+        let connection = rusqlite::Connection::open(path).context(msg::STATE_STORE_OPEN_FAILED)?;
+        connection
+            .busy_timeout(std::time::Duration::from_secs(5))
+            .and_then(|_| {
+                connection.execute(
+                    "CREATE TABLE IF NOT EXISTS state (key TEXT PRIMARY KEY, value TEXT NOT NULL, updated_at INTEGER NOT NULL)",
+                    (),
+                )
+            })
+            .context(msg::STATE_STORE_OPEN_FAILED)?;
+
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> std::sync::MutexGuard<'_, rusqlite::Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(feature = "sqlite-state")]
+impl StateStore for SqliteStore {
+    fn load(&self, key: &str) -> Result<Option<String>> {
+        use rusqlite::OptionalExtension;
+
+        self.connection()
+            .query_row("SELECT value FROM state WHERE key = ?1", [key], |row| row.get(0))
+            .optional()
+            .with_context(|| format!("{}: {}", msg::STATE_STORE_READ_FAILED, key))
+    }
+
+    fn save(&self, key: &str, value: &str) -> Result<()> {
+        self.connection()
+            .execute(
+                "INSERT INTO state (key, value, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+                (key, value, chrono::Utc::now().timestamp()),
+            )
+            .map(|_| ())
+            .with_context(|| format!("{}: {}", msg::STATE_STORE_WRITE_FAILED, key))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.connection()
+            .execute("DELETE FROM state WHERE key = ?1", [key])
+            .map(|_| ())
+            .with_context(|| format!("{}: {}", msg::STATE_STORE_WRITE_FAILED, key))
+    }
+}
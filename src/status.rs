@@ -0,0 +1,398 @@
+//! 실행 중 상태 레지스트리
+//!
+//! 각 컴포넌트가 갱신하는 상태(Collector 위치, 큐, 연결 상태 등)를 모아 관리 API 에 스냅샷으로 제공
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::Sender;
+
+use crate::auth::lifecycle::{self, TokenLifecycle};
+use crate::auth::token_manager::SharedAccessToken;
+use crate::clock::{self, ClockSnapshot};
+use crate::collector;
+use crate::crash;
+use crate::crash_loop::{self, CrashLoopSnapshot};
+use crate::disk_guard::{self, DiskSnapshot};
+use crate::egress::{self, SourceEgress};
+use crate::error_code::{self, ErrorCodeStats};
+use crate::memory::MemoryBudget;
+use crate::retry::{self, RetryState};
+use crate::schedule;
+use crate::sink_health::{self, SinkSnapshot};
+use crate::spool::{self, Spool, SpoolStats};
+use crate::watch_health::{self, WatcherHealth};
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+pub fn registry() -> &'static Registry {
+    &REGISTRY
+}
+
+/// 서버 연결 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// 서버 전송 없음 (dry-run, 인프로세스 Sink)
+    Local,
+    Connecting,
+    /// 서버 미연결, 배치를 디스크 큐에 저장하며 재시도 중
+    Degraded,
+    Connected,
+    ShuttingDown,
+}
+
+/// 감독 중인 서브시스템 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubsystemState {
+    Running,
+    /// 비정상 종료 후 backoff 대기 중
+    Restarting,
+    /// 재시작 한도 초과
+    Failed,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemStatus {
+    pub state: SubsystemState,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    pub since: DateTime<Utc>,
+}
+
+/// 전송을 기다리는 배치와 재시도 상태
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BacklogSnapshot {
+    /// 디스크 큐에서 전송을 기다리는 배치
+    pub spooled_batches: usize,
+    /// 메모리 배치 채널에서 전송을 기다리는 배치
+    pub queued_batches: usize,
+    /// 전송하지 못한 가장 오래된 이벤트의 나이 (초), 밀린 것이 없으면 None
+    pub oldest_event_age_secs: Option<i64>,
+    pub retry: RetryState,
+}
+
+/// 마지막으로 서버에 전송한 배치
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastSend {
+    pub batch_id: String,
+    pub count: usize,
+    pub at: DateTime<Utc>,
+}
+
+type DepthFn = Box<dyn Fn() -> usize + Send + Sync>;
+
+pub struct Registry {
+    started_at: DateTime<Utc>,
+    connection: Mutex<ConnectionState>,
+    sources: Mutex<BTreeMap<String, SourceState>>,
+    queues: Mutex<Vec<(&'static str, DepthFn)>>,
+    spool: Mutex<Option<Spool>>,
+    budget: Mutex<Option<MemoryBudget>>,
+    access_token: Mutex<Option<SharedAccessToken>>,
+    subsystems: Mutex<BTreeMap<&'static str, SubsystemStatus>>,
+    last_send: Mutex<Option<LastSend>>,
+    /// `max_event_age` 를 넘겨 버린 로그/메트릭 수
+    expired_events: AtomicU64,
+    /// 수집 대상별 UTF-8 이 아니라 버린 레코드 수 (`invalid_utf8: skip`)
+    invalid_utf8: Mutex<BTreeMap<String, u64>>,
+}
+
+#[derive(Debug, Clone)]
+struct SourceState {
+    path: PathBuf,
+    position: u64,
+    updated_at: DateTime<Utc>,
+}
+
+/// 관리 API `status` 응답
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub uptime_secs: i64,
+    pub connection: ConnectionState,
+    pub sources: Vec<SourceSnapshot>,
+    /// 채널별 대기 중인 항목 수
+    pub queues: BTreeMap<String, usize>,
+    pub spool_batches: usize,
+    /// 전송 대기 배치, 가장 오래된 이벤트의 나이, 재시도 상태
+    #[serde(default)]
+    pub backlog: BacklogSnapshot,
+    pub memory_used_bytes: usize,
+    /// 0 이면 무제한
+    pub memory_limit_bytes: usize,
+    pub token_expires_at: Option<DateTime<Utc>>,
+    /// 토큰 발급 시각, 갱신 성공/실패 수, 마지막 재등록
+    #[serde(default)]
+    pub token: TokenLifecycle,
+    pub last_send: Option<LastSend>,
+    /// 디스크 큐에 `max_event_age` 보다 오래 머물러 버린 이벤트 수
+    #[serde(default)]
+    pub expired_events: u64,
+    /// 수집 대상별 누적 전송량
+    #[serde(default)]
+    pub egress: BTreeMap<String, SourceEgress>,
+    /// 디스크 큐별 크기와 용량 상한으로 버린/거부한 배치 수
+    #[serde(default)]
+    pub spools: BTreeMap<String, SpoolStats>,
+    /// 레인별 Sink 상태
+    #[serde(default)]
+    pub sinks: BTreeMap<String, SinkSnapshot>,
+    /// `state/` 디스크 여유 공간
+    #[serde(default)]
+    pub disk: DiskSnapshot,
+    /// 재시작 반복 상태
+    #[serde(default)]
+    pub crash_loop: CrashLoopSnapshot,
+    /// 서버 대비 시계 오차
+    #[serde(default)]
+    pub clock: ClockSnapshot,
+    /// 수집 대상별 파일 감시자 상태 (멈춘 횟수, 폴링 전환)
+    #[serde(default)]
+    pub watchers: BTreeMap<String, WatcherHealth>,
+    /// 수집 대상별 UTF-8 이 아니라 버린 레코드 수
+    #[serde(default)]
+    pub invalid_utf8_skipped: BTreeMap<String, u64>,
+    /// 오류 코드별 발생 횟수와 마지막 문구
+    #[serde(default)]
+    pub error_codes: BTreeMap<String, ErrorCodeStats>,
+    pub subsystems: BTreeMap<String, SubsystemStatus>,
+    pub recent_errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourceSnapshot {
+    pub label: String,
+    pub path: PathBuf,
+    pub position: u64,
+    pub size: Option<u64>,
+    /// 아직 읽지 않은 바이트 수
+    pub lag_bytes: Option<u64>,
+    pub updated_at: DateTime<Utc>,
+    /// `pause_source` 로 일시 중지됨
+    #[serde(default)]
+    pub paused: bool,
+    /// `schedule` 구간 안인지, 구간이 없으면 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_open: Option<bool>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            connection: Mutex::new(ConnectionState::Local),
+            sources: Mutex::new(BTreeMap::new()),
+            queues: Mutex::new(Vec::new()),
+            spool: Mutex::new(None),
+            budget: Mutex::new(None),
+            access_token: Mutex::new(None),
+            subsystems: Mutex::new(BTreeMap::new()),
+            last_send: Mutex::new(None),
+            expired_events: AtomicU64::new(0),
+            invalid_utf8: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn set_connection(&self, state: ConnectionState) {
+        *lock(&self.connection) = state;
+    }
+
+    pub fn connection(&self) -> ConnectionState {
+        *lock(&self.connection)
+    }
+
+    pub fn update_source(&self, label: &str, path: &Path, position: u64) {
+        lock(&self.sources).insert(
+            label.to_string(),
+            SourceState {
+                path: path.to_path_buf(),
+                position,
+                updated_at: Utc::now(),
+            },
+        );
+    }
+
+    /// 실행 중 제거한 수집 대상
+    pub fn remove_source(&self, label: &str) {
+        lock(&self.sources).remove(label);
+        lock(&self.invalid_utf8).remove(label);
+    }
+
+    /// 채널 대기 항목 수 추적, 채널을 붙잡지 않도록 약한 참조만 보관
+    pub fn register_queue<T: Send + 'static>(&self, name: &'static str, tx: &Sender<T>) {
+        let weak = tx.downgrade();
+        let depth: DepthFn = Box::new(move || {
+            weak.upgrade()
+                .map(|tx| tx.max_capacity() - tx.capacity())
+                .unwrap_or(0)
+        });
+
+        let mut queues = lock(&self.queues);
+        queues.retain(|(n, _)| *n != name);
+        queues.push((name, depth));
+    }
+
+    pub fn set_spool(&self, spool: Spool) {
+        *lock(&self.spool) = Some(spool);
+    }
+
+    pub fn set_budget(&self, budget: MemoryBudget) {
+        *lock(&self.budget) = Some(budget);
+    }
+
+    pub fn set_access_token(&self, token: SharedAccessToken) {
+        *lock(&self.access_token) = Some(token);
+    }
+
+    pub fn set_subsystem(
+        &self,
+        name: &'static str,
+        state: SubsystemState,
+        restarts: u32,
+        last_error: Option<String>,
+    ) {
+        let mut subsystems = lock(&self.subsystems);
+        // 오류 없이 상태만 바뀐 경우 직전 오류 유지
+        let last_error = last_error.or_else(|| subsystems.get(name).and_then(|s| s.last_error.clone()));
+
+        subsystems.insert(
+            name,
+            SubsystemStatus {
+                state,
+                restarts,
+                last_error,
+                since: Utc::now(),
+            },
+        );
+    }
+
+    pub fn record_send(&self, batch_id: &str, count: usize) {
+        *lock(&self.last_send) = Some(LastSend {
+            batch_id: batch_id.to_string(),
+            count,
+            at: Utc::now(),
+        });
+    }
+
+    pub fn record_expired(&self, count: u64) {
+        self.expired_events.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_invalid_utf8(&self, label: &str) {
+        *lock(&self.invalid_utf8).entry(label.to_string()).or_default() += 1;
+    }
+
+    /// 수집 대상별 UTF-8 이 아니라 버린 레코드 수
+    pub fn invalid_utf8(&self) -> BTreeMap<String, u64> {
+        lock(&self.invalid_utf8).clone()
+    }
+
+    /// 전송 대기 상태, 디스크 큐에서는 맨 앞 배치를, 메모리에서는 재시도 중인 배치를 가장 오래된 것으로 봄
+    pub fn backlog(&self) -> BacklogSnapshot {
+        let (spooled_batches, oldest_spooled) = lock(&self.spool)
+            .as_ref()
+            .map_or((0, None), |spool| {
+                (spool.list().map_or(0, |paths| paths.len()), spool.oldest_event_at())
+            });
+
+        let queued_batches = lock(&self.queues)
+            .iter()
+            .filter(|(name, _)| *name == "batch" || name.starts_with("batch-"))
+            .map(|(_, depth)| depth())
+            .sum();
+
+        let retry = retry::send_state_snapshot();
+        let oldest = oldest_spooled.into_iter().chain(retry.failing_since).min();
+
+        BacklogSnapshot {
+            spooled_batches,
+            queued_batches,
+            oldest_event_age_secs: oldest.map(|at| (Utc::now() - at).num_seconds().max(0)),
+            retry,
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let now = Utc::now();
+        let windows = schedule::snapshot();
+
+        let sources = lock(&self.sources)
+            .iter()
+            .map(|(label, state)| {
+                let size = std::fs::metadata(&state.path).ok().map(|m| m.len());
+
+                SourceSnapshot {
+                    label: label.clone(),
+                    path: state.path.clone(),
+                    position: state.position,
+                    size,
+                    lag_bytes: size.map(|s| s.saturating_sub(state.position)),
+                    updated_at: state.updated_at,
+                    paused: collector::is_paused(label),
+                    window_open: windows.get(label).copied(),
+                }
+            })
+            .collect();
+
+        let queues = lock(&self.queues)
+            .iter()
+            .map(|(name, depth)| (name.to_string(), depth()))
+            .collect();
+
+        let backlog = self.backlog();
+
+        let (memory_used_bytes, memory_limit_bytes) = lock(&self.budget)
+            .as_ref()
+            .map_or((0, 0), |b| (b.used(), b.limit()));
+
+        let token = lock(&self.access_token)
+            .as_ref()
+            .and_then(|token| token.read().ok().map(|t| t.clone()));
+        let token = lifecycle::snapshot(token.as_deref());
+
+        Snapshot {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            pid: std::process::id(),
+            started_at: self.started_at,
+            uptime_secs: (now - self.started_at).num_seconds(),
+            connection: self.connection(),
+            sources,
+            queues,
+            spool_batches: backlog.spooled_batches,
+            backlog,
+            memory_used_bytes,
+            memory_limit_bytes,
+            token_expires_at: token.expires_at,
+            token,
+            last_send: lock(&self.last_send).clone(),
+            expired_events: self.expired_events.load(Ordering::Relaxed),
+            egress: egress::snapshot(),
+            spools: spool::stats(),
+            sinks: sink_health::snapshot(),
+            disk: disk_guard::snapshot(),
+            crash_loop: crash_loop::snapshot(),
+            clock: clock::snapshot(),
+            watchers: watch_health::snapshot(),
+            invalid_utf8_skipped: self.invalid_utf8(),
+            error_codes: error_code::snapshot(),
+            subsystems: lock(&self.subsystems)
+                .iter()
+                .map(|(name, status)| (name.to_string(), status.clone()))
+                .collect(),
+            recent_errors: crash::recent_errors(),
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -1,81 +1,727 @@
 use anyhow::Result;
+use chrono::Utc;
+use prost_types::Timestamp;
+use prost::Message;
 use std::sync::Arc;
-use tokio::sync::{RwLock, mpsc::Receiver};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::{Notify, RwLock};
+use tokio::sync::mpsc::Receiver;
+use tokio::time;
 use tonic::Code;
-use tonic::codec::CompressionEncoding;
+use tonic::client::Grpc;
+use tonic::metadata::MetadataValue;
 use tonic::service::interceptor::InterceptedService;
+use tokio_util::sync::CancellationToken;
 use tonic::transport::Channel;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 use crate::auth::interceptor::AuthInterceptor;
 use crate::auth::token_manager::TokenManager;
-use crate::proto::log::LogBatch;
-use crate::proto::log::log_service_client::LogServiceClient;
+use crate::batch_pool;
+use crate::batch_codec::{self, SharedBatchCodec};
+use crate::capabilities::Negotiated;
+use crate::chaos;
+use crate::egress;
+use crate::error_code::ErrorCode;
+use crate::governor;
+use crate::http_fallback::HttpFallback;
+use crate::memory::{self, MemoryBudget};
+use crate::messages as msg;
+use crate::proto::log::{LogBatch, LogRejection, SendResponse};
+use crate::retry::{self, Backoff};
+use crate::sink_health::SinkHealth;
+use crate::spool::{Spool, DEAD_LETTER_DIR};
+use crate::status;
+use crate::supervisor::SharedReceiver;
 
-type LogClient = LogServiceClient<InterceptedService<Channel, AuthInterceptor>>;
+/// 배치 추적 식별자를 싣는 gRPC 메타데이터 키 (`LogBatch.trace_id` 와 같은 값)
+pub static TRACE_ID_HEADER: &str = "x-trace-id";
 
+static RESEND_REQUESTED: Notify = Notify::const_new();
+
+/// 전송 실패로 디스크 큐에 넣은 배치가 있음, 전송이 다시 성공하면 재전송을 요청
+static SPOOLED_ON_FAILURE: AtomicBool = AtomicBool::new(false);
+
+/// 다음 실행을 기다리지 않고 스풀에 쌓인 배치를 바로 재전송
+///
+/// 재전송 담당 Streamer 가 전송 중이어도 요청을 잃지 않도록 permit 을 남김 (전송을 마치면 바로 재전송)
+pub fn request_resend() {
+    RESEND_REQUESTED.notify_one();
+}
+
+/// 서버가 일부 로그를 재시도 가능으로 거부했을 때 해당 로그만 다시 보내는 최대 횟수
+const MAX_PARTIAL_RESENDS: u32 = 3;
+
+/// 라벨별 전송량 환산에 쓰는 압축률을 다시 재는 배치 간격
+const COMPRESSION_SAMPLE_EVERY: u64 = 32;
+
+/// `LogBatch.logs` 필드 번호, 분할 시 로그 한 건이 차지하는 크기 계산에 사용
+const LOGS_FIELD_TAG: u32 = 3;
+
+/// 배치를 복제하지 않고 전송하도록 생성된 클라이언트 대신 [`SharedBatchCodec`] 으로 직접 호출
+type LogClient = Grpc<InterceptedService<Channel, AuthInterceptor>>;
+
+/// [`LogBatch`] 를 gRPC 클라이언트 스트리밍으로 서버에 전송
+///
+/// 인증 만료 시 토큰 갱신 후, 일시적 오류면 backoff 후 재시도하며, 종료 드레인 제한 시간을 넘긴 배치는 [`Spool`] 에 보관
 pub struct Streamer {
-    rx: Receiver<LogBatch>,
+    rx: SharedReceiver<LogBatch>,
     client: LogClient,
+    /// 압축하지 않는 클라이언트, 호스트 자원 압박으로 감속 중에 사용
+    plain_client: LogClient,
     token_manager: Arc<RwLock<TokenManager>>,
+    spool: Spool,
+    budget: MemoryBudget,
+    negotiated: Negotiated,
+    /// 여러 레인 중 한 Streamer 만 이전 실행의 스풀을 재전송
+    resend_spool: bool,
+    /// 스풀 재전송 시 이보다 오래된 로그/메트릭은 버림 (초), 0 이면 무제한
+    max_event_age: u64,
+    /// 마지막으로 잰 압축률 (압축 후/전), 라벨별 전송량 환산용
+    compression_ratio: f64,
+    /// 이 Streamer 가 전송한 배치 수, 압축률을 다시 잴 때를 정함
+    sent_batches: u64,
+    /// 전송 결과를 Forwarder 에 알리는 레인 Sink 상태
+    sink_health: Option<SinkHealth>,
+    /// gRPC 가 계속 실패하면 쓰는 HTTP/JSON 전송, 모든 레인이 공유
+    http_fallback: Option<Arc<HttpFallback>>,
 }
 
 impl Streamer {
     pub fn new(
-        rx: Receiver<LogBatch>,
+        rx: SharedReceiver<LogBatch>,
         channel: Channel,
         interceptor: AuthInterceptor,
         token_manager: Arc<RwLock<TokenManager>>,
+        spool: Spool,
+        budget: MemoryBudget,
     ) -> Self {
-        let client = LogServiceClient::with_interceptor(channel, interceptor)
-            .send_compressed(CompressionEncoding::Gzip);
+        let client = Grpc::new(InterceptedService::new(channel, interceptor));
         Self {
             rx,
+            plain_client: client.clone(),
             client,
             token_manager,
+            spool,
+            budget,
+            negotiated: Negotiated::default(),
+            resend_spool: true,
+            max_event_age: 0,
+            compression_ratio: 1.0,
+            sent_batches: 0,
+            sink_health: None,
+            http_fallback: None,
         }
     }
 
-    pub async fn start(mut self) {
-        info!("Streamer 시작");
+    /// 시작 시 스풀 재전송을 다른 레인에 맡김
+    pub fn skip_spool_resend(mut self) -> Self {
+        self.resend_spool = false;
+        self
+    }
+
+    /// 스풀에 오래 머문 이벤트를 버리는 기준 (설정의 `max_event_age`)
+    pub fn with_max_event_age(mut self, secs: u64) -> Self {
+        self.max_event_age = secs;
+        self
+    }
+
+    /// 전송 결과를 기록할 레인 Sink 상태
+    pub fn with_sink_health(mut self, health: SinkHealth) -> Self {
+        self.sink_health = Some(health);
+        self
+    }
+
+    /// gRPC 가 계속 실패할 때 쓸 HTTP/JSON 전송 (설정의 `http_fallback`)
+    pub fn with_http_fallback(mut self, fallback: Option<Arc<HttpFallback>>) -> Self {
+        self.http_fallback = fallback;
+        self
+    }
+
+    /// 채널이 닫힐 때까지 전송, `drain_deadline` 취소 시 남은 배치는 스풀에 저장 후 종료
+    pub async fn start(mut self, drain_deadline: CancellationToken) {
+        info!("{}", msg::STREAMER_STARTED);
+
+        let negotiated = self.token_manager.read().await.negotiated();
+        self.apply_capabilities(negotiated);
+
+        let rx = Arc::clone(&self.rx);
+        let mut rx = rx.lock().await;
+
+        if self.resend_spool {
+            self.resend_spooled().await;
+        }
 
-        while let Some(batch) = self.rx.recv().await {
-            if let Err(e) = self.send_with_retry(batch).await {
-                error!("로그 전송 실패: {}", e);
+        loop {
+            let batch = tokio::select! {
+                _ = drain_deadline.cancelled() => break,
+                _ = RESEND_REQUESTED.notified(), if self.resend_spool => {
+                    self.resend_spooled().await;
+                    continue;
+                }
+                batch = rx.recv() => match batch {
+                    Some(batch) => Arc::new(self.strip_unsupported(batch)),
+                    None => break,
+                },
+            };
+
+            // 재시도와 스풀 저장은 같은 배치를 공유 (복제 없음)
+            let sent = tokio::select! {
+                result = self.deliver(Arc::clone(&batch)) => Some(result),
+                _ = drain_deadline.cancelled() => None,
+            };
+
+            // 전송 성공/실패/스풀 저장 모두 메모리에서 배치가 빠짐
+            self.budget.release(memory::batch_bytes(&batch));
+
+            match sent {
+                Some(Ok(())) => {
+                    if let Some(health) = &self.sink_health {
+                        health.record_success();
+                    }
+                    // 실패로 디스크 큐에 넣은 배치가 있으면 다음 실행을 기다리지 않고 재전송
+                    if SPOOLED_ON_FAILURE.swap(false, Ordering::Relaxed) {
+                        request_resend();
+                    }
+                }
+                // 서버가 거부한 배치는 다시 보내도 실패하므로 디스크 큐 대신 dead-letter 에 보관
+                Some(Err(e)) if is_rejected(&e) => {
+                    error!(
+                        batch_id = %batch.batch_id,
+                        trace_id = %batch.trace_id,
+                        error = %e,
+                        code = %ErrorCode::SendFailed,
+                        "{}",
+                        msg::LOG_SEND_FAILED
+                    );
+                    self.dead_letter(&batch);
+                }
+                // 재시도 예산 소진, 토큰 갱신 실패 등으로 보내지 못한 배치는 버리지 않고 디스크 큐에 보관
+                Some(Err(e)) => {
+                    error!(
+                        batch_id = %batch.batch_id,
+                        trace_id = %batch.trace_id,
+                        error = %e,
+                        code = %ErrorCode::SendFailed,
+                        "{}",
+                        msg::LOG_SEND_FAILED
+                    );
+                    if let Some(health) = &self.sink_health {
+                        health.record_failure();
+                    }
+                    self.spool_batch(&batch);
+                    SPOOLED_ON_FAILURE.store(true, Ordering::Relaxed);
+                }
+                None => {
+                    self.spool_batch(&batch);
+                    break;
+                }
+            }
+
+            // 분할/재전송 중인 조각이 없으면 버퍼를 Forwarder 에 돌려줌
+            if let Ok(batch) = Arc::try_unwrap(batch) {
+                batch_pool::recycle(batch);
             }
         }
 
-        info!("Streamer 종료");
+        if drain_deadline.is_cancelled() {
+            self.spool_remaining(&mut rx);
+        }
+
+        info!("{}", msg::STREAMER_STOPPED);
     }
 
-    async fn send_with_retry(&mut self, batch: LogBatch) -> Result<()> {
-        let batch_id = batch.batch_id.clone();
+    /// 협상 결과에 맞춰 압축 방식과 메시지 크기 제한 설정
+    fn apply_capabilities(&mut self, negotiated: Negotiated) {
+        negotiated.log();
+
+        if let Some(encoding) = negotiated.compression {
+            self.client = self.client.clone().send_compressed(encoding);
+        }
+        self.client = self
+            .client
+            .clone()
+            .max_encoding_message_size(negotiated.max_message_bytes);
+        self.plain_client = self
+            .plain_client
+            .clone()
+            .max_encoding_message_size(negotiated.max_message_bytes);
+        self.negotiated = negotiated;
+    }
+
+    /// 스풀된 배치 전송 (시작 시 또는 관리 API 요청 시), 실패 시 남은 스풀은 다음 실행으로 미룸.
+    /// 서버가 거부한 파일은 dead-letter 로 옮기고 다음 파일로 넘어가 뒤의 배치를 막지 않음
+    async fn resend_spooled(&mut self) {
+        let paths = match self.spool.list() {
+            Ok(paths) => paths,
+            Err(e) => {
+                error!(error = %e, "{}", msg::SPOOL_LIST_FAILED);
+                return;
+            }
+        };
+
+        if paths.is_empty() {
+            return;
+        }
+
+        info!(count = paths.len(), "{}", msg::SPOOL_RESEND_STARTED);
+
+        for path in paths {
+            let Some(mut batch) = self.spool.read(&path) else {
+                continue;
+            };
+            self.drop_expired(&mut batch);
+            if batch.logs.is_empty() && batch.metrics.is_empty() {
+                self.spool.remove(&path);
+                continue;
+            }
+
+            let batch = Arc::new(self.strip_unsupported(batch));
+            match self.deliver(Arc::clone(&batch)).await {
+                Ok(()) => {}
+                Err(e) if is_rejected(&e) => {
+                    warn!(batch_id = %batch.batch_id, error = %e, "{}", msg::SPOOL_RESEND_REJECTED);
+                    self.dead_letter(&batch);
+                }
+                Err(e) => {
+                    warn!(error = %e, "{}", msg::SPOOL_RESEND_FAILED);
+                    return;
+                }
+            }
+
+            self.spool.remove(&path);
+        }
+    }
+
+    /// 수집 시각이 `max_event_age` 보다 오래된 로그/메트릭 제거, 모두 빠진 배치는 전송 없이 스풀에서 삭제됨
+    fn drop_expired(&self, batch: &mut LogBatch) {
+        if self.max_event_age == 0 {
+            return;
+        }
+
+        let cutoff = Utc::now().timestamp().saturating_sub(self.max_event_age as i64);
+        let fresh = |timestamp: &Option<Timestamp>| timestamp.as_ref().is_none_or(|t| t.seconds >= cutoff);
+
+        let before = batch.logs.len() + batch.metrics.len();
+        batch.logs.retain(|log| fresh(&log.timestamp));
+        batch.metrics.retain(|metric| fresh(&metric.timestamp));
+
+        let expired = before - (batch.logs.len() + batch.metrics.len());
+        if expired > 0 {
+            status::registry().record_expired(expired as u64);
+            warn!(
+                batch_id = %batch.batch_id,
+                expired = expired,
+                max_event_age = self.max_event_age,
+                "{}",
+                msg::SPOOL_EVENTS_EXPIRED
+            );
+        }
+    }
+
+    fn spool_remaining(&self, rx: &mut Receiver<LogBatch>) {
+        let mut count = 0;
+
+        while let Ok(batch) = rx.try_recv() {
+            self.spool_batch(&batch);
+            self.budget.release(memory::batch_bytes(&batch));
+            count += 1;
+        }
+
+        if count > 0 {
+            warn!(count = count, "{}", msg::DRAIN_TIMEOUT_SPOOLED);
+        }
+    }
+
+    fn spool_batch(&self, batch: &LogBatch) {
+        if let Err(e) = self.spool.push(batch) {
+            error!(
+                batch_id = %batch.batch_id,
+                trace_id = %batch.trace_id,
+                error = %e,
+                code = %ErrorCode::SpoolWriteFailed,
+                "{}",
+                msg::BATCH_SPOOL_FAILED
+            );
+        }
+    }
+
+    /// 메트릭을 처리하지 못하는 서버에는 로그만 전송
+    fn strip_unsupported(&self, mut batch: LogBatch) -> LogBatch {
+        if !self.negotiated.metrics {
+            batch.metrics.clear();
+        }
+        // 원문 라인은 그대로 있으므로 서버가 필드를 모르면 필드만 뺌
+        if !self.negotiated.structured_fields {
+            batch.logs.iter_mut().for_each(|log| log.fields.clear());
+        }
+        batch
+    }
+
+    /// gRPC 로 전송, 연속 실패로 HTTP 대체 전송이 전환되어 있으면 HTTP 로 전송
+    async fn deliver(&mut self, batch: Arc<LogBatch>) -> Result<()> {
+        let Some(fallback) = self.http_fallback.clone() else {
+            return self.send_with_retry(batch).await;
+        };
+
+        if !fallback.is_engaged() {
+            match self.send_with_retry(Arc::clone(&batch)).await {
+                Ok(()) => {
+                    fallback.record_grpc_success();
+                    return Ok(());
+                }
+                // 일시적 오류는 재시도마다 이미 세었으므로 그 사이 전환되었으면 바로 HTTP 로
+                Err(_) if fallback.is_engaged() => {}
+                Err(e) if !fallback.record_grpc_failure() => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        let bytes = fallback.send(&batch).await?;
+        retry::record_send_success();
+        status::registry().record_send(&batch.batch_id, batch.logs.len());
+        egress::record(&batch, bytes as f64 / batch.encoded_len().max(1) as f64);
+        info!(
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            count = batch.logs.len(),
+            "{}",
+            msg::LOG_SENT_HTTP_FALLBACK
+        );
+        Ok(())
+    }
+
+    /// 배치 전송, 인증 갱신 후 재시도까지 `send` span 으로 기록
+    async fn send_with_retry(&mut self, batch: Arc<LogBatch>) -> Result<()> {
+        if batch.logs.is_empty() && batch.metrics.is_empty() {
+            return Ok(());
+        }
+
+        let span = info_span!(
+            "send",
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            count = batch.logs.len()
+        );
+        async {
+            for part in self.split_oversized(batch) {
+                self.send_part(part).await?;
+            }
+            Ok(())
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// 서버 메시지 크기 제한을 넘는 배치를 나눔, 로그 한 건이 제한을 넘으면 dead-letter 에 보관
+    fn split_oversized(&self, batch: Arc<LogBatch>) -> Vec<Arc<LogBatch>> {
+        let limit = self.negotiated.max_message_bytes;
+        if batch.encoded_len() <= limit {
+            return vec![batch];
+        }
+
+        warn!(
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            bytes = batch.encoded_len(),
+            limit = limit,
+            "{}",
+            msg::BATCH_SPLIT
+        );
+
+        let derive = |suffix: String| LogBatch {
+            batch_id: format!("{}-{}", batch.batch_id, suffix),
+            send_at: batch.send_at,
+            logs: Vec::new(),
+            trace_id: batch.trace_id.clone(),
+            metrics: Vec::new(),
+            tags: batch.tags.clone(),
+        };
+
+        let empty_len = derive("part0".to_string()).encoded_len();
+        let mut parts = Vec::new();
+        let mut too_large = derive("too_large".to_string());
+
+        // 메트릭은 첫 조각에 모두 실음
+        let mut current = derive("part0".to_string());
+        current.metrics = batch.metrics.clone();
+        let mut current_len = current.encoded_len();
+
+        for log in &batch.logs {
+            let log_len = prost::encoding::message::encoded_len(LOGS_FIELD_TAG, log);
+
+            if empty_len + log_len > limit {
+                warn!(batch_id = %batch.batch_id, bytes = log_len, limit = limit, "{}", msg::LOG_TOO_LARGE);
+                too_large.logs.push(log.clone());
+                continue;
+            }
+
+            if current_len + log_len > limit {
+                let next = derive(format!("part{}", parts.len() + 1));
+                parts.push(Arc::new(std::mem::replace(&mut current, next)));
+                current_len = empty_len;
+            }
+
+            current.logs.push(log.clone());
+            current_len += log_len;
+        }
+
+        if !current.logs.is_empty() || !current.metrics.is_empty() {
+            parts.push(Arc::new(current));
+        }
+        self.dead_letter(&too_large);
+
+        parts
+    }
+
+    /// 일시적 오류(서버 재시작 등)면 backoff 후 다시 전송, 재시도 예산을 다 쓰면 실패로 반환
+    async fn send_part(&mut self, batch: Arc<LogBatch>) -> Result<()> {
+        let mut backoff = Backoff::new();
+
+        loop {
+            let e = match self.try_send(Arc::clone(&batch)).await {
+                Err(e) if e.downcast_ref::<tonic::Status>().is_some_and(retry::is_transient) => e,
+                result => {
+                    if result.is_ok() {
+                        retry::record_send_success();
+                    }
+                    return result;
+                }
+            };
+
+            // HTTP/2 를 막는 프록시는 보통 Unavailable 이므로 재시도 예산을 다 쓰기 전에 대체 전송으로 전환하도록 매번 셈
+            if self.http_fallback.as_ref().is_some_and(|fallback| fallback.record_grpc_failure()) {
+                return Err(e);
+            }
+
+            if !retry::try_acquire() {
+                retry::record_send_exhausted();
+                warn!(
+                    batch_id = %batch.batch_id,
+                    trace_id = %batch.trace_id,
+                    code = %ErrorCode::RetryBudgetExhausted,
+                    "{}",
+                    msg::RETRY_BUDGET_EXHAUSTED
+                );
+                return Err(e);
+            }
+
+            let delay = backoff.next_delay();
+            retry::record_send_backoff(delay);
+            warn!(
+                batch_id = %batch.batch_id,
+                trace_id = %batch.trace_id,
+                backoff_ms = delay.as_millis() as u64,
+                error = %e,
+                code = %ErrorCode::SendRetrying,
+                "{}",
+                msg::LOG_SEND_RETRYING
+            );
+            time::sleep(delay).await;
+        }
+    }
+
+    async fn try_send(&mut self, batch: Arc<LogBatch>) -> Result<()> {
+        let batch_id = batch.batch_id.as_str();
+        let trace_id = batch.trace_id.as_str();
         let log_count = batch.logs.len();
 
-        match self.send_batch(batch.clone()).await {
-            Ok(_) => {
-                info!(batch_id = %batch_id, count = log_count, "로그 전송 완료");
+        match self.send_batch(Arc::clone(&batch)).await {
+            Ok(response) => {
+                status::registry().record_send(batch_id, log_count);
+                self.account_egress(&batch);
+                info!(batch_id = %batch_id, trace_id = %trace_id, count = log_count, "{}", msg::LOG_SENT);
+                if self.negotiated.partial_acks {
+                    self.handle_rejections(Arc::clone(&batch), response).await;
+                }
                 Ok(())
             }
             Err(status) if status.code() == Code::Unauthenticated => {
-                warn!("토큰 만료, 재발급 시도");
+                warn!(batch_id = %batch_id, trace_id = %trace_id, code = %ErrorCode::TokenExpired, "{}", msg::TOKEN_EXPIRED_RETRY);
 
                 {
                     let mut tm = self.token_manager.write().await;
                     tm.refresh().await?;
                 }
 
-                self.send_batch(batch).await?;
-                info!(batch_id = %batch_id, count = log_count, "재시도 후 로그 전송 완료");
+                let response = self.send_batch(Arc::clone(&batch)).await?;
+                status::registry().record_send(batch_id, log_count);
+                self.account_egress(&batch);
+                info!(
+                    batch_id = %batch_id,
+                    trace_id = %trace_id,
+                    count = log_count,
+                    "{}",
+                    msg::LOG_SENT_AFTER_RETRY
+                );
+                if self.negotiated.partial_acks {
+                    self.handle_rejections(Arc::clone(&batch), response).await;
+                }
                 Ok(())
             }
             Err(e) => Err(e.into()),
         }
     }
 
-    async fn send_batch(&mut self, batch: LogBatch) -> Result<(), tonic::Status> {
-        let stream = tokio_stream::once(batch);
-        self.client.send(stream).await?;
-        Ok(())
+    /// 서버가 받은 배치의 라벨별 전송량 누적, 압축하는 서버면 주기적으로 압축률을 다시 잼
+    fn account_egress(&mut self, batch: &LogBatch) {
+        if self.negotiated.compression.is_none() || governor::is_throttled() {
+            self.compression_ratio = 1.0;
+        } else if self.sent_batches.is_multiple_of(COMPRESSION_SAMPLE_EVERY) {
+            self.compression_ratio = egress::compression_ratio(batch);
+        }
+        self.sent_batches += 1;
+
+        egress::record(batch, self.compression_ratio);
+    }
+
+    /// 배치 하나를 스트림으로 전송, 추적 식별자는 요청 메타데이터에도 실음
+    async fn send_batch(&mut self, batch: Arc<LogBatch>) -> Result<SendResponse, tonic::Status> {
+        if let Some(status) = chaos::send_fault().await {
+            return Err(status);
+        }
+
+        let trace_id = MetadataValue::try_from(batch.trace_id.as_str()).ok();
+        let (path, method) = batch_codec::send_method();
+
+        let mut request = tonic::Request::new(tokio_stream::once(batch));
+        request.extensions_mut().insert(method);
+        if let Some(trace_id) = trace_id {
+            request.metadata_mut().insert(TRACE_ID_HEADER, trace_id);
+        }
+
+        // 호스트 자원 압박 중에는 압축 CPU 를 아낌
+        let client = if governor::is_throttled() {
+            &mut self.plain_client
+        } else {
+            &mut self.client
+        };
+
+        client
+            .ready()
+            .await
+            .map_err(|e| tonic::Status::unknown(format!("{}: {}", msg::SERVICE_NOT_READY, e)))?;
+        Ok(client
+            .client_streaming(request, path, SharedBatchCodec)
+            .await?
+            .into_inner())
+    }
+
+    /// 서버가 거부한 로그 처리, 재시도 가능한 항목만 다시 보내고 나머지는 dead-letter 에 보관
+    async fn handle_rejections(&mut self, batch: Arc<LogBatch>, mut response: SendResponse) {
+        let mut pending = batch;
+        let mut attempt = 0;
+
+        while !response.rejected.is_empty() {
+            let (retry, rejected) = split_rejected(&pending, &response.rejected, attempt + 1);
+
+            warn!(
+                batch_id = %pending.batch_id,
+                trace_id = %pending.trace_id,
+                retryable = retry.logs.len(),
+                rejected = rejected.logs.len(),
+                "{}",
+                msg::LOGS_REJECTED
+            );
+            for rejection in &response.rejected {
+                debug!(
+                    batch_id = %pending.batch_id,
+                    index = rejection.index,
+                    reason = ?rejection.reason(),
+                    detail = %rejection.message,
+                    retryable = rejection.retryable,
+                    "{}",
+                    msg::LOG_REJECTED
+                );
+            }
+
+            self.dead_letter(&rejected);
+
+            if retry.logs.is_empty() {
+                return;
+            }
+
+            attempt += 1;
+            if attempt > MAX_PARTIAL_RESENDS {
+                self.dead_letter(&retry);
+                return;
+            }
+
+            let retry = Arc::new(retry);
+            response = match self.send_batch(Arc::clone(&retry)).await {
+                Ok(response) => response,
+                Err(e) => {
+                    // 연결 문제 등은 일반 배치와 같이 스풀 후 다음 실행에서 재전송
+                    warn!(batch_id = %retry.batch_id, trace_id = %retry.trace_id, error = %e, "{}", msg::PARTIAL_RESEND_FAILED);
+                    self.spool_batch(&retry);
+                    return;
+                }
+            };
+            pending = retry;
+        }
+    }
+
+    fn dead_letter(&self, batch: &LogBatch) {
+        if batch.logs.is_empty() {
+            return;
+        }
+
+        if let Err(e) = Spool::open(DEAD_LETTER_DIR).and_then(|dead_letter| dead_letter.push(batch)) {
+            error!(
+                batch_id = %batch.batch_id,
+                trace_id = %batch.trace_id,
+                count = batch.logs.len(),
+                error = %format!("{:#}", e),
+                code = %ErrorCode::SpoolWriteFailed,
+                "{}",
+                msg::DEAD_LETTER_FAILED
+            );
+            return;
+        }
+
+        warn!(
+            batch_id = %batch.batch_id,
+            trace_id = %batch.trace_id,
+            count = batch.logs.len(),
+            code = %ErrorCode::DeadLettered,
+            "{}",
+            msg::DEAD_LETTERED
+        );
     }
 }
+
+/// 서버가 배치를 거부해 다시 보내도 성공하지 않는 오류
+fn is_rejected(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<tonic::Status>().is_some_and(retry::is_permanent)
+}
+
+/// 거부 항목을 재전송할 배치와 dead-letter 배치로 분리 (범위를 벗어난 index 는 무시)
+///
+/// 두 배치 모두 원본 `trace_id` 를 유지하고, `batch_id` 는 원본에서 파생해 서버가 중복으로 버리지 않게 함
+fn split_rejected(batch: &LogBatch, rejected: &[LogRejection], attempt: u32) -> (LogBatch, LogBatch) {
+    let derive = |suffix: String| LogBatch {
+        batch_id: format!("{}-{}", batch.batch_id, suffix),
+        send_at: batch.send_at,
+        logs: Vec::new(),
+        trace_id: batch.trace_id.clone(),
+        metrics: Vec::new(),
+        tags: batch.tags.clone(),
+    };
+
+    let mut retry = derive(format!("retry{}", attempt));
+    let mut dead = derive("rejected".to_string());
+
+    for rejection in rejected {
+        let Some(log) = batch.logs.get(rejection.index as usize) else {
+            continue;
+        };
+
+        if rejection.retryable {
+            retry.logs.push(log.clone());
+        } else {
+            dead.logs.push(log.clone());
+        }
+    }
+
+    (retry, dead)
+}
@@ -0,0 +1,125 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::Receiver;
+use tokio::time::{self, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::crash;
+use crate::crash_loop;
+use crate::messages as msg;
+use crate::status::{self, SubsystemState};
+
+/// 재시작된 서브시스템이 이어받을 수 있도록 공유되는 수신 채널
+///
+/// 태스크가 panic 으로 종료되어도 tokio Mutex 는 poison 되지 않으므로 새 인스턴스가 다시 잠글 수 있음
+pub type SharedReceiver<T> = Arc<Mutex<Receiver<T>>>;
+
+pub fn shared<T>(rx: Receiver<T>) -> SharedReceiver<T> {
+    Arc::new(Mutex::new(rx))
+}
+
+/// 서브시스템 재시작 정책
+#[derive(Debug, Clone, Copy)]
+pub struct RestartPolicy {
+    /// `window` 안에서 허용되는 최대 재시작 횟수, 초과 시 복구 불가로 판단
+    pub max_restarts: u32,
+    pub window: Duration,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            window: Duration::from_secs(300),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+/// 서브시스템을 실행하고 비정상 종료(panic, 오류) 시 backoff 후 재생성
+///
+/// `factory` 는 재시작 횟수를 받아 새 인스턴스를 만들어 실행하는 future 를 반환,
+/// 정상 종료(Ok)는 더 이상 재시작하지 않음
+pub async fn supervise<F, Fut>(
+    name: &'static str,
+    policy: RestartPolicy,
+    shutdown: CancellationToken,
+    mut factory: F,
+) -> Result<()>
+where
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let mut attempt = 0u32;
+    let mut backoff = policy.initial_backoff;
+    let mut restarts: Vec<Instant> = Vec::new();
+
+    loop {
+        status::registry().set_subsystem(name, SubsystemState::Running, attempt, None);
+
+        let started = Instant::now();
+        let result = tokio::spawn(crash::in_subsystem(name, factory(attempt))).await;
+
+        // window 이상 정상 동작했다면 backoff 초기화
+        if started.elapsed() >= policy.window {
+            backoff = policy.initial_backoff;
+            crash_loop::record_subsystem(name, 0);
+        }
+
+        let reason = match result {
+            Ok(Ok(())) => {
+                status::registry().set_subsystem(name, SubsystemState::Stopped, attempt, None);
+                return Ok(());
+            }
+            Ok(Err(e)) => format!("{:#}", e),
+            Err(e) if e.is_panic() => "panic".to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        if shutdown.is_cancelled() {
+            status::registry().set_subsystem(name, SubsystemState::Stopped, attempt, Some(reason.clone()));
+            warn!(subsystem = name, reason = %reason, "{}", msg::SUBSYSTEM_FAILED_DURING_SHUTDOWN);
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        restarts.retain(|t| now.duration_since(*t) < policy.window);
+        if restarts.len() as u32 >= policy.max_restarts {
+            error!(subsystem = name, restarts = restarts.len(), reason = %reason, "{}", msg::RESTART_LIMIT_EXCEEDED);
+            crash::record_failure(name, &reason);
+            status::registry().set_subsystem(name, SubsystemState::Failed, attempt, Some(reason.clone()));
+            bail!("{}: {} ({})", msg::SUBSYSTEM_UNRECOVERABLE, name, reason);
+        }
+        restarts.push(now);
+        crash_loop::record_subsystem(name, restarts.len() as u32);
+        status::registry().set_subsystem(name, SubsystemState::Restarting, attempt, Some(reason.clone()));
+
+        error!(
+            subsystem = name,
+            backoff_secs = backoff.as_secs(),
+            reason = %reason,
+            "{}",
+            msg::SUBSYSTEM_RESTART_SCHEDULED
+        );
+
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                status::registry().set_subsystem(name, SubsystemState::Stopped, attempt, None);
+                return Ok(());
+            }
+            _ = time::sleep(backoff) => {}
+        }
+
+        attempt += 1;
+        backoff = (backoff * 2).min(policy.max_backoff);
+        info!(subsystem = name, attempt = attempt, "{}", msg::SUBSYSTEM_RESTARTING);
+    }
+}
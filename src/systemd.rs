@@ -0,0 +1,192 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::messages as msg;
+
+static ENV_NOTIFY_SOCKET: &str = "NOTIFY_SOCKET";
+static ENV_WATCHDOG_USEC: &str = "WATCHDOG_USEC";
+
+/// systemd unit 생성 옵션
+pub struct UnitOptions<'a> {
+    pub exec_path: &'a Path,
+    /// 실행 파일 뒤에 붙일 인자 (`--profile`, 명령줄 지정 값)
+    pub args: &'a [String],
+    pub state_dir: &'a str,
+    pub user: Option<&'a str>,
+    pub watchdog_sec: u64,
+    /// 자체 업데이트용 실행 파일 디렉터리 쓰기 허용
+    pub self_update: bool,
+}
+
+/// 샌드박싱, 상태 디렉터리, watchdog, 재시작 정책이 포함된 unit 파일 생성
+///
+/// 에이전트는 작업 디렉터리 기준 `config/`, `state/` 를 사용하므로
+/// `StateDirectory` 를 작업 디렉터리로 지정
+pub fn render_unit(options: &UnitOptions) -> String {
+    let working_dir = format!("/var/lib/{}", options.state_dir);
+    let mut unit = String::new();
+
+    let _ = writeln!(unit, "[Unit]");
+    let _ = writeln!(unit, "Description=rlog-agent log collector");
+    let _ = writeln!(unit, "Documentation=https://github.com/juny0955/rlog-agent");
+    let _ = writeln!(unit, "Wants=network-online.target");
+    let _ = writeln!(unit, "After=network-online.target");
+    let _ = writeln!(unit, "StartLimitIntervalSec=300");
+    let _ = writeln!(unit, "StartLimitBurst=5");
+    let _ = writeln!(unit);
+
+    let _ = writeln!(unit, "[Service]");
+    let _ = writeln!(unit, "Type=notify");
+    let exec_start: Vec<String> = std::iter::once(options.exec_path.display().to_string())
+        .chain(options.args.iter().cloned())
+        .map(|arg| exec_arg(&arg))
+        .collect();
+    let _ = writeln!(unit, "ExecStart={}", exec_start.join(" "));
+    // 바이너리 교체 후 `systemctl reload` 로 무중단 재실행
+    let _ = writeln!(unit, "ExecReload=/bin/kill -USR2 $MAINPID");
+    let _ = writeln!(unit, "WorkingDirectory={}", working_dir);
+    let _ = writeln!(unit, "StateDirectory={}", options.state_dir);
+    let _ = writeln!(unit, "StateDirectoryMode=0700");
+    // 최초 등록 시 필요한 SERVER_ADDR, PROJECT_KEY
+    let _ = writeln!(unit, "EnvironmentFile=-/etc/{}/agent.env", options.state_dir);
+    let _ = writeln!(unit, "Restart=on-failure");
+    let _ = writeln!(unit, "RestartSec=5s");
+    let _ = writeln!(unit, "TimeoutStopSec=60s");
+    if options.watchdog_sec > 0 {
+        let _ = writeln!(unit, "WatchdogSec={}s", options.watchdog_sec);
+    }
+    let _ = writeln!(unit);
+
+    match options.user {
+        Some(user) => {
+            let _ = writeln!(unit, "User={}", user);
+        }
+        None => {
+            let _ = writeln!(unit, "DynamicUser=yes");
+        }
+    }
+    // /var/log 하위 로그 파일 읽기 권한
+    let _ = writeln!(unit, "SupplementaryGroups=adm systemd-journal");
+    let _ = writeln!(unit);
+
+    let _ = writeln!(unit, "NoNewPrivileges=yes");
+    let _ = writeln!(unit, "CapabilityBoundingSet=");
+    let _ = writeln!(unit, "AmbientCapabilities=");
+    let _ = writeln!(unit, "ProtectSystem=strict");
+    // 자체 업데이트는 실행 파일 옆에 새 바이너리를 쓰고 rename 하므로 그 디렉터리만 쓰기 허용
+    if options.self_update
+        && let Some(dir) = options.exec_path.parent()
+    {
+        let _ = writeln!(unit, "ReadWritePaths={}", dir.display());
+    }
+    let _ = writeln!(unit, "ProtectHome=read-only");
+    let _ = writeln!(unit, "PrivateTmp=yes");
+    let _ = writeln!(unit, "PrivateDevices=yes");
+    let _ = writeln!(unit, "ProtectKernelTunables=yes");
+    let _ = writeln!(unit, "ProtectKernelModules=yes");
+    let _ = writeln!(unit, "ProtectKernelLogs=yes");
+    let _ = writeln!(unit, "ProtectControlGroups=yes");
+    let _ = writeln!(unit, "ProtectClock=yes");
+    let _ = writeln!(unit, "ProtectHostname=yes");
+    let _ = writeln!(unit, "RestrictAddressFamilies=AF_UNIX AF_INET AF_INET6");
+    let _ = writeln!(unit, "RestrictNamespaces=yes");
+    let _ = writeln!(unit, "RestrictRealtime=yes");
+    let _ = writeln!(unit, "RestrictSUIDSGID=yes");
+    let _ = writeln!(unit, "LockPersonality=yes");
+    let _ = writeln!(unit, "MemoryDenyWriteExecute=yes");
+    let _ = writeln!(unit, "SystemCallArchitectures=native");
+    let _ = writeln!(unit, "SystemCallFilter=@system-service");
+    let _ = writeln!(unit, "UMask=0077");
+    let _ = writeln!(unit);
+
+    let _ = writeln!(unit, "[Install]");
+    let _ = writeln!(unit, "WantedBy=multi-user.target");
+
+    unit
+}
+
+/// `ExecStart` 인자 하나, 지정자(`%`)와 환경 변수 치환(`$`)을 막고 공백이나 따옴표가 있으면 큰따옴표로 묶음
+fn exec_arg(arg: &str) -> String {
+    let escaped = arg.replace('%', "%%").replace('$', "$$");
+
+    if escaped.is_empty() || escaped.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        escaped
+    }
+}
+
+/// sd_notify 프로토콜로 systemd에 상태 전달 (NOTIFY_SOCKET 없으면 무시)
+pub fn notify(state: &str) {
+    let Ok(socket_path) = std::env::var(ENV_NOTIFY_SOCKET) else {
+        return;
+    };
+
+    if let Err(e) = send_notify(&socket_path, state) {
+        warn!(error = %e, "{}", msg::SYSTEMD_NOTIFY_FAILED);
+    }
+}
+
+#[cfg(unix)]
+fn send_notify(socket_path: &str, state: &str) -> std::io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let socket = UnixDatagram::unbound()?;
+
+    // '@' 로 시작하면 abstract namespace 소켓
+    #[cfg(target_os = "linux")]
+    if let Some(name) = socket_path.strip_prefix('@') {
+        use std::os::linux::net::SocketAddrExt;
+        use std::os::unix::net::SocketAddr;
+
+        let addr = SocketAddr::from_abstract_name(name.as_bytes())?;
+        socket.send_to_addr(state.as_bytes(), &addr)?;
+        return Ok(());
+    }
+
+    socket.send_to(state.as_bytes(), socket_path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send_notify(_socket_path: &str, _state: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// WATCHDOG_USEC 이 설정된 경우 주기의 절반마다 WATCHDOG=1 전송
+pub fn spawn_watchdog(shutdown: CancellationToken) {
+    let Some(period) = watchdog_period() else {
+        return;
+    };
+
+    info!(period_ms = period.as_millis() as u64, "{}", msg::SYSTEMD_WATCHDOG_ENABLED);
+
+    tokio::spawn(async move {
+        let mut ticker = interval(period);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = ticker.tick() => {
+                    notify("WATCHDOG=1");
+                    debug!("systemd watchdog ping");
+                }
+            }
+        }
+    });
+}
+
+fn watchdog_period() -> Option<Duration> {
+    let usec: u64 = std::env::var(ENV_WATCHDOG_USEC).ok()?.parse().ok()?;
+
+    if usec == 0 {
+        return None;
+    }
+
+    Some(Duration::from_micros(usec / 2))
+}
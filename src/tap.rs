@@ -0,0 +1,41 @@
+//! 실시간 이벤트 탭
+//!
+//! 배치에 담기기 직전의 이벤트를 구독자에게 복제해 전달 (관리 API `tap` 명령).
+//! 구독자가 없으면 복제하지 않으므로 평상시 비용은 없음
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::models::LogEvent;
+
+/// 느린 구독자는 이보다 오래된 이벤트를 건너뜀
+const TAP_CAPACITY: usize = 256;
+
+static TAP: LazyLock<broadcast::Sender<TapEvent>> =
+    LazyLock::new(|| broadcast::channel(TAP_CAPACITY).0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapEvent {
+    pub label: String,
+    pub timestamp: DateTime<Utc>,
+    pub content: String,
+}
+
+pub fn publish(event: &LogEvent) {
+    if TAP.receiver_count() == 0 {
+        return;
+    }
+
+    let _ = TAP.send(TapEvent {
+        label: event.label.clone(),
+        timestamp: event.timestamp,
+        content: event.line().to_string(),
+    });
+}
+
+pub fn subscribe() -> broadcast::Receiver<TapEvent> {
+    TAP.subscribe()
+}
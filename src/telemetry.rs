@@ -0,0 +1,74 @@
+//! OpenTelemetry 트레이스 내보내기
+//!
+//! 수집(collect) → 배치(batch) → 전송(send) 단계의 tracing span 을 OTLP(gRPC)로 내보냄.
+//! `otel` 기능으로 빌드하고 `OTEL_EXPORTER_OTLP_ENDPOINT` 를 설정한 경우에만 활성화
+
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+#[cfg(feature = "otel")]
+static ENV_OTLP_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+#[cfg(feature = "otel")]
+static PROVIDER: std::sync::OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> =
+    std::sync::OnceLock::new();
+
+pub type BoxedLayer<S> = Box<dyn Layer<S> + Send + Sync + 'static>;
+
+/// OTLP 내보내기 레이어 생성, 비활성화 상태면 None
+///
+/// 내보내기는 tonic 채널을 사용하므로 Tokio 런타임 컨텍스트 안에서 호출해야 함
+#[cfg(feature = "otel")]
+pub fn layer<S>() -> Option<BoxedLayer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+{
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::SpanExporter;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+
+    std::env::var(ENV_OTLP_ENDPOINT).ok()?;
+
+    // tracing 초기화 전이므로 표준 에러로 출력
+    let exporter = match SpanExporter::builder().with_tonic().build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("{}: {}", crate::messages::OTLP_INIT_FAILED, e);
+            return None;
+        }
+    };
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(env!("CARGO_PKG_NAME"))
+                .build(),
+        )
+        .build();
+
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+    let _ = PROVIDER.set(provider);
+
+    Some(Box::new(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+#[cfg(not(feature = "otel"))]
+pub fn layer<S>() -> Option<BoxedLayer<S>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+{
+    None
+}
+
+/// 종료(또는 재실행) 전 대기 중인 span 내보내기
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!(error = %e, "{}", crate::messages::OTLP_SHUTDOWN_FAILED);
+    }
+}
@@ -0,0 +1,362 @@
+//! 인프로세스 테스트 서버 (`test-server` 기능)
+//!
+//! AuthService, LogService, HealthService 의 최소 구현. 받은 배치, heartbeat, 크래시 보고서, 등록 요청을 기록하고
+//! 액세스 토큰 만료와 RPC 오류를 흉내 내 Streamer/TokenManager 를 실제 gRPC 로 통합 테스트하거나,
+//! `rlog-agent test-server` 로 띄워 서버 없이 설정을 확인할 때 사용. 인증은 발급한 토큰인지만 확인
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_util::sync::CancellationToken;
+use tonic::codec::CompressionEncoding;
+use tonic::transport::Server;
+use tonic::{Code, Request, Response, Status, Streaming};
+use tracing::{info, warn};
+
+use crate::capabilities;
+use crate::messages as msg;
+use crate::proto::auth::auth_service_server::{AuthService, AuthServiceServer};
+use crate::proto::auth::register_response::Source;
+use crate::proto::auth::{RefreshRequest, RefreshResponse, RegisterRequest, RegisterResponse};
+use crate::proto::health::health_service_server::{HealthService, HealthServiceServer};
+use crate::proto::health::{CrashReportRequest, HeartbeatRequest};
+use crate::proto::log::log_service_server::{LogService, LogServiceServer};
+use crate::proto::log::{Log, LogBatch, SendResponse};
+
+static AUTHORIZATION: &str = "authorization";
+static BEARER_PREFIX: &str = "Bearer ";
+
+/// 오류를 흉내 낼 RPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rpc {
+    Register,
+    Refresh,
+    Send,
+    Heartbeat,
+}
+
+/// 서버가 받은 요청과 흉내 낼 동작
+#[derive(Default)]
+struct State {
+    registrations: Vec<RegisterRequest>,
+    batches: Vec<LogBatch>,
+    heartbeats: Vec<HeartbeatRequest>,
+    crash_reports: Vec<CrashReportRequest>,
+    /// 유효한 액세스 토큰, `expire_tokens` 로 비움
+    access_tokens: HashSet<String>,
+    refresh_tokens: HashSet<String>,
+    /// RPC 별로 남은 실패 횟수와 응답 코드
+    failures: HashMap<Rpc, (usize, Code)>,
+    /// 등록 응답에 실을 수집 대상
+    sources: Vec<Source>,
+}
+
+#[derive(Clone, Default)]
+struct Shared {
+    state: Arc<Mutex<State>>,
+    /// 배치를 받을 때마다 깨움
+    received: Arc<Notify>,
+    /// 토큰 발급 순번
+    issued: Arc<AtomicU64>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 남은 실패 횟수가 있으면 하나 쓰고 오류 반환
+    fn inject(&self, rpc: Rpc) -> Result<(), Status> {
+        let mut state = self.lock();
+        let Some((remaining, code)) = state.failures.get_mut(&rpc) else {
+            return Ok(());
+        };
+
+        let code = *code;
+        *remaining -= 1;
+        if *remaining == 0 {
+            state.failures.remove(&rpc);
+        }
+        Err(Status::new(code, format!("{} ({:?})", msg::TEST_SERVER_INJECTED_FAILURE, rpc)))
+    }
+
+    /// 발급한 액세스 토큰인지 확인, 아니면 Unauthenticated (에이전트가 토큰 갱신)
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let token = request
+            .metadata()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX));
+
+        match token {
+            Some(token) if self.lock().access_tokens.contains(token) => Ok(()),
+            _ => Err(Status::unauthenticated(msg::TEST_SERVER_TOKEN_REJECTED.to_string())),
+        }
+    }
+
+    /// 새 액세스/리프레시 토큰 발급
+    fn issue(&self) -> (String, String) {
+        let n = self.issued.fetch_add(1, Ordering::Relaxed);
+        let (access, refresh) = (format!("test-access-{}", n), format!("test-refresh-{}", n));
+
+        let mut state = self.lock();
+        state.access_tokens.insert(access.clone());
+        state.refresh_tokens.insert(refresh.clone());
+        (access, refresh)
+    }
+}
+
+/// 실행 중인 테스트 서버, drop 해도 `shutdown` 전까지 계속 실행
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use std::time::Duration;
+/// use rlog_agent::test_server::{Rpc, TestServer};
+/// use rlog_agent::{Agent, Sink};
+/// use rlog_agent::settings::SourceSettings;
+/// use tokio_util::sync::CancellationToken;
+///
+/// let server = TestServer::start().await?;
+/// // 첫 전송을 거부해 재시도 경로까지 확인
+/// server.fail_next(Rpc::Send, 1, tonic::Code::Unavailable);
+///
+/// let agent = Agent::builder()
+///     .source(SourceSettings::new("app", "/tmp/app.log"))
+///     .sink(Sink::Grpc { server_addr: server.url(), project_key: "test".into() })
+///     .build()?;
+/// let shutdown = CancellationToken::new();
+/// tokio::spawn(agent.run(shutdown.clone()));
+///
+/// assert!(server.wait_for_logs(10, Duration::from_secs(30)).await);
+/// shutdown.cancel();
+/// server.shutdown().await;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TestServer {
+    addr: SocketAddr,
+    shared: Shared,
+    shutdown: CancellationToken,
+    handle: JoinHandle<()>,
+}
+
+impl TestServer {
+    /// 빈 포트에서 시작
+    pub async fn start() -> Result<Self> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    pub async fn bind(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("{}: {}", msg::TEST_SERVER_BIND_FAILED, addr))?;
+        let addr = listener.local_addr().context(msg::TEST_SERVER_BIND_FAILED)?;
+
+        let shared = Shared::default();
+        let shutdown = CancellationToken::new();
+
+        let router = Server::builder()
+            .add_service(AuthServiceServer::new(shared.clone()))
+            .add_service(LogServiceServer::new(shared.clone()).accept_compressed(CompressionEncoding::Gzip))
+            .add_service(HealthServiceServer::new(shared.clone()));
+
+        let handle = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move {
+                let incoming = TcpListenerStream::new(listener);
+                if let Err(e) = router.serve_with_incoming_shutdown(incoming, shutdown.cancelled_owned()).await {
+                    warn!(error = %e, "{}", msg::TEST_SERVER_FAILED);
+                }
+            }
+        });
+
+        info!(addr = %addr, "{}", msg::TEST_SERVER_STARTED);
+        Ok(Self {
+            addr,
+            shared,
+            shutdown,
+            handle,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// 설정의 `server_addr` 로 쓸 주소
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// 등록 응답에 실을 수집 대상 (`register_response.sources`)
+    pub fn set_sources(&self, sources: Vec<Source>) {
+        self.shared.lock().sources = sources;
+    }
+
+    /// 발급한 액세스 토큰을 모두 만료, 이후 요청은 Unauthenticated 로 거부되어 에이전트가 refresh 함
+    pub fn expire_tokens(&self) {
+        self.shared.lock().access_tokens.clear();
+    }
+
+    /// 리프레시 토큰까지 모두 무효화, 에이전트는 재등록해야 함
+    pub fn revoke_tokens(&self) {
+        let mut state = self.shared.lock();
+        state.access_tokens.clear();
+        state.refresh_tokens.clear();
+    }
+
+    /// 다음 `count` 번의 `rpc` 호출을 `code` 로 실패
+    pub fn fail_next(&self, rpc: Rpc, count: usize, code: Code) {
+        let mut state = self.shared.lock();
+        if count == 0 {
+            state.failures.remove(&rpc);
+        } else {
+            state.failures.insert(rpc, (count, code));
+        }
+    }
+
+    pub fn registrations(&self) -> Vec<RegisterRequest> {
+        self.shared.lock().registrations.clone()
+    }
+
+    /// 받은 순서대로 배치
+    pub fn batches(&self) -> Vec<LogBatch> {
+        self.shared.lock().batches.clone()
+    }
+
+    /// 받은 배치의 로그를 순서대로
+    pub fn logs(&self) -> Vec<Log> {
+        self.shared.lock().batches.iter().flat_map(|batch| batch.logs.clone()).collect()
+    }
+
+    pub fn heartbeats(&self) -> Vec<HeartbeatRequest> {
+        self.shared.lock().heartbeats.clone()
+    }
+
+    pub fn crash_reports(&self) -> Vec<CrashReportRequest> {
+        self.shared.lock().crash_reports.clone()
+    }
+
+    /// 받은 로그가 `count` 개 이상이 될 때까지 대기, 제한 시간 안에 모이면 true
+    pub async fn wait_for_logs(&self, count: usize, timeout: Duration) -> bool {
+        let received = || self.shared.lock().batches.iter().map(|batch| batch.logs.len()).sum::<usize>();
+
+        time::timeout(timeout, async {
+            loop {
+                // 확인과 대기 사이에 온 배치를 놓치지 않도록 먼저 등록
+                let notified = self.shared.received.notified();
+                if received() >= count {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    /// 서버를 멈추고 종료까지 대기
+    pub async fn shutdown(self) {
+        self.shutdown.cancel();
+        let _ = self.handle.await;
+    }
+}
+
+#[tonic::async_trait]
+impl AuthService for Shared {
+    async fn register(&self, request: Request<RegisterRequest>) -> Result<Response<RegisterResponse>, Status> {
+        self.inject(Rpc::Register)?;
+
+        let request = request.into_inner();
+        let agent_uuid = request
+            .agent_uuid
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let (access_token, refresh_token) = self.issue();
+
+        let sources = {
+            let mut state = self.lock();
+            state.registrations.push(request);
+            state.sources.clone()
+        };
+
+        Ok(Response::new(RegisterResponse {
+            success: true,
+            agent_uuid,
+            access_token,
+            refresh_token,
+            timezone: String::new(),
+            batch_size: 0,
+            flush_interval_sec: 0,
+            sources,
+            capabilities: Some(capabilities::local()),
+        }))
+    }
+
+    async fn refresh(&self, request: Request<RefreshRequest>) -> Result<Response<RefreshResponse>, Status> {
+        self.inject(Rpc::Refresh)?;
+
+        let request = request.into_inner();
+        if !self.lock().refresh_tokens.remove(&request.refresh_token) {
+            return Err(Status::unauthenticated(msg::TEST_SERVER_TOKEN_REJECTED.to_string()));
+        }
+
+        let (access_token, refresh_token) = self.issue();
+        Ok(Response::new(RefreshResponse {
+            success: true,
+            access_token,
+            refresh_token,
+            capabilities: Some(capabilities::local()),
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl LogService for Shared {
+    async fn send(&self, request: Request<Streaming<LogBatch>>) -> Result<Response<SendResponse>, Status> {
+        self.authorize(&request)?;
+        self.inject(Rpc::Send)?;
+
+        let mut stream = request.into_inner();
+        while let Some(batch) = stream.message().await? {
+            info!(
+                batch_id = %batch.batch_id,
+                count = batch.logs.len(),
+                metrics = batch.metrics.len(),
+                "{}",
+                msg::TEST_SERVER_BATCH_RECEIVED
+            );
+            self.lock().batches.push(batch);
+            self.received.notify_waiters();
+        }
+
+        Ok(Response::new(SendResponse::default()))
+    }
+}
+
+#[tonic::async_trait]
+impl HealthService for Shared {
+    async fn heartbeat(&self, request: Request<HeartbeatRequest>) -> Result<Response<()>, Status> {
+        self.authorize(&request)?;
+        self.inject(Rpc::Heartbeat)?;
+
+        self.lock().heartbeats.push(request.into_inner());
+        Ok(Response::new(()))
+    }
+
+    async fn crash_report(&self, request: Request<CrashReportRequest>) -> Result<Response<()>, Status> {
+        self.authorize(&request)?;
+
+        self.lock().crash_reports.push(request.into_inner());
+        Ok(Response::new(()))
+    }
+}
@@ -0,0 +1,261 @@
+//! 서명 검증 자체 업데이트 (`update`)
+//!
+//! `manifest_url` 의 매니페스트나 서버의 `UpdateAgent` 명령이 현재보다 새 버전을 알리면 바이너리를 받아
+//! `public_key` 로 Ed25519 서명을 검증한 뒤 실행 파일을 rename 으로 원자적으로 교체하고 재실행(Hot Restart).
+//! 서명 대상은 바이너리가 아니라 버전, 대상, 바이너리 SHA-256 을 묶은 [`signed_payload`] 이므로
+//! 서명된 예전 바이너리를 새 버전으로, 다른 대상의 바이너리를 이 대상으로 속여 설치할 수 없음.
+//! 수집 위치는 핸드오버로 이어가며, 이전 바이너리는 `<실행 파일>.prev` 로 남김 (Unix)
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::hot_restart;
+use crate::messages as msg;
+use crate::settings::UpdateSettings;
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 받을 바이너리 크기 상한
+const MAX_BINARY_BYTES: usize = 256 * 1024 * 1024;
+
+/// 새 버전 정보
+#[derive(Debug, Clone)]
+pub struct Release {
+    pub version: String,
+    pub url: String,
+    /// [`signed_payload`] 에 대한 Ed25519 서명 (base64)
+    pub signature: String,
+}
+
+/// `manifest_url` 의 JSON, 대상(`linux-x86_64` 등)별 바이너리
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    version: String,
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    target: String,
+    url: String,
+    signature: String,
+}
+
+pub struct Updater {
+    settings: UpdateSettings,
+    /// 에이전트 종료 신호, 교체 후 재실행을 위해 직접 취소
+    shutdown: CancellationToken,
+}
+
+impl Updater {
+    pub fn new(settings: UpdateSettings, shutdown: CancellationToken) -> Self {
+        Self { settings, shutdown }
+    }
+
+    /// `check_interval` 마다 매니페스트 확인, 새 버전을 설치하면 재실행을 요청하고 종료
+    pub async fn start(self) {
+        if self.settings.manifest_url.trim().is_empty() {
+            return;
+        }
+
+        let mut ticker = interval(Duration::from_secs(self.settings.check_interval.max(60)));
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+
+            let installed = match fetch_manifest(&self.settings.manifest_url).await {
+                Ok(release) => apply(&self.settings, &release).await,
+                Err(e) => Err(e),
+            };
+
+            match installed {
+                Ok(true) => {
+                    info!("{}", msg::UPDATE_RESTARTING);
+                    hot_restart::request();
+                    self.shutdown.cancel();
+                    break;
+                }
+                Ok(false) => {}
+                Err(e) => warn!(error = %format!("{:#}", e), "{}", msg::UPDATE_FAILED),
+            }
+        }
+    }
+}
+
+/// 현재보다 새 버전이면 받아서 검증 후 실행 파일 교체, 교체했으면 true (재실행은 호출자가 요청)
+pub async fn apply(settings: &UpdateSettings, release: &Release) -> Result<bool> {
+    let current = env!("CARGO_PKG_VERSION");
+    if !is_newer(&release.version, current) {
+        return Ok(false);
+    }
+
+    if !cfg!(unix) {
+        bail!(msg::RESTART_UNIX_ONLY);
+    }
+
+    info!(current = current, version = %release.version, url = %release.url, "{}", msg::UPDATE_DOWNLOADING);
+    let binary = download(&release.url).await?;
+    let payload = signed_payload(&release.version, &target(), &binary);
+    verify(&settings.public_key, &release.signature, payload.as_bytes())?;
+
+    let exe = hot_restart::current_exe()?;
+    install(&exe, &binary)?;
+
+    info!(version = %release.version, exe = %exe.display(), "{}", msg::UPDATE_INSTALLED);
+    Ok(true)
+}
+
+/// 이 에이전트의 대상 (`{os}-{arch}`)
+fn target() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+async fn fetch_manifest(url: &str) -> Result<Release> {
+    let manifest: Manifest = client()?
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .context(msg::UPDATE_MANIFEST_FAILED)?
+        .json()
+        .await
+        .context(msg::UPDATE_MANIFEST_FAILED)?;
+
+    let target = target();
+    let artifact = manifest
+        .artifacts
+        .into_iter()
+        .find(|artifact| artifact.target == target)
+        .ok_or_else(|| anyhow!("{}: {}", msg::UPDATE_NO_ARTIFACT, target))?;
+
+    Ok(Release {
+        version: manifest.version,
+        url: artifact.url,
+        signature: artifact.signature,
+    })
+}
+
+/// 알려진 크기가 상한을 넘으면 받기 전에, 크기를 알리지 않거나 다르게 보내는 응답은 받는 중에 상한에서 중단
+async fn download(url: &str) -> Result<Vec<u8>> {
+    let mut response = client()?
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .context(msg::UPDATE_DOWNLOAD_FAILED)?;
+
+    if let Some(length) = response.content_length()
+        && length > MAX_BINARY_BYTES as u64
+    {
+        bail!("{}: {} bytes", msg::UPDATE_BINARY_TOO_LARGE, length);
+    }
+
+    let mut binary = Vec::new();
+    while let Some(chunk) = response.chunk().await.context(msg::UPDATE_DOWNLOAD_FAILED)? {
+        if binary.len() + chunk.len() > MAX_BINARY_BYTES {
+            bail!("{}: > {} bytes", msg::UPDATE_BINARY_TOO_LARGE, MAX_BINARY_BYTES);
+        }
+        binary.extend_from_slice(&chunk);
+    }
+
+    if binary.is_empty() {
+        bail!("{}: 0 bytes", msg::UPDATE_DOWNLOAD_FAILED);
+    }
+    Ok(binary)
+}
+
+fn client() -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()
+        .context(msg::UPDATE_DOWNLOAD_FAILED)
+}
+
+/// 서명 대상, 한 줄씩 `rlog-agent-update`, 버전, 대상(`linux-x86_64` 등), 바이너리 SHA-256(hex)
+pub fn signed_payload(version: &str, target: &str, binary: &[u8]) -> String {
+    let digest: String = Sha256::digest(binary).iter().map(|b| format!("{:02x}", b)).collect();
+    format!("rlog-agent-update\n{}\n{}\n{}\n", version.trim(), target, digest)
+}
+
+/// 설정의 공개키로 서명 대상의 서명 검증
+fn verify(public_key: &str, signature: &str, payload: &[u8]) -> Result<()> {
+    let key: [u8; 32] = STANDARD
+        .decode(public_key.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .context(msg::UPDATE_INVALID_PUBLIC_KEY)?;
+    let key = VerifyingKey::from_bytes(&key).context(msg::UPDATE_INVALID_PUBLIC_KEY)?;
+
+    let signature = STANDARD
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .context(msg::UPDATE_SIGNATURE_INVALID)?;
+
+    key.verify_strict(payload, &signature)
+        .map_err(|_| anyhow!(msg::UPDATE_SIGNATURE_INVALID))
+}
+
+/// 같은 디렉터리에 새 바이너리를 쓰고 rename 으로 교체, 실행 중인 프로세스는 이전 inode 를 계속 사용
+fn install(exe: &Path, binary: &[u8]) -> Result<()> {
+    let staged = sibling(exe, "new");
+    let previous = sibling(exe, "prev");
+
+    fs::write(&staged, binary).context(msg::UPDATE_INSTALL_FAILED)?;
+    let permissions = fs::metadata(exe).context(msg::UPDATE_INSTALL_FAILED)?.permissions();
+    fs::set_permissions(&staged, permissions).context(msg::UPDATE_INSTALL_FAILED)?;
+    fs::File::open(&staged)
+        .and_then(|file| file.sync_all())
+        .context(msg::UPDATE_INSTALL_FAILED)?;
+
+    // 되돌리기용, 남기지 못해도 업데이트는 진행
+    let _ = fs::remove_file(&previous);
+    if let Err(e) = fs::hard_link(exe, &previous).or_else(|_| fs::copy(exe, &previous).map(|_| ())) {
+        warn!(path = %previous.display(), error = %e, "{}", msg::UPDATE_BACKUP_FAILED);
+    }
+
+    if let Err(e) = fs::rename(&staged, exe) {
+        let _ = fs::remove_file(&staged);
+        return Err(e).context(msg::UPDATE_INSTALL_FAILED);
+    }
+    Ok(())
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let mut name = exe.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// `major.minor.patch` 비교, 숫자가 아닌 꼬리(`-rc.1` 등)는 무시
+fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parse(version: &str) -> [u64; 3] {
+        let mut parts = version
+            .trim()
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0));
+
+        [(); 3].map(|_| parts.next().unwrap_or(0))
+    }
+
+    parse(candidate) > parse(current)
+}
@@ -0,0 +1,164 @@
+//! io_uring 파일 읽기 백엔드 (Linux, `io-uring` 기능)
+//!
+//! tokio-uring 은 자체 런타임에서만 동작하므로 전용 스레드가 열린 파일을 보관하고,
+//! Collector 는 채널로 위치 지정 읽기를 요청. 읽기는 Collector 버퍼의 남은 공간에 바로 기록
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::rc::Rc;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bytes::BytesMut;
+use tokio::sync::{mpsc, oneshot};
+use tokio_uring::buf::BoundedBuf;
+use tracing::{info, warn};
+
+use crate::messages as msg;
+
+type ReadReply = oneshot::Sender<(io::Result<usize>, BytesMut)>;
+
+enum Request {
+    Register { id: u64, file: std::fs::File },
+    Read { id: u64, buf: BytesMut, offset: u64, reply: ReadReply },
+    Close { id: u64 },
+}
+
+/// 런타임 스레드로 가는 요청 채널, 시작에 실패했으면 None
+static RUNTIME: OnceLock<Option<mpsc::UnboundedSender<Request>>> = OnceLock::new();
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// 런타임 스레드 시작 (최초 호출 시 1회), 커널이 io_uring 을 지원하지 않으면 false
+pub fn is_available() -> bool {
+    sender().is_some()
+}
+
+fn sender() -> Option<&'static mpsc::UnboundedSender<Request>> {
+    RUNTIME.get_or_init(start).as_ref()
+}
+
+fn start() -> Option<mpsc::UnboundedSender<Request>> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+
+    let spawned = std::thread::Builder::new()
+        .name("rlog-uring".to_string())
+        .spawn(move || {
+            let runtime = match tokio_uring::Runtime::new(&tokio_uring::builder()) {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    let _ = ready_tx.send(Err(e));
+                    return;
+                }
+            };
+            let _ = ready_tx.send(Ok(()));
+            runtime.block_on(serve(rx));
+        });
+
+    let ready = match spawned {
+        Ok(_) => ready_rx
+            .recv()
+            .unwrap_or_else(|_| Err(io::Error::other(msg::URING_THREAD_EXITED.to_string()))),
+        Err(e) => Err(e),
+    };
+
+    match ready {
+        Ok(()) => {
+            info!("{}", msg::URING_STARTED);
+            Some(tx)
+        }
+        Err(e) => {
+            warn!(error = %e, "{}", msg::URING_UNAVAILABLE);
+            None
+        }
+    }
+}
+
+/// 모든 송신측이 사라질 때까지 요청 처리, 읽기는 서로 기다리지 않도록 각각 태스크로 실행
+async fn serve(mut rx: mpsc::UnboundedReceiver<Request>) {
+    let files: Rc<RefCell<HashMap<u64, Rc<tokio_uring::fs::File>>>> = Rc::default();
+
+    while let Some(request) = rx.recv().await {
+        match request {
+            Request::Register { id, file } => {
+                files
+                    .borrow_mut()
+                    .insert(id, Rc::new(tokio_uring::fs::File::from_std(file)));
+            }
+            Request::Read {
+                id,
+                buf,
+                offset,
+                reply,
+            } => {
+                let Some(file) = files.borrow().get(&id).cloned() else {
+                    let _ = reply.send((Err(io::ErrorKind::NotFound.into()), buf));
+                    continue;
+                };
+
+                tokio_uring::spawn(async move {
+                    let len = buf.len();
+                    let (result, slice) = file.read_at(buf.slice(len..), offset).await;
+                    let _ = reply.send((result, slice.into_inner()));
+                });
+            }
+            Request::Close { id } => {
+                files.borrow_mut().remove(&id);
+            }
+        }
+    }
+}
+
+/// 런타임 스레드에 등록된 파일, drop 시 닫힘
+pub struct UringFile {
+    id: u64,
+    tx: &'static mpsc::UnboundedSender<Request>,
+}
+
+impl UringFile {
+    pub fn register(file: std::fs::File) -> io::Result<Self> {
+        let tx = sender().ok_or_else(|| io::Error::other(msg::URING_UNAVAILABLE.to_string()))?;
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        tx.send(Request::Register { id, file })
+            .map_err(|_| io::Error::other(msg::URING_THREAD_EXITED.to_string()))?;
+
+        Ok(Self { id, tx })
+    }
+
+    /// `offset` 부터 `buf` 의 남은 공간만큼 읽어 뒤에 이어 붙임
+    ///
+    /// 완료 전에 취소되면 버퍼는 런타임 스레드에서 버려지고 `buf` 는 비어 있음
+    pub async fn read_at(&self, buf: &mut BytesMut, offset: u64) -> io::Result<usize> {
+        let (reply, response) = oneshot::channel();
+
+        let request = Request::Read {
+            id: self.id,
+            buf: mem::take(buf),
+            offset,
+            reply,
+        };
+        if let Err(mpsc::error::SendError(request)) = self.tx.send(request) {
+            if let Request::Read { buf: returned, .. } = request {
+                *buf = returned;
+            }
+            return Err(io::Error::other(msg::URING_THREAD_EXITED.to_string()));
+        }
+
+        let (result, returned) = response
+            .await
+            .map_err(|_| io::Error::other(msg::URING_THREAD_EXITED.to_string()))?;
+        *buf = returned;
+
+        result
+    }
+}
+
+impl Drop for UringFile {
+    fn drop(&mut self) {
+        let _ = self.tx.send(Request::Close { id: self.id });
+    }
+}
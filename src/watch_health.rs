@@ -0,0 +1,54 @@
+//! 파일 감시자 상태 집계
+//!
+//! 파일 Collector 가 주기적인 stat 으로 감시자가 멈춘 것(변경 이벤트 없이 읽지 않은 데이터가 남음)을 찾으면
+//! 수집 대상별로 횟수와 폴링 전환 여부를 기록. 편집기식 교체(rename)나 overlayfs 에서 inotify 가 이벤트를 놓치는 경우.
+//! 누적값은 관리 API `status` 와 헬스 리포터의 메트릭으로 노출
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+static WATCHERS: LazyLock<Mutex<BTreeMap<String, WatcherHealth>>> = LazyLock::new(Default::default);
+
+/// 수집 대상 하나의 감시자 상태
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    /// 감시자가 멈춘 것을 찾은 횟수
+    pub stale_incidents: u64,
+    /// 감시자를 다시 만든 횟수
+    pub recreated: u64,
+    /// 감시자 대신 주기적으로 읽는 중
+    pub polling: bool,
+}
+
+fn lock() -> MutexGuard<'static, BTreeMap<String, WatcherHealth>> {
+    WATCHERS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 감시 시작, 이전 실행의 폴링 상태는 지우고 횟수는 유지
+pub fn start(label: &str) {
+    lock().entry(label.to_string()).or_default().polling = false;
+}
+
+pub fn record_stale(label: &str) {
+    lock().entry(label.to_string()).or_default().stale_incidents += 1;
+}
+
+pub fn record_recreated(label: &str) {
+    lock().entry(label.to_string()).or_default().recreated += 1;
+}
+
+pub fn set_polling(label: &str) {
+    lock().entry(label.to_string()).or_default().polling = true;
+}
+
+/// 실행 중 제거한 수집 대상
+pub fn remove(label: &str) {
+    lock().remove(label);
+}
+
+/// 라벨별 감시자 상태
+pub fn snapshot() -> BTreeMap<String, WatcherHealth> {
+    lock().clone()
+}
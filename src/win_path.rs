@@ -0,0 +1,65 @@
+//! Windows 수집 경로 정규화
+//!
+//! `MAX_PATH`(260자)를 넘는 경로와 `\\fileserver\logs\app.log` 같은 UNC 경로를 확장 길이 형식(`\\?\C:\...`,
+//! `\\?\UNC\fileserver\...`)으로 바꿔 파일 감시자(`ReadDirectoryChangesW`)까지 같은 경로를 씀.
+//! SMB 공유(UNC, 네트워크 드라이브)는 다른 호스트에서 쓴 변경을 알려 주지 않는 경우가 많아 감시 대신 폴링.
+//! 다른 OS 에서는 경로를 그대로 쓰고 네트워크 파일 시스템을 판별하지 않음 (`sources[].poll` 로 지정)
+
+use std::path::{Path, PathBuf};
+
+/// 파일 시스템 호출에 쓸 경로, 이미 확장 길이 형식이거나 절대 경로로 만들 수 없으면 그대로
+#[cfg(windows)]
+pub fn extended(path: &Path) -> PathBuf {
+    // `/` 구분자와 `.`/`..` 는 확장 길이 형식에서 해석되지 않으므로 GetFullPathNameW 로 먼저 정리
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    let Some(full) = absolute.to_str() else {
+        return absolute;
+    };
+
+    if full.starts_with(r"\\?\") || full.starts_with(r"\\.\") {
+        return absolute;
+    }
+    match full.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!(r"\\?\UNC\{}", unc)),
+        None => PathBuf::from(format!(r"\\?\{}", full)),
+    }
+}
+
+#[cfg(not(windows))]
+pub fn extended(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// UNC 경로이거나 네트워크 드라이브(`net use`)에 있는 경로
+#[cfg(windows)]
+pub fn is_network(path: &Path) -> bool {
+    use std::path::{Component, Prefix};
+
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+        return false;
+    };
+    match prefix.kind() {
+        Prefix::UNC(..) | Prefix::VerbatimUNC(..) => true,
+        Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => is_remote_drive(letter),
+        _ => false,
+    }
+}
+
+#[cfg(not(windows))]
+pub fn is_network(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(windows)]
+fn is_remote_drive(letter: u8) -> bool {
+    use windows_sys::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    /// `GetDriveTypeW` 의 네트워크 드라이브
+    const DRIVE_REMOTE: u32 = 4;
+
+    let root: Vec<u16> = format!(r"{}:\", letter as char).encode_utf16().chain([0]).collect();
+    // SAFETY: `root` 는 NUL 로 끝나는 UTF-16 문자열
+    unsafe { GetDriveTypeW(root.as_ptr()) == DRIVE_REMOTE }
+}
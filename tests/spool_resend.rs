@@ -0,0 +1,83 @@
+//! 서버가 거부한 스풀 파일이 뒤의 파일 재전송을 막지 않는지 확인 (`test-server` 기능)
+//!
+//! 디스크 큐와 dead-letter 가 작업 디렉터리 기준 `state/` 아래에 있으므로 임시 디렉터리로 옮겨 실행.
+//! 작업 디렉터리는 프로세스 전역이라 별도 테스트 바이너리로 둠
+
+use std::path::Path;
+use std::time::Duration;
+
+use prost_types::Timestamp;
+use rlog_agent::proto::log::{Log, LogBatch};
+use rlog_agent::settings::SourceSettings;
+use rlog_agent::spool::{DEAD_LETTER_DIR, SPOOL_DIR, Spool};
+use rlog_agent::test_server::{Rpc, TestServer};
+use rlog_agent::{Agent, Sink};
+use tokio_util::sync::CancellationToken;
+use tonic::Code;
+
+fn batch(id: &str) -> LogBatch {
+    let now = chrono::Utc::now();
+    LogBatch {
+        batch_id: id.to_string(),
+        logs: vec![Log {
+            label: "it".to_string(),
+            line: format!("line from {id}").into(),
+            timestamp: Some(Timestamp {
+                seconds: now.timestamp(),
+                nanos: 0,
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn files(dir: &str) -> usize {
+    Spool::open(dir).and_then(|spool| spool.list()).map_or(0, |paths| paths.len())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejected_spool_file_does_not_block_the_rest() {
+    let dir = std::env::temp_dir().join(format!("rlog-agent-spool-it-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    // 파일명이 저장 시각 순이므로 거부될 배치가 맨 앞
+    let spool = Spool::open(SPOOL_DIR).unwrap();
+    for id in ["rejected", "second", "third"] {
+        spool.push(&batch(id)).unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    let server = TestServer::start().await.unwrap();
+    server.fail_next(Rpc::Send, 1, Code::InvalidArgument);
+
+    let log = Path::new("app.log");
+    std::fs::write(log, "").unwrap();
+
+    let agent = Agent::builder()
+        .source(SourceSettings::new("it", log.to_string_lossy()))
+        .sink(Sink::Grpc {
+            server_addr: server.url(),
+            project_key: "it-project".into(),
+        })
+        .drain_timeout(5)
+        .build()
+        .unwrap();
+
+    let shutdown = CancellationToken::new();
+    let handle = tokio::spawn(agent.run(shutdown.clone()));
+
+    assert!(server.wait_for_logs(2, Duration::from_secs(30)).await, "batches behind the rejected one not sent");
+    let sent: Vec<String> = server.batches().into_iter().map(|batch| batch.batch_id).collect();
+    assert_eq!(sent, ["second", "third"]);
+
+    shutdown.cancel();
+    handle.await.unwrap().unwrap();
+    server.shutdown().await;
+
+    assert_eq!(files(SPOOL_DIR), 0);
+    assert_eq!(files(DEAD_LETTER_DIR), 1);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,84 @@
+//! TestServer 를 상대로 Streamer/TokenManager 를 실제 gRPC 로 통합 테스트 (`test-server` 기능)
+//!
+//! 에이전트 상태(토큰, 상태 레지스트리, 재시도 설정)가 프로세스 전역이라 한 프로세스에서 Agent 는 하나만 돌리므로
+//! 시나리오를 한 테스트에서 차례로 확인. `state/`, `config/` 에 쓰지 않도록 상태 없는 실행으로 띄움
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rlog_agent::settings::SourceSettings;
+use rlog_agent::test_server::{Rpc, TestServer};
+use rlog_agent::{Agent, Sink, ephemeral};
+use tokio_util::sync::CancellationToken;
+use tonic::Code;
+
+const WAIT: Duration = Duration::from_secs(30);
+
+/// 수집기가 파일 끝에서 시작하든 처음부터 읽든 상관없도록 `count` 개가 더 도착할 때까지 한 줄씩 덧붙임
+async fn deliver(server: &TestServer, path: &Path, count: usize) -> bool {
+    let target = server.logs().len() + count;
+    let deadline = tokio::time::Instant::now() + WAIT;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path).unwrap();
+    let mut line = 0;
+    while tokio::time::Instant::now() < deadline {
+        writeln!(file, "line {line}").unwrap();
+        line += 1;
+        if server.wait_for_logs(target, Duration::from_millis(200)).await {
+            return true;
+        }
+    }
+    false
+}
+
+fn log_path() -> PathBuf {
+    std::env::temp_dir().join(format!("rlog-agent-it-{}.log", std::process::id()))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn streamer_recovers_from_server_failures() {
+    ephemeral::configure(true);
+
+    let server = TestServer::start().await.unwrap();
+    let path = log_path();
+    std::fs::write(&path, "").unwrap();
+
+    let agent = Agent::builder()
+        .source(SourceSettings::new("it", path.to_string_lossy()))
+        .sink(Sink::Grpc {
+            server_addr: server.url(),
+            project_key: "it-project".into(),
+        })
+        .batch_size(1)
+        .flush_interval(1)
+        .drain_timeout(5)
+        .build()
+        .unwrap();
+
+    let shutdown = CancellationToken::new();
+    let handle = tokio::spawn(agent.run(shutdown.clone()));
+
+    assert!(deliver(&server, &path, 1).await, "first log not delivered");
+    assert_eq!(server.registrations().len(), 1);
+
+    // 일시 장애는 재시도로 넘김
+    server.fail_next(Rpc::Send, 2, Code::Unavailable);
+    assert!(deliver(&server, &path, 3).await, "logs lost after Unavailable");
+
+    // 액세스 토큰 만료는 refresh 로 넘기고 재등록하지 않음
+    server.expire_tokens();
+    assert!(deliver(&server, &path, 3).await, "logs lost after token expiry");
+    assert_eq!(server.registrations().len(), 1);
+
+    // 리프레시 토큰까지 폐기되면 재등록
+    server.revoke_tokens();
+    assert!(deliver(&server, &path, 3).await, "logs lost after token revocation");
+    assert_eq!(server.registrations().len(), 2);
+
+    shutdown.cancel();
+    handle.await.unwrap().unwrap();
+    server.shutdown().await;
+    let _ = std::fs::remove_file(&path);
+}